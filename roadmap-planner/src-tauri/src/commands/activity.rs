@@ -0,0 +1,107 @@
+// Tauri commands for the activity report
+// Rows of every entity type last updated within a window, for "what changed this week" emails
+
+use crate::commands::engine::dates::parse_date;
+use crate::commands::entities::EntityType;
+use crate::commands::rows::row_to_json;
+use crate::commands::workspace_diff::normalise_timestamp;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+
+// updated_at in the form normalise_timestamp gives, so RFC 3339 and datetime('now') values compare
+const UPDATED_AT: &str = "REPLACE(SUBSTR(updated_at, 1, 19), 'T', ' ')";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityActivity {
+    pub entity_type: EntityType,
+    pub count: i64,
+    // Newest first
+    pub rows: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityReport {
+    pub from: String,
+    pub to: String,
+    pub total_count: i64,
+    // One entry per entity type, including those with nothing in the window
+    pub entity_types: Vec<EntityActivity>,
+}
+
+/// The inclusive bounds to compare updated_at against. A date on its own covers the whole day.
+pub fn activity_window(from: &str, to: &str) -> Result<(String, String), String> {
+    for (name, value) in [("from", from), ("to", to)] {
+        if parse_date(value).is_none() {
+            return Err(format!("{} must be a date or timestamp, got {}", name, value));
+        }
+    }
+    let start = normalise_timestamp(from);
+    let mut end = normalise_timestamp(to);
+    if end.len() == 10 {
+        end.push_str(" 23:59:59");
+    }
+    if start > end {
+        return Err(format!("from ({}) is after to ({})", from, to));
+    }
+    Ok((start, end))
+}
+
+// ============================================
+// ACTIVITY REPORT COMMANDS
+// ============================================
+
+/// Every capability, system, initiative and other entity whose updated_at falls within
+/// [from, to], grouped by entity type with counts
+#[tauri::command]
+pub async fn get_activity_report(db: State<'_, tauri_plugin_sql::DbInstances>, from: String, to: String) -> Result<ActivityReport, String> {
+    let (start, end) = activity_window(&from, &to)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut entity_types = Vec::with_capacity(EntityType::ALL.len());
+    for entity_type in EntityType::ALL {
+        let rows: Vec<Value> = sqlx::query(&format!(
+            "SELECT * FROM {table} WHERE {updated} BETWEEN ? AND ? ORDER BY {updated} DESC, id",
+            table = entity_type.table(),
+            updated = UPDATED_AT
+        ))
+        .bind(&start)
+        .bind(&end)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(row_to_json)
+        .collect();
+
+        entity_types.push(EntityActivity { entity_type, count: rows.len() as i64, rows });
+    }
+
+    Ok(ActivityReport {
+        from,
+        to,
+        total_count: entity_types.iter().map(|e| e.count).sum(),
+        entity_types,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_bare_date_covers_the_whole_day() {
+        assert_eq!(
+            activity_window("2027-03-01", "2027-03-07").unwrap(),
+            ("2027-03-01".to_string(), "2027-03-07 23:59:59".to_string())
+        );
+        assert_eq!(
+            activity_window("2027-03-01T09:00:00.000Z", "2027-03-01 17:30:00").unwrap(),
+            ("2027-03-01 09:00:00".to_string(), "2027-03-01 17:30:00".to_string())
+        );
+        assert!(activity_window("2027-03-08", "2027-03-07").is_err());
+        assert!(activity_window("last week", "2027-03-07").is_err());
+    }
+}
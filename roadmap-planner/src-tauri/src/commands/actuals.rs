@@ -0,0 +1,133 @@
+// Tauri commands for period actuals
+// Recorded spend per initiative per financial period
+
+use crate::commands::period_close::ensure_period_open;
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodActual {
+    pub id: String,
+    pub financial_period_id: String,
+    pub initiative_id: String,
+    pub amount: f64,
+    pub notes: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+// ============================================
+// ACTUALS COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_period_actuals(db: State<'_, tauri_plugin_sql::DbInstances>, period_id: String) -> Result<Vec<PeriodActual>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<PeriodActual> = sqlx::query_as!(
+        PeriodActual,
+        r#"SELECT id, financial_period_id, initiative_id, amount, notes, created_at, updated_at
+        FROM period_actuals WHERE financial_period_id = ?"#,
+        period_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_period_actual(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<PeriodActual, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let row: PeriodActual = sqlx::query_as!(
+        PeriodActual,
+        r#"SELECT id, financial_period_id, initiative_id, amount, notes, created_at, updated_at
+        FROM period_actuals WHERE id = ?"#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn create_period_actual(db: State<'_, tauri_plugin_sql::DbInstances>, actual: PeriodActual) -> Result<PeriodActual, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    ensure_period_open(pool, &actual.financial_period_id, "actuals").await?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO period_actuals (id, financial_period_id, initiative_id, amount, notes, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+        actual.id,
+        actual.financial_period_id,
+        actual.initiative_id,
+        actual.amount,
+        actual.notes,
+        now,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_period_actual(db, actual.id).await
+}
+
+#[tauri::command]
+pub async fn update_period_actual(db: State<'_, tauri_plugin_sql::DbInstances>, actual: PeriodActual) -> Result<PeriodActual, String> {
+    let existing = get_period_actual(db.clone(), actual.id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // Both the period it leaves and the period it lands in must be open
+    ensure_period_open(pool, &existing.financial_period_id, "actuals").await?;
+    ensure_period_open(pool, &actual.financial_period_id, "actuals").await?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"UPDATE period_actuals SET
+            financial_period_id = ?, initiative_id = ?, amount = ?, notes = ?, updated_at = ?
+        WHERE id = ?"#,
+        actual.financial_period_id,
+        actual.initiative_id,
+        actual.amount,
+        actual.notes,
+        now,
+        actual.id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_period_actual(db, actual.id).await
+}
+
+#[tauri::command]
+pub async fn delete_period_actual(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let existing = get_period_actual(db.clone(), id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    ensure_period_open(pool, &existing.financial_period_id, "actuals").await?;
+
+    sqlx::query!("DELETE FROM period_actuals WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
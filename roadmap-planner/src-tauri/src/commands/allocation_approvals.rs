@@ -0,0 +1,291 @@
+// Tauri commands for approving demand on resource pools
+// Planners request effort from a pool; the pool's manager approves or rejects it
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::dates::parse_date;
+use crate::commands::engine::resources::{APPROVAL_STATUSES, InitiativeResourceRequirement};
+use crate::commands::ensure_scenarios_unlocked;
+use crate::commands::entities::EntityType;
+use crate::commands::fetch::{fetch_initiatives, fetch_resource_pools, single};
+use crate::commands::get_resource_pool;
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewAllocationRequest {
+    pub initiative_id: String,
+    pub resource_pool_id: String,
+    pub effort_required: f64,
+    pub period_start: Option<String>,
+    pub period_end: Option<String>,
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingApproval {
+    pub requirement: InitiativeResourceRequirement,
+    pub initiative_name: String,
+    pub scenario_id: String,
+    pub scenario_name: String,
+}
+
+pub fn validate_allocation_request(request: &NewAllocationRequest) -> Result<(), String> {
+    if request.effort_required.is_nan() || request.effort_required <= 0.0 {
+        return Err(format!("Effort required must be more than zero, got {}", request.effort_required));
+    }
+    for date in [&request.period_start, &request.period_end].into_iter().flatten() {
+        if parse_date(date).is_none() {
+            return Err(format!("Invalid date {}", date));
+        }
+    }
+    if let (Some(start), Some(end)) = (&request.period_start, &request.period_end) {
+        if start > end {
+            return Err(format!("Allocation starts on {} after it ends on {}", start, end));
+        }
+    }
+    Ok(())
+}
+
+/// Only requested demand can be decided on; a decision is final, so a rejected allocation
+/// is requested again rather than reopened
+pub fn check_decision(current_status: &str, decision: &str) -> Result<(), String> {
+    if !APPROVAL_STATUSES.contains(&decision) || decision == "Requested" {
+        return Err(format!("Unknown approval decision {}", decision));
+    }
+    if current_status != "Requested" {
+        return Err(format!("Allocation is already {}", current_status));
+    }
+    Ok(())
+}
+
+async fn decide_allocation(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, decision: &str, note: Option<String>) -> Result<InitiativeResourceRequirement, String> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let before = sqlx::query_as!(
+        InitiativeResourceRequirement,
+        r#"SELECT id, initiative_id, resource_pool_id, effort_required, period_start, period_end, role,
+            approval_status, approver_note, generated as "generated: bool", created_at
+        FROM initiative_resource_requirements WHERE id = ?"#,
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Allocation not found: {}", id))?;
+
+    check_decision(&before.approval_status, decision)?;
+    ensure_scenarios_unlocked(&mut tx, &[], std::slice::from_ref(&before.initiative_id)).await?;
+
+    let initiative = single(
+        fetch_initiatives(&mut tx, std::slice::from_ref(&before.initiative_id)).await?,
+        EntityType::Initiative,
+        &before.initiative_id,
+    )?;
+
+    sqlx::query!(
+        "UPDATE initiative_resource_requirements SET approval_status = ?, approver_note = ? WHERE id = ?",
+        decision,
+        note,
+        id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let after = InitiativeResourceRequirement {
+        approval_status: decision.to_string(),
+        approver_note: note,
+        ..before.clone()
+    };
+
+    // Rejections carry their own action so anyone following the audit trail can pick them out
+    let (action, verb) = if decision == "Rejected" {
+        ("RejectAllocation", "Rejected")
+    } else {
+        ("ApproveAllocation", "Approved")
+    };
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::ResourcePool.name().to_string(),
+        entity_id: Some(before.resource_pool_id.clone()),
+        action: action.to_string(),
+        description: Some(format!("{} {} of effort for \"{}\"", verb, before.effort_required, initiative.name)),
+        before: serde_json::to_value(&before).ok(),
+        after: serde_json::to_value(&after).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(after)
+}
+
+// ============================================
+// ALLOCATION APPROVAL COMMANDS
+// ============================================
+
+/// Ask a pool for effort; it counts as requested demand until the pool's manager decides
+#[tauri::command]
+pub async fn request_allocation(db: State<'_, tauri_plugin_sql::DbInstances>, request: NewAllocationRequest) -> Result<InitiativeResourceRequirement, String> {
+    validate_allocation_request(&request)?;
+    let role = request.role.as_deref().map(str::trim).filter(|r| !r.is_empty()).map(str::to_string);
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let initiative = single(
+        fetch_initiatives(&mut tx, std::slice::from_ref(&request.initiative_id)).await?,
+        EntityType::Initiative,
+        &request.initiative_id,
+    )?;
+    let resource_pool = single(
+        fetch_resource_pools(&mut tx, std::slice::from_ref(&request.resource_pool_id)).await?,
+        EntityType::ResourcePool,
+        &request.resource_pool_id,
+    )?;
+    ensure_scenarios_unlocked(&mut tx, &[], std::slice::from_ref(&initiative.id)).await?;
+
+    let requirement = InitiativeResourceRequirement {
+        id: uuid::Uuid::new_v4().to_string(),
+        initiative_id: initiative.id.clone(),
+        resource_pool_id: resource_pool.id.clone(),
+        effort_required: request.effort_required,
+        period_start: request.period_start,
+        period_end: request.period_end,
+        role,
+        approval_status: "Requested".to_string(),
+        approver_note: None,
+        generated: false,
+        created_at: Some(get_current_timestamp()),
+    };
+
+    sqlx::query!(
+        r#"INSERT INTO initiative_resource_requirements
+            (id, initiative_id, resource_pool_id, effort_required, period_start, period_end, role, approval_status, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        requirement.id,
+        requirement.initiative_id,
+        requirement.resource_pool_id,
+        requirement.effort_required,
+        requirement.period_start,
+        requirement.period_end,
+        requirement.role,
+        requirement.approval_status,
+        requirement.created_at
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::ResourcePool.name().to_string(),
+        entity_id: Some(resource_pool.id.clone()),
+        action: "RequestAllocation".to_string(),
+        description: Some(format!(
+            "Requested {} of effort from \"{}\" for \"{}\"",
+            requirement.effort_required, resource_pool.name, initiative.name
+        )),
+        after: serde_json::to_value(&requirement).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(requirement)
+}
+
+#[tauri::command]
+pub async fn approve_allocation(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, note: Option<String>) -> Result<InitiativeResourceRequirement, String> {
+    decide_allocation(db, id, "Approved", note).await
+}
+
+/// Turn a request down; the note tells the requester why, so it can't be blank
+#[tauri::command]
+pub async fn reject_allocation(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, note: String) -> Result<InitiativeResourceRequirement, String> {
+    if note.trim().is_empty() {
+        return Err("Give a reason for rejecting the allocation".to_string());
+    }
+    decide_allocation(db, id, "Rejected", Some(note)).await
+}
+
+/// Requests waiting on the pool's manager, oldest first
+#[tauri::command]
+pub async fn get_pending_approvals(db: State<'_, tauri_plugin_sql::DbInstances>, pool_id: String) -> Result<Vec<PendingApproval>, String> {
+    get_resource_pool(db.clone(), pool_id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows = sqlx::query!(
+        r#"SELECT r.id as "id!", r.initiative_id, r.resource_pool_id, r.effort_required,
+            r.period_start, r.period_end, r.role, r.approval_status, r.approver_note,
+            r.generated as "generated: bool", r.created_at as "created_at?",
+            i.name as initiative_name, s.id as "scenario_id!", s.name as scenario_name
+        FROM initiative_resource_requirements r
+        JOIN initiatives i ON i.id = r.initiative_id
+        JOIN scenarios s ON s.id = i.scenario_id
+        WHERE r.resource_pool_id = ? AND r.approval_status = 'Requested'
+        ORDER BY r.created_at, i.name"#,
+        pool_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| PendingApproval {
+            requirement: InitiativeResourceRequirement {
+                id: r.id,
+                initiative_id: r.initiative_id,
+                resource_pool_id: r.resource_pool_id,
+                effort_required: r.effort_required,
+                period_start: r.period_start,
+                period_end: r.period_end,
+                role: r.role,
+                approval_status: r.approval_status,
+                approver_note: r.approver_note,
+                generated: r.generated,
+                created_at: r.created_at,
+            },
+            initiative_name: r.initiative_name,
+            scenario_id: r.scenario_id,
+            scenario_name: r.scenario_name,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_requested_allocations_can_be_decided() {
+        assert!(check_decision("Requested", "Approved").is_ok());
+        assert!(check_decision("Requested", "Rejected").is_ok());
+        assert!(check_decision("Approved", "Rejected").is_err());
+        assert!(check_decision("Rejected", "Approved").is_err());
+        assert!(check_decision("Requested", "Requested").is_err());
+
+        let request = NewAllocationRequest {
+            initiative_id: "i".to_string(),
+            resource_pool_id: "p".to_string(),
+            effort_required: 10.0,
+            period_start: Some("2027-01-01".to_string()),
+            period_end: Some("2027-03-31".to_string()),
+            role: None,
+        };
+        assert!(validate_allocation_request(&request).is_ok());
+        assert!(validate_allocation_request(&NewAllocationRequest { effort_required: 0.0, ..request.clone() }).is_err());
+        assert!(validate_allocation_request(&NewAllocationRequest { period_end: Some("2026-12-31".to_string()), ..request }).is_err());
+    }
+}
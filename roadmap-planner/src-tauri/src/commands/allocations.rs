@@ -0,0 +1,670 @@
+// Tauri commands for named resource allocations
+// Links individual resources to initiatives with a percentage of their time
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::assignments::{self, Contention, PeriodHeadcount, ResourceConflict};
+use crate::commands::engine::dates::{parse_date, today};
+use crate::commands::entities::EntityType;
+use crate::commands::time_off::ResourceTimeOff;
+use crate::commands::{ensure_scenarios_unlocked, get_financial_periods, get_initiatives, get_resource, get_resources, get_scenario, require_scenario};
+use crate::db::{Resource, get_current_timestamp};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeResource {
+    pub id: String,
+    pub initiative_id: String,
+    pub resource_id: String,
+    pub allocation_percent: f64,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+fn validate_allocation(allocation: &InitiativeResource) -> Result<(), String> {
+    if allocation.allocation_percent <= 0.0 || allocation.allocation_percent > 100.0 {
+        return Err(format!(
+            "Allocation percent must be greater than 0 and at most 100, got {}",
+            allocation.allocation_percent
+        ));
+    }
+    Ok(())
+}
+
+// An allocation folded into another on the same initiative, when reassigning work
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergedAllocation {
+    pub initiative_id: String,
+    pub allocation_percent: f64,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    // Percentage points lost to the 100% cap
+    pub capped_by: f64,
+}
+
+/// Combine two allocations to one initiative: percentages add up to at most 100 and the dates
+/// cover both, open-ended if either is
+pub fn merge_allocation(kept: &InitiativeResource, moved: &InitiativeResource) -> MergedAllocation {
+    let total = kept.allocation_percent + moved.allocation_percent;
+    let start_date = match (&kept.start_date, &moved.start_date) {
+        (Some(a), Some(b)) => Some(a.min(b).clone()),
+        _ => None,
+    };
+    let end_date = match (&kept.end_date, &moved.end_date) {
+        (Some(a), Some(b)) => Some(a.max(b).clone()),
+        _ => None,
+    };
+
+    MergedAllocation {
+        initiative_id: kept.initiative_id.clone(),
+        allocation_percent: total.min(100.0),
+        start_date,
+        end_date,
+        capped_by: (total - 100.0).max(0.0),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillCoverage {
+    // As requested
+    pub skill: String,
+    pub resource_count: i64,
+    pub resource_ids: Vec<String>,
+    // Nobody allocated has the skill, so more headcount of the same people won't help
+    pub is_gap: bool,
+}
+
+/// A resource's skills from the stored JSON array; anything unreadable counts as none
+pub fn resource_skills(resource: &Resource) -> Vec<String> {
+    resource
+        .skills
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<Vec<String>>(json).ok())
+        .unwrap_or_default()
+}
+
+/// How many of the resources have each required skill, matching case-insensitively.
+/// Repeated skills are reported once.
+pub fn skill_coverage(required_skills: &[String], resources: &[Resource]) -> Vec<SkillCoverage> {
+    let held: Vec<(&Resource, HashSet<String>)> = resources
+        .iter()
+        .map(|r| (r, resource_skills(r).iter().map(|s| s.trim().to_lowercase()).collect()))
+        .collect();
+
+    let mut seen = HashSet::new();
+    required_skills
+        .iter()
+        .map(|skill| skill.trim())
+        .filter(|skill| seen.insert(skill.to_lowercase()))
+        .map(|skill| {
+            let key = skill.to_lowercase();
+            let resource_ids: Vec<String> = held
+                .iter()
+                .filter(|(_, skills)| skills.contains(&key))
+                .map(|(r, _)| r.id.clone())
+                .collect();
+            SkillCoverage {
+                skill: skill.to_string(),
+                resource_count: resource_ids.len() as i64,
+                is_gap: resource_ids.is_empty(),
+                resource_ids,
+            }
+        })
+        .collect()
+}
+
+// A named allocation on an open initiative, with the dates the work runs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveAssignment {
+    pub resource_id: String,
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub initiative_status: String,
+    pub scenario_id: String,
+    pub scenario_name: String,
+    // The allocation's own end, else the initiative's; None runs on indefinitely
+    pub end_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepartingResource {
+    pub resource: Resource,
+    pub days_until_departure: i64,
+    // Work the resource is booked on past their end date
+    pub unfinished_assignments: Vec<ActiveAssignment>,
+    pub has_unfinished_work: bool,
+}
+
+/// Resources whose end date falls between `as_of` and `within_days` later, soonest first,
+/// each with the assignments that run past the day they leave
+pub fn departing_resources(resources: Vec<Resource>, assignments: &[ActiveAssignment], as_of: NaiveDate, within_days: i64) -> Vec<DepartingResource> {
+    let mut departing: Vec<DepartingResource> = resources
+        .into_iter()
+        .filter_map(|resource| {
+            let end = resource.end_date.as_deref().and_then(parse_date)?;
+            let days_until_departure = (end - as_of).num_days();
+            if !(0..=within_days).contains(&days_until_departure) {
+                return None;
+            }
+            let unfinished_assignments: Vec<ActiveAssignment> = assignments
+                .iter()
+                .filter(|a| a.resource_id == resource.id)
+                .filter(|a| a.end_date.as_deref().and_then(parse_date).is_none_or(|work_end| work_end > end))
+                .cloned()
+                .collect();
+            Some(DepartingResource {
+                has_unfinished_work: !unfinished_assignments.is_empty(),
+                unfinished_assignments,
+                days_until_departure,
+                resource,
+            })
+        })
+        .collect();
+
+    departing.sort_by(|a, b| a.days_until_departure.cmp(&b.days_until_departure).then_with(|| a.resource.name.cmp(&b.resource.name)));
+    departing
+}
+
+// Named allocations on the scenario's initiatives, optionally for one resource
+async fn get_scenario_allocations(db: &State<'_, tauri_plugin_sql::DbInstances>, scenario_id: &str, resource_id: Option<&str>) -> Result<Vec<InitiativeResource>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query_as!(
+        InitiativeResource,
+        r#"SELECT
+            ir.id, ir.initiative_id, ir.resource_id, ir.allocation_percent,
+            ir.start_date, ir.end_date, ir.created_at, ir.updated_at
+        FROM initiative_resources ir
+        JOIN initiatives i ON i.id = ir.initiative_id
+        WHERE i.scenario_id = ? AND (? IS NULL OR ir.resource_id = ?)"#,
+        scenario_id,
+        resource_id,
+        resource_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+// ============================================
+// ALLOCATIONS COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_initiative_resources(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String) -> Result<Vec<InitiativeResource>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<InitiativeResource> = sqlx::query_as!(
+        InitiativeResource,
+        r#"SELECT
+            id, initiative_id, resource_id, allocation_percent,
+            start_date, end_date, created_at, updated_at
+        FROM initiative_resources WHERE initiative_id = ?"#,
+        initiative_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_initiative_resource(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<InitiativeResource, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let row: InitiativeResource = sqlx::query_as!(
+        InitiativeResource,
+        r#"SELECT
+            id, initiative_id, resource_id, allocation_percent,
+            start_date, end_date, created_at, updated_at
+        FROM initiative_resources WHERE id = ?"#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn create_initiative_resource(db: State<'_, tauri_plugin_sql::DbInstances>, allocation: InitiativeResource) -> Result<InitiativeResource, String> {
+    validate_allocation(&allocation)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO initiative_resources (id, initiative_id, resource_id, allocation_percent,
+            start_date, end_date, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+        allocation.id,
+        allocation.initiative_id,
+        allocation.resource_id,
+        allocation.allocation_percent,
+        allocation.start_date,
+        allocation.end_date,
+        now,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_initiative_resource(db, allocation.id).await
+}
+
+#[tauri::command]
+pub async fn update_initiative_resource(db: State<'_, tauri_plugin_sql::DbInstances>, allocation: InitiativeResource) -> Result<InitiativeResource, String> {
+    validate_allocation(&allocation)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"UPDATE initiative_resources SET
+            initiative_id = ?, resource_id = ?, allocation_percent = ?,
+            start_date = ?, end_date = ?, updated_at = ?
+        WHERE id = ?"#,
+        allocation.initiative_id,
+        allocation.resource_id,
+        allocation.allocation_percent,
+        allocation.start_date,
+        allocation.end_date,
+        now,
+        allocation.id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_initiative_resource(db, allocation.id).await
+}
+
+#[tauri::command]
+pub async fn delete_initiative_resource(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM initiative_resources WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Move every named allocation from one resource to another, merging where both are already on
+/// an initiative. Merges capped at 100% are listed in the audit entry. Returns the number moved.
+#[tauri::command]
+pub async fn reassign_resource_allocations(db: State<'_, tauri_plugin_sql::DbInstances>, from_resource_id: String, to_resource_id: String) -> Result<u64, String> {
+    if from_resource_id == to_resource_id {
+        return Err("Choose a different resource to reassign the work to".to_string());
+    }
+    let from = get_resource(db.clone(), from_resource_id.clone()).await?;
+    let to = get_resource(db.clone(), to_resource_id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let allocations: Vec<InitiativeResource> = sqlx::query_as!(
+        InitiativeResource,
+        r#"SELECT id, initiative_id, resource_id, allocation_percent,
+            start_date, end_date, created_at, updated_at
+        FROM initiative_resources WHERE resource_id IN (?, ?)"#,
+        from_resource_id,
+        to_resource_id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let (moving, existing): (Vec<InitiativeResource>, Vec<InitiativeResource>) =
+        allocations.into_iter().partition(|a| a.resource_id == from_resource_id);
+    let initiative_ids: Vec<String> = moving.iter().map(|a| a.initiative_id.clone()).collect();
+    ensure_scenarios_unlocked(&mut tx, &[], &initiative_ids).await?;
+
+    let mut merged = Vec::new();
+    for allocation in &moving {
+        match existing.iter().find(|a| a.initiative_id == allocation.initiative_id) {
+            Some(kept) => {
+                let merge = merge_allocation(kept, allocation);
+                sqlx::query!(
+                    r#"UPDATE initiative_resources SET allocation_percent = ?, start_date = ?, end_date = ?, updated_at = ?
+                    WHERE id = ?"#,
+                    merge.allocation_percent,
+                    merge.start_date,
+                    merge.end_date,
+                    now,
+                    kept.id
+                )
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+
+                sqlx::query!("DELETE FROM initiative_resources WHERE id = ?", allocation.id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                merged.push(merge);
+            }
+            None => {
+                sqlx::query!(
+                    "UPDATE initiative_resources SET resource_id = ?, updated_at = ? WHERE id = ?",
+                    to_resource_id,
+                    now,
+                    allocation.id
+                )
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    let capped: Vec<&MergedAllocation> = merged.iter().filter(|m| m.capped_by > 0.0).collect();
+    let mut description = format!("Moved {} allocation(s) from {} to {}", moving.len(), from.name, to.name);
+    if !capped.is_empty() {
+        description.push_str(&format!("; {} merge(s) capped at 100%", capped.len()));
+    }
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Resource.name().to_string(),
+        entity_id: Some(from_resource_id),
+        action: "ReassignAllocations".to_string(),
+        description: Some(description),
+        before: serde_json::to_value(&moving).ok(),
+        after: Some(serde_json::json!({ "resource_id": to_resource_id, "merged": merged, "capped": capped })),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(moving.len() as u64)
+}
+
+// ============================================
+// ALLOCATION ANALYSIS COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_unallocated_resources(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<Resource>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // Most available (most idle) first
+    let rows: Vec<Resource> = sqlx::query_as!(
+        Resource,
+        r#"SELECT
+            r.id, r.name, r.role, r.skills, r.availability,
+            r.resource_pool_id, r.start_date, r.end_date, r.created_at, r.updated_at
+        FROM resources r
+        WHERE NOT EXISTS (
+            SELECT 1 FROM initiative_resources ir
+            JOIN initiatives i ON i.id = ir.initiative_id
+            WHERE ir.resource_id = r.id AND i.scenario_id = ?
+        )
+        ORDER BY r.availability DESC, r.name"#,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_headcount_demand(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<PeriodHeadcount>, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let initiatives = get_initiatives(db.clone(), Some(scenario_id.clone())).await?;
+    let periods = get_financial_periods(db.clone()).await?;
+    let allocations = get_scenario_allocations(&db, &scenario_id, None).await?;
+
+    Ok(assignments::calculate_headcount_demand(&periods, &allocations, &initiatives))
+}
+
+#[tauri::command]
+pub async fn detect_resource_conflicts(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, resource_id: Option<String>) -> Result<Vec<ResourceConflict>, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let initiatives = get_initiatives(db.clone(), Some(scenario_id.clone())).await?;
+
+    let mut resources = get_resources(db.clone(), None).await?;
+    if let Some(resource_id) = &resource_id {
+        resources.retain(|r| &r.id == resource_id);
+        if resources.is_empty() {
+            return Err(format!("Resource {} not found", resource_id));
+        }
+    }
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let allocations = get_scenario_allocations(&db, &scenario_id, resource_id.as_deref()).await?;
+
+    let time_off: Vec<ResourceTimeOff> = sqlx::query_as!(
+        ResourceTimeOff,
+        r#"SELECT id, resource_id, start_date, end_date, reason, created_at, updated_at
+        FROM resource_time_off WHERE ? IS NULL OR resource_id = ?"#,
+        resource_id,
+        resource_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut conflicts = assignments::detect_resource_conflicts(&resources, &allocations, &initiatives, &time_off);
+    conflicts.sort_by(|a, b| a.window_start.cmp(&b.window_start).then_with(|| a.resource_name.cmp(&b.resource_name)));
+
+    Ok(conflicts)
+}
+
+/// Named resources booked on overlapping dates in two or more of the given scenarios,
+/// which matters when the scenarios aren't alternatives and could all go ahead
+#[tauri::command]
+pub async fn detect_cross_scenario_contention(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_ids: Vec<String>) -> Result<Vec<Contention>, String> {
+    let mut seen = HashSet::new();
+    let scenario_ids: Vec<String> = scenario_ids.into_iter().filter(|id| seen.insert(id.clone())).collect();
+    if scenario_ids.len() < 2 {
+        return Err("Select at least two scenarios to compare".to_string());
+    }
+
+    let mut scenarios = Vec::with_capacity(scenario_ids.len());
+    let mut initiatives = Vec::new();
+    let mut allocations = Vec::new();
+    for scenario_id in &scenario_ids {
+        scenarios.push(get_scenario(db.clone(), scenario_id.clone()).await?);
+        initiatives.extend(get_initiatives(db.clone(), Some(scenario_id.clone())).await?);
+        allocations.extend(get_scenario_allocations(&db, scenario_id, None).await?);
+    }
+
+    let resources = get_resources(db.clone(), None).await?;
+
+    let mut contentions = assignments::detect_cross_scenario_contention(&resources, &scenarios, &allocations, &initiatives);
+    contentions.sort_by(|a, b| a.overlap_start.cmp(&b.overlap_start).then_with(|| a.resource_name.cmp(&b.resource_name)));
+
+    Ok(contentions)
+}
+
+/// For each required skill, the resources allocated in the scenario who have it
+#[tauri::command]
+pub async fn check_skill_coverage(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, required_skills: Vec<String>) -> Result<Vec<SkillCoverage>, String> {
+    if required_skills.iter().any(|s| s.trim().is_empty()) {
+        return Err("Required skills can't be blank".to_string());
+    }
+
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let allocated: HashSet<String> = get_scenario_allocations(&db, &scenario_id, None)
+        .await?
+        .into_iter()
+        .map(|a| a.resource_id)
+        .collect();
+    let mut resources = get_resources(db.clone(), None).await?;
+    resources.retain(|r| allocated.contains(&r.id));
+
+    Ok(skill_coverage(&required_skills, &resources))
+}
+
+/// Resources leaving within the next `within_days`, flagging those still booked on open
+/// initiatives beyond their end date. Locked snapshots are left out, as they repeat the
+/// scenarios they were taken from.
+#[tauri::command]
+pub async fn get_departing_resources(db: State<'_, tauri_plugin_sql::DbInstances>, within_days: i64) -> Result<Vec<DepartingResource>, String> {
+    if within_days < 0 {
+        return Err(format!("Days ahead must be zero or more, got {}", within_days));
+    }
+
+    let resources = get_resources(db.clone(), None).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let assignments: Vec<ActiveAssignment> = sqlx::query_as!(
+        ActiveAssignment,
+        r#"SELECT ir.resource_id, i.id as "initiative_id!", i.name as initiative_name, i.status as initiative_status,
+            s.id as "scenario_id!", s.name as scenario_name, COALESCE(ir.end_date, i.end_date) as end_date
+        FROM initiative_resources ir
+        JOIN initiatives i ON i.id = ir.initiative_id
+        JOIN scenarios s ON s.id = i.scenario_id
+        WHERE i.status NOT IN ('Complete', 'Cancelled') AND s.is_locked = 0
+        ORDER BY s.name, i.name"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(departing_resources(resources, &assignments, today(), within_days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allocation(resource_id: &str, percent: f64, start: Option<&str>, end: Option<&str>) -> InitiativeResource {
+        InitiativeResource {
+            id: format!("{}-alloc", resource_id),
+            initiative_id: "init".to_string(),
+            resource_id: resource_id.to_string(),
+            allocation_percent: percent,
+            start_date: start.map(str::to_string),
+            end_date: end.map(str::to_string),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn merged_allocations_are_capped_at_one_hundred() {
+        let kept = allocation("to", 60.0, Some("2027-01-01"), Some("2027-06-30"));
+        let moved = allocation("from", 50.0, Some("2026-11-01"), Some("2027-03-31"));
+
+        let merged = merge_allocation(&kept, &moved);
+        assert_eq!(merged.allocation_percent, 100.0);
+        assert_eq!(merged.capped_by, 10.0);
+        assert_eq!(merged.start_date.as_deref(), Some("2026-11-01"));
+        assert_eq!(merged.end_date.as_deref(), Some("2027-06-30"));
+
+        let open_ended = merge_allocation(&allocation("to", 20.0, None, None), &moved);
+        assert_eq!(open_ended.allocation_percent, 70.0);
+        assert_eq!(open_ended.capped_by, 0.0);
+        assert_eq!(open_ended.start_date, None);
+    }
+
+    #[test]
+    fn skills_match_case_insensitively_and_gaps_are_flagged() {
+        let resource = |id: &str, skills: Option<&str>| Resource {
+            id: id.to_string(),
+            name: id.to_string(),
+            role: None,
+            skills: skills.map(str::to_string),
+            availability: Some(100.0),
+            resource_pool_id: None,
+            start_date: None,
+            end_date: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let resources = vec![
+            resource("ana", Some(r#"["Rust", "SQL"]"#)),
+            resource("ben", Some(r#"["rust "]"#)),
+            resource("cal", Some("not json")),
+            resource("dee", None),
+        ];
+        let required = ["RUST".to_string(), "Kafka".to_string(), "rust".to_string()];
+
+        let coverage = skill_coverage(&required, &resources);
+        assert_eq!(coverage.len(), 2);
+        assert_eq!(coverage[0].skill, "RUST");
+        assert_eq!(coverage[0].resource_ids, vec!["ana", "ben"]);
+        assert!(!coverage[0].is_gap);
+        assert_eq!(coverage[1].resource_count, 0);
+        assert!(coverage[1].is_gap);
+    }
+
+    #[test]
+    fn departing_resources_flag_work_running_past_their_end() {
+        let resource = |id: &str, end: Option<&str>| Resource {
+            id: id.to_string(),
+            name: id.to_string(),
+            role: None,
+            skills: None,
+            availability: Some(100.0),
+            resource_pool_id: None,
+            start_date: None,
+            end_date: end.map(str::to_string),
+            created_at: None,
+            updated_at: None,
+        };
+        let assignment = |resource_id: &str, initiative_id: &str, end: Option<&str>| ActiveAssignment {
+            resource_id: resource_id.to_string(),
+            initiative_id: initiative_id.to_string(),
+            initiative_name: initiative_id.to_string(),
+            initiative_status: "InProgress".to_string(),
+            scenario_id: "baseline".to_string(),
+            scenario_name: "Baseline".to_string(),
+            end_date: end.map(str::to_string),
+        };
+        let resources = vec![
+            resource("ana", Some("2027-02-28")),
+            resource("ben", Some("2027-01-15")),
+            resource("cal", Some("2027-06-30")),
+            resource("dee", None),
+            resource("eve", Some("2026-12-31")),
+        ];
+        let assignments = vec![
+            assignment("ana", "migrate", Some("2027-03-31")),
+            assignment("ana", "tidy", Some("2027-02-01")),
+            assignment("ben", "tidy", Some("2027-01-15")),
+            assignment("cal", "migrate", None),
+        ];
+
+        let departing = departing_resources(resources, &assignments, parse_date("2027-01-01").unwrap(), 90);
+        let ids: Vec<&str> = departing.iter().map(|d| d.resource.id.as_str()).collect();
+        assert_eq!(ids, ["ben", "ana"]);
+        assert_eq!(departing[0].days_until_departure, 14);
+        // Finishing on the last day counts as finished
+        assert!(!departing[0].has_unfinished_work);
+        assert!(departing[1].has_unfinished_work);
+        assert_eq!(departing[1].unfinished_assignments.len(), 1);
+        assert_eq!(departing[1].unfinished_assignments[0].initiative_id, "migrate");
+    }
+}
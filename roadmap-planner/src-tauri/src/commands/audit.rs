@@ -0,0 +1,91 @@
+// Audit log for Roadmap Planner
+// Helpers for recording changes plus read commands
+
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: String,
+    pub group_id: Option<String>,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub action: String,
+    pub description: Option<String>,
+    pub before_json: Option<String>,
+    pub after_json: Option<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NewAuditEntry {
+    pub group_id: Option<String>,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub action: String,
+    pub description: Option<String>,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// Write an audit entry on the given connection, so it joins the caller's transaction
+pub async fn record_audit(conn: &mut SqliteConnection, entry: NewAuditEntry) -> Result<String, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = get_current_timestamp();
+    let before_json = entry.before.map(|v| v.to_string());
+    let after_json = entry.after.map(|v| v.to_string());
+
+    sqlx::query!(
+        r#"INSERT INTO audit_log (id, group_id, entity_type, entity_id, action, description, before_json, after_json, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        id,
+        entry.group_id,
+        entry.entity_type,
+        entry.entity_id,
+        entry.action,
+        entry.description,
+        before_json,
+        after_json,
+        now
+    )
+    .execute(conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(id)
+}
+
+// ============================================
+// AUDIT LOG COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_audit_log(db: State<'_, tauri_plugin_sql::DbInstances>, entity_type: Option<String>, entity_id: Option<String>, limit: Option<i64>) -> Result<Vec<AuditEntry>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let limit = limit.unwrap_or(100);
+
+    let rows: Vec<AuditEntry> = sqlx::query_as!(
+        AuditEntry,
+        r#"SELECT
+            id, group_id, entity_type, entity_id, action, description,
+            before_json, after_json, created_at
+        FROM audit_log
+        WHERE (? IS NULL OR entity_type = ?) AND (? IS NULL OR entity_id = ?)
+        ORDER BY created_at DESC
+        LIMIT ?"#,
+        entity_type,
+        entity_type,
+        entity_id,
+        entity_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
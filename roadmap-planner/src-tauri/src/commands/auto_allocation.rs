@@ -0,0 +1,271 @@
+// Tauri commands for capability default pools and generated pool allocations
+// An initiative's effort is requested from the pools behind its linked systems' capabilities
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::resources::InitiativeResourceRequirement;
+use crate::commands::ensure_scenarios_unlocked;
+use crate::commands::entities::EntityType;
+use crate::commands::fetch::{fetch_capabilities, fetch_initiatives, fetch_resource_pools, single};
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tauri::State;
+
+// How an initiative's effort estimate is shared between the pools it maps to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AllocationWeighting {
+    // The same share for every pool
+    #[default]
+    Even,
+    // In proportion to the linked systems each pool covers
+    BySystemCount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoAllocation {
+    pub initiative_id: String,
+    pub weighting: AllocationWeighting,
+    // Generated allocations from an earlier run that these replace
+    pub replaced: i64,
+    pub created: Vec<InitiativeResourceRequirement>,
+    // Linked systems with no capability, or whose capability has no default pool up its tree
+    pub unmapped_system_ids: Vec<String>,
+    // One line for the confirmation toast
+    pub message: String,
+}
+
+/// The capability's default pool, else the nearest ancestor's. `capabilities` maps each
+/// capability to its parent and default pool.
+pub fn resolve_default_pool(capability_id: &str, capabilities: &HashMap<String, (Option<String>, Option<String>)>) -> Option<String> {
+    let mut seen = HashSet::new();
+    let mut current = capability_id;
+    while seen.insert(current) {
+        let (parent_id, pool_id) = capabilities.get(current)?;
+        if let Some(pool_id) = pool_id {
+            return Some(pool_id.clone());
+        }
+        current = parent_id.as_deref()?;
+    }
+    None
+}
+
+/// Share `estimate` between pools by their system counts, in pool order
+pub fn split_effort(estimate: f64, system_counts: &BTreeMap<String, i64>, weighting: AllocationWeighting) -> Vec<(String, f64)> {
+    let weight = |count: i64| match weighting {
+        AllocationWeighting::Even => 1.0,
+        AllocationWeighting::BySystemCount => count as f64,
+    };
+    let total: f64 = system_counts.values().map(|c| weight(*c)).sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    system_counts
+        .iter()
+        .map(|(pool_id, count)| (pool_id.clone(), estimate * weight(*count) / total))
+        .collect()
+}
+
+// ============================================
+// AUTO ALLOCATION COMMANDS
+// ============================================
+
+/// Set or clear the pool that works on a capability by default
+#[tauri::command]
+pub async fn set_capability_default_pool(db: State<'_, tauri_plugin_sql::DbInstances>, capability_id: String, pool_id: Option<String>) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let capability = single(fetch_capabilities(&mut tx, std::slice::from_ref(&capability_id)).await?, EntityType::Capability, &capability_id)?;
+    let resource_pool = match &pool_id {
+        Some(id) => Some(single(fetch_resource_pools(&mut tx, std::slice::from_ref(id)).await?, EntityType::ResourcePool, id)?),
+        None => None,
+    };
+    let before = sqlx::query_scalar!("SELECT default_pool_id FROM capabilities WHERE id = ?", capability.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let now = get_current_timestamp();
+    sqlx::query!("UPDATE capabilities SET default_pool_id = ?, updated_at = ? WHERE id = ?", pool_id, now, capability.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let description = match &resource_pool {
+        Some(p) => format!("Made \"{}\" the default pool for \"{}\"", p.name, capability.name),
+        None => format!("Cleared the default pool for \"{}\"", capability.name),
+    };
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Capability.name().to_string(),
+        entity_id: Some(capability.id.clone()),
+        action: "SetDefaultPool".to_string(),
+        description: Some(description),
+        before: Some(serde_json::json!({ "default_pool_id": before })),
+        after: Some(serde_json::json!({ "default_pool_id": pool_id })),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Request the initiative's effort estimate from the default pools of its linked systems'
+/// capabilities. Allocations this generated before are replaced; ones entered by hand are
+/// left alone. New allocations await the pools' approval like any other request.
+#[tauri::command]
+pub async fn auto_allocate_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String, weighting: Option<AllocationWeighting>) -> Result<AutoAllocation, String> {
+    let weighting = weighting.unwrap_or_default();
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let initiative = single(fetch_initiatives(&mut tx, std::slice::from_ref(&initiative_id)).await?, EntityType::Initiative, &initiative_id)?;
+    ensure_scenarios_unlocked(&mut tx, &[], std::slice::from_ref(&initiative.id)).await?;
+    let estimate = initiative
+        .effort_estimate
+        .filter(|e| *e > 0.0)
+        .ok_or_else(|| format!("\"{}\" has no effort estimate to allocate", initiative.name))?;
+
+    let capabilities: HashMap<String, (Option<String>, Option<String>)> =
+        sqlx::query!("SELECT id, parent_id, default_pool_id FROM capabilities")
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|r| (r.id, (r.parent_id, r.default_pool_id)))
+            .collect();
+
+    let systems = sqlx::query!(
+        r#"SELECT s.id, s.capability_id FROM system_initiatives si
+        JOIN systems s ON s.id = si.system_id
+        WHERE si.initiative_id = ?
+        ORDER BY s.name, s.id"#,
+        initiative.id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut system_counts: BTreeMap<String, i64> = BTreeMap::new();
+    let mut unmapped_system_ids = Vec::new();
+    for system in systems {
+        match system.capability_id.as_deref().and_then(|c| resolve_default_pool(c, &capabilities)) {
+            Some(pool_id) => *system_counts.entry(pool_id).or_default() += 1,
+            None => unmapped_system_ids.push(system.id),
+        }
+    }
+
+    let replaced = sqlx::query!(
+        "DELETE FROM initiative_resource_requirements WHERE initiative_id = ? AND generated = 1",
+        initiative.id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected() as i64;
+
+    let now = get_current_timestamp();
+    let mut created = Vec::new();
+    for (pool_id, effort) in split_effort(estimate, &system_counts, weighting) {
+        let requirement = InitiativeResourceRequirement {
+            id: uuid::Uuid::new_v4().to_string(),
+            initiative_id: initiative.id.clone(),
+            resource_pool_id: pool_id,
+            effort_required: effort,
+            period_start: None,
+            period_end: None,
+            role: None,
+            approval_status: "Requested".to_string(),
+            approver_note: None,
+            generated: true,
+            created_at: Some(now.clone()),
+        };
+        sqlx::query!(
+            r#"INSERT INTO initiative_resource_requirements
+                (id, initiative_id, resource_pool_id, effort_required, approval_status, generated, created_at)
+            VALUES (?, ?, ?, ?, ?, 1, ?)"#,
+            requirement.id,
+            requirement.initiative_id,
+            requirement.resource_pool_id,
+            requirement.effort_required,
+            requirement.approval_status,
+            requirement.created_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        created.push(requirement);
+    }
+
+    let message = if created.is_empty() {
+        format!("No linked system of \"{}\" maps to a default pool; removed {} generated allocation(s)", initiative.name, replaced)
+    } else {
+        format!(
+            "Requested {} of effort for \"{}\" from {} pool(s), replacing {} generated allocation(s)",
+            estimate,
+            initiative.name,
+            created.len(),
+            replaced
+        )
+    };
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Initiative.name().to_string(),
+        entity_id: Some(initiative.id.clone()),
+        action: "AutoAllocate".to_string(),
+        description: Some(message.clone()),
+        after: serde_json::to_value(&created).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(AutoAllocation {
+        initiative_id: initiative.id,
+        weighting,
+        replaced,
+        created,
+        unmapped_system_ids,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn children_inherit_the_nearest_default_pool() {
+        let capability = |parent: Option<&str>, pool: Option<&str>| (parent.map(str::to_string), pool.map(str::to_string));
+        let capabilities: HashMap<String, (Option<String>, Option<String>)> = [
+            ("payments".to_string(), capability(None, Some("payments-team"))),
+            ("cards".to_string(), capability(Some("payments"), None)),
+            ("disputes".to_string(), capability(Some("cards"), Some("disputes-team"))),
+            ("orphan".to_string(), capability(None, None)),
+            ("loop".to_string(), capability(Some("loop"), None)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(resolve_default_pool("cards", &capabilities).as_deref(), Some("payments-team"));
+        assert_eq!(resolve_default_pool("disputes", &capabilities).as_deref(), Some("disputes-team"));
+        assert_eq!(resolve_default_pool("orphan", &capabilities), None);
+        assert_eq!(resolve_default_pool("loop", &capabilities), None);
+    }
+
+    #[test]
+    fn effort_is_split_evenly_or_by_system_count() {
+        let counts: BTreeMap<String, i64> = [("a".to_string(), 3), ("b".to_string(), 1)].into_iter().collect();
+
+        assert_eq!(split_effort(100.0, &counts, AllocationWeighting::Even), [("a".to_string(), 50.0), ("b".to_string(), 50.0)]);
+        assert_eq!(split_effort(100.0, &counts, AllocationWeighting::BySystemCount), [("a".to_string(), 75.0), ("b".to_string(), 25.0)]);
+        assert!(split_effort(100.0, &BTreeMap::new(), AllocationWeighting::Even).is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+// Database backup helpers for Roadmap Planner commands
+// Snapshots are written beside the workspace file with VACUUM INTO
+
+use chrono::Utc;
+use sqlx::{Row, SqlitePool};
+use std::path::PathBuf;
+
+const BACKUP_DIR: &str = "backups";
+
+/// Path of the open workspace file, as reported by SQLite
+pub async fn database_path(pool: &SqlitePool) -> Result<PathBuf, String> {
+    let rows = sqlx::query("PRAGMA database_list")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    rows.iter()
+        .find(|r| r.get::<String, _>("name") == "main")
+        .map(|r| r.get::<String, _>("file"))
+        .filter(|file| !file.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| "The workspace is not backed by a file".to_string())
+}
+
+/// Write a consistent copy of the workspace to backups/<label>-<timestamp>.db.
+/// Must be called outside a transaction.
+pub async fn create_backup(pool: &SqlitePool, label: &str) -> Result<String, String> {
+    let database = database_path(pool).await?;
+    let dir = database
+        .parent()
+        .map(|p| p.join(BACKUP_DIR))
+        .ok_or_else(|| "Could not resolve the backup directory".to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let file = dir.join(format!("{}-{}.db", label, Utc::now().format("%Y%m%dT%H%M%S%.3f")));
+    let target = file.to_string_lossy().to_string();
+
+    sqlx::query("VACUUM INTO ?")
+        .bind(&target)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Backup failed: {}", e))?;
+
+    Ok(target)
+}
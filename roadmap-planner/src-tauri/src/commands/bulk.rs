@@ -0,0 +1,521 @@
+// Tauri commands for bulk operations
+// Batch deletes with a cascade impact preview, and batch field updates
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::entities::EntityType;
+use crate::commands::journal::JournaledOperation;
+use crate::commands::{SCENARIO_LOCKED, ensure_scenarios_unlocked, get_capabilities, validate_hex_colour, validate_initiative_appearance};
+use crate::db::{Capability, get_current_timestamp};
+use crate::commands::rows::{ids_json, row_to_json};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+// {ids} is replaced with the bound id list for each rule
+const IDS: &str = "(SELECT value FROM json_each(?1))";
+
+// Rows removed by ON DELETE CASCADE (or by the single-delete command) per entity type
+fn cascade_rules(entity_type: EntityType) -> &'static [(&'static str, &'static str, &'static str)] {
+    match entity_type {
+        EntityType::Initiative => &[
+            ("Milestones", "milestones", "initiative_id IN {ids}"),
+            ("NamedAllocations", "initiative_resources", "initiative_id IN {ids}"),
+            ("PoolAllocations", "initiative_resource_requirements", "initiative_id IN {ids}"),
+            ("PoolSplits", "initiative_pool_splits", "initiative_id IN {ids}"),
+            ("Dependencies", "initiative_dependencies", "predecessor_id IN {ids} OR successor_id IN {ids}"),
+            ("SystemLinks", "system_initiatives", "initiative_id IN {ids}"),
+            ("ConstraintLinks", "initiative_constraints", "initiative_id IN {ids}"),
+            ("CapabilityLinks", "initiative_capabilities", "initiative_id IN {ids}"),
+            ("Comments", "comments", "initiative_id IN {ids}"),
+            ("ObjectiveLinks", "initiative_objectives", "initiative_id IN {ids}"),
+        ],
+        EntityType::System => &[
+            ("SystemDependencies", "system_dependencies", "source_system_id IN {ids} OR target_system_id IN {ids}"),
+            ("SystemLinks", "system_initiatives", "system_id IN {ids}"),
+            ("Interfaces", "interfaces", "source_system_id IN {ids} OR target_system_id IN {ids}"),
+        ],
+        EntityType::Capability => &[
+            ("CapabilityLinks", "initiative_capabilities", "capability_id IN {ids}"),
+            ("Assessments", "capability_assessments", "capability_id IN {ids}"),
+        ],
+        EntityType::Scenario => &[
+            ("Initiatives", "initiatives", "scenario_id IN {ids}"),
+            ("Milestones", "milestones", "initiative_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids})"),
+            ("NamedAllocations", "initiative_resources", "initiative_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids})"),
+            ("PoolAllocations", "initiative_resource_requirements", "initiative_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids})"),
+            ("PoolSplits", "initiative_pool_splits", "initiative_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids})"),
+            ("Dependencies", "initiative_dependencies", "predecessor_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids}) OR successor_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids})"),
+            ("SystemLinks", "system_initiatives", "initiative_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids})"),
+            ("ConstraintLinks", "initiative_constraints", "initiative_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids})"),
+            ("CapabilityLinks", "initiative_capabilities", "initiative_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids})"),
+            ("Comments", "comments", "initiative_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids})"),
+            ("ObjectiveLinks", "initiative_objectives", "initiative_id IN (SELECT id FROM initiatives WHERE scenario_id IN {ids})"),
+        ],
+        EntityType::ResourcePool => &[
+            ("PoolAllocations", "initiative_resource_requirements", "resource_pool_id IN {ids}"),
+            ("PoolSplits", "initiative_pool_splits", "resource_pool_id IN {ids}"),
+            ("RoleCapacities", "pool_role_capacities", "resource_pool_id IN {ids}"),
+        ],
+        EntityType::Resource => &[
+            ("NamedAllocations", "initiative_resources", "resource_id IN {ids}"),
+            ("Mentions", "comment_mentions", "resource_id IN {ids}"),
+            ("TimeOff", "resource_time_off", "resource_id IN {ids}"),
+        ],
+        EntityType::Constraint => &[
+            ("ConstraintLinks", "initiative_constraints", "constraint_id IN {ids}"),
+        ],
+        EntityType::FinancialPeriod => &[],
+    }
+}
+
+// References set to NULL by ON DELETE SET NULL, leaving the surviving row detached
+fn dangling_rules(entity_type: EntityType) -> &'static [(&'static str, &'static str)] {
+    match entity_type {
+        EntityType::Capability => &[("capabilities", "parent_id"), ("systems", "capability_id")],
+        EntityType::Scenario => &[("scenarios", "parent_scenario_id")],
+        EntityType::ResourcePool => &[("resources", "resource_pool_id")],
+        EntityType::Resource => &[("comments", "author_resource_id")],
+        _ => &[],
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CascadeCount {
+    pub label: String,
+    pub table: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DanglingReference {
+    pub table: String,
+    pub column: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefusedDelete {
+    pub id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkDeleteResult {
+    pub entity_type: EntityType,
+    pub dry_run: bool,
+    pub deleted_ids: Vec<String>,
+    pub not_found_ids: Vec<String>,
+    pub refused: Vec<RefusedDelete>,
+    pub cascade: Vec<CascadeCount>,
+    pub dangling: Vec<DanglingReference>,
+    pub audit_group_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkUpdateResult {
+    pub updated_ids: Vec<String>,
+    pub not_found_ids: Vec<String>,
+    pub audit_group_id: Option<String>,
+}
+
+// ============================================
+// BULK DELETE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn bulk_delete(db: State<'_, tauri_plugin_sql::DbInstances>, entity_type: EntityType, ids: Vec<String>, dry_run: bool) -> Result<BulkDeleteResult, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let table = entity_type.table();
+
+    // De-duplicate while keeping the caller's order
+    let mut seen = HashSet::new();
+    let ids: Vec<String> = ids.into_iter().filter(|id| seen.insert(id.clone())).collect();
+
+    let found: HashSet<String> = sqlx::query_scalar::<_, String>(&format!("SELECT id FROM {} WHERE id IN {}", table, IDS))
+        .bind(ids_json(&ids))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .collect();
+
+    let mut refused = Vec::new();
+    if entity_type == EntityType::Scenario {
+        let baselines: HashSet<String> = sqlx::query_scalar::<_, String>(&format!("SELECT id FROM scenarios WHERE is_baseline = 1 AND id IN {}", IDS))
+            .bind(ids_json(&ids))
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .collect();
+        for id in ids.iter().filter(|id| baselines.contains(*id) || id.as_str() == "baseline") {
+            refused.push(RefusedDelete { id: id.clone(), reason: "Cannot delete the baseline scenario".to_string() });
+        }
+    }
+
+    // Owned systems and initiatives need a new owner first, which delete_resource can take
+    if entity_type == EntityType::Resource {
+        let owners = sqlx::query(&format!(
+            "SELECT owner_resource_id AS id, COUNT(*) AS owned FROM (
+                SELECT owner_resource_id FROM systems UNION ALL SELECT owner_resource_id FROM initiatives
+            ) WHERE owner_resource_id IN {} GROUP BY owner_resource_id",
+            IDS
+        ))
+        .bind(ids_json(&ids))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        for row in owners {
+            let owned: i64 = row.get("owned");
+            refused.push(RefusedDelete {
+                id: row.get("id"),
+                reason: format!("Owns {} system(s) or initiative(s); reassign ownership first", owned),
+            });
+        }
+    }
+
+    // Locked snapshots, and initiatives inside them, stay as they are
+    let locked_sql = match entity_type {
+        EntityType::Scenario => Some(format!("SELECT id FROM scenarios WHERE is_locked = 1 AND id IN {}", IDS)),
+        EntityType::Initiative => Some(format!(
+            "SELECT i.id FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE s.is_locked = 1 AND i.id IN {}",
+            IDS
+        )),
+        _ => None,
+    };
+    if let Some(sql) = locked_sql {
+        let locked: HashSet<String> = sqlx::query_scalar::<_, String>(&sql)
+            .bind(ids_json(&ids))
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .collect();
+        for id in ids.iter().filter(|id| locked.contains(*id)) {
+            if !refused.iter().any(|r| &r.id == id) {
+                refused.push(RefusedDelete { id: id.clone(), reason: SCENARIO_LOCKED.to_string() });
+            }
+        }
+    }
+
+    let refused_ids: HashSet<&str> = refused.iter().map(|r| r.id.as_str()).collect();
+    let not_found_ids: Vec<String> = ids.iter().filter(|id| !found.contains(*id)).cloned().collect();
+    let deletable: Vec<String> = ids
+        .iter()
+        .filter(|id| found.contains(*id) && !refused_ids.contains(id.as_str()))
+        .cloned()
+        .collect();
+    let deletable_json = ids_json(&deletable);
+
+    let mut cascade = Vec::new();
+    for (label, cascade_table, condition) in cascade_rules(entity_type) {
+        let sql = format!("SELECT COUNT(*) FROM {} WHERE {}", cascade_table, condition.replace("{ids}", IDS));
+        let count: i64 = sqlx::query_scalar(&sql)
+            .bind(&deletable_json)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        cascade.push(CascadeCount { label: label.to_string(), table: cascade_table.to_string(), count });
+    }
+
+    let mut dangling = Vec::new();
+    for (dangling_table, column) in dangling_rules(entity_type) {
+        // Rows that are themselves being deleted don't dangle
+        let sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE {} IN {} AND id NOT IN {}",
+            dangling_table, column, IDS, IDS
+        );
+        let count: i64 = sqlx::query_scalar(&sql)
+            .bind(&deletable_json)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        dangling.push(DanglingReference { table: dangling_table.to_string(), column: column.to_string(), count });
+    }
+
+    if dry_run || deletable.is_empty() {
+        return Ok(BulkDeleteResult {
+            entity_type,
+            dry_run,
+            deleted_ids: if dry_run { deletable } else { Vec::new() },
+            not_found_ids,
+            refused,
+            cascade,
+            dangling,
+            audit_group_id: None,
+        });
+    }
+
+    let operation = JournaledOperation::start(pool, "bulk_delete", serde_json::json!({ "entityType": entity_type, "ids": ids, "dryRun": false })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Snapshot the rows so the grouped audit entry can be undone
+    let snapshot: Vec<serde_json::Value> = sqlx::query(&format!("SELECT * FROM {} WHERE id IN {}", table, IDS))
+        .bind(&deletable_json)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(row_to_json)
+        .collect();
+
+    // Same rule as delete_system's default strategy
+    if entity_type == EntityType::System {
+        sqlx::query(&format!(
+            "DELETE FROM interfaces WHERE source_system_id IN {} OR target_system_id IN {}",
+            IDS, IDS
+        ))
+        .bind(&deletable_json)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    sqlx::query(&format!("DELETE FROM {} WHERE id IN {}", table, IDS))
+        .bind(&deletable_json)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let group_id = uuid::Uuid::new_v4().to_string();
+    record_audit(&mut tx, NewAuditEntry {
+        group_id: Some(group_id.clone()),
+        entity_type: entity_type.name().to_string(),
+        entity_id: None,
+        action: "BulkDelete".to_string(),
+        description: Some(format!("Deleted {} {} rows", deletable.len(), table)),
+        before: Some(serde_json::Value::Array(snapshot)),
+        after: None,
+    })
+    .await?;
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(BulkDeleteResult {
+        entity_type,
+        dry_run,
+        deleted_ids: deletable,
+        not_found_ids,
+        refused,
+        cascade,
+        dangling,
+        audit_group_id: Some(group_id),
+    })
+}
+
+// ============================================
+// BULK UPDATE COMMANDS
+// ============================================
+
+// Sets one initiative column on every listed row, with a single grouped audit entry
+async fn set_initiative_column(pool: &sqlx::SqlitePool, ids: Vec<String>, column: &'static str, value: Option<String>) -> Result<BulkUpdateResult, String> {
+    let mut seen = HashSet::new();
+    let ids: Vec<String> = ids.into_iter().filter(|id| seen.insert(id.clone())).collect();
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    ensure_scenarios_unlocked(&mut tx, &[], &ids).await?;
+
+    let before: Vec<serde_json::Value> = sqlx::query(&format!("SELECT id, {} FROM initiatives WHERE id IN {}", column, IDS))
+        .bind(ids_json(&ids))
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(row_to_json)
+        .collect();
+
+    let found: HashSet<&str> = before.iter().filter_map(|row| row["id"].as_str()).collect();
+    let updated_ids: Vec<String> = ids.iter().filter(|id| found.contains(id.as_str())).cloned().collect();
+    let not_found_ids: Vec<String> = ids.iter().filter(|id| !found.contains(id.as_str())).cloned().collect();
+
+    if updated_ids.is_empty() {
+        return Ok(BulkUpdateResult { updated_ids, not_found_ids, audit_group_id: None });
+    }
+
+    let now = get_current_timestamp();
+    sqlx::query(&format!("UPDATE initiatives SET {} = ?2, updated_at = ?3 WHERE id IN {}", column, IDS))
+        .bind(ids_json(&updated_ids))
+        .bind(&value)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let after: Vec<serde_json::Value> = updated_ids
+        .iter()
+        .map(|id| serde_json::json!({ "id": id, column: value }))
+        .collect();
+
+    let group_id = uuid::Uuid::new_v4().to_string();
+    record_audit(&mut tx, NewAuditEntry {
+        group_id: Some(group_id.clone()),
+        entity_type: EntityType::Initiative.name().to_string(),
+        entity_id: None,
+        action: "BulkUpdate".to_string(),
+        description: Some(format!("Set {} on {} initiatives", column, updated_ids.len())),
+        before: Some(serde_json::Value::Array(before)),
+        after: Some(serde_json::Value::Array(after)),
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(BulkUpdateResult { updated_ids, not_found_ids, audit_group_id: Some(group_id) })
+}
+
+/// A NULL colour puts the initiatives back on their capability lane colour
+#[tauri::command]
+pub async fn set_initiative_colours(db: State<'_, tauri_plugin_sql::DbInstances>, ids: Vec<String>, colour: Option<String>) -> Result<BulkUpdateResult, String> {
+    validate_initiative_appearance(colour.as_deref(), None)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    set_initiative_column(pool, ids, "colour", colour).await
+}
+
+#[tauri::command]
+pub async fn set_initiative_icons(db: State<'_, tauri_plugin_sql::DbInstances>, ids: Vec<String>, icon: Option<String>) -> Result<BulkUpdateResult, String> {
+    validate_initiative_appearance(None, icon.as_deref())?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    set_initiative_column(pool, ids, "icon", icon).await
+}
+
+/// The root and its descendants, parents before children and siblings in the order given,
+/// each paired with the next palette colour in turn
+pub fn palette_assignments<'a>(capabilities: &'a [Capability], root_id: &str, palette: &[String]) -> Vec<(&'a Capability, String)> {
+    let mut children: HashMap<&str, Vec<&Capability>> = HashMap::new();
+    for capability in capabilities {
+        if let Some(parent) = capability.parent_id.as_deref() {
+            children.entry(parent).or_default().push(capability);
+        }
+    }
+
+    let mut ordered = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack: Vec<&Capability> = capabilities.iter().filter(|c| c.id == root_id).collect();
+    while let Some(capability) = stack.pop() {
+        if !visited.insert(capability.id.as_str()) {
+            continue;
+        }
+        ordered.push(capability);
+        stack.extend(children.get(capability.id.as_str()).into_iter().flatten().rev());
+    }
+
+    ordered
+        .into_iter()
+        .zip(palette.iter().cycle())
+        .map(|(capability, colour)| (capability, colour.clone()))
+        .collect()
+}
+
+/// Recolour a capability and everything under it from the palette, round-robin in sort order.
+/// Returns how many capabilities were recoloured.
+#[tauri::command]
+pub async fn apply_palette(db: State<'_, tauri_plugin_sql::DbInstances>, root_capability_id: String, palette: Vec<String>) -> Result<u64, String> {
+    if palette.is_empty() {
+        return Err("The palette needs at least one colour".to_string());
+    }
+    for colour in &palette {
+        validate_hex_colour(colour)?;
+    }
+
+    // Already in sort order, which the walk keeps for siblings
+    let capabilities = get_capabilities(db.clone()).await?;
+    if !capabilities.iter().any(|c| c.id == root_capability_id) {
+        return Err(format!("Capability {} not found", root_capability_id));
+    }
+    let assignments = palette_assignments(&capabilities, &root_capability_id, &palette);
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let operation = JournaledOperation::start(pool, "apply_palette", serde_json::json!({ "rootCapabilityId": root_capability_id, "palette": palette })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let now = get_current_timestamp();
+    for (capability, colour) in &assignments {
+        sqlx::query!("UPDATE capabilities SET colour = ?, updated_at = ? WHERE id = ?", colour, now, capability.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let before: Vec<serde_json::Value> = assignments
+        .iter()
+        .map(|(c, _)| serde_json::json!({ "id": c.id, "colour": c.colour }))
+        .collect();
+    let after: Vec<serde_json::Value> = assignments
+        .iter()
+        .map(|(c, colour)| serde_json::json!({ "id": c.id, "colour": colour }))
+        .collect();
+
+    record_audit(&mut tx, NewAuditEntry {
+        group_id: Some(uuid::Uuid::new_v4().to_string()),
+        entity_type: EntityType::Capability.name().to_string(),
+        entity_id: Some(root_capability_id),
+        action: "BulkUpdate".to_string(),
+        description: Some(format!("Applied a {} colour palette to {} capabilities", palette.len(), assignments.len())),
+        before: Some(serde_json::Value::Array(before)),
+        after: Some(serde_json::Value::Array(after)),
+    })
+    .await?;
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(assignments.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capability(id: &str, parent_id: Option<&str>) -> Capability {
+        Capability {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            capability_type: "Business".to_string(),
+            parent_id: parent_id.map(|p| p.to_string()),
+            colour: None,
+            sort_order: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn palette_cycles_through_the_branch_in_order() {
+        let capabilities = vec![
+            capability("payments", None),
+            capability("cards", Some("payments")),
+            capability("issuing", Some("cards")),
+            capability("transfers", Some("payments")),
+            capability("onboarding", None),
+        ];
+        let palette = vec!["#111111".to_string(), "#222222".to_string()];
+
+        let assigned: Vec<(&str, String)> = palette_assignments(&capabilities, "payments", &palette)
+            .into_iter()
+            .map(|(c, colour)| (c.id.as_str(), colour))
+            .collect();
+        assert_eq!(
+            assigned,
+            [
+                ("payments", "#111111".to_string()),
+                ("cards", "#222222".to_string()),
+                ("issuing", "#111111".to_string()),
+                ("transfers", "#222222".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,213 @@
+// Tauri commands for working calendars
+// Working weekdays and holidays used when effort is turned into dates
+
+use crate::commands::engine::calendar::{WorkingCalendar, parse_working_days};
+use crate::commands::engine::dates::parse_date;
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Calendar {
+    pub id: String,
+    pub name: String,
+    // Comma-separated weekday abbreviations, e.g. "Mon,Tue,Wed,Thu,Fri"
+    pub working_days: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarHoliday {
+    pub id: String,
+    pub calendar_id: String,
+    pub date: String,
+    pub name: Option<String>,
+    pub created_at: Option<String>,
+}
+
+/// The calendar to schedule with; without an id, a Monday-to-Friday week with no holidays
+pub async fn load_working_calendar(db: &State<'_, tauri_plugin_sql::DbInstances>, calendar_id: Option<&str>) -> Result<WorkingCalendar, String> {
+    let Some(calendar_id) = calendar_id else {
+        return Ok(WorkingCalendar::default());
+    };
+
+    let calendar = get_working_calendar(db.clone(), calendar_id.to_string()).await?;
+    let holidays: BTreeSet<_> = get_calendar_holidays(db.clone(), calendar_id.to_string())
+        .await?
+        .iter()
+        .filter_map(|h| parse_date(&h.date))
+        .collect();
+
+    WorkingCalendar::new(&calendar.working_days, holidays)
+}
+
+// ============================================
+// WORKING CALENDAR COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_working_calendars(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<Vec<Calendar>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<Calendar> = sqlx::query_as!(
+        Calendar,
+        r#"SELECT id, name, working_days, created_at, updated_at
+        FROM working_calendars ORDER BY name"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_working_calendar(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<Calendar, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query_as!(
+        Calendar,
+        r#"SELECT id, name, working_days, created_at, updated_at
+        FROM working_calendars WHERE id = ?"#,
+        id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Working calendar {} not found", id))
+}
+
+#[tauri::command]
+pub async fn create_working_calendar(db: State<'_, tauri_plugin_sql::DbInstances>, calendar: Calendar) -> Result<Calendar, String> {
+    parse_working_days(&calendar.working_days)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO working_calendars (id, name, working_days, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?)"#,
+        calendar.id,
+        calendar.name,
+        calendar.working_days,
+        now,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_working_calendar(db, calendar.id).await
+}
+
+#[tauri::command]
+pub async fn update_working_calendar(db: State<'_, tauri_plugin_sql::DbInstances>, calendar: Calendar) -> Result<Calendar, String> {
+    parse_working_days(&calendar.working_days)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"UPDATE working_calendars SET name = ?, working_days = ?, updated_at = ? WHERE id = ?"#,
+        calendar.name,
+        calendar.working_days,
+        now,
+        calendar.id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_working_calendar(db, calendar.id).await
+}
+
+#[tauri::command]
+pub async fn delete_working_calendar(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM working_calendars WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================
+// CALENDAR HOLIDAY COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_calendar_holidays(db: State<'_, tauri_plugin_sql::DbInstances>, calendar_id: String) -> Result<Vec<CalendarHoliday>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<CalendarHoliday> = sqlx::query_as!(
+        CalendarHoliday,
+        r#"SELECT id, calendar_id, date, name, created_at
+        FROM calendar_holidays WHERE calendar_id = ? ORDER BY date"#,
+        calendar_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn create_calendar_holiday(db: State<'_, tauri_plugin_sql::DbInstances>, holiday: CalendarHoliday) -> Result<CalendarHoliday, String> {
+    if parse_date(&holiday.date).is_none() {
+        return Err(format!("Holiday date must be YYYY-MM-DD, got {}", holiday.date));
+    }
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO calendar_holidays (id, calendar_id, date, name, created_at)
+        VALUES (?, ?, ?, ?, ?)"#,
+        holiday.id,
+        holiday.calendar_id,
+        holiday.date,
+        holiday.name,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query_as!(
+        CalendarHoliday,
+        r#"SELECT id, calendar_id, date, name, created_at
+        FROM calendar_holidays WHERE id = ?"#,
+        holiday.id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_calendar_holiday(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM calendar_holidays WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,430 @@
+// Tauri commands for capability maturity assessments
+// CRUD plus heatmap and trend views over time
+
+use crate::commands::get_capabilities;
+use crate::db::{Capability, get_current_timestamp};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityAssessment {
+    pub id: String,
+    pub capability_id: String,
+    pub assessment_date: String,
+    pub maturity_score: i64,
+    pub target_score: Option<i64>,
+    pub notes: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaturityHeatmapNode {
+    pub capability_id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    pub depth: u32,
+    pub assessment_date: Option<String>,
+    pub maturity_score: Option<i64>,
+    pub target_score: Option<i64>,
+    // Average of the children's scores, used when the capability itself was never assessed
+    pub children_average: Option<f64>,
+    pub children: Vec<MaturityHeatmapNode>,
+}
+
+fn validate_assessment(assessment: &CapabilityAssessment) -> Result<(), String> {
+    if !(1..=5).contains(&assessment.maturity_score) {
+        return Err(format!("Maturity score must be between 1 and 5, got {}", assessment.maturity_score));
+    }
+    if let Some(target) = assessment.target_score {
+        if !(1..=5).contains(&target) {
+            return Err(format!("Target score must be between 1 and 5, got {}", target));
+        }
+    }
+    Ok(())
+}
+
+// ============================================
+// CAPABILITY ASSESSMENT COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_capability_assessments(db: State<'_, tauri_plugin_sql::DbInstances>, capability_id: String) -> Result<Vec<CapabilityAssessment>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<CapabilityAssessment> = sqlx::query_as!(
+        CapabilityAssessment,
+        r#"SELECT
+            id, capability_id, assessment_date, maturity_score, target_score,
+            notes, created_at, updated_at
+        FROM capability_assessments WHERE capability_id = ? ORDER BY assessment_date DESC"#,
+        capability_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_capability_assessment(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<CapabilityAssessment, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let row: CapabilityAssessment = sqlx::query_as!(
+        CapabilityAssessment,
+        r#"SELECT
+            id, capability_id, assessment_date, maturity_score, target_score,
+            notes, created_at, updated_at
+        FROM capability_assessments WHERE id = ?"#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn create_capability_assessment(db: State<'_, tauri_plugin_sql::DbInstances>, assessment: CapabilityAssessment) -> Result<CapabilityAssessment, String> {
+    validate_assessment(&assessment)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // One assessment per capability per date
+    let existing = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM capability_assessments WHERE capability_id = ? AND assessment_date = ?",
+        assessment.capability_id,
+        assessment.assessment_date
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if existing > 0 {
+        return Err(format!(
+            "Capability {} already has an assessment on {}",
+            assessment.capability_id, assessment.assessment_date
+        ));
+    }
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO capability_assessments (id, capability_id, assessment_date, maturity_score, target_score, notes, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+        assessment.id,
+        assessment.capability_id,
+        assessment.assessment_date,
+        assessment.maturity_score,
+        assessment.target_score,
+        assessment.notes,
+        now,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_capability_assessment(db, assessment.id).await
+}
+
+#[tauri::command]
+pub async fn update_capability_assessment(db: State<'_, tauri_plugin_sql::DbInstances>, assessment: CapabilityAssessment) -> Result<CapabilityAssessment, String> {
+    validate_assessment(&assessment)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // Moving an assessment to another date must not collide with an existing one
+    let existing = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM capability_assessments WHERE capability_id = ? AND assessment_date = ? AND id != ?",
+        assessment.capability_id,
+        assessment.assessment_date,
+        assessment.id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if existing > 0 {
+        return Err(format!(
+            "Capability {} already has an assessment on {}",
+            assessment.capability_id, assessment.assessment_date
+        ));
+    }
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"UPDATE capability_assessments SET
+            capability_id = ?, assessment_date = ?, maturity_score = ?,
+            target_score = ?, notes = ?, updated_at = ?
+        WHERE id = ?"#,
+        assessment.capability_id,
+        assessment.assessment_date,
+        assessment.maturity_score,
+        assessment.target_score,
+        assessment.notes,
+        now,
+        assessment.id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_capability_assessment(db, assessment.id).await
+}
+
+#[tauri::command]
+pub async fn delete_capability_assessment(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM capability_assessments WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================
+// MATURITY REPORTING COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_maturity_trend(db: State<'_, tauri_plugin_sql::DbInstances>, capability_id: String) -> Result<Vec<CapabilityAssessment>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<CapabilityAssessment> = sqlx::query_as!(
+        CapabilityAssessment,
+        r#"SELECT
+            id, capability_id, assessment_date, maturity_score, target_score,
+            notes, created_at, updated_at
+        FROM capability_assessments WHERE capability_id = ? ORDER BY assessment_date"#,
+        capability_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_maturity_heatmap(db: State<'_, tauri_plugin_sql::DbInstances>, as_of: String) -> Result<Vec<MaturityHeatmapNode>, String> {
+    let capabilities = get_capabilities(db.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let assessments: Vec<CapabilityAssessment> = sqlx::query_as!(
+        CapabilityAssessment,
+        r#"SELECT
+            id, capability_id, assessment_date, maturity_score, target_score,
+            notes, created_at, updated_at
+        FROM capability_assessments WHERE assessment_date <= ?"#,
+        as_of
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(build_heatmap(&capabilities, &latest_assessments(assessments, &as_of)))
+}
+
+/// Latest assessment at or before `as_of` for each capability. Two on the same date resolve to
+/// the one created last, then the higher id, so the choice never depends on row order.
+fn latest_assessments(assessments: Vec<CapabilityAssessment>, as_of: &str) -> HashMap<String, CapabilityAssessment> {
+    let key = |a: &CapabilityAssessment| (a.assessment_date.clone(), a.created_at.clone(), a.id.clone());
+    let mut latest: HashMap<String, CapabilityAssessment> = HashMap::new();
+    for assessment in assessments.into_iter().filter(|a| a.assessment_date.as_str() <= as_of) {
+        match latest.get(&assessment.capability_id) {
+            Some(current) if key(current) >= key(&assessment) => {}
+            _ => {
+                latest.insert(assessment.capability_id.clone(), assessment);
+            }
+        }
+    }
+    latest
+}
+
+fn build_heatmap(capabilities: &[Capability], latest: &HashMap<String, CapabilityAssessment>) -> Vec<MaturityHeatmapNode> {
+    let known: HashSet<&str> = capabilities.iter().map(|c| c.id.as_str()).collect();
+    let mut children: HashMap<&str, Vec<&Capability>> = HashMap::new();
+    let mut roots: Vec<&Capability> = Vec::new();
+
+    // Capabilities arrive in sort order, so sibling order is preserved
+    for capability in capabilities {
+        match capability.parent_id.as_deref() {
+            Some(parent) if known.contains(parent) => children.entry(parent).or_default().push(capability),
+            _ => roots.push(capability),
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut nodes: Vec<MaturityHeatmapNode> = roots
+        .into_iter()
+        .map(|root| build_node(root, 0, &children, latest, &mut visited))
+        .collect();
+
+    // A cycle whose members all have known parents is reachable from no root; its first member
+    // in sort order is shown as a root rather than the whole cycle going missing
+    for capability in capabilities {
+        if !visited.contains(capability.id.as_str()) {
+            nodes.push(build_node(capability, 0, &children, latest, &mut visited));
+        }
+    }
+    nodes
+}
+
+fn build_node<'a>(
+    capability: &'a Capability,
+    depth: u32,
+    children: &HashMap<&str, Vec<&'a Capability>>,
+    latest: &HashMap<String, CapabilityAssessment>,
+    visited: &mut HashSet<&'a str>,
+) -> MaturityHeatmapNode {
+    visited.insert(capability.id.as_str());
+
+    let child_nodes: Vec<MaturityHeatmapNode> = children
+        .get(capability.id.as_str())
+        .map(|kids| {
+            kids.iter()
+                .filter(|kid| !visited.contains(kid.id.as_str()))
+                .copied()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .map(|kid| build_node(kid, depth + 1, children, latest, visited))
+        .collect();
+
+    // A child contributes its own score, or its children's average when unassessed
+    let child_scores: Vec<f64> = child_nodes
+        .iter()
+        .filter_map(|n| n.maturity_score.map(|s| s as f64).or(n.children_average))
+        .collect();
+    let children_average = if child_scores.is_empty() {
+        None
+    } else {
+        Some(child_scores.iter().sum::<f64>() / child_scores.len() as f64)
+    };
+
+    let assessment = latest.get(&capability.id);
+
+    MaturityHeatmapNode {
+        capability_id: capability.id.clone(),
+        name: capability.name.clone(),
+        parent_id: capability.parent_id.clone(),
+        depth,
+        assessment_date: assessment.map(|a| a.assessment_date.clone()),
+        maturity_score: assessment.map(|a| a.maturity_score),
+        target_score: assessment.and_then(|a| a.target_score),
+        children_average,
+        children: child_nodes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capability(id: &str, parent_id: Option<&str>) -> Capability {
+        Capability {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            capability_type: "Business".to_string(),
+            parent_id: parent_id.map(|p| p.to_string()),
+            colour: None,
+            sort_order: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn assessment(id: &str, capability_id: &str, date: &str, score: i64, created_at: &str) -> CapabilityAssessment {
+        CapabilityAssessment {
+            id: id.to_string(),
+            capability_id: capability_id.to_string(),
+            assessment_date: date.to_string(),
+            maturity_score: score,
+            target_score: None,
+            notes: None,
+            created_at: Some(created_at.to_string()),
+            updated_at: None,
+        }
+    }
+
+    fn ids(nodes: &[MaturityHeatmapNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.capability_id.as_str()).collect()
+    }
+
+    #[test]
+    fn unassessed_capabilities_roll_up_their_childrens_average() {
+        let capabilities = [
+            capability("customer", None),
+            capability("sales", Some("customer")),
+            capability("service", Some("customer")),
+            capability("billing", Some("service")),
+            capability("support", Some("service")),
+        ];
+        let latest = latest_assessments(
+            vec![
+                assessment("a1", "sales", "2026-01-10", 2, "2026-01-10 09:00:00"),
+                assessment("a2", "sales", "2026-06-01", 4, "2026-06-01 09:00:00"),
+                // After the as-of date, so ignored
+                assessment("a3", "sales", "2026-12-01", 5, "2026-12-01 09:00:00"),
+                assessment("a4", "billing", "2026-03-01", 1, "2026-03-01 09:00:00"),
+                assessment("a5", "support", "2026-03-01", 3, "2026-03-01 09:00:00"),
+            ],
+            "2026-10-01",
+        );
+
+        let heatmap = build_heatmap(&capabilities, &latest);
+        assert_eq!(ids(&heatmap), ["customer"]);
+        let customer = &heatmap[0];
+        assert_eq!(ids(&customer.children), ["sales", "service"]);
+        assert_eq!(customer.children[0].maturity_score, Some(4));
+        assert_eq!(ids(&customer.children[1].children), ["billing", "support"]);
+        assert_eq!(customer.children[1].maturity_score, None);
+        assert_eq!(customer.children[1].children_average, Some(2.0));
+        // Sales counts its own 4, service its children's 2
+        assert_eq!(customer.maturity_score, None);
+        assert_eq!(customer.children_average, Some(3.0));
+    }
+
+    #[test]
+    fn same_day_assessments_resolve_to_the_one_created_last() {
+        let rows = || {
+            vec![
+                assessment("later", "sales", "2026-06-01", 4, "2026-06-02 09:00:00"),
+                assessment("earlier", "sales", "2026-06-01", 2, "2026-06-01 09:00:00"),
+            ]
+        };
+        assert_eq!(latest_assessments(rows(), "2026-10-01")["sales"].id, "later");
+        assert_eq!(latest_assessments(rows().into_iter().rev().collect(), "2026-10-01")["sales"].id, "later");
+    }
+
+    #[test]
+    fn cycles_unreachable_from_a_root_are_still_shown() {
+        let capabilities = [
+            capability("customer", None),
+            capability("loop-a", Some("loop-b")),
+            capability("loop-b", Some("loop-a")),
+            capability("leaf", Some("loop-b")),
+        ];
+        let heatmap = build_heatmap(&capabilities, &HashMap::new());
+        assert_eq!(ids(&heatmap), ["customer", "loop-a"]);
+        assert_eq!(ids(&heatmap[1].children), ["loop-b"]);
+        assert_eq!(ids(&heatmap[1].children[0].children), ["leaf"]);
+    }
+}
@@ -0,0 +1,453 @@
+// Tauri commands for importing the capability model from Excel
+// Level 1, Level 2, ... columns give each row's place in the hierarchy
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::entities::EntityType;
+use crate::commands::get_capabilities;
+use crate::commands::journal::JournaledOperation;
+use crate::db::{Capability, get_current_timestamp};
+use calamine::{Reader, Xlsx, open_workbook};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+
+const CAPABILITY_TYPES: &[&str] = &["Business", "Technical"];
+const DEFAULT_CAPABILITY_TYPE: &str = "Business";
+
+/// Where the level, description and type columns are in the header row
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapabilityColumns {
+    // Level 1 first
+    pub levels: Vec<usize>,
+    pub description: Option<usize>,
+    pub capability_type: Option<usize>,
+}
+
+/// Read "Level 1", "Level 2", ... (or "L1", "L2", ...) headers, which must run from 1 without gaps
+pub fn find_capability_columns(headers: &[String]) -> Result<CapabilityColumns, String> {
+    let mut levels: Vec<(usize, usize)> = Vec::new();
+    let mut description = None;
+    let mut capability_type = None;
+
+    for (index, header) in headers.iter().enumerate() {
+        let key = header.trim().to_lowercase().replace([' ', '_', '-'], "");
+        let level = key.strip_prefix("level").or_else(|| key.strip_prefix('l')).and_then(|n| n.parse::<usize>().ok());
+        match (level, key.as_str()) {
+            (Some(level), _) => levels.push((level, index)),
+            (None, "description") => description = Some(index),
+            (None, "type") => capability_type = Some(index),
+            _ => {}
+        }
+    }
+
+    levels.sort();
+    if levels.is_empty() {
+        return Err("No Level 1, Level 2, ... columns found in the header row".to_string());
+    }
+    for (expected, (level, _)) in levels.iter().enumerate() {
+        if *level != expected + 1 {
+            return Err(format!("Level columns must run from Level 1 without gaps or repeats; found Level {} where Level {} was expected", level, expected + 1));
+        }
+    }
+
+    Ok(CapabilityColumns { levels: levels.into_iter().map(|(_, index)| index).collect(), description, capability_type })
+}
+
+/// One row of the sheet: its place in the hierarchy, with blanks above filled from earlier rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetCapability {
+    // As numbered in Excel
+    pub row: usize,
+    // Level 1 first; the last name is the row's own capability
+    pub path: Vec<String>,
+    pub description: Option<String>,
+    pub capability_type: Option<String>,
+}
+
+/// Build each row's path from the level columns. A blank level above the row's deepest one is
+/// "same as above", carried from the previous row; once a level is given, the levels beneath it
+/// can't be carried, as they belonged to a different parent. Blank rows are skipped.
+/// Errors carry the row number with the message.
+pub fn read_capability_rows(columns: &CapabilityColumns, rows: &[(usize, Vec<String>)]) -> Vec<Result<SheetCapability, (usize, String)>> {
+    let cell = |cells: &[String], index: usize| cells.get(index).map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+
+    let mut carried: Vec<String> = Vec::new();
+    let mut read = Vec::new();
+
+    for (row, cells) in rows {
+        let names: Vec<Option<String>> = columns.levels.iter().map(|index| cell(cells, *index)).collect();
+        let Some(deepest) = names.iter().rposition(Option::is_some) else {
+            continue;
+        };
+
+        let mut path = Vec::with_capacity(deepest + 1);
+        let mut given_above = false;
+        let mut error = None;
+        for (level, name) in names.iter().take(deepest + 1).enumerate() {
+            match name {
+                Some(name) => {
+                    given_above = true;
+                    path.push(name.clone());
+                }
+                None if !given_above && level < carried.len() => path.push(carried[level].clone()),
+                None => {
+                    error = Some(format!(
+                        "Row {}: Level {} \"{}\" has no Level {} above it",
+                        row,
+                        deepest + 1,
+                        names[deepest].as_deref().unwrap_or_default(),
+                        level + 1
+                    ));
+                    break;
+                }
+            }
+        }
+
+        if let Some(error) = error {
+            read.push(Err((*row, error)));
+            continue;
+        }
+        carried = path.clone();
+        read.push(Ok(SheetCapability {
+            row: *row,
+            path,
+            description: columns.description.and_then(|index| cell(cells, index)),
+            capability_type: columns.capability_type.and_then(|index| cell(cells, index)),
+        }));
+    }
+    read
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityImportRow {
+    pub row: usize,
+    pub path: Vec<String>,
+    // Created, Updated, Unchanged or Error
+    pub action: String,
+    pub capability_id: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityImportReport {
+    pub dry_run: bool,
+    // False for a dry run, or when any row has an error and so nothing was written
+    pub applied: bool,
+    pub rows: Vec<CapabilityImportRow>,
+    pub created: Vec<Capability>,
+    pub updated: Vec<Capability>,
+    pub error_count: usize,
+}
+
+fn match_key(parent_id: Option<&str>, name: &str) -> (Option<String>, String) {
+    (parent_id.map(str::to_string), name.trim().to_lowercase())
+}
+
+/// Match each row's path against the existing capabilities by name and parent, planning a
+/// capability for anything missing (including parents no row of their own names) and an
+/// update where the row's description or type differs
+pub fn plan_capability_import(sheet: &[Result<SheetCapability, (usize, String)>], existing: &[Capability]) -> CapabilityImportReport {
+    let mut capabilities: HashMap<String, Capability> = existing.iter().map(|c| (c.id.clone(), c.clone())).collect();
+    let mut by_key: HashMap<(Option<String>, String), String> = HashMap::new();
+    for capability in existing {
+        by_key.entry(match_key(capability.parent_id.as_deref(), &capability.name)).or_insert_with(|| capability.id.clone());
+    }
+    let mut next_sort_order: HashMap<Option<String>, i64> = HashMap::new();
+    for capability in existing {
+        let next = next_sort_order.entry(capability.parent_id.clone()).or_insert(0);
+        *next = (*next).max(capability.sort_order.unwrap_or(0) + 1);
+    }
+
+    let mut created_ids: Vec<String> = Vec::new();
+    let mut rows = Vec::new();
+
+    for entry in sheet {
+        let sheet_row = match entry {
+            Ok(sheet_row) => sheet_row,
+            Err((row, message)) => {
+                rows.push(CapabilityImportRow { row: *row, path: Vec::new(), action: "Error".to_string(), capability_id: None, message: Some(message.clone()) });
+                continue;
+            }
+        };
+
+        let capability_type = match sheet_row.capability_type.as_deref() {
+            Some(value) => match CAPABILITY_TYPES.iter().find(|t| t.eq_ignore_ascii_case(value)) {
+                Some(t) => Some(t.to_string()),
+                None => {
+                    rows.push(CapabilityImportRow {
+                        row: sheet_row.row,
+                        path: sheet_row.path.clone(),
+                        action: "Error".to_string(),
+                        capability_id: None,
+                        message: Some(format!("Row {}: Type must be one of {}, got \"{}\"", sheet_row.row, CAPABILITY_TYPES.join(", "), value)),
+                    });
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        let mut parent_id: Option<String> = None;
+        let mut new_parents = Vec::new();
+        let mut own_created = false;
+        for (depth, name) in sheet_row.path.iter().enumerate() {
+            let key = match_key(parent_id.as_deref(), name);
+            let id = match by_key.get(&key) {
+                Some(id) => id.clone(),
+                None => {
+                    let sort_order = next_sort_order.entry(parent_id.clone()).or_insert(0);
+                    let capability = Capability {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        name: name.clone(),
+                        description: None,
+                        capability_type: DEFAULT_CAPABILITY_TYPE.to_string(),
+                        parent_id: parent_id.clone(),
+                        colour: None,
+                        sort_order: Some(*sort_order),
+                        created_at: None,
+                        updated_at: None,
+                    };
+                    *sort_order += 1;
+                    by_key.insert(key, capability.id.clone());
+                    created_ids.push(capability.id.clone());
+                    if depth + 1 == sheet_row.path.len() {
+                        own_created = true;
+                    } else {
+                        new_parents.push(name.clone());
+                    }
+                    let id = capability.id.clone();
+                    capabilities.insert(id.clone(), capability);
+                    id
+                }
+            };
+            parent_id = Some(id);
+        }
+
+        let Some(capability) = parent_id.as_ref().and_then(|id| capabilities.get_mut(id)) else {
+            continue;
+        };
+        let mut changed = false;
+        if let Some(description) = &sheet_row.description {
+            changed |= capability.description.as_ref() != Some(description);
+            capability.description = Some(description.clone());
+        }
+        if let Some(capability_type) = capability_type {
+            changed |= capability.capability_type != capability_type;
+            capability.capability_type = capability_type;
+        }
+
+        let action = if own_created {
+            "Created"
+        } else if changed {
+            "Updated"
+        } else {
+            "Unchanged"
+        };
+        rows.push(CapabilityImportRow {
+            row: sheet_row.row,
+            path: sheet_row.path.clone(),
+            action: action.to_string(),
+            capability_id: parent_id,
+            message: (!new_parents.is_empty()).then(|| format!("Also creates {}", new_parents.join(" > "))),
+        });
+    }
+
+    let originals: HashMap<&str, &Capability> = existing.iter().map(|c| (c.id.as_str(), c)).collect();
+    let created = created_ids.iter().filter_map(|id| capabilities.get(id).cloned()).collect();
+    let mut updated: Vec<Capability> = existing
+        .iter()
+        .filter_map(|c| capabilities.get(&c.id))
+        .filter(|planned| {
+            originals.get(planned.id.as_str()).is_some_and(|original| {
+                original.description != planned.description || original.capability_type != planned.capability_type
+            })
+        })
+        .cloned()
+        .collect();
+    updated.sort_by(|a, b| a.name.cmp(&b.name));
+
+    CapabilityImportReport {
+        dry_run: true,
+        applied: false,
+        error_count: rows.iter().filter(|r| r.action == "Error").count(),
+        rows,
+        created,
+        updated,
+    }
+}
+
+// ============================================
+// CAPABILITY IMPORT COMMANDS
+// ============================================
+
+/// Import the capability hierarchy from a sheet of an .xlsx workbook. The first row holds the
+/// headers; Description and Type columns are optional. Rows are reported one by one, and nothing
+/// is written on a dry run or when any row has an error.
+#[tauri::command]
+pub async fn import_capabilities_xlsx(db: State<'_, tauri_plugin_sql::DbInstances>, path: String, sheet: String, dry_run: bool) -> Result<CapabilityImportReport, String> {
+    let mut workbook: Xlsx<_> = open_workbook(&path).map_err(|e| format!("Could not open {}: {}", path, e))?;
+    let range = workbook
+        .worksheet_range(&sheet)
+        .map_err(|e| format!("Could not read sheet \"{}\": {}", sheet, e))?;
+
+    // Excel row numbers, allowing for a sheet that doesn't start on row 1
+    let first_row = range.start().map(|(row, _)| row as usize + 1).unwrap_or(1);
+    let mut cells = range.rows().enumerate().map(|(index, row)| (first_row + index, row.iter().map(|c| c.to_string()).collect::<Vec<_>>()));
+    let (_, headers) = cells.next().ok_or_else(|| format!("Sheet \"{}\" is empty", sheet))?;
+    let columns = find_capability_columns(&headers)?;
+    let rows: Vec<(usize, Vec<String>)> = cells.collect();
+
+    let existing = get_capabilities(db.clone()).await?;
+    let mut report = plan_capability_import(&read_capability_rows(&columns, &rows), &existing);
+    report.dry_run = dry_run;
+    if dry_run || report.error_count > 0 || (report.created.is_empty() && report.updated.is_empty()) {
+        return Ok(report);
+    }
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let operation = JournaledOperation::start(pool, "import_capabilities_xlsx", serde_json::json!({ "path": path, "sheet": sheet, "dryRun": false })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Parents come before their children, so the parent keys always resolve
+    for capability in &report.created {
+        sqlx::query!(
+            r#"INSERT INTO capabilities (id, name, description, type, parent_id, colour, sort_order, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            capability.id,
+            capability.name,
+            capability.description,
+            capability.capability_type,
+            capability.parent_id,
+            capability.colour,
+            capability.sort_order,
+            now,
+            now
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    for capability in &report.updated {
+        sqlx::query!(
+            "UPDATE capabilities SET description = ?, type = ?, updated_at = ? WHERE id = ?",
+            capability.description,
+            capability.capability_type,
+            now,
+            capability.id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    let before: Vec<&Capability> = existing.iter().filter(|c| report.updated.iter().any(|u| u.id == c.id)).collect();
+    record_audit(&mut tx, NewAuditEntry {
+        group_id: Some(uuid::Uuid::new_v4().to_string()),
+        entity_type: EntityType::Capability.name().to_string(),
+        entity_id: None,
+        action: "Import".to_string(),
+        description: Some(format!(
+            "Imported capabilities from {}: {} created, {} updated",
+            sheet,
+            report.created.len(),
+            report.updated.len()
+        )),
+        before: serde_json::to_value(&before).ok(),
+        after: Some(serde_json::json!({ "created": report.created, "updated": report.updated })),
+    })
+    .await?;
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    report.applied = true;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sheet(rows: &[&[&str]]) -> Vec<(usize, Vec<String>)> {
+        rows.iter().enumerate().map(|(i, cells)| (i + 2, cells.iter().map(|c| c.to_string()).collect())).collect()
+    }
+
+    fn columns() -> CapabilityColumns {
+        find_capability_columns(&["Level 1", "Level 2", "level_3", "Description"].map(String::from)).unwrap()
+    }
+
+    #[test]
+    fn blank_levels_carry_down_and_gaps_are_errors() {
+        assert_eq!(columns().levels, vec![0, 1, 2]);
+        assert_eq!(columns().description, Some(3));
+        assert!(find_capability_columns(&["Level 1", "Level 3"].map(String::from)).is_err());
+
+        let rows = sheet(&[
+            &["Payments", "", "", ""],
+            &["", "Cards", "", "Card schemes"],
+            &["", "", "Issuing", ""],
+            &["", "", "", ""],
+            &["", "Transfers", "", ""],
+            &["Onboarding", "", "KYC", ""],
+        ]);
+        let read = read_capability_rows(&columns(), &rows);
+        assert_eq!(read.len(), 5);
+        assert_eq!(read[2].as_ref().unwrap().path, ["Payments", "Cards", "Issuing"]);
+        assert_eq!(read[1].as_ref().unwrap().description.as_deref(), Some("Card schemes"));
+        assert_eq!(read[3].as_ref().unwrap().path, ["Payments", "Transfers"]);
+        assert_eq!(read[4].as_ref().unwrap_err(), &(7, "Row 7: Level 3 \"KYC\" has no Level 2 above it".to_string()));
+
+        let orphan = read_capability_rows(&columns(), &sheet(&[&["", "", "Issuing", ""]]));
+        assert_eq!(orphan[0].as_ref().unwrap_err().0, 2);
+    }
+
+    #[test]
+    fn paths_match_existing_capabilities_by_name_and_parent() {
+        let existing = vec![
+            Capability {
+                id: "payments".to_string(),
+                name: "Payments".to_string(),
+                description: None,
+                capability_type: "Business".to_string(),
+                parent_id: None,
+                colour: None,
+                sort_order: Some(3),
+                created_at: None,
+                updated_at: None,
+            },
+            Capability {
+                id: "cards".to_string(),
+                name: "cards".to_string(),
+                description: Some("Old".to_string()),
+                capability_type: "Business".to_string(),
+                parent_id: Some("payments".to_string()),
+                colour: None,
+                sort_order: Some(0),
+                created_at: None,
+                updated_at: None,
+            },
+        ];
+        let rows = sheet(&[
+            &["Payments", "", "", ""],
+            &["", "Cards", "", "Card schemes"],
+            &["Onboarding", "KYC", "", ""],
+        ]);
+
+        let report = plan_capability_import(&read_capability_rows(&columns(), &rows), &existing);
+        let actions: Vec<&str> = report.rows.iter().map(|r| r.action.as_str()).collect();
+        assert_eq!(actions, ["Unchanged", "Updated", "Created"]);
+        assert_eq!(report.rows[2].message.as_deref(), Some("Also creates Onboarding"));
+        assert_eq!(report.error_count, 0);
+
+        assert_eq!(report.updated.len(), 1);
+        assert_eq!(report.updated[0].description.as_deref(), Some("Card schemes"));
+
+        let names: Vec<&str> = report.created.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Onboarding", "KYC"]);
+        assert_eq!(report.created[0].sort_order, Some(4));
+        assert_eq!(report.created[1].parent_id.as_deref(), Some(report.created[0].id.as_str()));
+    }
+}
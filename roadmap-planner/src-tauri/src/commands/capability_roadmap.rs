@@ -0,0 +1,281 @@
+// Tauri commands for the capability-level roadmap
+// One bar per capability, showing when the scenario's initiatives touch it
+
+use crate::commands::engine::currency::{CurrencyConverter, CurrencyWarning};
+use crate::commands::engine::dates::{DateSpan, format_date, merge_spans, parse_date, today};
+use crate::commands::exchange_rates::load_currency_converter;
+use crate::commands::investment::{CapabilityLink, capability_shares, fetch_capability_links};
+use crate::commands::{get_capabilities, get_initiatives, require_scenario};
+use crate::db::{Capability, Initiative};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoadmapInterval {
+    pub start_date: String,
+    // Last included day
+    pub end_date: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityRoadmapRow {
+    pub capability_id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    // Roots are at depth 0
+    pub depth: u32,
+    // Merged date ranges of the contributing initiatives; empty shows a gap in investment
+    pub intervals: Vec<RoadmapInterval>,
+    // The contributing initiatives' cost, split across capabilities as the investment heatmap does
+    pub total_cost: f64,
+    pub initiative_count: i64,
+    pub initiative_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityRoadmap {
+    pub scenario_id: String,
+    pub level: u32,
+    pub currency: String,
+    pub rows: Vec<CapabilityRoadmapRow>,
+    // Active initiatives linked to no capability through their systems
+    pub unmapped_initiative_ids: Vec<String>,
+    pub warnings: Vec<CurrencyWarning>,
+}
+
+struct RowTotals {
+    spans: Vec<DateSpan>,
+    cost: f64,
+    initiative_ids: BTreeSet<String>,
+}
+
+/// Depth of each capability reachable from a root, in depth-first tree order. A missing
+/// parent makes a capability a root, as the investment rollup treats it; cycles are skipped.
+fn tree_order(capabilities: &[Capability]) -> Vec<(&Capability, u32)> {
+    let known: HashSet<&str> = capabilities.iter().map(|c| c.id.as_str()).collect();
+    let mut children: HashMap<&str, Vec<&Capability>> = HashMap::new();
+    let mut roots: Vec<&Capability> = Vec::new();
+
+    for capability in capabilities {
+        match capability.parent_id.as_deref() {
+            Some(parent) if known.contains(parent) => children.entry(parent).or_default().push(capability),
+            _ => roots.push(capability),
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(capabilities.len());
+    let mut visited = HashSet::new();
+    let mut stack: Vec<(&Capability, u32)> = roots.into_iter().rev().map(|c| (c, 0)).collect();
+    while let Some((capability, depth)) = stack.pop() {
+        if !visited.insert(capability.id.as_str()) {
+            continue;
+        }
+        ordered.push((capability, depth));
+        for kid in children.get(capability.id.as_str()).into_iter().flatten().rev() {
+            stack.push((kid, depth + 1));
+        }
+    }
+    ordered
+}
+
+/// Roll initiatives up to the capabilities at `level`. Each capability at that depth gets a
+/// row, as does any shallower one that is a leaf or has systems of its own, so nothing linked
+/// above the requested level is lost.
+pub fn build_capability_roadmap(
+    capabilities: &[Capability],
+    level: u32,
+    initiatives: &[Initiative],
+    links: &[CapabilityLink],
+    converter: &CurrencyConverter,
+    as_of: NaiveDate,
+) -> (Vec<CapabilityRoadmapRow>, Vec<String>, Vec<CurrencyWarning>) {
+    let ordered = tree_order(capabilities);
+    let depth_of: HashMap<&str, u32> = ordered.iter().map(|(c, d)| (c.id.as_str(), *d)).collect();
+    let parent_of: HashMap<&str, &str> = capabilities
+        .iter()
+        .filter_map(|c| Some((c.id.as_str(), c.parent_id.as_deref()?)))
+        .collect();
+    let has_children: HashSet<&str> = parent_of.values().copied().collect();
+
+    // Walk up until the requested level; capabilities in a cycle have no depth and drop out
+    let target = |capability_id: &str| -> Option<String> {
+        let mut id = capability_id;
+        let mut depth = *depth_of.get(id)?;
+        while depth > level {
+            id = parent_of.get(id)?;
+            depth -= 1;
+        }
+        Some(id.to_string())
+    };
+
+    let mut links_by_initiative: HashMap<&str, Vec<&CapabilityLink>> = HashMap::new();
+    for link in links {
+        links_by_initiative.entry(link.initiative_id.as_str()).or_default().push(link);
+    }
+
+    let mut totals: HashMap<String, RowTotals> = HashMap::new();
+    let mut unmapped = Vec::new();
+    let mut warnings = Vec::new();
+
+    for initiative in initiatives.iter().filter(|i| i.status != "Cancelled") {
+        let Some(initiative_links) = links_by_initiative.get(initiative.id.as_str()) else {
+            unmapped.push(initiative.id.clone());
+            continue;
+        };
+
+        // Whole-initiative costs convert at the rate effective when the initiative starts
+        let on = initiative.start_date.as_deref().and_then(parse_date).unwrap_or(as_of);
+        let currency = converter.currency_of(initiative.currency.as_deref());
+        let cost = initiative
+            .cost_estimate
+            .and_then(|native| converter.convert_or_warn(native, currency, on, "Initiative", &initiative.id, &mut warnings))
+            .unwrap_or(0.0);
+        let span = DateSpan::parse_inclusive(initiative.start_date.as_deref(), initiative.end_date.as_deref());
+
+        for (capability_id, share) in capability_shares(initiative_links) {
+            let Some(row_id) = target(&capability_id) else {
+                continue;
+            };
+            let row = totals.entry(row_id).or_insert_with(|| RowTotals { spans: Vec::new(), cost: 0.0, initiative_ids: BTreeSet::new() });
+            row.cost += cost * share;
+            // A share from each of several systems under one capability is still one initiative
+            if row.initiative_ids.insert(initiative.id.clone()) {
+                row.spans.extend(span);
+            }
+        }
+    }
+
+    let rows = ordered
+        .into_iter()
+        .filter(|(c, depth)| {
+            *depth == level || (*depth < level && (!has_children.contains(c.id.as_str()) || totals.contains_key(&c.id)))
+        })
+        .map(|(capability, depth)| {
+            let (spans, total_cost, initiative_ids) = match totals.get(&capability.id) {
+                Some(row) => (row.spans.clone(), row.cost, row.initiative_ids.iter().cloned().collect()),
+                None => (Vec::new(), 0.0, Vec::new()),
+            };
+            CapabilityRoadmapRow {
+                capability_id: capability.id.clone(),
+                name: capability.name.clone(),
+                parent_id: capability.parent_id.clone(),
+                depth,
+                intervals: merge_spans(spans)
+                    .into_iter()
+                    .map(|s| RoadmapInterval { start_date: format_date(s.start), end_date: format_date(s.last_day()) })
+                    .collect(),
+                total_cost,
+                initiative_count: initiative_ids.len() as i64,
+                initiative_ids,
+            }
+        })
+        .collect();
+
+    (rows, unmapped, warnings)
+}
+
+// ============================================
+// CAPABILITY ROADMAP COMMANDS
+// ============================================
+
+/// The scenario's roadmap at capability granularity, mapping initiatives through their systems
+/// and rolling up to `level` of the capability tree (0 for the roots)
+#[tauri::command]
+pub async fn get_capability_roadmap(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, level: u32) -> Result<CapabilityRoadmap, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+    let capabilities = get_capabilities(db.clone()).await?;
+    let initiatives = get_initiatives(db.clone(), Some(scenario_id.clone())).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let links = fetch_capability_links(pool, &scenario_id).await?;
+    let converter = load_currency_converter(pool).await?;
+
+    let (rows, unmapped_initiative_ids, warnings) =
+        build_capability_roadmap(&capabilities, level, &initiatives, &links, &converter, today());
+
+    Ok(CapabilityRoadmap {
+        scenario_id,
+        level,
+        currency: converter.reporting_currency.clone(),
+        rows,
+        unmapped_initiative_ids,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::test_initiative;
+
+    fn capability(id: &str, parent_id: Option<&str>) -> Capability {
+        Capability {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            capability_type: "Business".to_string(),
+            parent_id: parent_id.map(|p| p.to_string()),
+            colour: None,
+            sort_order: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn initiative(id: &str, start: &str, end: &str, cost: f64) -> Initiative {
+        Initiative {
+            start_date: Some(start.to_string()),
+            end_date: Some(end.to_string()),
+            cost_estimate: Some(cost),
+            ..test_initiative(id)
+        }
+    }
+
+    fn link(initiative_id: &str, capability_id: &str) -> CapabilityLink {
+        CapabilityLink { initiative_id: initiative_id.to_string(), capability_id: capability_id.to_string(), weight: None }
+    }
+
+    #[test]
+    fn initiatives_roll_up_to_the_requested_level() {
+        let capabilities = vec![
+            capability("payments", None),
+            capability("cards", Some("payments")),
+            capability("transfers", Some("payments")),
+            capability("onboarding", None),
+            capability("kyc", Some("onboarding")),
+        ];
+        let initiatives = vec![
+            initiative("a", "2027-01-01", "2027-03-31", 100.0),
+            // Touches the Q1 bar and extends it
+            initiative("b", "2027-03-15", "2027-05-31", 200.0),
+            initiative("c", "2027-09-01", "2027-10-31", 50.0),
+            initiative("d", "2027-01-01", "2027-12-31", 10.0),
+        ];
+        let links = vec![link("a", "cards"), link("b", "transfers"), link("c", "cards")];
+        let converter = CurrencyConverter::new("GBP", &[]);
+        let as_of = parse_date("2026-10-16").unwrap();
+
+        let (rows, unmapped, warnings) = build_capability_roadmap(&capabilities, 0, &initiatives, &links, &converter, as_of);
+        assert!(warnings.is_empty());
+        assert_eq!(unmapped, vec!["d"]);
+        assert_eq!(rows.iter().map(|r| r.capability_id.as_str()).collect::<Vec<_>>(), ["payments", "onboarding"]);
+
+        let payments = &rows[0];
+        assert_eq!(payments.initiative_count, 3);
+        assert_eq!(payments.total_cost, 350.0);
+        let intervals: Vec<(&str, &str)> = payments.intervals.iter().map(|i| (i.start_date.as_str(), i.end_date.as_str())).collect();
+        assert_eq!(intervals, [("2027-01-01", "2027-05-31"), ("2027-09-01", "2027-10-31")]);
+
+        // Nothing touches onboarding, so it shows as a gap
+        assert!(rows[1].intervals.is_empty());
+        assert_eq!(rows[1].initiative_count, 0);
+
+        let (rows, _, _) = build_capability_roadmap(&capabilities, 1, &initiatives, &links, &converter, as_of);
+        assert_eq!(rows.iter().map(|r| r.capability_id.as_str()).collect::<Vec<_>>(), ["cards", "transfers", "kyc"]);
+        assert_eq!(rows[0].intervals.len(), 2);
+    }
+}
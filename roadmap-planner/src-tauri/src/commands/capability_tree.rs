@@ -0,0 +1,157 @@
+// Tauri commands for capability tree health
+// Shape of the hierarchy, and rows that have fallen out of it
+
+use crate::commands::get_capabilities;
+use crate::db::Capability;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedCapability {
+    pub id: String,
+    pub name: String,
+    // The parent_id that matches no capability
+    pub missing_parent_id: String,
+    // The orphan plus everything below it
+    pub subtree_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeMetrics {
+    pub capability_count: i64,
+    pub root_count: i64,
+    pub leaf_count: i64,
+    // Levels below and including the roots; a lone root has depth 1
+    pub max_depth: i64,
+    // Capabilities at each level, roots first
+    pub level_counts: Vec<i64>,
+    pub orphans: Vec<OrphanedCapability>,
+    // Capabilities whose parent chain loops back on itself, so no root reaches them
+    pub cyclic_ids: Vec<String>,
+}
+
+/// Walk the tree from its roots; orphaned subtrees are measured separately
+pub fn compute_tree_metrics(capabilities: &[Capability]) -> TreeMetrics {
+    let known: HashSet<&str> = capabilities.iter().map(|c| c.id.as_str()).collect();
+    let mut children: HashMap<&str, Vec<&Capability>> = HashMap::new();
+    let mut roots: Vec<&Capability> = Vec::new();
+    let mut orphans: Vec<&Capability> = Vec::new();
+
+    for capability in capabilities {
+        match capability.parent_id.as_deref() {
+            Some(parent) if known.contains(parent) => children.entry(parent).or_default().push(capability),
+            Some(_) => orphans.push(capability),
+            None => roots.push(capability),
+        }
+    }
+
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut level_counts: Vec<i64> = Vec::new();
+    let mut level: Vec<&Capability> = roots.clone();
+    while !level.is_empty() {
+        level.retain(|c| visited.insert(c.id.as_str()));
+        if level.is_empty() {
+            break;
+        }
+        level_counts.push(level.len() as i64);
+        level = level
+            .iter()
+            .flat_map(|c| children.get(c.id.as_str()).into_iter().flatten().copied())
+            .collect();
+    }
+
+    let orphans: Vec<OrphanedCapability> = orphans
+        .into_iter()
+        .map(|orphan| {
+            let mut subtree_size = 0;
+            let mut stack = vec![orphan];
+            while let Some(capability) = stack.pop() {
+                if visited.insert(capability.id.as_str()) {
+                    subtree_size += 1;
+                    stack.extend(children.get(capability.id.as_str()).into_iter().flatten().copied());
+                }
+            }
+            OrphanedCapability {
+                id: orphan.id.clone(),
+                name: orphan.name.clone(),
+                missing_parent_id: orphan.parent_id.clone().unwrap_or_default(),
+                subtree_size,
+            }
+        })
+        .collect();
+
+    let cyclic_ids: Vec<String> = capabilities
+        .iter()
+        .filter(|c| !visited.contains(c.id.as_str()))
+        .map(|c| c.id.clone())
+        .collect();
+
+    TreeMetrics {
+        capability_count: capabilities.len() as i64,
+        root_count: roots.len() as i64,
+        leaf_count: capabilities.iter().filter(|c| !children.contains_key(c.id.as_str())).count() as i64,
+        max_depth: level_counts.len() as i64,
+        level_counts,
+        orphans,
+        cyclic_ids,
+    }
+}
+
+// ============================================
+// CAPABILITY TREE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_capability_tree_metrics(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<TreeMetrics, String> {
+    let capabilities = get_capabilities(db).await?;
+
+    Ok(compute_tree_metrics(&capabilities))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capability(id: &str, parent_id: Option<&str>) -> Capability {
+        Capability {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            capability_type: "Business".to_string(),
+            parent_id: parent_id.map(|p| p.to_string()),
+            colour: None,
+            sort_order: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn metrics_cover_depth_leaves_orphans_and_cycles() {
+        let capabilities = vec![
+            capability("root", None),
+            capability("child", Some("root")),
+            capability("grandchild", Some("child")),
+            capability("other-root", None),
+            // Parent was deleted with foreign keys off
+            capability("orphan", Some("gone")),
+            capability("orphan-child", Some("orphan")),
+            // Each is the other's parent
+            capability("loop-a", Some("loop-b")),
+            capability("loop-b", Some("loop-a")),
+        ];
+
+        let metrics = compute_tree_metrics(&capabilities);
+        assert_eq!(metrics.capability_count, 8);
+        assert_eq!(metrics.root_count, 2);
+        assert_eq!(metrics.max_depth, 3);
+        assert_eq!(metrics.level_counts, vec![2, 1, 1]);
+        assert_eq!(metrics.leaf_count, 3);
+
+        assert_eq!(metrics.orphans.len(), 1);
+        assert_eq!(metrics.orphans[0].missing_parent_id, "gone");
+        assert_eq!(metrics.orphans[0].subtree_size, 2);
+        assert_eq!(metrics.cyclic_ids, vec!["loop-a", "loop-b"]);
+    }
+}
@@ -0,0 +1,163 @@
+// Tauri commands for resource capacity reporting
+// Pool demand per period, normalised into each pool's capacity unit, leveling suggestions, and effort profile previews
+
+use crate::commands::engine::dates::{DateSpan, format_date, generate_periods};
+use crate::commands::engine::effort::{EffortProfile, validate_effort_profile};
+use crate::commands::engine::leveling::{self, LevelingSuggestion};
+use crate::commands::engine::resources::{OverAllocation, PoolPeriodAllocation, detect_overallocations};
+use crate::commands::get_initiative;
+use crate::commands::scenario_data::load_scenario_data;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolCapacity {
+    pub pool_id: String,
+    pub pool_name: String,
+    // Demand and capacity are both in this unit, per period
+    pub unit: String,
+    pub period_type: String,
+    pub peak_utilisation: f64,
+    pub over_allocated_periods: i64,
+    pub periods: Vec<PoolPeriodAllocation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacityReport {
+    pub scenario_id: String,
+    pub pools: Vec<PoolCapacity>,
+}
+
+// A pool's capacity in one period left over after a committed scenario's demand
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodCapacity {
+    pub pool_id: String,
+    pub pool_name: String,
+    pub unit: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub capacity: f64,
+    pub committed: f64,
+    // Negative when the commitments already over-allocate the pool
+    pub remaining: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffortPeriod {
+    pub period_start: String,
+    pub period_end: String,
+    pub effort: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffortProfilePreview {
+    pub initiative_id: String,
+    pub profile: EffortProfile,
+    pub effort_unit: Option<String>,
+    pub total_effort: f64,
+    pub periods: Vec<EffortPeriod>,
+}
+
+// ============================================
+// CAPACITY REPORT COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_capacity_report(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, include_unapproved: Option<bool>) -> Result<CapacityReport, String> {
+    let data = load_scenario_data(db, &scenario_id).await?;
+    let allocations = data.resource_allocation_including(include_unapproved.unwrap_or(true));
+
+    let pools = data
+        .pools
+        .iter()
+        .map(|pool| {
+            let periods: Vec<PoolPeriodAllocation> = allocations.iter().filter(|a| a.pool_id == pool.id).cloned().collect();
+            PoolCapacity {
+                pool_id: pool.id.clone(),
+                pool_name: pool.name.clone(),
+                unit: pool.capacity_unit.clone(),
+                period_type: pool.period_type.clone(),
+                peak_utilisation: periods.iter().map(|p| p.utilisation).fold(0.0, f64::max),
+                over_allocated_periods: periods.iter().filter(|p| p.is_over_allocated()).count() as i64,
+                periods,
+            }
+        })
+        .collect();
+
+    Ok(CapacityReport { scenario_id, pools })
+}
+
+/// Every pool and role short of capacity, with roles the pool has nobody for listed first
+#[tauri::command]
+pub async fn get_overallocations(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, include_unapproved: Option<bool>) -> Result<Vec<OverAllocation>, String> {
+    let data = load_scenario_data(db, &scenario_id).await?;
+    let allocations = data.resource_allocation_including(include_unapproved.unwrap_or(true));
+
+    Ok(detect_overallocations(&allocations))
+}
+
+/// Proposed moves that would clear the scenario's over-allocations, lowest priority moving
+/// first and dependents following. Nothing is changed; apply a suggestion by editing the dates.
+#[tauri::command]
+pub async fn suggest_leveling(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<LevelingSuggestion>, String> {
+    let data = load_scenario_data(db, &scenario_id).await?;
+    let allocations = data.resource_allocation();
+
+    Ok(leveling::suggest_leveling(&data.initiatives, &data.dependencies, &allocations, |initiatives, pool_id| data.pool_allocation_for(initiatives, pool_id)))
+}
+
+/// Capacity per pool and period after the baseline's demand, for planning new work on top
+/// of it. Only periods the baseline's initiatives span are returned.
+#[tauri::command]
+pub async fn get_remaining_capacity(db: State<'_, tauri_plugin_sql::DbInstances>, baseline_id: String, include_unapproved: Option<bool>) -> Result<Vec<PeriodCapacity>, String> {
+    let data = load_scenario_data(db, &baseline_id).await?;
+    let allocations = data.resource_allocation_including(include_unapproved.unwrap_or(true));
+
+    Ok(allocations
+        .into_iter()
+        .map(|a| PeriodCapacity {
+            remaining: a.capacity - a.demand,
+            pool_id: a.pool_id,
+            pool_name: a.pool_name,
+            unit: a.unit,
+            period_start: a.period_start,
+            period_end: a.period_end,
+            capacity: a.capacity,
+            committed: a.demand,
+        })
+        .collect())
+}
+
+/// Spread an initiative's effort estimate over calendar periods. `profile` overrides the
+/// saved one so the UI can chart a shape before saving it.
+#[tauri::command]
+pub async fn preview_effort_profile(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String, profile: Option<String>, period_type: Option<String>) -> Result<EffortProfilePreview, String> {
+    let initiative = get_initiative(db, initiative_id.clone()).await?;
+
+    let profile_name = profile.unwrap_or(initiative.effort_profile);
+    validate_effort_profile(&profile_name)?;
+    let profile = EffortProfile::parse(&profile_name).unwrap_or(EffortProfile::Flat);
+
+    let span = DateSpan::parse_inclusive(initiative.start_date.as_deref(), initiative.end_date.as_deref())
+        .ok_or_else(|| format!("Initiative {} needs start and end dates to spread effort", initiative_id))?;
+    let total_effort = initiative
+        .effort_estimate
+        .ok_or_else(|| format!("Initiative {} has no effort estimate", initiative_id))?;
+
+    let periods = generate_periods(&span, period_type.as_deref().unwrap_or("Month"))
+        .iter()
+        .map(|period| EffortPeriod {
+            period_start: format_date(period.start),
+            period_end: format_date(period.last_day()),
+            effort: total_effort * profile.share(&span, period),
+        })
+        .collect();
+
+    Ok(EffortProfilePreview {
+        initiative_id,
+        profile,
+        effort_unit: initiative.effort_unit,
+        total_effort,
+        periods,
+    })
+}
@@ -0,0 +1,335 @@
+// Tauri commands for copying entities between workspaces
+// Self-contained JSON payloads that are pasted under fresh ids
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::entities::EntityType;
+use crate::commands::journal::JournaledOperation;
+use crate::commands::{ensure_unlocked, get_scenario};
+use crate::commands::id_remap::{RemappedRow, UnresolvedReference, remap_rows, table_rule};
+use crate::commands::reference_codes::{claim_reference_code, next_reference_code};
+use crate::commands::rows::{bind_json, ids_json, row_to_json};
+use crate::commands::workspace_diff::table_columns;
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::SqliteConnection;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use tauri::State;
+
+const PAYLOAD_FORMAT: &str = "planscape-clipboard";
+const PAYLOAD_VERSION: u32 = 1;
+
+// {ids} is replaced with the bound id list, as in bulk deletes
+const IDS: &str = "(SELECT value FROM json_each(?1))";
+
+// References into these tables are matched by name in the target workspace
+const NAME_MATCHED_TABLES: [&str; 6] = ["capabilities", "resource_pools", "resources", "systems", "constraints", "objectives"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalReference {
+    pub table: String,
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardPayload {
+    pub format: String,
+    pub version: u32,
+    pub entity_type: EntityType,
+    pub tables: BTreeMap<String, Vec<Map<String, Value>>>,
+    // Rows referenced from the payload but not part of it
+    pub references: Vec<ExternalReference>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PastedRow {
+    pub table: String,
+    pub old_id: String,
+    pub new_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasteResult {
+    pub entity_type: EntityType,
+    pub created: Vec<PastedRow>,
+    pub unresolved: Vec<UnresolvedReference>,
+    pub audit_group_id: Option<String>,
+}
+
+// Rows copied along with each entity type; links only travel when both ends do
+fn export_rules(entity_type: EntityType) -> Result<&'static [(&'static str, &'static str)], String> {
+    match entity_type {
+        EntityType::Initiative => Ok(&[
+            ("initiatives", "id IN {ids}"),
+            ("milestones", "initiative_id IN {ids}"),
+            ("initiative_dependencies", "predecessor_id IN {ids} AND successor_id IN {ids}"),
+            ("initiative_capabilities", "initiative_id IN {ids}"),
+            ("initiative_resource_requirements", "initiative_id IN {ids}"),
+            ("initiative_pool_splits", "initiative_id IN {ids}"),
+            ("initiative_resources", "initiative_id IN {ids}"),
+            ("system_initiatives", "initiative_id IN {ids}"),
+            ("initiative_constraints", "initiative_id IN {ids}"),
+            ("initiative_objectives", "initiative_id IN {ids}"),
+        ]),
+        EntityType::Capability => Ok(&[
+            ("capabilities", "id IN {ids}"),
+            ("capability_assessments", "capability_id IN {ids}"),
+        ]),
+        EntityType::System => Ok(&[
+            ("systems", "id IN {ids}"),
+            ("system_dependencies", "source_system_id IN {ids} AND target_system_id IN {ids}"),
+            ("interfaces", "source_system_id IN {ids} AND target_system_id IN {ids}"),
+        ]),
+        EntityType::ResourcePool => Ok(&[
+            ("resource_pools", "id IN {ids}"),
+            ("pool_role_capacities", "resource_pool_id IN {ids}"),
+        ]),
+        EntityType::Resource => Ok(&[
+            ("resources", "id IN {ids}"),
+            ("resource_time_off", "resource_id IN {ids}"),
+        ]),
+        EntityType::Constraint => Ok(&[("constraints", "id IN {ids}")]),
+        EntityType::FinancialPeriod => Ok(&[("financial_periods", "id IN {ids}")]),
+        EntityType::Scenario => Err("Scenarios can't be copied; use merge_workspace to bring in a whole scenario".to_string()),
+    }
+}
+
+/// The given entities plus the rows that travel with them, keyed by table
+pub async fn collect_entity_rows(conn: &mut SqliteConnection, entity_type: EntityType, ids: &[String]) -> Result<BTreeMap<String, Vec<Map<String, Value>>>, String> {
+    let bound_ids = ids_json(ids);
+    let mut tables = BTreeMap::new();
+
+    for (table, condition) in export_rules(entity_type)? {
+        let sql = format!("SELECT * FROM {} WHERE {}", table, condition.replace("{ids}", IDS));
+        let rows: Vec<Map<String, Value>> = sqlx::query(&sql)
+            .bind(&bound_ids)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?
+            .iter()
+            .filter_map(|row| match row_to_json(row) {
+                Value::Object(object) => Some(object),
+                _ => None,
+            })
+            .collect();
+        if !rows.is_empty() {
+            tables.insert(table.to_string(), rows);
+        }
+    }
+
+    Ok(tables)
+}
+
+/// Insert remapped rows as new rows, dropping columns the table lacks and stamping them as created now
+pub async fn insert_remapped_rows(conn: &mut SqliteConnection, rows: Vec<RemappedRow>) -> Result<Vec<RemappedRow>, String> {
+    let now = get_current_timestamp();
+    let mut columns_by_table: HashMap<String, Vec<String>> = HashMap::new();
+    let mut inserted = Vec::with_capacity(rows.len());
+
+    for mut remapped in rows {
+        if !columns_by_table.contains_key(&remapped.table) {
+            let (columns, _) = table_columns(conn, &remapped.table).await?;
+            columns_by_table.insert(remapped.table.clone(), columns);
+        }
+        for stamp in ["created_at", "updated_at"] {
+            if remapped.row.contains_key(stamp) {
+                remapped.row.insert(stamp.to_string(), Value::String(now.clone()));
+            }
+        }
+
+        let columns: Vec<&String> = columns_by_table[&remapped.table].iter().filter(|c| remapped.row.contains_key(*c)).collect();
+        let sql = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            remapped.table,
+            columns.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(", "),
+            vec!["?"; columns.len()].join(", ")
+        );
+        let mut query = sqlx::query(&sql);
+        for column in &columns {
+            query = bind_json(query, &remapped.row[column.as_str()]);
+        }
+        query.execute(&mut *conn).await.map_err(|e| e.to_string())?;
+
+        inserted.push(remapped);
+    }
+
+    Ok(inserted)
+}
+
+// ============================================
+// CLIPBOARD COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn export_entities_to_clipboard_payload(db: State<'_, tauri_plugin_sql::DbInstances>, entity_type: EntityType, ids: Vec<String>) -> Result<String, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // One read transaction so the payload is a consistent snapshot
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let tables = collect_entity_rows(&mut tx, entity_type, &ids).await?;
+
+    if !tables.contains_key(entity_type.table()) {
+        return Err(format!("No {} found for the given ids", entity_type.table()));
+    }
+
+    // Everything a payload row points at that isn't itself in the payload
+    let in_payload: HashSet<(&str, &str)> = tables
+        .iter()
+        .flat_map(|(table, rows)| rows.iter().filter_map(move |r| Some((table.as_str(), r.get("id")?.as_str()?))))
+        .collect();
+    let mut outside: BTreeSet<(&'static str, String)> = BTreeSet::new();
+    for (table, rows) in &tables {
+        let Some(rule) = table_rule(table) else {
+            continue;
+        };
+        for fk in rule.foreign_keys.iter().filter(|fk| NAME_MATCHED_TABLES.contains(&fk.table)) {
+            for target in rows.iter().filter_map(|r| r.get(fk.column)?.as_str()) {
+                if !in_payload.contains(&(fk.table, target)) {
+                    outside.insert((fk.table, target.to_string()));
+                }
+            }
+        }
+    }
+
+    let mut references = Vec::new();
+    for (table, id) in outside {
+        let name: Option<String> = sqlx::query_scalar(&format!("SELECT name FROM {} WHERE id = ?", table))
+            .bind(&id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        if let Some(name) = name {
+            references.push(ExternalReference { table: table.to_string(), id, name });
+        }
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    let payload = ClipboardPayload {
+        format: PAYLOAD_FORMAT.to_string(),
+        version: PAYLOAD_VERSION,
+        entity_type,
+        tables,
+        references,
+    };
+    serde_json::to_string(&payload).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn import_entities_from_payload(db: State<'_, tauri_plugin_sql::DbInstances>, payload: String, scenario_id: String) -> Result<PasteResult, String> {
+    // The payload as given, for the journal; it is parsed over below
+    let journal_args = serde_json::json!({ "payload": payload, "scenarioId": scenario_id });
+    let payload: ClipboardPayload = serde_json::from_str(&payload).map_err(|e| format!("Not a clipboard payload: {}", e))?;
+    if payload.format != PAYLOAD_FORMAT || payload.version != PAYLOAD_VERSION {
+        return Err(format!("Unsupported clipboard payload {} v{}", payload.format, payload.version));
+    }
+    if let Some(table) = payload.tables.keys().find(|t| table_rule(t).is_none()) {
+        return Err(format!("Clipboard payload contains unsupported table {}", table));
+    }
+
+    let mut tables = payload.tables;
+    if let Some(initiatives) = tables.get_mut("initiatives") {
+        ensure_unlocked(&get_scenario(db.clone(), scenario_id.clone()).await?)?;
+        for row in initiatives {
+            row.insert("scenario_id".to_string(), Value::String(scenario_id.clone()));
+        }
+    }
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let operation = JournaledOperation::start(pool, "import_entities_from_payload", journal_args).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Pasted rows reference each other in any order
+    sqlx::query("PRAGMA defer_foreign_keys = ON")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Pasted initiatives keep their reference code unless this workspace already uses it
+    for row in tables.get_mut("initiatives").into_iter().flatten() {
+        let carried = row.get("reference_code").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let code = match claim_reference_code(&mut tx, &carried).await? {
+            Some(code) => code,
+            None => next_reference_code(&mut tx).await?,
+        };
+        row.insert("reference_code".to_string(), Value::String(code));
+    }
+
+    let mut external: HashMap<(String, String), String> = HashMap::new();
+    let mut names: HashMap<(String, String), String> = HashMap::new();
+    for reference in payload.references.iter().filter(|r| NAME_MATCHED_TABLES.contains(&r.table.as_str())) {
+        let key = (reference.table.clone(), reference.id.clone());
+        names.insert(key.clone(), reference.name.clone());
+
+        let matched: Option<String> = sqlx::query_scalar(&format!(
+            "SELECT id FROM {} WHERE name = ? COLLATE NOCASE ORDER BY created_at, id LIMIT 1",
+            reference.table
+        ))
+        .bind(&reference.name)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        if let Some(id) = matched {
+            external.insert(key, id);
+        }
+    }
+
+    let outcome = remap_rows(&tables, &external, || uuid::Uuid::new_v4().to_string());
+    let inserted = insert_remapped_rows(&mut tx, outcome.rows).await?;
+
+    let mut created = Vec::with_capacity(inserted.len());
+    let mut snapshot = Vec::with_capacity(inserted.len());
+    for remapped in inserted {
+        let new_id = remapped.row.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        snapshot.push(serde_json::json!({ "table": remapped.table, "row": remapped.row }));
+        created.push(PastedRow { table: remapped.table, old_id: remapped.old_id, new_id });
+    }
+
+    let violations = sqlx::query("PRAGMA foreign_key_check")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !violations.is_empty() {
+        return Err("Pasted rows reference rows that don't exist in this workspace".to_string());
+    }
+
+    let audit_group_id = if created.is_empty() {
+        None
+    } else {
+        let group_id = uuid::Uuid::new_v4().to_string();
+        record_audit(&mut tx, NewAuditEntry {
+            group_id: Some(group_id.clone()),
+            entity_type: payload.entity_type.name().to_string(),
+            entity_id: None,
+            action: "Paste".to_string(),
+            description: Some(format!("Pasted {} rows", created.len())),
+            before: None,
+            after: Some(Value::Array(snapshot)),
+        })
+        .await?;
+        Some(group_id)
+    };
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    let unresolved = outcome
+        .unresolved
+        .into_iter()
+        .map(|mut u| {
+            u.target_name = names.get(&(u.target_table.clone(), u.target_id.clone())).cloned();
+            u
+        })
+        .collect();
+
+    Ok(PasteResult {
+        entity_type: payload.entity_type,
+        created,
+        unresolved,
+        audit_group_id,
+    })
+}
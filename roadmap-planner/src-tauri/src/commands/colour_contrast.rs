@@ -0,0 +1,137 @@
+// Tauri commands for the colour accessibility audit
+// Capability colours sit behind the white labels the timeline draws, so each must leave them readable
+
+use crate::commands::{get_capabilities, validate_hex_colour};
+use crate::db::Capability;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+// WCAG 2.x AA minimum contrast for normal-sized text
+pub const AA_CONTRAST_RATIO: f64 = 4.5;
+
+// The label colour bars are drawn with
+pub const DRAWN_LABEL_COLOUR: &str = "#FFFFFF";
+// Label colours a capability could switch to
+pub const LABEL_COLOURS: [&str; 2] = ["#000000", "#FFFFFF"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContrastIssue {
+    pub capability_id: String,
+    pub name: String,
+    pub colour: String,
+    // Contrast of the drawn white label, below AA
+    pub label_ratio: f64,
+    // The label colour that reads best, and its contrast ratio against the capability colour
+    pub best_label_colour: String,
+    pub best_ratio: f64,
+}
+
+/// Red, green and blue of a #RGB or #RRGGBB colour
+pub fn parse_hex_colour(colour: &str) -> Result<[u8; 3], String> {
+    validate_hex_colour(colour)?;
+    let digits = &colour[1..];
+    let channel = |i: usize| {
+        if digits.len() == 3 {
+            u8::from_str_radix(&digits[i..i + 1], 16).map(|v| v * 17)
+        } else {
+            u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16)
+        }
+    };
+    Ok([channel(0), channel(1), channel(2)].map(|c| c.expect("validated as hex")))
+}
+
+/// WCAG relative luminance, from 0 for black to 1 for white
+pub fn relative_luminance(rgb: [u8; 3]) -> f64 {
+    let linear = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * linear(rgb[0]) + 0.7152 * linear(rgb[1]) + 0.0722 * linear(rgb[2])
+}
+
+/// WCAG contrast ratio between two colours, from 1 to 21
+pub fn contrast_ratio(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Capabilities whose colour leaves the drawn white label short of AA, least readable first,
+/// with the best of black or white. Every colour reaches at least 4.58:1 against one of the two,
+/// so the best label always passes. Capabilities without a colour, or with one that isn't valid
+/// hex, are not audited.
+pub fn find_contrast_issues(capabilities: &[Capability]) -> Vec<ContrastIssue> {
+    let label = |colour: &str| parse_hex_colour(colour).expect("label colours are valid");
+    let mut issues: Vec<ContrastIssue> = capabilities
+        .iter()
+        .filter_map(|capability| {
+            let colour = capability.colour.as_deref()?;
+            let rgb = parse_hex_colour(colour).ok()?;
+            let label_ratio = contrast_ratio(rgb, label(DRAWN_LABEL_COLOUR));
+            if label_ratio >= AA_CONTRAST_RATIO {
+                return None;
+            }
+            let (best_label_colour, best_ratio) = LABEL_COLOURS
+                .iter()
+                .map(|l| (*l, contrast_ratio(rgb, label(l))))
+                .max_by(|a, b| a.1.total_cmp(&b.1))?;
+            Some(ContrastIssue {
+                capability_id: capability.id.clone(),
+                name: capability.name.clone(),
+                colour: colour.to_string(),
+                label_ratio,
+                best_label_colour: best_label_colour.to_string(),
+                best_ratio,
+            })
+        })
+        .collect();
+    issues.sort_by(|a, b| a.label_ratio.total_cmp(&b.label_ratio).then_with(|| a.name.cmp(&b.name)));
+    issues
+}
+
+// ============================================
+// COLOUR ACCESSIBILITY COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn check_colour_accessibility(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<Vec<ContrastIssue>, String> {
+    let capabilities = get_capabilities(db).await?;
+    Ok(find_contrast_issues(&capabilities))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capability(id: &str, colour: Option<&str>) -> Capability {
+        Capability {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            capability_type: "Business".to_string(),
+            parent_id: None,
+            colour: colour.map(str::to_string),
+            sort_order: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn light_colours_are_flagged_with_the_label_that_reads_best() {
+        assert!((contrast_ratio([0, 0, 0], [255, 255, 255]) - 21.0).abs() < 1e-9);
+        assert_eq!(parse_hex_colour("#f80").unwrap(), [255, 136, 0]);
+
+        let capabilities = vec![
+            capability("navy", Some("#000080")),
+            capability("yellow", Some("#FFFF00")),
+            capability("grey", Some("#777777")),
+            capability("red", Some("#E53935")),
+            capability("unset", None),
+            capability("invalid", Some("yellow")),
+        ];
+        let issues = find_contrast_issues(&capabilities);
+        let flagged: Vec<(&str, &str)> = issues.iter().map(|i| (i.capability_id.as_str(), i.best_label_colour.as_str())).collect();
+        assert_eq!(flagged, [("yellow", "#000000"), ("red", "#000000"), ("grey", "#000000")]);
+        assert!(issues.iter().all(|i| i.label_ratio < AA_CONTRAST_RATIO && i.best_ratio >= AA_CONTRAST_RATIO));
+    }
+}
@@ -0,0 +1,313 @@
+// Tauri commands for initiative comment threads
+// Comment CRUD with @[resource name] mentions resolved against resources
+
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+use std::collections::HashMap;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    pub initiative_id: String,
+    pub author_resource_id: Option<String>,
+    pub body: String,
+    pub reply_to_comment_id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MentionedResource {
+    pub resource_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentWithMentions {
+    pub comment: Comment,
+    pub mentions: Vec<MentionedResource>,
+    // @[name] tokens that match no resource
+    pub unresolved_mentions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceMention {
+    pub comment: Comment,
+    pub initiative_name: String,
+}
+
+/// Names referenced as @[name] in a comment body, in order of first appearance
+pub fn extract_mentions(body: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find("@[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(']') else {
+            break;
+        };
+        let name = after[..end].trim();
+
+        // An unclosed token swallows the next one; resume scanning inside it
+        if name.contains('[') || name.contains('\n') {
+            rest = after;
+            continue;
+        }
+        if !name.is_empty() && !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            names.push(name.to_string());
+        }
+        rest = &after[end + 1..];
+    }
+
+    names
+}
+
+// Replace a comment's mentions with those in its body; names match case-insensitively
+async fn store_mentions(conn: &mut SqliteConnection, comment_id: &str, body: &str) -> Result<(), String> {
+    sqlx::query!("DELETE FROM comment_mentions WHERE comment_id = ?", comment_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let now = get_current_timestamp();
+    for name in extract_mentions(body) {
+        let resource_ids: Vec<String> = sqlx::query_scalar!("SELECT id FROM resources WHERE name = ? COLLATE NOCASE", name)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        for resource_id in resource_ids {
+            let id = uuid::Uuid::new_v4().to_string();
+            sqlx::query!(
+                r#"INSERT OR IGNORE INTO comment_mentions (id, comment_id, resource_id, created_at)
+                VALUES (?, ?, ?, ?)"#,
+                id,
+                comment_id,
+                resource_id,
+                now
+            )
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+// Attach resolved mentions, and the tokens that resolved to nothing, to each comment
+async fn with_mentions(conn: &mut SqliteConnection, comments: Vec<Comment>) -> Result<Vec<CommentWithMentions>, String> {
+    let ids = serde_json::to_string(&comments.iter().map(|c| &c.id).collect::<Vec<_>>()).map_err(|e| e.to_string())?;
+
+    let rows = sqlx::query!(
+        r#"SELECT m.comment_id, r.id as "resource_id!", r.name as "name!"
+        FROM comment_mentions m
+        JOIN resources r ON r.id = m.resource_id
+        WHERE m.comment_id IN (SELECT value FROM json_each(?))
+        ORDER BY r.name"#,
+        ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut by_comment: HashMap<String, Vec<MentionedResource>> = HashMap::new();
+    for row in rows {
+        by_comment.entry(row.comment_id).or_default().push(MentionedResource {
+            resource_id: row.resource_id,
+            name: row.name,
+        });
+    }
+
+    Ok(comments
+        .into_iter()
+        .map(|comment| {
+            let mentions = by_comment.remove(&comment.id).unwrap_or_default();
+            let unresolved_mentions = extract_mentions(&comment.body)
+                .into_iter()
+                .filter(|name| !mentions.iter().any(|m| m.name.eq_ignore_ascii_case(name)))
+                .collect();
+            CommentWithMentions { comment, mentions, unresolved_mentions }
+        })
+        .collect())
+}
+
+async fn fetch_comment(conn: &mut SqliteConnection, id: &str) -> Result<CommentWithMentions, String> {
+    let comment: Comment = sqlx::query_as!(
+        Comment,
+        r#"SELECT id, initiative_id, author_resource_id, body, reply_to_comment_id, created_at, updated_at
+        FROM comments WHERE id = ?"#,
+        id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Comment {} not found", id))?;
+
+    let mut comments = with_mentions(conn, vec![comment]).await?;
+    Ok(comments.remove(0))
+}
+
+fn validate_body(body: &str) -> Result<(), String> {
+    if body.trim().is_empty() {
+        return Err("Comment body cannot be empty".to_string());
+    }
+    Ok(())
+}
+
+// ============================================
+// COMMENTS COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_comments(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String) -> Result<Vec<CommentWithMentions>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+
+    let comments: Vec<Comment> = sqlx::query_as!(
+        Comment,
+        r#"SELECT id, initiative_id, author_resource_id, body, reply_to_comment_id, created_at, updated_at
+        FROM comments WHERE initiative_id = ? ORDER BY created_at, id"#,
+        initiative_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    with_mentions(&mut conn, comments).await
+}
+
+#[tauri::command]
+pub async fn get_comment(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<CommentWithMentions, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    fetch_comment(&mut conn, &id).await
+}
+
+#[tauri::command]
+pub async fn create_comment(db: State<'_, tauri_plugin_sql::DbInstances>, comment: Comment) -> Result<CommentWithMentions, String> {
+    validate_body(&comment.body)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Replies stay within their initiative's thread
+    if let Some(parent_id) = &comment.reply_to_comment_id {
+        let parent_initiative = sqlx::query_scalar!("SELECT initiative_id FROM comments WHERE id = ?", parent_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Comment {} not found", parent_id))?;
+        if parent_initiative != comment.initiative_id {
+            return Err(format!("Comment {} belongs to a different initiative", parent_id));
+        }
+    }
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO comments (id, initiative_id, author_resource_id, body, reply_to_comment_id, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+        comment.id,
+        comment.initiative_id,
+        comment.author_resource_id,
+        comment.body,
+        comment.reply_to_comment_id,
+        now,
+        now
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    store_mentions(&mut tx, &comment.id, &comment.body).await?;
+    let created = fetch_comment(&mut tx, &comment.id).await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_comment(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, body: String) -> Result<CommentWithMentions, String> {
+    validate_body(&body)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let now = get_current_timestamp();
+
+    let result = sqlx::query!("UPDATE comments SET body = ?, updated_at = ? WHERE id = ?", body, now, id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    if result.rows_affected() == 0 {
+        return Err(format!("Comment {} not found", id));
+    }
+
+    store_mentions(&mut tx, &id, &body).await?;
+    let updated = fetch_comment(&mut tx, &id).await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_comment(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // Replies are kept and become top-level comments
+    sqlx::query!("DELETE FROM comments WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_mentions_for_resource(db: State<'_, tauri_plugin_sql::DbInstances>, resource_id: String) -> Result<Vec<ResourceMention>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows = sqlx::query!(
+        r#"SELECT c.id, c.initiative_id, c.author_resource_id, c.body, c.reply_to_comment_id,
+            c.created_at, c.updated_at, i.name as "initiative_name!"
+        FROM comment_mentions m
+        JOIN comments c ON c.id = m.comment_id
+        JOIN initiatives i ON i.id = c.initiative_id
+        WHERE m.resource_id = ?
+        ORDER BY c.created_at DESC"#,
+        resource_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| ResourceMention {
+            comment: Comment {
+                id: r.id,
+                initiative_id: r.initiative_id,
+                author_resource_id: r.author_resource_id,
+                body: r.body,
+                reply_to_comment_id: r.reply_to_comment_id,
+                created_at: Some(r.created_at),
+                updated_at: Some(r.updated_at),
+            },
+            initiative_name: r.initiative_name,
+        })
+        .collect())
+}
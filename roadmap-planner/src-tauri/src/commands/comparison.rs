@@ -0,0 +1,313 @@
+// Tauri commands for comparing scenarios side by side
+// One column per scenario and one row per headline metric, for the portfolio trade-off board
+
+use crate::commands::engine::dates::{DateSpan, today};
+use crate::commands::engine::resources::PoolPeriodAllocation;
+use crate::commands::exchange_rates::load_currency_converter;
+use crate::commands::risk::{RiskScore, RiskWeights, calculate_risk_score};
+use crate::commands::scenario_data::load_scenario_data;
+use crate::commands::summaries::{DEFAULT_BEHIND_THRESHOLD, ScenarioSummary, summarise_scenario};
+use crate::commands::{get_initiatives, get_scenario};
+use crate::db::FinancialPeriod;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioColumn {
+    pub scenario_id: String,
+    pub scenario_name: String,
+    pub is_baseline: bool,
+    // Green, Amber or Red, as get_scenario_risk_score reports it
+    pub risk_level: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonRow {
+    pub metric: String,
+    // One value per column, in column order
+    pub values: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonMatrix {
+    // Reporting currency of the total cost row
+    pub currency: String,
+    pub scenarios: Vec<ScenarioColumn>,
+    pub rows: Vec<ComparisonRow>,
+}
+
+// What each scenario contributes to its column
+pub struct ScenarioMetrics {
+    pub summary: ScenarioSummary,
+    pub risk: RiskScore,
+    // Highest utilisation of any pool in any period, as a percentage
+    pub peak_utilisation: f64,
+}
+
+/// Lay the per-scenario metrics out as rows across scenario columns
+pub fn build_comparison_matrix(currency: String, metrics: Vec<ScenarioMetrics>) -> ComparisonMatrix {
+    let row = |metric: &str, value: fn(&ScenarioMetrics) -> f64| ComparisonRow {
+        metric: metric.to_string(),
+        values: metrics.iter().map(value).collect(),
+    };
+
+    let rows = vec![
+        row("TotalCost", |m| m.summary.total_cost),
+        row("TotalEffort", |m| m.summary.total_effort),
+        row("InitiativeCount", |m| m.summary.initiative_count as f64),
+        row("RiskScore", |m| m.risk.score),
+        row("PeakUtilisation", |m| m.peak_utilisation),
+    ];
+
+    let scenarios = metrics
+        .into_iter()
+        .map(|m| ScenarioColumn {
+            scenario_id: m.summary.scenario_id,
+            scenario_name: m.summary.scenario_name,
+            is_baseline: m.summary.is_baseline,
+            risk_level: m.risk.level,
+        })
+        .collect();
+
+    ComparisonMatrix { currency, scenarios, rows }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemandDelta {
+    pub period_id: String,
+    pub period_name: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub pool_id: String,
+    pub pool_name: String,
+    // The pool's capacity unit; demand is only ever compared within a pool
+    pub unit: String,
+    pub base_demand: f64,
+    pub compare_demand: f64,
+    // Positive when the compare scenario needs more in the period
+    pub delta: f64,
+}
+
+// Demand per (period start, period id, pool name, pool id), with the pool's unit
+type PeriodDemand = BTreeMap<(String, String, String, String), (String, f64)>;
+
+/// Re-bucket pool demand into financial periods, pro-rating each pool period by the days it
+/// shares with the financial period
+fn demand_by_period(allocations: &[PoolPeriodAllocation], periods: &[FinancialPeriod]) -> PeriodDemand {
+    let mut demand = PeriodDemand::new();
+    for period in periods {
+        let Some(window) = DateSpan::parse_inclusive(Some(&period.start_date), Some(&period.end_date)) else {
+            continue;
+        };
+        for allocation in allocations {
+            let Some(span) = DateSpan::parse_inclusive(Some(&allocation.period_start), Some(&allocation.period_end)) else {
+                continue;
+            };
+            let shared = span.overlap_days(&window);
+            if shared == 0 {
+                continue;
+            }
+            let key = (period.start_date.clone(), period.id.clone(), allocation.pool_name.clone(), allocation.pool_id.clone());
+            let entry = demand.entry(key).or_insert_with(|| (allocation.unit.clone(), 0.0));
+            entry.1 += allocation.demand * shared as f64 / span.days() as f64;
+        }
+    }
+    demand
+}
+
+/// Change in each pool's demand per financial period going from `base` to `compare`, leaving
+/// out pairs with no demand in either scenario
+pub fn diff_demand(base: &[PoolPeriodAllocation], compare: &[PoolPeriodAllocation], periods: &[FinancialPeriod]) -> Vec<DemandDelta> {
+    let base = demand_by_period(base, periods);
+    let compare = demand_by_period(compare, periods);
+    let names: BTreeMap<&str, (&str, &str)> = periods
+        .iter()
+        .map(|p| (p.id.as_str(), (p.name.as_str(), p.end_date.as_str())))
+        .collect();
+
+    let keys: BTreeSet<_> = base.keys().chain(compare.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let (start_date, period_id, pool_name, pool_id) = key;
+            let base_demand = base.get(key).map_or(0.0, |(_, d)| *d);
+            let compare_demand = compare.get(key).map_or(0.0, |(_, d)| *d);
+            if base_demand == 0.0 && compare_demand == 0.0 {
+                return None;
+            }
+            let unit = base.get(key).or_else(|| compare.get(key)).map(|(u, _)| u.clone()).unwrap_or_default();
+            let (period_name, end_date) = names.get(period_id.as_str()).copied().unwrap_or_default();
+            Some(DemandDelta {
+                period_id: period_id.clone(),
+                period_name: period_name.to_string(),
+                start_date: start_date.clone(),
+                end_date: end_date.to_string(),
+                pool_id: pool_id.clone(),
+                pool_name: pool_name.clone(),
+                unit,
+                base_demand,
+                compare_demand,
+                delta: compare_demand - base_demand,
+            })
+        })
+        .collect()
+}
+
+// ============================================
+// SCENARIO COMPARISON COMMANDS
+// ============================================
+
+/// Cost, effort, size, risk and peak utilisation for each scenario, worked out the same way as
+/// get_scenario_summaries, get_scenario_risk_score and get_capacity_report
+#[tauri::command]
+pub async fn compare_scenarios(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_ids: Vec<String>) -> Result<ComparisonMatrix, String> {
+    if scenario_ids.is_empty() {
+        return Err("Choose at least one scenario to compare".to_string());
+    }
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = scenario_ids.iter().find(|id| !seen.insert(id.as_str())) {
+        return Err(format!("Scenario {} appears more than once", duplicate));
+    }
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+    let converter = load_currency_converter(pool).await?;
+    let as_of = today();
+    let weights = RiskWeights::default();
+
+    let mut metrics = Vec::with_capacity(scenario_ids.len());
+    for scenario_id in scenario_ids {
+        let scenario = get_scenario(db.clone(), scenario_id.clone()).await?;
+        let initiatives = get_initiatives(db.clone(), Some(scenario_id.clone())).await?;
+        let data = load_scenario_data(db.clone(), &scenario_id).await?;
+
+        let peak_utilisation = data.resource_allocation().iter().map(|a| a.utilisation).fold(0.0, f64::max);
+        metrics.push(ScenarioMetrics {
+            summary: summarise_scenario(scenario, &initiatives, &converter, as_of, DEFAULT_BEHIND_THRESHOLD),
+            risk: calculate_risk_score(&scenario_id, &data, &weights),
+            peak_utilisation,
+        });
+    }
+
+    Ok(build_comparison_matrix(converter.reporting_currency.clone(), metrics))
+}
+
+/// How much more (or less) of each pool the compare scenario needs than the base, per
+/// financial period, on the same demand figures as get_capacity_report
+#[tauri::command]
+pub async fn diff_resource_demand(db: State<'_, tauri_plugin_sql::DbInstances>, base_id: String, compare_id: String) -> Result<Vec<DemandDelta>, String> {
+    if base_id == compare_id {
+        return Err("Choose two different scenarios to compare".to_string());
+    }
+    let base = load_scenario_data(db.clone(), &base_id).await?;
+    let compare = load_scenario_data(db.clone(), &compare_id).await?;
+
+    Ok(diff_demand(&base.resource_allocation(), &compare.resource_allocation(), &base.periods))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(id: &str, total_cost: f64, count: i64, risk: f64, peak_utilisation: f64) -> ScenarioMetrics {
+        ScenarioMetrics {
+            summary: ScenarioSummary {
+                scenario_id: id.to_string(),
+                scenario_name: id.to_uppercase(),
+                is_baseline: id == "baseline",
+                initiative_count: count,
+                currency: "GBP".to_string(),
+                total_cost_native: BTreeMap::new(),
+                total_cost,
+                total_effort: 10.0 * count as f64,
+                planned_value: 0.0,
+                earned_value: 0.0,
+                schedule_performance_index: None,
+                behind_schedule: Vec::new(),
+                stale_critical_systems: 0,
+                approval_state: "NotSubmitted".to_string(),
+                warnings: Vec::new(),
+            },
+            risk: RiskScore { scenario_id: id.to_string(), score: risk, level: "Green".to_string(), breakdown: Vec::new() },
+            peak_utilisation,
+        }
+    }
+
+    #[test]
+    fn metrics_become_rows_across_scenario_columns() {
+        let matrix = build_comparison_matrix(
+            "GBP".to_string(),
+            vec![metrics("baseline", 1000.0, 3, 5.0, 80.0), metrics("stretch", 1500.0, 4, 12.0, 120.0)],
+        );
+
+        assert_eq!(matrix.scenarios.iter().map(|s| s.scenario_id.as_str()).collect::<Vec<_>>(), ["baseline", "stretch"]);
+        assert!(matrix.scenarios[0].is_baseline);
+
+        let values = |metric: &str| matrix.rows.iter().find(|r| r.metric == metric).unwrap().values.clone();
+        assert_eq!(values("TotalCost"), [1000.0, 1500.0]);
+        assert_eq!(values("TotalEffort"), [30.0, 40.0]);
+        assert_eq!(values("InitiativeCount"), [3.0, 4.0]);
+        assert_eq!(values("RiskScore"), [5.0, 12.0]);
+        assert_eq!(values("PeakUtilisation"), [80.0, 120.0]);
+    }
+
+    fn allocation(pool_id: &str, start: &str, end: &str, demand: f64) -> PoolPeriodAllocation {
+        PoolPeriodAllocation {
+            pool_id: pool_id.to_string(),
+            pool_name: pool_id.to_uppercase(),
+            unit: "FTE".to_string(),
+            period_start: start.to_string(),
+            period_end: end.to_string(),
+            demand,
+            capacity: 10.0,
+            utilisation: demand * 10.0,
+            contributing_initiatives: Vec::new(),
+            roles: Vec::new(),
+        }
+    }
+
+    fn period(id: &str, start: &str, end: &str) -> FinancialPeriod {
+        FinancialPeriod {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            period_type: "Quarter".to_string(),
+            start_date: start.to_string(),
+            end_date: end.to_string(),
+            budget_available: None,
+            currency: None,
+            closed: false,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn demand_changes_are_reported_per_period_and_pool() {
+        let periods = vec![period("q1", "2027-01-01", "2027-03-31"), period("q2", "2027-04-01", "2027-06-30")];
+        let base = vec![
+            allocation("dev", "2027-01-01", "2027-01-31", 3.0),
+            allocation("dev", "2027-02-01", "2027-02-28", 3.0),
+            allocation("ops", "2027-04-01", "2027-04-30", 2.0),
+        ];
+        let compare = vec![
+            allocation("dev", "2027-01-01", "2027-01-31", 5.0),
+            allocation("dev", "2027-02-01", "2027-02-28", 3.0),
+            allocation("dev", "2027-05-01", "2027-05-31", 1.0),
+        ];
+
+        let deltas = diff_demand(&base, &compare, &periods);
+        let summary: Vec<(&str, &str, f64, f64, f64)> = deltas
+            .iter()
+            .map(|d| (d.period_id.as_str(), d.pool_id.as_str(), d.base_demand, d.compare_demand, d.delta))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("q1", "dev", 6.0, 8.0, 2.0),
+                ("q2", "dev", 0.0, 1.0, 1.0),
+                ("q2", "ops", 2.0, 0.0, -2.0),
+            ]
+        );
+        assert_eq!(deltas[0].end_date, "2027-03-31");
+    }
+}
@@ -0,0 +1,26 @@
+// Tauri commands for constraint compliance
+// Which constraints bite on a scenario, and where they are broken
+
+use crate::commands::engine::constraints::{ConstraintViolation, ScenarioConstraint, check_hard_constraints, summarise_constraints};
+use crate::commands::scenario_data::load_scenario_data;
+use tauri::State;
+
+// ============================================
+// COMPLIANCE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_scenario_constraints(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<ScenarioConstraint>, String> {
+    let data = load_scenario_data(db, &scenario_id).await?;
+
+    Ok(summarise_constraints(&data.initiatives, &data.constraints, &data.constraint_links))
+}
+
+/// The initiatives breaking a hard constraint, for a go/no-go gate. Soft constraints are left
+/// out; an empty list means nothing blocks the scenario.
+#[tauri::command]
+pub async fn get_hard_constraint_violations(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<ConstraintViolation>, String> {
+    let data = load_scenario_data(db, &scenario_id).await?;
+
+    Ok(check_hard_constraints(&data.initiatives, &data.constraints, &data.constraint_links))
+}
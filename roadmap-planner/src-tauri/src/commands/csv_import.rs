@@ -0,0 +1,325 @@
+// Tauri commands for importing records from CSV
+// The first row names the columns; every row goes in or none do
+
+use crate::commands::engine::dates::{format_date, parse_date};
+use crate::commands::fetch::fetch_systems;
+use crate::commands::journal::JournaledOperation;
+use crate::db::{System, get_current_timestamp};
+use std::collections::HashSet;
+use tauri::State;
+
+pub const LIFECYCLE_STAGES: &[&str] = &["Discovery", "Development", "Production", "Sunset", "Retired"];
+pub const CRITICALITIES: &[&str] = &["Critical", "High", "Medium", "Low"];
+
+pub const DEFAULT_LIFECYCLE_STAGE: &str = "Production";
+pub const DEFAULT_CRITICALITY: &str = "Medium";
+
+// Separators accepted between technologies in a single cell
+const TECHNOLOGY_SEPARATORS: &[char] = &[';', '|', ','];
+
+/// One parsed row and the line it starts on, counting the header as line 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRecord {
+    pub line: usize,
+    pub fields: Vec<String>,
+}
+
+/// Split CSV text into records, allowing quoted fields with doubled quotes and line breaks
+pub fn parse_csv(text: &str) -> Result<Vec<CsvRecord>, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                fields.push(std::mem::take(&mut field));
+                push_record(&mut records, record_line, std::mem::take(&mut fields));
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("Line {}: quoted field is never closed", record_line));
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        push_record(&mut records, record_line, fields);
+    }
+    Ok(records)
+}
+
+// Blank lines are skipped rather than read as a row of empty fields
+fn push_record(records: &mut Vec<CsvRecord>, line: usize, fields: Vec<String>) {
+    if fields.len() == 1 && fields[0].trim().is_empty() {
+        return;
+    }
+    records.push(CsvRecord { line, fields });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SystemColumn {
+    Id,
+    Name,
+    Description,
+    Owner,
+    Vendor,
+    TechnologyStack,
+    LifecycleStage,
+    Criticality,
+    SupportEndDate,
+    ExtendedSupportEndDate,
+    CapabilityId,
+    ExternalRef,
+}
+
+impl SystemColumn {
+    const ALL: &'static [(SystemColumn, &'static str)] = &[
+        (SystemColumn::Id, "id"),
+        (SystemColumn::Name, "name"),
+        (SystemColumn::Description, "description"),
+        (SystemColumn::Owner, "owner"),
+        (SystemColumn::Vendor, "vendor"),
+        (SystemColumn::TechnologyStack, "technology_stack"),
+        (SystemColumn::LifecycleStage, "lifecycle_stage"),
+        (SystemColumn::Criticality, "criticality"),
+        (SystemColumn::SupportEndDate, "support_end_date"),
+        (SystemColumn::ExtendedSupportEndDate, "extended_support_end_date"),
+        (SystemColumn::CapabilityId, "capability_id"),
+        (SystemColumn::ExternalRef, "external_ref"),
+    ];
+
+    // Matches field names and the headers the TSV export writes, e.g. "Technology Stack"
+    fn from_header(header: &str) -> Option<SystemColumn> {
+        let key = header.trim().to_lowercase().replace([' ', '-'], "_");
+        Self::ALL.iter().find(|(_, name)| *name == key).map(|(column, _)| *column)
+    }
+}
+
+/// The delimited technologies in a cell, as the JSON array stored on the system
+pub fn technology_stack_json(cell: &str) -> Option<String> {
+    let technologies: Vec<&str> = cell
+        .split(TECHNOLOGY_SEPARATORS)
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .collect();
+    (!technologies.is_empty()).then(|| serde_json::to_string(&technologies).unwrap_or_default())
+}
+
+fn choice(value: &str, allowed: &[&'static str], what: &str, line: usize) -> Result<String, String> {
+    allowed
+        .iter()
+        .find(|a| a.eq_ignore_ascii_case(value))
+        .map(|a| a.to_string())
+        .ok_or_else(|| format!("Line {}: {} must be one of {}, got \"{}\"", line, what, allowed.join(", "), value))
+}
+
+fn date(value: &str, what: &str, line: usize) -> Result<String, String> {
+    parse_date(value)
+        .map(format_date)
+        .ok_or_else(|| format!("Line {}: {} must be a YYYY-MM-DD date, got \"{}\"", line, what, value))
+}
+
+/// Read systems from CSV text, each with the line it came from
+pub fn parse_systems_csv(csv: &str) -> Result<Vec<(usize, System)>, String> {
+    let mut records = parse_csv(csv)?.into_iter();
+    let header = records.next().ok_or_else(|| "The CSV is empty".to_string())?;
+
+    let mut columns = Vec::with_capacity(header.fields.len());
+    for name in &header.fields {
+        let column = SystemColumn::from_header(name).ok_or_else(|| {
+            let known: Vec<&str> = SystemColumn::ALL.iter().map(|(_, n)| *n).collect();
+            format!("Line 1: unknown column \"{}\"; expected {}", name.trim(), known.join(", "))
+        })?;
+        if columns.contains(&column) {
+            return Err(format!("Line 1: column \"{}\" appears more than once", name.trim()));
+        }
+        columns.push(column);
+    }
+    if !columns.contains(&SystemColumn::Name) {
+        return Err("Line 1: a name column is required".to_string());
+    }
+
+    let mut ids = HashSet::new();
+    let mut systems = Vec::new();
+    for record in records {
+        let line = record.line;
+        if record.fields.len() > columns.len() {
+            return Err(format!("Line {}: {} values for {} columns", line, record.fields.len(), columns.len()));
+        }
+
+        let mut system = System {
+            id: String::new(),
+            name: String::new(),
+            description: None,
+            owner: None,
+            vendor: None,
+            technology_stack: None,
+            lifecycle_stage: DEFAULT_LIFECYCLE_STAGE.to_string(),
+            criticality: DEFAULT_CRITICALITY.to_string(),
+            support_end_date: None,
+            extended_support_end_date: None,
+            capability_id: None,
+            external_ref: None,
+            created_at: None,
+            updated_at: None,
+        };
+
+        for (column, value) in columns.iter().zip(&record.fields) {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            let text = Some(value.to_string());
+            match column {
+                SystemColumn::Id => system.id = value.to_string(),
+                SystemColumn::Name => system.name = value.to_string(),
+                SystemColumn::Description => system.description = text,
+                SystemColumn::Owner => system.owner = text,
+                SystemColumn::Vendor => system.vendor = text,
+                SystemColumn::TechnologyStack => system.technology_stack = technology_stack_json(value),
+                SystemColumn::LifecycleStage => system.lifecycle_stage = choice(value, LIFECYCLE_STAGES, "lifecycle_stage", line)?,
+                SystemColumn::Criticality => system.criticality = choice(value, CRITICALITIES, "criticality", line)?,
+                SystemColumn::SupportEndDate => system.support_end_date = Some(date(value, "support_end_date", line)?),
+                SystemColumn::ExtendedSupportEndDate => system.extended_support_end_date = Some(date(value, "extended_support_end_date", line)?),
+                SystemColumn::CapabilityId => system.capability_id = text,
+                SystemColumn::ExternalRef => system.external_ref = text,
+            }
+        }
+
+        if system.name.is_empty() {
+            return Err(format!("Line {}: name is required", line));
+        }
+        if let (Some(support), Some(extended)) = (&system.support_end_date, &system.extended_support_end_date) {
+            if extended < support {
+                return Err(format!("Line {}: extended_support_end_date is before support_end_date", line));
+            }
+        }
+        if system.id.is_empty() {
+            system.id = uuid::Uuid::new_v4().to_string();
+        }
+        if !ids.insert(system.id.clone()) {
+            return Err(format!("Line {}: id {} appears more than once", line, system.id));
+        }
+        systems.push((line, system));
+    }
+
+    Ok(systems)
+}
+
+// ============================================
+// CSV IMPORT COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn import_systems_csv(db: State<'_, tauri_plugin_sql::DbInstances>, csv: String) -> Result<Vec<System>, String> {
+    // Every row is checked before anything is written
+    let systems = parse_systems_csv(&csv)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let operation = JournaledOperation::start(pool, "import_systems_csv", serde_json::json!({ "csv": csv })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    for (line, system) in &systems {
+        sqlx::query!(
+            r#"INSERT INTO systems (id, name, description, owner, vendor, technology_stack,
+                lifecycle_stage, criticality, support_end_date, extended_support_end_date,
+                capability_id, external_ref, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            system.id,
+            system.name,
+            system.description,
+            system.owner,
+            system.vendor,
+            system.technology_stack,
+            system.lifecycle_stage,
+            system.criticality,
+            system.support_end_date,
+            system.extended_support_end_date,
+            system.capability_id,
+            system.external_ref,
+            now,
+            now
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            Some(d) if d.is_unique_violation() => format!("Line {}: a system with id {} already exists", line, system.id),
+            Some(d) if d.is_foreign_key_violation() => format!("Line {}: capability {} not found", line, system.capability_id.as_deref().unwrap_or_default()),
+            _ => format!("Line {}: {}", line, e),
+        })?;
+    }
+
+    let ids: Vec<String> = systems.iter().map(|(_, s)| s.id.clone()).collect();
+    let mut created = fetch_systems(&mut tx, &ids).await?;
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    // In file order, not the order the database returns them
+    created.sort_by_key(|s| ids.iter().position(|id| *id == s.id));
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_fields_keep_commas_quotes_and_line_breaks() {
+        let records = parse_csv("name,description\r\n\"Core, Ledger\",\"Says \"\"hi\"\"\nover two lines\"\n\nCRM,\n").unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1].fields, vec!["Core, Ledger", "Says \"hi\"\nover two lines"]);
+        assert_eq!(records[2], CsvRecord { line: 5, fields: vec!["CRM".to_string(), String::new()] });
+
+        assert!(parse_csv("name\n\"unclosed").is_err());
+    }
+
+    #[test]
+    fn systems_are_read_from_mapped_columns() {
+        let csv = "Name,Technology Stack,lifecycle_stage,Support End Date\nLedger,Java; Oracle | Kafka,sunset,2027-03-31\nCRM,,,\n";
+        let systems: Vec<System> = parse_systems_csv(csv).unwrap().into_iter().map(|(_, s)| s).collect();
+
+        assert_eq!(systems[0].technology_stack.as_deref(), Some(r#"["Java","Oracle","Kafka"]"#));
+        assert_eq!(systems[0].lifecycle_stage, "Sunset");
+        assert_eq!(systems[0].support_end_date.as_deref(), Some("2027-03-31"));
+        assert!(!systems[0].id.is_empty());
+        assert_eq!(systems[1].criticality, DEFAULT_CRITICALITY);
+        assert_eq!(systems[1].technology_stack, None);
+
+        let bad_date = parse_systems_csv("name,support_end_date\nLedger,2027-03-31\nCRM,31/03/2027\n").unwrap_err();
+        assert!(bad_date.starts_with("Line 3:"), "{}", bad_date);
+        assert!(parse_systems_csv("name,colour\nLedger,red\n").is_err());
+        assert!(parse_systems_csv("id,name\nsys-1,Ledger\nsys-1,CRM\n").is_err());
+    }
+}
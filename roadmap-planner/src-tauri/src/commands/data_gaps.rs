@@ -0,0 +1,243 @@
+// Tauri commands for the data gaps panel
+// Quick filters over a scenario's initiatives that are missing key data, one bucket per rule
+
+use crate::commands::engine::dates::parse_date;
+use crate::commands::settings::read_bool_setting;
+use crate::commands::{get_initiatives, require_scenario};
+use crate::db::Initiative;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::collections::HashSet;
+use tauri::State;
+
+pub struct GapRule {
+    pub id: &'static str,
+    pub label: &'static str,
+    // Condition on the initiatives row `i`; cancelled initiatives are never reported
+    pub condition: &'static str,
+}
+
+const fn gap(id: &'static str, label: &'static str, condition: &'static str) -> GapRule {
+    GapRule { id, label, condition }
+}
+
+// The order here is the order of the buckets in the response
+pub const GAP_RULES: &[GapRule] = &[
+    gap("cost.missing", "No cost estimate", "i.cost_estimate IS NULL"),
+    gap("effort.missing", "No effort estimate", "i.effort_estimate IS NULL"),
+    gap(
+        "dates.missing",
+        "Planned without dates",
+        "i.status IN ('Planned', 'InProgress') AND (i.start_date IS NULL OR i.end_date IS NULL)",
+    ),
+    gap(
+        "resources.missing",
+        "In progress with no resources",
+        "i.status = 'InProgress'
+            AND NOT EXISTS (SELECT 1 FROM initiative_resources ir WHERE ir.initiative_id = i.id)
+            AND NOT EXISTS (SELECT 1 FROM initiative_resource_requirements rr WHERE rr.initiative_id = i.id AND rr.approval_status <> 'Rejected')",
+    ),
+    gap("description.missing", "No description", "TRIM(COALESCE(i.description, '')) = ''"),
+    gap(
+        "systems.missing",
+        "Not linked to a system",
+        "NOT EXISTS (SELECT 1 FROM system_initiatives si WHERE si.initiative_id = i.id)",
+    ),
+    gap(
+        "objectives.missing",
+        "Not linked to an objective",
+        "NOT EXISTS (SELECT 1 FROM initiative_objectives io WHERE io.initiative_id = i.id)",
+    ),
+];
+
+/// Setting that turns a gap rule off, e.g. data_gaps.description.missing.enabled = false
+pub fn gap_rule_setting(rule_id: &str) -> String {
+    format!("data_gaps.{}.enabled", rule_id)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GapInitiative {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GapBucket {
+    pub rule_id: String,
+    pub label: String,
+    // A disabled rule keeps its bucket, empty, so the panel's layout never shifts
+    pub enabled: bool,
+    pub count: i64,
+    pub initiatives: Vec<GapInitiative>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataGaps {
+    pub scenario_id: String,
+    // Initiatives the rules look at, i.e. everything not cancelled
+    pub initiative_count: i64,
+    // Initiatives in at least one enabled bucket, for the dashboard tile
+    pub initiatives_with_gaps: i64,
+    pub buckets: Vec<GapBucket>,
+}
+
+/// Fill in a bucket for every rule, in rule order, from the initiatives each enabled rule matched
+pub fn build_data_gaps(scenario_id: String, initiative_count: i64, matches: Vec<(&GapRule, Option<Vec<GapInitiative>>)>) -> DataGaps {
+    let mut with_gaps = HashSet::new();
+    let buckets = matches
+        .into_iter()
+        .map(|(rule, found)| {
+            let enabled = found.is_some();
+            let initiatives = found.unwrap_or_default();
+            with_gaps.extend(initiatives.iter().map(|i| i.id.clone()));
+            GapBucket {
+                rule_id: rule.id.to_string(),
+                label: rule.label.to_string(),
+                enabled,
+                count: initiatives.len() as i64,
+                initiatives,
+            }
+        })
+        .collect();
+
+    DataGaps { scenario_id, initiative_count, initiatives_with_gaps: with_gaps.len() as i64, buckets }
+}
+
+// An initiative whose end date is on or before its start date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DurationIssue {
+    #[serde(flatten)]
+    pub initiative: Initiative,
+    // End date minus start date, so zero or negative
+    pub duration_days: i64,
+}
+
+/// Initiatives ending on or before the day they start, most negative first. Unparseable dates
+/// are left to validate_workspace.
+pub fn zero_or_negative_durations(initiatives: Vec<Initiative>) -> Vec<DurationIssue> {
+    let mut issues: Vec<DurationIssue> = initiatives
+        .into_iter()
+        .filter_map(|initiative| {
+            let start = parse_date(initiative.start_date.as_deref()?)?;
+            let end = parse_date(initiative.end_date.as_deref()?)?;
+            let duration_days = (end - start).num_days();
+            (duration_days <= 0).then_some(DurationIssue { initiative, duration_days })
+        })
+        .collect();
+    issues.sort_by(|a, b| a.duration_days.cmp(&b.duration_days).then_with(|| a.initiative.name.cmp(&b.initiative.name)));
+    issues
+}
+
+// ============================================
+// DATA GAP COMMANDS
+// ============================================
+
+/// Initiatives in the scenario missing key data, bucketed by gap rule with counts for the dashboard
+#[tauri::command]
+pub async fn get_data_gaps(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<DataGaps, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let initiative_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM initiatives WHERE scenario_id = ? AND status <> 'Cancelled'"#,
+        scenario_id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut matches = Vec::with_capacity(GAP_RULES.len());
+    for rule in GAP_RULES {
+        if !read_bool_setting(pool, &gap_rule_setting(rule.id), true).await? {
+            matches.push((rule, None));
+            continue;
+        }
+
+        let found = sqlx::query(&format!(
+            "SELECT i.id, i.name FROM initiatives i
+            WHERE i.scenario_id = ? AND i.status <> 'Cancelled' AND ({})
+            ORDER BY i.name, i.id",
+            rule.condition
+        ))
+        .bind(&scenario_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Gap rule {} failed: {}", rule.id, e))?
+        .iter()
+        .map(|r| GapInitiative { id: r.get("id"), name: r.get("name") })
+        .collect();
+        matches.push((rule, Some(found)));
+    }
+
+    Ok(build_data_gaps(scenario_id, initiative_count, matches))
+}
+
+/// Initiatives already in the database whose end date equals or precedes their start date,
+/// which the timeline draws as a sliver or not at all
+#[tauri::command]
+pub async fn get_zero_or_negative_duration(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<DurationIssue>, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+    let initiatives = get_initiatives(db, Some(scenario_id)).await?;
+    Ok(zero_or_negative_durations(initiatives))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::test_initiative;
+
+    fn found(ids: &[&str]) -> Option<Vec<GapInitiative>> {
+        Some(ids.iter().map(|id| GapInitiative { id: id.to_string(), name: id.to_uppercase() }).collect())
+    }
+
+    #[test]
+    fn every_rule_gets_a_bucket_and_gaps_are_counted_once() {
+        let rule = |id: &str| GAP_RULES.iter().find(|r| r.id == id).unwrap();
+        let ids: HashSet<&str> = GAP_RULES.iter().map(|r| r.id).collect();
+        assert_eq!(ids.len(), GAP_RULES.len());
+
+        let gaps = build_data_gaps(
+            "baseline".to_string(),
+            5,
+            vec![
+                (rule("cost.missing"), found(&["a", "b"])),
+                (rule("effort.missing"), found(&["b", "c"])),
+                (rule("description.missing"), None),
+                (rule("systems.missing"), found(&[])),
+            ],
+        );
+
+        assert_eq!(gaps.initiatives_with_gaps, 3);
+        assert_eq!(
+            gaps.buckets.iter().map(|b| (b.rule_id.as_str(), b.enabled, b.count)).collect::<Vec<_>>(),
+            [("cost.missing", true, 2), ("effort.missing", true, 2), ("description.missing", false, 0), ("systems.missing", true, 0)]
+        );
+        assert_eq!(gaps.buckets[0].initiatives[1].name, "B");
+    }
+
+    fn initiative(id: &str, start: Option<&str>, end: Option<&str>) -> Initiative {
+        Initiative {
+            start_date: start.map(|d| d.to_string()),
+            end_date: end.map(|d| d.to_string()),
+            ..test_initiative(id)
+        }
+    }
+
+    #[test]
+    fn zero_and_negative_durations_come_most_negative_first() {
+        let issues = zero_or_negative_durations(vec![
+            initiative("fine", Some("2027-01-01"), Some("2027-03-31")),
+            initiative("same-day", Some("2027-02-01"), Some("2027-02-01")),
+            initiative("backwards", Some("2027-03-01"), Some("2027-02-20")),
+            initiative("undated", Some("2027-03-01"), None),
+            initiative("garbled", Some("2027-03-01"), Some("soon")),
+        ]);
+
+        assert_eq!(
+            issues.iter().map(|i| (i.initiative.id.as_str(), i.duration_days)).collect::<Vec<_>>(),
+            [("backwards", -9), ("same-day", 0)]
+        );
+    }
+}
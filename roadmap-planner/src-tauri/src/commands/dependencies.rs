@@ -0,0 +1,236 @@
+// Tauri commands for creating dependencies and moving initiatives between scenarios
+// A dependency only ever links two initiatives of the same scenario
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::dependencies::{InitiativeDependency, validate_lag_days};
+use crate::commands::entities::EntityType;
+use crate::commands::fetch::{fetch_initiatives, single};
+use crate::commands::journal::JournaledOperation;
+use crate::commands::rows::ids_json;
+use crate::commands::{ensure_exists, ensure_scenarios_unlocked};
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use tauri::State;
+
+// What happens to an initiative's dependencies when it moves to another scenario
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DependencyMove {
+    // Take every initiative it is linked to, directly or through others, so no edge is cut
+    MoveLinked,
+    // Move it alone and delete its dependencies
+    Break,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeMove {
+    pub initiative_id: String,
+    pub from_scenario_id: String,
+    pub to_scenario_id: String,
+    // The initiative itself and anything moved with it
+    pub moved_initiative_ids: Vec<String>,
+    pub dependencies_kept: i64,
+    pub dependencies_removed: i64,
+    // One line for the confirmation toast
+    pub message: String,
+}
+
+/// Every initiative reachable from `start` over dependency edges in either direction, itself included
+pub fn linked_initiatives(start: &str, edges: &[(String, String)]) -> BTreeSet<String> {
+    let mut neighbours: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (predecessor, successor) in edges {
+        neighbours.entry(predecessor).or_default().push(successor);
+        neighbours.entry(successor).or_default().push(predecessor);
+    }
+
+    let mut linked = BTreeSet::from([start.to_string()]);
+    let mut stack = vec![start];
+    while let Some(id) = stack.pop() {
+        for next in neighbours.get(id).into_iter().flatten() {
+            if linked.insert(next.to_string()) {
+                stack.push(next);
+            }
+        }
+    }
+    linked
+}
+
+// ============================================
+// DEPENDENCY COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn create_initiative_dependency(db: State<'_, tauri_plugin_sql::DbInstances>, dependency: InitiativeDependency) -> Result<InitiativeDependency, String> {
+    if dependency.predecessor_id == dependency.successor_id {
+        return Err("An initiative cannot depend on itself".to_string());
+    }
+    validate_lag_days(dependency.lag_days.unwrap_or(0))?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let predecessor = single(fetch_initiatives(&mut tx, std::slice::from_ref(&dependency.predecessor_id)).await?, EntityType::Initiative, &dependency.predecessor_id)?;
+    let successor = single(fetch_initiatives(&mut tx, std::slice::from_ref(&dependency.successor_id)).await?, EntityType::Initiative, &dependency.successor_id)?;
+    if predecessor.scenario_id != successor.scenario_id {
+        return Err(format!(
+            "\"{}\" and \"{}\" are in different scenarios; dependencies must link initiatives in the same scenario",
+            predecessor.name, successor.name
+        ));
+    }
+    ensure_scenarios_unlocked(&mut tx, std::slice::from_ref(&successor.scenario_id), &[]).await?;
+
+    let created_at = get_current_timestamp();
+    sqlx::query!(
+        r#"INSERT INTO initiative_dependencies (id, predecessor_id, successor_id, dependency_type, lag_days, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)"#,
+        dependency.id,
+        dependency.predecessor_id,
+        dependency.successor_id,
+        dependency.dependency_type,
+        dependency.lag_days,
+        created_at
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let created = InitiativeDependency { created_at: Some(created_at), ..dependency };
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Initiative.name().to_string(),
+        entity_id: Some(successor.id.clone()),
+        action: "AddDependency".to_string(),
+        description: Some(format!("\"{}\" now depends on \"{}\"", successor.name, predecessor.name)),
+        after: serde_json::to_value(&created).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(created)
+}
+
+/// Move an initiative into another scenario, taking its linked initiatives along or breaking
+/// its dependencies as `dependencies` says, and report what happened to them
+#[tauri::command]
+pub async fn move_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, scenario_id: String, dependencies: DependencyMove) -> Result<InitiativeMove, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let operation = JournaledOperation::start(pool, "move_initiative", serde_json::json!({ "id": id, "scenarioId": scenario_id, "dependencies": dependencies })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let initiative = single(fetch_initiatives(&mut tx, std::slice::from_ref(&id)).await?, EntityType::Initiative, &id)?;
+    if initiative.scenario_id == scenario_id {
+        return Err(format!("\"{}\" is already in scenario {}", initiative.name, scenario_id));
+    }
+    ensure_exists(&mut tx, EntityType::Scenario, &scenario_id).await?;
+    ensure_scenarios_unlocked(&mut tx, &[initiative.scenario_id.clone(), scenario_id.clone()], &[]).await?;
+
+    let edges: Vec<(String, String)> = sqlx::query!(
+        r#"SELECT d.predecessor_id, d.successor_id FROM initiative_dependencies d
+        JOIN initiatives p ON p.id = d.predecessor_id
+        JOIN initiatives s ON s.id = d.successor_id
+        WHERE p.scenario_id = ?1 AND s.scenario_id = ?1"#,
+        initiative.scenario_id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .into_iter()
+    .map(|r| (r.predecessor_id, r.successor_id))
+    .collect();
+
+    let moving: Vec<String> = match dependencies {
+        DependencyMove::MoveLinked => linked_initiatives(&initiative.id, &edges).into_iter().collect(),
+        DependencyMove::Break => vec![initiative.id.clone()],
+    };
+    let moving_json = ids_json(&moving);
+
+    let dependencies_removed = match dependencies {
+        DependencyMove::MoveLinked => 0,
+        DependencyMove::Break => sqlx::query!(
+            "DELETE FROM initiative_dependencies WHERE predecessor_id = ?1 OR successor_id = ?1",
+            initiative.id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?
+        .rows_affected() as i64,
+    };
+    let dependencies_kept = edges
+        .iter()
+        .filter(|(p, s)| dependencies == DependencyMove::MoveLinked && moving.contains(p) && moving.contains(s))
+        .count() as i64;
+
+    let now = get_current_timestamp();
+    sqlx::query!(
+        "UPDATE initiatives SET scenario_id = ?, updated_at = ? WHERE id IN (SELECT value FROM json_each(?))",
+        scenario_id,
+        now,
+        moving_json
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let message = match dependencies {
+        DependencyMove::MoveLinked if moving.len() > 1 => format!(
+            "Moved \"{}\" and {} linked initiative(s) to {}, keeping {} dependency(ies)",
+            initiative.name,
+            moving.len() - 1,
+            scenario_id,
+            dependencies_kept
+        ),
+        DependencyMove::MoveLinked => format!("Moved \"{}\" to {}", initiative.name, scenario_id),
+        DependencyMove::Break => format!(
+            "Moved \"{}\" to {}, removing {} dependency(ies)",
+            initiative.name, scenario_id, dependencies_removed
+        ),
+    };
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Initiative.name().to_string(),
+        entity_id: Some(initiative.id.clone()),
+        action: "MoveScenario".to_string(),
+        description: Some(message.clone()),
+        before: Some(serde_json::json!({ "scenario_id": initiative.scenario_id })),
+        after: Some(serde_json::json!({ "scenario_id": scenario_id, "moved_initiative_ids": moving })),
+        ..Default::default()
+    })
+    .await?;
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(InitiativeMove {
+        initiative_id: initiative.id,
+        from_scenario_id: initiative.scenario_id,
+        to_scenario_id: scenario_id,
+        moved_initiative_ids: moving,
+        dependencies_kept,
+        dependencies_removed,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(predecessor: &str, successor: &str) -> (String, String) {
+        (predecessor.to_string(), successor.to_string())
+    }
+
+    #[test]
+    fn linked_initiatives_follow_edges_both_ways() {
+        let edges = vec![edge("a", "b"), edge("c", "b"), edge("c", "d"), edge("x", "y")];
+
+        let linked: Vec<String> = linked_initiatives("a", &edges).into_iter().collect();
+        assert_eq!(linked, ["a", "b", "c", "d"]);
+
+        assert_eq!(linked_initiatives("lonely", &edges).len(), 1);
+    }
+}
@@ -0,0 +1,82 @@
+// Tauri commands for the dependency graph
+// Adjacency list of a scenario's initiatives for the network diagram
+
+use crate::commands::engine::dependencies::{DependencyDateIssue, check_dependency_dates, critical_path};
+use crate::commands::scenario_data::load_scenario_data;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub name: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub is_critical: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub id: String,
+    pub predecessor_id: String,
+    pub successor_id: String,
+    pub dependency_type: String,
+    pub lag_days: Option<i64>,
+    pub is_critical: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+// ============================================
+// DEPENDENCY GRAPH COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_dependency_graph(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<DependencyGraph, String> {
+    let data = load_scenario_data(db, &scenario_id).await?;
+    let path = critical_path(&data.initiatives, &data.dependencies);
+
+    let nodes = data
+        .initiatives
+        .iter()
+        .map(|i| GraphNode {
+            id: i.id.clone(),
+            name: i.name.clone(),
+            start_date: i.start_date.clone(),
+            end_date: i.end_date.clone(),
+            is_critical: path.initiative_ids.contains(&i.id),
+        })
+        .collect();
+
+    let edges = data
+        .dependencies
+        .iter()
+        .map(|d| GraphEdge {
+            id: d.id.clone(),
+            predecessor_id: d.predecessor_id.clone(),
+            successor_id: d.successor_id.clone(),
+            dependency_type: d.dependency_type.clone(),
+            lag_days: d.lag_days,
+            is_critical: path.dependency_ids.contains(&d.id),
+        })
+        .collect();
+
+    Ok(DependencyGraph { nodes, edges })
+}
+
+// ============================================
+// DEPENDENCY VALIDATION COMMANDS
+// ============================================
+
+/// Pre-publish gate: dependencies whose successor starts too early or that are missing the
+/// dates they would be checked against. Empty when the schedule is consistent.
+#[tauri::command]
+pub async fn validate_dependency_dates(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<DependencyDateIssue>, String> {
+    let data = load_scenario_data(db, &scenario_id).await?;
+
+    Ok(check_dependency_dates(&data.initiatives, &data.dependencies))
+}
@@ -0,0 +1,553 @@
+// Tauri commands for the change digest
+// What changed in the roadmap since a point in time, read back from the audit log
+
+use crate::commands::audit::AuditEntry;
+use crate::commands::engine::constraints::{ConstraintViolation, InitiativeConstraintLink, check_constraint};
+use crate::commands::engine::dates::parse_date;
+use crate::commands::fetch::fetch_initiatives;
+use crate::commands::markdown::escape_markdown;
+use crate::commands::settings::read_setting;
+use crate::commands::{get_constraints, get_scenarios};
+use crate::db::{Constraint, Initiative};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use tauri::State;
+
+// Cost changes smaller than this percentage of the earlier cost are left out
+pub const DIGEST_COST_THRESHOLD_SETTING: &str = "digest.cost_change_percent";
+const DEFAULT_COST_THRESHOLD_PERCENT: f64 = 10.0;
+
+// "ChangeType" or "Scenario": how the Markdown is sectioned
+pub const DIGEST_GROUPING_SETTING: &str = "digest.group_by";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DigestGrouping {
+    ChangeType,
+    Scenario,
+}
+
+impl DigestGrouping {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "changetype" | "change_type" | "type" => Ok(DigestGrouping::ChangeType),
+            "scenario" => Ok(DigestGrouping::Scenario),
+            _ => Err(format!("Setting {} must be ChangeType or Scenario, got {}", DIGEST_GROUPING_SETTING, value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestInitiative {
+    pub initiative_id: String,
+    pub name: String,
+    pub scenario_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DateMove {
+    pub initiative: DigestInitiative,
+    pub start_before: Option<String>,
+    pub start_after: Option<String>,
+    pub end_before: Option<String>,
+    pub end_after: Option<String>,
+    // Days the end date moved; positive is later
+    pub slippage_days: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusTransition {
+    pub initiative: DigestInitiative,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostChange {
+    pub initiative: DigestInitiative,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+    // None when there was no earlier cost to compare against
+    pub change_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeDigest {
+    pub since: String,
+    pub scenario_id: Option<String>,
+    pub cost_threshold_percent: f64,
+    pub grouping: DigestGrouping,
+    pub created: Vec<DigestInitiative>,
+    pub deleted: Vec<DigestInitiative>,
+    pub date_moves: Vec<DateMove>,
+    pub status_transitions: Vec<StatusTransition>,
+    pub cost_changes: Vec<CostChange>,
+    // Violations of linked constraints that the changes brought in
+    pub new_violations: Vec<ConstraintViolation>,
+    pub markdown: String,
+}
+
+/// An initiative's state before its first change and after its last in the window; None
+/// before means it was created, None after means it was deleted
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetChange {
+    pub initiative_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// (initiative id, before, after) for each initiative an initiative audit entry touched
+pub fn entry_changes(entry: &AuditEntry) -> Vec<(String, Option<Value>, Option<Value>)> {
+    let parse = |json: &Option<String>| json.as_deref().and_then(|j| serde_json::from_str::<Value>(j).ok());
+    let (before, after) = (parse(&entry.before_json), parse(&entry.after_json));
+    let id_of = |row: &Value| row.get("id").and_then(Value::as_str).map(str::to_string);
+
+    match entry.action.as_str() {
+        "Create" | "Update" | "Delete" => {
+            let id = entry.entity_id.clone().or_else(|| before.as_ref().or(after.as_ref()).and_then(id_of));
+            id.map(|id| vec![(id, before, after)]).unwrap_or_default()
+        }
+        // Full rows before, and only the changed column after
+        "BulkUpdate" => {
+            let updates: HashMap<String, &Value> = after
+                .as_ref()
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|row| Some((id_of(row)?, row)))
+                .collect();
+            before
+                .as_ref()
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|row| {
+                    let id = id_of(row)?;
+                    let mut merged = row.clone();
+                    if let (Some(target), Some(Value::Object(changes))) = (merged.as_object_mut(), updates.get(&id).copied()) {
+                        target.extend(changes.clone());
+                    }
+                    Some((id, Some(row.clone()), Some(merged)))
+                })
+                .collect()
+        }
+        "BulkDelete" => before
+            .as_ref()
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|row| Some((id_of(row)?, Some(row.clone()), None)))
+            .collect(),
+        // Rows from every pasted table, tagged with the table
+        "Paste" => after
+            .as_ref()
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|item| item.get("table").and_then(Value::as_str) == Some("initiatives"))
+            .filter_map(|item| {
+                let row = item.get("row")?;
+                Some((id_of(row)?, None, Some(row.clone())))
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Fold initiative audit entries, oldest first, into one net change per initiative. Initiatives
+/// created and deleted inside the window are dropped.
+pub fn net_changes(entries: &[AuditEntry]) -> Vec<NetChange> {
+    let mut order: Vec<String> = Vec::new();
+    let mut changes: HashMap<String, NetChange> = HashMap::new();
+
+    for entry in entries.iter().filter(|e| e.entity_type == "Initiative") {
+        for (id, before, after) in entry_changes(entry) {
+            match changes.get_mut(&id) {
+                Some(change) => change.after = after,
+                None => {
+                    order.push(id.clone());
+                    changes.insert(id.clone(), NetChange { initiative_id: id, before, after });
+                }
+            }
+        }
+    }
+
+    order
+        .into_iter()
+        .filter_map(|id| changes.remove(&id))
+        .filter(|c| c.before.is_some() || c.after.is_some())
+        .collect()
+}
+
+fn text(row: &Value, key: &str) -> Option<String> {
+    row.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn number(row: &Value, key: &str) -> Option<f64> {
+    row.get(key).and_then(Value::as_f64)
+}
+
+fn describe(change: &NetChange) -> DigestInitiative {
+    let row = change.after.as_ref().or(change.before.as_ref());
+    DigestInitiative {
+        initiative_id: change.initiative_id.clone(),
+        name: row.and_then(|r| text(r, "name")).unwrap_or_else(|| change.initiative_id.clone()),
+        scenario_id: row.and_then(|r| text(r, "scenario_id")).unwrap_or_default(),
+    }
+}
+
+/// Created, deleted, moved, re-statused and re-costed initiatives, without violations or Markdown
+pub fn summarise_changes(changes: &[NetChange], cost_threshold_percent: f64) -> ChangeDigest {
+    let mut digest = ChangeDigest {
+        since: String::new(),
+        scenario_id: None,
+        cost_threshold_percent,
+        grouping: DigestGrouping::ChangeType,
+        created: Vec::new(),
+        deleted: Vec::new(),
+        date_moves: Vec::new(),
+        status_transitions: Vec::new(),
+        cost_changes: Vec::new(),
+        new_violations: Vec::new(),
+        markdown: String::new(),
+    };
+
+    for change in changes {
+        let (before, after) = match (&change.before, &change.after) {
+            (None, Some(_)) => {
+                digest.created.push(describe(change));
+                continue;
+            }
+            (Some(_), None) => {
+                digest.deleted.push(describe(change));
+                continue;
+            }
+            (Some(before), Some(after)) => (before, after),
+            (None, None) => continue,
+        };
+
+        let (start_before, start_after) = (text(before, "start_date"), text(after, "start_date"));
+        let (end_before, end_after) = (text(before, "end_date"), text(after, "end_date"));
+        if start_before != start_after || end_before != end_after {
+            let slippage_days = match (end_before.as_deref().and_then(parse_date), end_after.as_deref().and_then(parse_date)) {
+                (Some(was), Some(now)) => Some((now - was).num_days()),
+                _ => None,
+            };
+            digest.date_moves.push(DateMove { initiative: describe(change), start_before, start_after, end_before, end_after, slippage_days });
+        }
+
+        if let (Some(from), Some(to)) = (text(before, "status"), text(after, "status")) {
+            if from != to {
+                digest.status_transitions.push(StatusTransition { initiative: describe(change), from, to });
+            }
+        }
+
+        let (cost_before, cost_after) = (number(before, "cost_estimate"), number(after, "cost_estimate"));
+        if cost_before != cost_after {
+            let change_percent = cost_before
+                .filter(|was| *was != 0.0)
+                .map(|was| (cost_after.unwrap_or(0.0) - was) / was * 100.0);
+            if change_percent.is_none_or(|pct| pct.abs() >= cost_threshold_percent) {
+                digest.cost_changes.push(CostChange { initiative: describe(change), before: cost_before, after: cost_after, change_percent });
+            }
+        }
+    }
+
+    digest
+}
+
+/// Violations the changed initiatives have now that their dates before the window didn't cause
+pub fn introduced_violations(changes: &[NetChange], current: &[Initiative], constraints: &[Constraint], links: &[InitiativeConstraintLink]) -> Vec<ConstraintViolation> {
+    let mut violations = Vec::new();
+
+    for initiative in current {
+        let Some(change) = changes.iter().find(|c| c.initiative_id == initiative.id) else {
+            continue;
+        };
+        let earlier = change.before.as_ref().map(|before| Initiative {
+            start_date: text(before, "start_date"),
+            end_date: text(before, "end_date"),
+            ..initiative.clone()
+        });
+
+        for link in links.iter().filter(|l| l.initiative_id == initiative.id) {
+            let Some(constraint) = constraints.iter().find(|c| c.id == link.constraint_id) else {
+                continue;
+            };
+            let Some(violation) = check_constraint(initiative, constraint) else {
+                continue;
+            };
+            if earlier.as_ref().is_none_or(|e| check_constraint(e, constraint).is_none()) {
+                violations.push(violation);
+            }
+        }
+    }
+
+    violations
+}
+
+fn initiative_line(item: &DigestInitiative, detail: &str) -> String {
+    if detail.is_empty() {
+        format!("- {}", escape_markdown(&item.name))
+    } else {
+        format!("- {}: {}", escape_markdown(&item.name), detail)
+    }
+}
+
+fn or_none(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("none")
+}
+
+// Bullet lines per change type, each tagged with the scenario it belongs to
+fn digest_lines(digest: &ChangeDigest) -> Vec<(&'static str, String, String)> {
+    let mut lines = Vec::new();
+
+    for item in &digest.created {
+        lines.push(("Created", item.scenario_id.clone(), initiative_line(item, "")));
+    }
+    for item in &digest.deleted {
+        lines.push(("Deleted", item.scenario_id.clone(), initiative_line(item, "")));
+    }
+    for moved in &digest.date_moves {
+        let mut detail = format!(
+            "{} – {} → {} – {}",
+            or_none(&moved.start_before),
+            or_none(&moved.end_before),
+            or_none(&moved.start_after),
+            or_none(&moved.end_after)
+        );
+        if let Some(days) = moved.slippage_days.filter(|d| *d != 0) {
+            detail.push_str(&format!(" ({:+} days)", days));
+        }
+        lines.push(("Date moves", moved.initiative.scenario_id.clone(), initiative_line(&moved.initiative, &detail)));
+    }
+    for transition in &digest.status_transitions {
+        let detail = format!("{} → {}", transition.from, transition.to);
+        lines.push(("Status changes", transition.initiative.scenario_id.clone(), initiative_line(&transition.initiative, &detail)));
+    }
+    for cost in &digest.cost_changes {
+        let amount = |v: Option<f64>| v.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "none".to_string());
+        let mut detail = format!("{} → {}", amount(cost.before), amount(cost.after));
+        if let Some(pct) = cost.change_percent {
+            detail.push_str(&format!(" ({:+.1}%)", pct));
+        }
+        lines.push(("Cost changes", cost.initiative.scenario_id.clone(), initiative_line(&cost.initiative, &detail)));
+    }
+
+    lines
+}
+
+/// The digest as Markdown, sectioned by change type or by scenario
+pub fn render_digest_markdown(digest: &ChangeDigest, scenario_names: &HashMap<String, String>) -> String {
+    let mut out = format!("# Roadmap changes since {}\n", digest.since);
+
+    let lines = digest_lines(digest);
+    let violations: Vec<String> = digest
+        .new_violations
+        .iter()
+        .map(|v| format!("- {} ({})", escape_markdown(&v.message), v.hardness))
+        .collect();
+
+    if lines.is_empty() && violations.is_empty() {
+        out.push_str("\nNo changes.\n");
+        return out;
+    }
+
+    let scenario_name = |id: &str| scenario_names.get(id).cloned().unwrap_or_else(|| id.to_string());
+
+    match digest.grouping {
+        DigestGrouping::ChangeType => {
+            for heading in ["Created", "Deleted", "Date moves", "Status changes", "Cost changes"] {
+                let section: Vec<&(&str, String, String)> = lines.iter().filter(|(h, _, _)| *h == heading).collect();
+                if section.is_empty() {
+                    continue;
+                }
+                out.push_str(&format!("\n## {}\n\n", heading));
+                for (_, _, line) in section {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+        DigestGrouping::Scenario => {
+            let mut by_scenario: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for (heading, scenario_id, line) in &lines {
+                by_scenario.entry(scenario_name(scenario_id)).or_default().push(format!("{} ({})", line, heading.to_lowercase()));
+            }
+            for (name, section) in by_scenario {
+                out.push_str(&format!("\n## {}\n\n", escape_markdown(&name)));
+                for line in section {
+                    out.push_str(&line);
+                    out.push('\n');
+                }
+            }
+        }
+    }
+
+    if !violations.is_empty() {
+        out.push_str("\n## New constraint violations\n\n");
+        for line in violations {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+// ============================================
+// CHANGE DIGEST COMMANDS
+// ============================================
+
+/// Net initiative changes since a date or timestamp, optionally for one scenario
+#[tauri::command]
+pub async fn generate_change_digest(db: State<'_, tauri_plugin_sql::DbInstances>, since: String, scenario_id: Option<String>) -> Result<ChangeDigest, String> {
+    if parse_date(&since).is_none() {
+        return Err(format!("since must be a date or timestamp, got {}", since));
+    }
+
+    let scenarios = get_scenarios(db.clone()).await?;
+    if let Some(id) = &scenario_id {
+        if !scenarios.iter().any(|s| &s.id == id) {
+            return Err(format!("Scenario {} not found", id));
+        }
+    }
+    let scenario_names: HashMap<String, String> = scenarios.into_iter().map(|s| (s.id, s.name)).collect();
+    let constraints = get_constraints(db.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let cost_threshold_percent = match read_setting(pool, DIGEST_COST_THRESHOLD_SETTING).await? {
+        Some(value) => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|pct| *pct >= 0.0)
+            .ok_or_else(|| format!("Setting {} must be a percentage of zero or more, got {}", DIGEST_COST_THRESHOLD_SETTING, value))?,
+        None => DEFAULT_COST_THRESHOLD_PERCENT,
+    };
+    let grouping = match read_setting(pool, DIGEST_GROUPING_SETTING).await? {
+        Some(value) => DigestGrouping::parse(&value)?,
+        None => DigestGrouping::ChangeType,
+    };
+
+    let entries: Vec<AuditEntry> = sqlx::query_as!(
+        AuditEntry,
+        r#"SELECT
+            id, group_id, entity_type, entity_id, action, description,
+            before_json, after_json, created_at
+        FROM audit_log
+        WHERE entity_type = 'Initiative' AND created_at >= ?
+        ORDER BY created_at, rowid"#,
+        since
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut changes = net_changes(&entries);
+    if let Some(id) = &scenario_id {
+        changes.retain(|c| {
+            let row = c.after.as_ref().or(c.before.as_ref());
+            row.and_then(|r| text(r, "scenario_id")).as_deref() == Some(id.as_str())
+        });
+    }
+
+    let mut digest = summarise_changes(&changes, cost_threshold_percent);
+    digest.since = since;
+    digest.scenario_id = scenario_id;
+    digest.grouping = grouping;
+
+    // Only initiatives that still exist can be violating anything
+    let ids: Vec<String> = changes.iter().filter(|c| c.after.is_some()).map(|c| c.initiative_id.clone()).collect();
+    let ids_json = serde_json::to_string(&ids).map_err(|e| e.to_string())?;
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let current = fetch_initiatives(&mut conn, &ids).await?;
+
+    let links: Vec<InitiativeConstraintLink> = sqlx::query_as!(
+        InitiativeConstraintLink,
+        r#"SELECT id, initiative_id, constraint_id, created_at
+        FROM initiative_constraints WHERE initiative_id IN (SELECT value FROM json_each(?))"#,
+        ids_json
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    digest.new_violations = introduced_violations(&changes, &current, &constraints, &links);
+    digest.markdown = render_digest_markdown(&digest, &scenario_names);
+
+    Ok(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(action: &str, entity_id: Option<&str>, before: Option<Value>, after: Option<Value>) -> AuditEntry {
+        AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            group_id: None,
+            entity_type: "Initiative".to_string(),
+            entity_id: entity_id.map(str::to_string),
+            action: action.to_string(),
+            description: None,
+            before_json: before.map(|v| v.to_string()),
+            after_json: after.map(|v| v.to_string()),
+            created_at: None,
+        }
+    }
+
+    fn row(id: &str, status: &str, end: &str, cost: f64) -> Value {
+        json!({ "id": id, "name": format!("Init {}", id), "scenario_id": "baseline", "status": status,
+            "start_date": "2027-01-01", "end_date": end, "cost_estimate": cost })
+    }
+
+    #[test]
+    fn repeated_changes_net_out() {
+        let entries = [
+            entry("Update", Some("a"), Some(row("a", "Planned", "2027-03-31", 1000.0)), Some(row("a", "InProgress", "2027-04-30", 1050.0))),
+            entry("Update", Some("a"), Some(row("a", "InProgress", "2027-04-30", 1050.0)), Some(row("a", "InProgress", "2027-05-10", 1200.0))),
+            entry("Create", Some("b"), None, Some(row("b", "Proposed", "2027-06-30", 0.0))),
+            entry("Delete", Some("b"), Some(row("b", "Proposed", "2027-06-30", 0.0)), None),
+            entry("Paste", None, None, Some(json!([{ "table": "initiatives", "row": row("c", "Planned", "2027-06-30", 10.0) }]))),
+            entry("BulkUpdate", None, Some(json!([row("d", "Planned", "2027-06-30", 10.0)])), Some(json!([{ "id": "d", "status": "Cancelled" }]))),
+        ];
+
+        let changes = net_changes(&entries);
+        assert_eq!(changes.iter().map(|c| c.initiative_id.as_str()).collect::<Vec<_>>(), ["a", "c", "d"]);
+
+        let digest = summarise_changes(&changes, 10.0);
+        assert_eq!(digest.created[0].initiative_id, "c");
+        assert_eq!(digest.date_moves.len(), 1);
+        assert_eq!(digest.date_moves[0].slippage_days, Some(40));
+        assert_eq!(
+            digest.status_transitions.iter().map(|t| (t.from.as_str(), t.to.as_str())).collect::<Vec<_>>(),
+            [("Planned", "InProgress"), ("Planned", "Cancelled")]
+        );
+        assert_eq!(digest.cost_changes.len(), 1);
+        assert_eq!(digest.cost_changes[0].change_percent, Some(20.0));
+
+        let quiet = summarise_changes(&changes, 25.0);
+        assert!(quiet.cost_changes.is_empty());
+    }
+
+    #[test]
+    fn markdown_is_sectioned_by_change_type() {
+        let changes = net_changes(&[entry(
+            "Update",
+            Some("a"),
+            Some(row("a", "Planned", "2027-03-31", 1000.0)),
+            Some(row("a", "Planned", "2027-03-21", 1000.0)),
+        )]);
+        let mut digest = summarise_changes(&changes, 10.0);
+        digest.since = "2026-10-12".to_string();
+
+        assert_eq!(
+            render_digest_markdown(&digest, &HashMap::new()),
+            "# Roadmap changes since 2026-10-12\n\n## Date moves\n\n- Init a: 2027-01-01 – 2027-03-31 → 2027-01-01 – 2027-03-21 (-10 days)\n"
+        );
+    }
+}
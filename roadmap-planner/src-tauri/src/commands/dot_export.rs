@@ -0,0 +1,333 @@
+// Tauri commands for Graphviz exports
+// Initiative and system dependency graphs as DOT text, for rendering outside the app
+
+use crate::commands::{get_initiatives, get_scenario, get_systems};
+use crate::commands::scenario_data::load_scenario_data;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+use tauri::State;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DotGraph {
+    // A scenario's initiatives, linked by their schedule dependencies
+    InitiativeDependencies,
+    // Systems, linked by their integration dependencies
+    SystemDependencies,
+}
+
+#[derive(Debug, Clone)]
+pub struct DotNode {
+    pub id: String,
+    pub label: String,
+    pub fill: &'static str,
+    // Capability name, when nodes are clustered
+    pub cluster: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DotEdge {
+    pub from: String,
+    pub to: String,
+    pub label: String,
+}
+
+const DEFAULT_FILL: &str = "#e5e7eb";
+
+fn status_fill(status: &str) -> &'static str {
+    match status {
+        "Proposed" => "#e5e7eb",
+        "Planned" => "#bfdbfe",
+        "InProgress" => "#fde68a",
+        "Complete" => "#bbf7d0",
+        "Cancelled" => "#fecaca",
+        _ => DEFAULT_FILL,
+    }
+}
+
+fn lifecycle_fill(stage: &str) -> &'static str {
+    match stage {
+        "Discovery" => "#e9d5ff",
+        "Development" => "#bfdbfe",
+        "Production" => "#bbf7d0",
+        "Sunset" => "#fde68a",
+        "Retired" => "#fecaca",
+        _ => DEFAULT_FILL,
+    }
+}
+
+/// A double-quoted DOT string; line breaks become \n so labels stay on one line of the file
+pub fn dot_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => {}
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// "FinishToStart", "FinishToStart +5d" or "StartToStart -2d"
+pub fn initiative_edge_label(dependency_type: &str, lag_days: Option<i64>) -> String {
+    match lag_days.unwrap_or(0) {
+        0 => dependency_type.to_string(),
+        lag => format!("{} {:+}d", dependency_type, lag),
+    }
+}
+
+/// Graphviz text for the nodes and edges; nodes with a cluster are grouped into subgraphs in name order
+pub fn render_dot(name: &str, nodes: &[DotNode], edges: &[DotEdge]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "digraph {} {{", dot_quote(name));
+    let _ = writeln!(out, "  rankdir=LR;");
+    let _ = writeln!(out, "  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];");
+    let _ = writeln!(out, "  edge [fontname=\"Helvetica\", fontsize=10];");
+
+    let node_line = |n: &DotNode, indent: &str| {
+        format!("{}{} [label={}, fillcolor={}];\n", indent, dot_quote(&n.id), dot_quote(&n.label), dot_quote(n.fill))
+    };
+
+    let mut clusters: Vec<&str> = nodes.iter().filter_map(|n| n.cluster.as_deref()).collect();
+    clusters.sort_unstable();
+    clusters.dedup();
+
+    for (index, cluster) in clusters.iter().enumerate() {
+        let _ = writeln!(out, "  subgraph cluster_{} {{", index);
+        let _ = writeln!(out, "    label={};", dot_quote(cluster));
+        for node in nodes.iter().filter(|n| n.cluster.as_deref() == Some(*cluster)) {
+            out.push_str(&node_line(node, "    "));
+        }
+        let _ = writeln!(out, "  }}");
+    }
+    for node in nodes.iter().filter(|n| n.cluster.is_none()) {
+        out.push_str(&node_line(node, "  "));
+    }
+
+    for edge in edges {
+        let _ = writeln!(out, "  {} -> {} [label={}];", dot_quote(&edge.from), dot_quote(&edge.to), dot_quote(&edge.label));
+    }
+    out.push_str("}\n");
+    out
+}
+
+// ============================================
+// DOT EXPORT COMMANDS
+// ============================================
+
+async fn initiative_graph(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: &str, cluster: bool) -> Result<(String, Vec<DotNode>, Vec<DotEdge>), String> {
+    let scenario = get_scenario(db.clone(), scenario_id.to_string()).await?;
+    let data = load_scenario_data(db.clone(), scenario_id).await?;
+
+    // An initiative can serve several capabilities but sits in one cluster: the first by name
+    let mut capabilities: HashMap<String, String> = HashMap::new();
+    if cluster {
+        let pool = db.0.get("sqlite:roadmap.db")
+            .ok_or_else(|| "Database not found".to_string())?;
+
+        let rows = sqlx::query!(
+            r#"SELECT ic.initiative_id, c.name FROM initiative_capabilities ic
+            JOIN capabilities c ON c.id = ic.capability_id
+            JOIN initiatives i ON i.id = ic.initiative_id
+            WHERE i.scenario_id = ? ORDER BY c.name"#,
+            scenario_id
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        for row in rows {
+            capabilities.entry(row.initiative_id).or_insert(row.name);
+        }
+    }
+
+    let nodes = data
+        .initiatives
+        .iter()
+        .map(|i| DotNode {
+            id: i.id.clone(),
+            label: i.name.clone(),
+            fill: status_fill(&i.status),
+            cluster: capabilities.get(&i.id).cloned(),
+        })
+        .collect();
+
+    let edges = data
+        .dependencies
+        .iter()
+        .map(|d| DotEdge {
+            from: d.predecessor_id.clone(),
+            to: d.successor_id.clone(),
+            label: initiative_edge_label(&d.dependency_type, d.lag_days),
+        })
+        .collect();
+
+    Ok((scenario.name, nodes, edges))
+}
+
+async fn system_graph(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: Option<&str>, cluster: bool) -> Result<(String, Vec<DotNode>, Vec<DotEdge>), String> {
+    let mut systems = get_systems(db.clone()).await?;
+    let mut name = "Systems".to_string();
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // With a scenario, only the systems its initiatives touch
+    if let Some(scenario_id) = scenario_id {
+        name = get_scenario(db.clone(), scenario_id.to_string()).await?.name;
+        let initiative_ids: Vec<String> = get_initiatives(db.clone(), Some(scenario_id.to_string()))
+            .await?
+            .into_iter()
+            .map(|i| i.id)
+            .collect();
+        let ids_json = serde_json::to_string(&initiative_ids).map_err(|e| e.to_string())?;
+
+        let touched = sqlx::query_scalar!(
+            r#"SELECT DISTINCT system_id FROM system_initiatives
+            WHERE initiative_id IN (SELECT value FROM json_each(?))"#,
+            ids_json
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        systems.retain(|s| touched.contains(&s.id));
+    }
+
+    let capabilities: HashMap<String, String> = if cluster {
+        sqlx::query!("SELECT id, name FROM capabilities")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|r| (r.id, r.name))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    let dependencies = sqlx::query!(
+        r#"SELECT source_system_id, target_system_id, dependency_type, criticality
+        FROM system_dependencies ORDER BY created_at"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let nodes: Vec<DotNode> = systems
+        .iter()
+        .map(|s| DotNode {
+            id: s.id.clone(),
+            label: s.name.clone(),
+            fill: lifecycle_fill(&s.lifecycle_stage),
+            cluster: s.capability_id.as_ref().and_then(|id| capabilities.get(id)).cloned(),
+        })
+        .collect();
+
+    let edges = dependencies
+        .into_iter()
+        .filter(|d| {
+            nodes.iter().any(|n| n.id == d.source_system_id) && nodes.iter().any(|n| n.id == d.target_system_id)
+        })
+        .map(|d| DotEdge {
+            from: d.source_system_id,
+            to: d.target_system_id,
+            label: format!("{} ({})", d.dependency_type, d.criticality),
+        })
+        .collect();
+
+    Ok((name, nodes, edges))
+}
+
+/// Write a dependency graph to `path` as Graphviz DOT, returning the text written
+#[tauri::command]
+pub async fn export_dot(db: State<'_, tauri_plugin_sql::DbInstances>, graph: DotGraph, scenario_id: Option<String>, path: String, cluster_by_capability: Option<bool>) -> Result<String, String> {
+    let cluster = cluster_by_capability.unwrap_or(false);
+
+    let (name, nodes, edges) = match graph {
+        DotGraph::InitiativeDependencies => {
+            let scenario_id = scenario_id
+                .ok_or_else(|| "A scenario is needed to export initiative dependencies".to_string())?;
+            initiative_graph(db, &scenario_id, cluster).await?
+        }
+        DotGraph::SystemDependencies => system_graph(db, scenario_id.as_deref(), cluster).await?,
+    };
+
+    let dot = render_dot(&name, &nodes, &edges);
+    std::fs::write(&path, &dot).map_err(|e| format!("Could not write {}: {}", path, e))?;
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, label: &str, fill: &'static str, cluster: Option<&str>) -> DotNode {
+        DotNode { id: id.to_string(), label: label.to_string(), fill, cluster: cluster.map(str::to_string) }
+    }
+
+    fn edge(from: &str, to: &str, label: String) -> DotEdge {
+        DotEdge { from: from.to_string(), to: to.to_string(), label }
+    }
+
+    #[test]
+    fn renders_a_small_graph() {
+        let nodes = [
+            node("a", "Data \"Lake\"\nPhase 1", status_fill("InProgress"), None),
+            node("b", "Reporting", status_fill("Planned"), None),
+        ];
+        let edges = [edge("a", "b", initiative_edge_label("FinishToStart", Some(5)))];
+
+        assert_eq!(
+            render_dot("Plan B", &nodes, &edges),
+            r##"digraph "Plan B" {
+  rankdir=LR;
+  node [shape=box, style="rounded,filled", fontname="Helvetica"];
+  edge [fontname="Helvetica", fontsize=10];
+  "a" [label="Data \"Lake\"\nPhase 1", fillcolor="#fde68a"];
+  "b" [label="Reporting", fillcolor="#bfdbfe"];
+  "a" -> "b" [label="FinishToStart +5d"];
+}
+"##
+        );
+    }
+
+    #[test]
+    fn clusters_nodes_by_capability() {
+        let nodes = [
+            node("crm", "CRM", lifecycle_fill("Production"), Some("Sales")),
+            node("erp", "ERP", lifecycle_fill("Sunset"), Some("Finance")),
+            node("sso", "SSO", lifecycle_fill("Production"), None),
+        ];
+        let edges = [
+            edge("crm", "sso", "Authentication (High)".to_string()),
+            edge("erp", "crm", initiative_edge_label("StartToStart", Some(-2))),
+        ];
+
+        assert_eq!(
+            render_dot("Systems", &nodes, &edges),
+            r##"digraph "Systems" {
+  rankdir=LR;
+  node [shape=box, style="rounded,filled", fontname="Helvetica"];
+  edge [fontname="Helvetica", fontsize=10];
+  subgraph cluster_0 {
+    label="Finance";
+    "erp" [label="ERP", fillcolor="#fde68a"];
+  }
+  subgraph cluster_1 {
+    label="Sales";
+    "crm" [label="CRM", fillcolor="#bbf7d0"];
+  }
+  "sso" [label="SSO", fillcolor="#bbf7d0"];
+  "crm" -> "sso" [label="Authentication (High)"];
+  "erp" -> "crm" [label="StartToStart -2d"];
+}
+"##
+        );
+    }
+}
@@ -0,0 +1,391 @@
+// Assignment engine - overlays named resource allocations on a weekly grid
+// Finds weeks where a resource is booked beyond its availability, and headcount per period,
+// and resources booked in more than one scenario at the same time
+
+use super::dates::{DateSpan, format_date, generate_periods, is_working_day};
+use crate::commands::allocations::InitiativeResource;
+use crate::commands::kanban::priority_rank;
+use crate::commands::time_off::ResourceTimeOff;
+use crate::db::{FinancialPeriod, Initiative, Resource, Scenario};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Same bucketing as pool utilisation, at weekly grain
+pub const CONFLICT_PERIOD_TYPE: &str = "Week";
+
+const WORKING_DAYS_PER_WEEK: f64 = 5.0;
+
+// Loads within this many percentage points of availability are not conflicts
+const TOLERANCE_PERCENT: f64 = 0.01;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompetingAllocation {
+    pub allocation_id: String,
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub priority: String,
+    pub allocation_percent: f64,
+    // Share of a full-time week booked in the window's peak week
+    pub load_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllocationTrim {
+    pub allocation_id: String,
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub current_percent: f64,
+    // Zero means dropping the allocation for the window
+    pub suggested_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceConflict {
+    pub resource_id: String,
+    pub resource_name: String,
+    // Consecutive overloaded weeks with the same competing allocations; end is the last day
+    pub window_start: String,
+    pub window_end: String,
+    pub weeks: i64,
+    // Percentages of a full-time week, taken at the most overloaded week in the window
+    pub load_percent: f64,
+    pub available_percent: f64,
+    pub overload_percent: f64,
+    pub competing: Vec<CompetingAllocation>,
+    // Lowest priority first, enough to bring the peak week back within availability
+    pub suggested_trims: Vec<AllocationTrim>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodHeadcount {
+    pub period_id: String,
+    pub period_name: String,
+    pub start_date: String,
+    pub end_date: String,
+    // Highest single working day in the period
+    pub peak_headcount: f64,
+    // Mean over the period's working days
+    pub average_headcount: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContendingAllocation {
+    pub allocation_id: String,
+    pub scenario_id: String,
+    pub scenario_name: String,
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub allocation_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contention {
+    pub resource_id: String,
+    pub resource_name: String,
+    // Days both allocations cover; end is the last day
+    pub overlap_start: String,
+    pub overlap_end: String,
+    pub combined_percent: f64,
+    // Whether running both scenarios would book the resource beyond its availability
+    pub exceeds_availability: bool,
+    pub allocations: Vec<ContendingAllocation>,
+}
+
+// An allocation with the initiative it belongs to and the days it covers
+struct PlacedAllocation<'a> {
+    allocation: &'a InitiativeResource,
+    initiative: &'a Initiative,
+    span: DateSpan,
+}
+
+struct OverloadedWeek<'a> {
+    week: DateSpan,
+    load_percent: f64,
+    available_percent: f64,
+    // (allocation, fraction of the week's working days it covers)
+    active: Vec<(&'a PlacedAllocation<'a>, f64)>,
+}
+
+impl OverloadedWeek<'_> {
+    fn overload(&self) -> f64 {
+        self.load_percent - self.available_percent
+    }
+
+    fn allocation_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.active.iter().map(|(p, _)| p.allocation.id.as_str()).collect();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+/// The days an allocation covers: its own window, else the initiative's dates
+pub fn allocation_span(allocation: &InitiativeResource, initiative: &Initiative) -> Option<DateSpan> {
+    DateSpan::parse_inclusive(allocation.start_date.as_deref(), allocation.end_date.as_deref())
+        .or_else(|| DateSpan::parse_inclusive(initiative.start_date.as_deref(), initiative.end_date.as_deref()))
+}
+
+fn working_days_in(span: &DateSpan, excluded: &[DateSpan]) -> f64 {
+    span.iter_days()
+        .filter(|d| is_working_day(*d) && !excluded.iter().any(|x| x.contains(*d)))
+        .count() as f64
+}
+
+/// Weekly conflict windows for each resource whose named allocations exceed its availability
+pub fn detect_resource_conflicts(
+    resources: &[Resource],
+    allocations: &[InitiativeResource],
+    initiatives: &[Initiative],
+    time_off: &[ResourceTimeOff],
+) -> Vec<ResourceConflict> {
+    let by_id: HashMap<&str, &Initiative> = initiatives.iter().map(|i| (i.id.as_str(), i)).collect();
+    let mut conflicts = Vec::new();
+
+    for resource in resources {
+        // Undated allocations can't be placed
+        let placed: Vec<PlacedAllocation> = allocations
+            .iter()
+            .filter(|a| a.resource_id == resource.id)
+            .filter_map(|allocation| {
+                let initiative = by_id.get(allocation.initiative_id.as_str())?;
+                let span = allocation_span(allocation, initiative)?;
+                Some(PlacedAllocation { allocation, initiative, span })
+            })
+            .collect();
+
+        let Some(overall) = placed.iter().map(|p| p.span).reduce(|a, b| DateSpan { start: a.start.min(b.start), end: a.end.max(b.end) }) else {
+            continue;
+        };
+
+        let away: Vec<DateSpan> = time_off.iter().filter(|t| t.resource_id == resource.id).filter_map(|t| t.span()).collect();
+        let employed = DateSpan::parse_inclusive(resource.start_date.as_deref(), resource.end_date.as_deref());
+        let availability = resource.availability.unwrap_or(1.0) * 100.0;
+
+        let mut overloaded: Vec<OverloadedWeek> = Vec::new();
+
+        for week in generate_periods(&overall, CONFLICT_PERIOD_TYPE) {
+            let active: Vec<(&PlacedAllocation, f64)> = placed
+                .iter()
+                .filter_map(|p| {
+                    let covered = working_days_in(&p.span.intersect(&week)?, &[]);
+                    (covered > 0.0).then_some((p, covered / WORKING_DAYS_PER_WEEK))
+                })
+                .collect();
+            if active.is_empty() {
+                continue;
+            }
+
+            // Days outside the resource's start and end dates count as unavailable
+            let available_days = match employed {
+                Some(employed) => week.intersect(&employed).map(|s| working_days_in(&s, &away)).unwrap_or(0.0),
+                None => working_days_in(&week, &away),
+            };
+            let available_percent = availability * available_days / WORKING_DAYS_PER_WEEK;
+            let load_percent: f64 = active.iter().map(|(p, share)| p.allocation.allocation_percent * share).sum();
+
+            if load_percent > available_percent + TOLERANCE_PERCENT {
+                overloaded.push(OverloadedWeek { week, load_percent, available_percent, active });
+            }
+        }
+
+        // Merge runs of adjacent weeks booked by the same allocations
+        let mut windows: Vec<(DateSpan, i64, &OverloadedWeek)> = Vec::new();
+        for week in &overloaded {
+            match windows.last_mut() {
+                Some((span, weeks, peak)) if span.end == week.week.start && peak.allocation_ids() == week.allocation_ids() => {
+                    span.end = week.week.end;
+                    *weeks += 1;
+                    if week.overload() > peak.overload() {
+                        *peak = week;
+                    }
+                }
+                _ => windows.push((week.week, 1, week)),
+            }
+        }
+
+        for (span, weeks, peak) in windows {
+            conflicts.push(ResourceConflict {
+                resource_id: resource.id.clone(),
+                resource_name: resource.name.clone(),
+                window_start: format_date(span.start),
+                window_end: format_date(span.last_day()),
+                weeks,
+                load_percent: peak.load_percent,
+                available_percent: peak.available_percent,
+                overload_percent: peak.overload(),
+                competing: peak
+                    .active
+                    .iter()
+                    .map(|(p, share)| CompetingAllocation {
+                        allocation_id: p.allocation.id.clone(),
+                        initiative_id: p.initiative.id.clone(),
+                        initiative_name: p.initiative.name.clone(),
+                        priority: p.initiative.priority.clone(),
+                        allocation_percent: p.allocation.allocation_percent,
+                        load_percent: p.allocation.allocation_percent * share,
+                    })
+                    .collect(),
+                suggested_trims: suggest_trims(peak),
+            });
+        }
+    }
+
+    conflicts
+}
+
+/// Fractional headcount booked by named allocations on each working day of each period
+pub fn calculate_headcount_demand(
+    periods: &[FinancialPeriod],
+    allocations: &[InitiativeResource],
+    initiatives: &[Initiative],
+) -> Vec<PeriodHeadcount> {
+    let by_id: HashMap<&str, &Initiative> = initiatives.iter().map(|i| (i.id.as_str(), i)).collect();
+    let placed: Vec<(DateSpan, f64)> = allocations
+        .iter()
+        .filter_map(|a| {
+            let initiative = by_id.get(a.initiative_id.as_str())?;
+            Some((allocation_span(a, initiative)?, a.allocation_percent / 100.0))
+        })
+        .collect();
+
+    periods
+        .iter()
+        .filter_map(|period| {
+            let span = DateSpan::parse_inclusive(Some(&period.start_date), Some(&period.end_date))?;
+            let daily: Vec<f64> = span
+                .iter_days()
+                .filter(|d| is_working_day(*d))
+                .map(|d| placed.iter().filter(|(s, _)| s.contains(d)).map(|(_, fte)| fte).sum())
+                .collect();
+
+            let peak_headcount = daily.iter().copied().fold(0.0, f64::max);
+            let average_headcount = if daily.is_empty() { 0.0 } else { daily.iter().sum::<f64>() / daily.len() as f64 };
+
+            Some(PeriodHeadcount {
+                period_id: period.id.clone(),
+                period_name: period.name.clone(),
+                start_date: period.start_date.clone(),
+                end_date: period.end_date.clone(),
+                peak_headcount,
+                average_headcount,
+            })
+        })
+        .collect()
+}
+
+/// Pairs of a resource's allocations in different scenarios whose dates overlap
+pub fn detect_cross_scenario_contention(
+    resources: &[Resource],
+    scenarios: &[Scenario],
+    allocations: &[InitiativeResource],
+    initiatives: &[Initiative],
+) -> Vec<Contention> {
+    let by_id: HashMap<&str, &Initiative> = initiatives.iter().map(|i| (i.id.as_str(), i)).collect();
+    let scenario_names: HashMap<&str, &str> = scenarios.iter().map(|s| (s.id.as_str(), s.name.as_str())).collect();
+    let contending = |placed: &PlacedAllocation| ContendingAllocation {
+        allocation_id: placed.allocation.id.clone(),
+        scenario_id: placed.initiative.scenario_id.clone(),
+        scenario_name: scenario_names.get(placed.initiative.scenario_id.as_str()).copied().unwrap_or_default().to_string(),
+        initiative_id: placed.initiative.id.clone(),
+        initiative_name: placed.initiative.name.clone(),
+        allocation_percent: placed.allocation.allocation_percent,
+    };
+
+    let mut contentions = Vec::new();
+    for resource in resources {
+        let placed: Vec<PlacedAllocation> = allocations
+            .iter()
+            .filter(|a| a.resource_id == resource.id)
+            .filter_map(|allocation| {
+                let initiative = by_id.get(allocation.initiative_id.as_str())?;
+                let span = allocation_span(allocation, initiative)?;
+                Some(PlacedAllocation { allocation, initiative, span })
+            })
+            .collect();
+
+        for (i, a) in placed.iter().enumerate() {
+            for b in &placed[i + 1..] {
+                if a.initiative.scenario_id == b.initiative.scenario_id {
+                    continue;
+                }
+                let Some(overlap) = a.span.intersect(&b.span) else {
+                    continue;
+                };
+                let combined_percent = a.allocation.allocation_percent + b.allocation.allocation_percent;
+                contentions.push(Contention {
+                    resource_id: resource.id.clone(),
+                    resource_name: resource.name.clone(),
+                    overlap_start: format_date(overlap.start),
+                    overlap_end: format_date(overlap.last_day()),
+                    combined_percent,
+                    exceeds_availability: combined_percent > resource.availability.unwrap_or(1.0) * 100.0 + TOLERANCE_PERCENT,
+                    allocations: vec![contending(a), contending(b)],
+                });
+            }
+        }
+    }
+
+    contentions
+}
+
+// Trim the lowest-priority allocations first, later-starting ones before earlier on a tie
+fn suggest_trims(peak: &OverloadedWeek) -> Vec<AllocationTrim> {
+    let mut candidates: Vec<&(&PlacedAllocation, f64)> = peak.active.iter().collect();
+    candidates.sort_by(|(a, _), (b, _)| {
+        priority_rank(&b.initiative.priority)
+            .cmp(&priority_rank(&a.initiative.priority))
+            .then_with(|| b.span.start.cmp(&a.span.start))
+            .then_with(|| a.initiative.name.cmp(&b.initiative.name))
+    });
+
+    let mut remaining = peak.overload();
+    let mut trims = Vec::new();
+
+    for (placed, share) in candidates {
+        if remaining <= TOLERANCE_PERCENT {
+            break;
+        }
+        let current = placed.allocation.allocation_percent;
+        // Load falls by share for every point trimmed from the allocation
+        let cut = (remaining / share).min(current);
+        remaining -= cut * share;
+        trims.push(AllocationTrim {
+            allocation_id: placed.allocation.id.clone(),
+            initiative_id: placed.initiative.id.clone(),
+            initiative_name: placed.initiative.name.clone(),
+            current_percent: current,
+            suggested_percent: current - cut,
+        });
+    }
+
+    trims
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::dates::parse_date;
+
+    fn span(start: &str, end: &str) -> DateSpan {
+        DateSpan::parse_inclusive(Some(start), Some(end)).unwrap()
+    }
+
+    #[test]
+    fn weeks_start_on_monday_and_cover_the_span() {
+        // Wednesday 2025-01-08 to Tuesday 2025-01-21
+        let weeks = generate_periods(&span("2025-01-08", "2025-01-21"), CONFLICT_PERIOD_TYPE);
+        let starts: Vec<String> = weeks.iter().map(|w| format_date(w.start)).collect();
+        assert_eq!(starts, vec!["2025-01-06", "2025-01-13", "2025-01-20"]);
+        assert!(weeks.iter().all(|w| w.days() == 7));
+    }
+
+    #[test]
+    fn time_off_and_weekends_are_not_working_days() {
+        let week = span("2025-01-06", "2025-01-12");
+        assert_eq!(working_days_in(&week, &[]), 5.0);
+
+        // Friday to the following Monday only takes Friday out of this week
+        let away = [span("2025-01-10", "2025-01-13")];
+        assert_eq!(working_days_in(&week, &away), 4.0);
+        assert!(!is_working_day(parse_date("2025-01-11").unwrap()));
+    }
+}
@@ -0,0 +1,324 @@
+// Budget engine - phases initiative costs across financial periods
+// Costs are pro-rated by the days an initiative overlaps each period
+
+use super::currency::{CurrencyConverter, CurrencyWarning};
+use super::dates::{DateSpan, FINANCIAL_PERIOD_TYPES, format_date, parse_date};
+use crate::db::{FinancialPeriod, Initiative};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodBudget {
+    pub period_id: String,
+    pub period_name: String,
+    pub period_type: String,
+    pub start_date: String,
+    pub end_date: String,
+    // Reporting currency of the converted figures
+    pub currency: String,
+    pub budget_currency: String,
+    pub budget_available_native: Option<f64>,
+    pub budget_available: Option<f64>,
+    // Unconverted planned spend per initiative currency
+    pub planned_spend_native: BTreeMap<String, f64>,
+    pub planned_spend: f64,
+    // budget_available - planned_spend, when a budget is set
+    pub variance: Option<f64>,
+    pub is_overrun: bool,
+    pub warnings: Vec<CurrencyWarning>,
+}
+
+// Cost dated after the last financial period, which no period's planned spend picks up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizonOverrun {
+    pub initiative_id: String,
+    pub initiative_name: String,
+    // Last day of the last period; None when no periods are defined
+    pub horizon_end: Option<String>,
+    pub uncovered_start: String,
+    pub uncovered_end: String,
+    // In the initiative's own currency
+    pub unphased_cost: f64,
+    pub currency: Option<String>,
+    pub message: String,
+}
+
+// Spend that falls beyond the planning horizon, so period totals plus this reconcile with cost estimates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeyondHorizonBudget {
+    pub horizon_end: Option<String>,
+    pub currency: String,
+    pub planned_spend_native: BTreeMap<String, f64>,
+    pub planned_spend: f64,
+    pub initiatives: Vec<HorizonOverrun>,
+    pub warnings: Vec<CurrencyWarning>,
+}
+
+/// Last day covered by any financial period
+pub fn planning_horizon_end(periods: &[FinancialPeriod]) -> Option<NaiveDate> {
+    periods.iter().filter_map(|p| parse_date(&p.end_date)).max()
+}
+
+/// The part of an initiative's dates after the planning horizon, with the cost phased into it
+pub fn horizon_overrun(initiative: &Initiative, horizon_end: Option<NaiveDate>) -> Option<HorizonOverrun> {
+    let span = DateSpan::parse_inclusive(initiative.start_date.as_deref(), initiative.end_date.as_deref())?;
+    let uncovered = match horizon_end {
+        Some(horizon_end) => DateSpan { start: span.start.max(horizon_end + Duration::days(1)), end: span.end },
+        None => span,
+    };
+    if uncovered.days() <= 0 {
+        return None;
+    }
+
+    let unphased_cost = cost_in_span(initiative, &uncovered);
+    let message = match horizon_end {
+        Some(horizon_end) => format!(
+            "\"{}\" runs to {} but financial periods end on {}; {} of its cost is not in any budget period",
+            initiative.name,
+            format_date(uncovered.last_day()),
+            format_date(horizon_end),
+            unphased_cost
+        ),
+        None => format!("\"{}\" is dated but no financial periods are defined, so none of its cost is budgeted", initiative.name),
+    };
+
+    Some(HorizonOverrun {
+        initiative_id: initiative.id.clone(),
+        initiative_name: initiative.name.clone(),
+        horizon_end: horizon_end.map(format_date),
+        uncovered_start: format_date(uncovered.start),
+        uncovered_end: format_date(uncovered.last_day()),
+        unphased_cost,
+        currency: initiative.currency.clone(),
+        message,
+    })
+}
+
+/// Cost beyond the last financial period, converted at the rate on the first uncovered day
+pub fn calculate_beyond_horizon(initiatives: &[Initiative], periods: &[FinancialPeriod], converter: &CurrencyConverter) -> BeyondHorizonBudget {
+    let horizon_end = planning_horizon_end(periods);
+    let mut warnings = Vec::new();
+    let mut planned_spend_native: BTreeMap<String, f64> = BTreeMap::new();
+    let mut planned_spend = 0.0;
+    let mut overruns = Vec::new();
+
+    for initiative in initiatives {
+        let Some(overrun) = horizon_overrun(initiative, horizon_end) else {
+            continue;
+        };
+        if overrun.unphased_cost > 0.0 {
+            let currency = converter.currency_of(initiative.currency.as_deref());
+            *planned_spend_native.entry(currency.to_string()).or_default() += overrun.unphased_cost;
+            let on = parse_date(&overrun.uncovered_start);
+            if let Some(converted) = on.and_then(|on| converter.convert_or_warn(overrun.unphased_cost, currency, on, "Initiative", &initiative.id, &mut warnings)) {
+                planned_spend += converted;
+            }
+        }
+        overruns.push(overrun);
+    }
+
+    BeyondHorizonBudget {
+        horizon_end: horizon_end.map(format_date),
+        currency: converter.reporting_currency.clone(),
+        planned_spend_native,
+        planned_spend,
+        initiatives: overruns,
+        warnings,
+    }
+}
+
+/// Portion of an initiative's cost falling in a period; zero for undated or uncosted initiatives
+pub fn phased_cost(initiative: &Initiative, period: &FinancialPeriod) -> f64 {
+    match DateSpan::parse_inclusive(Some(&period.start_date), Some(&period.end_date)) {
+        Some(period_span) => cost_in_span(initiative, &period_span),
+        None => 0.0,
+    }
+}
+
+/// Portion of an initiative's cost falling in an arbitrary date span
+pub fn cost_in_span(initiative: &Initiative, window: &DateSpan) -> f64 {
+    let Some(cost) = initiative.cost_estimate else {
+        return 0.0;
+    };
+    let Some(span) = DateSpan::parse_inclusive(initiative.start_date.as_deref(), initiative.end_date.as_deref()) else {
+        return 0.0;
+    };
+
+    cost * span.overlap_days(window) as f64 / span.days() as f64
+}
+
+// Cost falling in a financial period: one initiative's share in its own currency, or a
+// capability's timeline summed in the reporting currency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodCost {
+    pub period_id: String,
+    pub period_name: String,
+    pub period_type: String,
+    pub start_date: String,
+    pub end_date: String,
+    // Days of the period the costed work covers
+    pub overlap_days: i64,
+    pub cost: f64,
+}
+
+/// An initiative's cost spread by days over the periods it spans, at the finest period type
+/// those periods are defined at so no day is counted twice
+pub fn phase_initiative_cost(initiative: &Initiative, periods: &[FinancialPeriod]) -> Result<Vec<PeriodCost>, String> {
+    let Some(cost) = initiative.cost_estimate else {
+        return Err(format!("Initiative {} has no cost estimate to phase", initiative.name));
+    };
+    let Some(span) = DateSpan::parse_inclusive(initiative.start_date.as_deref(), initiative.end_date.as_deref()) else {
+        return Err(format!("Initiative {} needs a start and end date before its cost can be phased", initiative.name));
+    };
+
+    let overlapping: Vec<(&FinancialPeriod, i64)> = periods
+        .iter()
+        .filter_map(|period| {
+            let period_span = DateSpan::parse_inclusive(Some(&period.start_date), Some(&period.end_date))?;
+            let days = span.overlap_days(&period_span);
+            (days > 0).then_some((period, days))
+        })
+        .collect();
+
+    let Some(finest) = FINANCIAL_PERIOD_TYPES
+        .iter()
+        .rev()
+        .find(|t| overlapping.iter().any(|(p, _)| p.period_type == **t))
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut phased: Vec<PeriodCost> = overlapping
+        .into_iter()
+        .filter(|(period, _)| period.period_type == *finest)
+        .map(|(period, days)| PeriodCost {
+            period_id: period.id.clone(),
+            period_name: period.name.clone(),
+            period_type: period.period_type.clone(),
+            start_date: period.start_date.clone(),
+            end_date: period.end_date.clone(),
+            overlap_days: days,
+            cost: cost * days as f64 / span.days() as f64,
+        })
+        .collect();
+
+    phased.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+    Ok(phased)
+}
+
+/// Planned spend against available budget for every financial period, in the reporting
+/// currency at the rate effective at each period's start
+pub fn calculate_budget_report(initiatives: &[Initiative], periods: &[FinancialPeriod], converter: &CurrencyConverter) -> Vec<PeriodBudget> {
+    periods
+        .iter()
+        .filter_map(|period| {
+            let on = parse_date(&period.start_date)?;
+            let mut warnings = Vec::new();
+            let mut planned_spend_native: BTreeMap<String, f64> = BTreeMap::new();
+            let mut planned_spend = 0.0;
+
+            for initiative in initiatives {
+                let cost = phased_cost(initiative, period);
+                if cost == 0.0 {
+                    continue;
+                }
+                let currency = converter.currency_of(initiative.currency.as_deref());
+                *planned_spend_native.entry(currency.to_string()).or_default() += cost;
+                if let Some(converted) = converter.convert_or_warn(cost, currency, on, "Initiative", &initiative.id, &mut warnings) {
+                    planned_spend += converted;
+                }
+            }
+
+            let budget_currency = converter.currency_of(period.currency.as_deref()).to_string();
+            let budget_available = period.budget_available.and_then(|budget| {
+                converter.convert_or_warn(budget, &budget_currency, on, "FinancialPeriod", &period.id, &mut warnings)
+            });
+            let variance = budget_available.map(|budget| budget - planned_spend);
+
+            Some(PeriodBudget {
+                period_id: period.id.clone(),
+                period_name: period.name.clone(),
+                period_type: period.period_type.clone(),
+                start_date: period.start_date.clone(),
+                end_date: period.end_date.clone(),
+                currency: converter.reporting_currency.clone(),
+                budget_currency,
+                budget_available_native: period.budget_available,
+                budget_available,
+                planned_spend_native,
+                planned_spend,
+                variance,
+                is_overrun: variance.is_some_and(|v| v < 0.0),
+                warnings,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::test_initiative;
+
+    fn initiative(start: &str, end: &str, cost: Option<f64>) -> Initiative {
+        Initiative {
+            name: "Data centre exit".to_string(),
+            start_date: Some(start.to_string()),
+            end_date: Some(end.to_string()),
+            cost_estimate: cost,
+            priority: "Must".to_string(),
+            ..test_initiative("init")
+        }
+    }
+
+    #[test]
+    fn cost_after_the_last_period_is_unphased() {
+        let horizon_end = parse_date("2027-03-31");
+
+        // Half of a 20-day initiative falls after the horizon
+        let overrun = horizon_overrun(&initiative("2027-03-22", "2027-04-10", Some(2000.0)), horizon_end).unwrap();
+        assert_eq!(overrun.uncovered_start, "2027-04-01");
+        assert_eq!(overrun.uncovered_end, "2027-04-10");
+        assert_eq!(overrun.unphased_cost, 1000.0);
+
+        assert!(horizon_overrun(&initiative("2027-01-01", "2027-03-31", Some(2000.0)), horizon_end).is_none());
+        assert_eq!(horizon_overrun(&initiative("2027-01-01", "2027-01-10", Some(2000.0)), None).unwrap().unphased_cost, 2000.0);
+    }
+
+    fn period(id: &str, period_type: &str, start: &str, end: &str) -> FinancialPeriod {
+        FinancialPeriod {
+            id: id.to_string(),
+            name: id.to_string(),
+            period_type: period_type.to_string(),
+            start_date: start.to_string(),
+            end_date: end.to_string(),
+            budget_available: None,
+            currency: None,
+            closed: false,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn cost_is_phased_over_the_finest_periods_spanned() {
+        let periods = [
+            period("FY27", "Year", "2026-04-01", "2027-03-31"),
+            period("Q1", "Quarter", "2026-04-01", "2026-06-30"),
+            period("Q2", "Quarter", "2026-07-01", "2026-09-30"),
+        ];
+
+        // 30 days in June and 31 in July
+        let phased = phase_initiative_cost(&initiative("2026-06-01", "2026-07-31", Some(6100.0)), &periods).unwrap();
+        assert_eq!(phased.iter().map(|p| p.period_id.as_str()).collect::<Vec<_>>(), ["Q1", "Q2"]);
+        assert_eq!(phased[0].overlap_days, 30);
+        assert_eq!(phased[0].cost, 3000.0);
+        assert_eq!(phased[1].cost, 3100.0);
+
+        assert!(phase_initiative_cost(&initiative("2026-06-01", "2026-07-31", None), &periods).is_err());
+        let mut undated = initiative("2026-06-01", "2026-07-31", Some(100.0));
+        undated.end_date = None;
+        assert!(phase_initiative_cost(&undated, &periods).is_err());
+    }
+}
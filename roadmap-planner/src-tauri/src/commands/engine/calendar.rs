@@ -0,0 +1,103 @@
+// Working calendar - which days count when turning effort into dates
+// Weekly working pattern plus specific non-working dates
+
+use super::dates::DateSpan;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::BTreeSet;
+
+pub const WEEKDAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+pub const DEFAULT_WORKING_DAYS: &str = "Mon,Tue,Wed,Thu,Fri";
+
+// Stops a calendar with no working days at all from searching forever
+const MAX_SEARCH_DAYS: i64 = 366 * 50;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkingCalendar {
+    // Indexed from Monday
+    pub weekdays: [bool; 7],
+    pub holidays: BTreeSet<NaiveDate>,
+}
+
+impl Default for WorkingCalendar {
+    /// Monday to Friday with no holidays
+    fn default() -> Self {
+        Self::new(DEFAULT_WORKING_DAYS, BTreeSet::new()).expect("default working days parse")
+    }
+}
+
+impl WorkingCalendar {
+    pub fn new(working_days: &str, holidays: BTreeSet<NaiveDate>) -> Result<Self, String> {
+        Ok(Self { weekdays: parse_working_days(working_days)?, holidays })
+    }
+
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        self.weekdays[date.weekday().num_days_from_monday() as usize] && !self.holidays.contains(&date)
+    }
+
+    pub fn working_days_in(&self, span: &DateSpan) -> i64 {
+        span.iter_days().filter(|d| self.is_working_day(*d)).count() as i64
+    }
+
+    /// The last day of a run of `days` working days starting on or after `start`
+    pub fn finish_date(&self, start: NaiveDate, days: i64) -> Option<NaiveDate> {
+        let mut remaining = days.max(1);
+        let mut date = start;
+        for _ in 0..MAX_SEARCH_DAYS {
+            if self.is_working_day(date) {
+                remaining -= 1;
+                if remaining == 0 {
+                    return Some(date);
+                }
+            }
+            date += Duration::days(1);
+        }
+        None
+    }
+}
+
+/// Parse a comma-separated list of weekday abbreviations, e.g. "Mon,Tue,Wed,Thu,Fri"
+pub fn parse_working_days(value: &str) -> Result<[bool; 7], String> {
+    let mut weekdays = [false; 7];
+    for name in value.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let index = WEEKDAY_NAMES
+            .iter()
+            .position(|w| w.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Unknown weekday {}, expected one of {}", name, WEEKDAY_NAMES.join(", ")))?;
+        weekdays[index] = true;
+    }
+    if !weekdays.contains(&true) {
+        return Err("A working calendar needs at least one working day".to_string());
+    }
+    Ok(weekdays)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::dates::parse_date;
+
+    fn day(value: &str) -> NaiveDate {
+        parse_date(value).unwrap()
+    }
+
+    #[test]
+    fn default_calendar_skips_weekends() {
+        let calendar = WorkingCalendar::default();
+        // Friday 2025-01-10 plus two more working days lands on Tuesday
+        assert_eq!(calendar.finish_date(day("2025-01-10"), 3), Some(day("2025-01-14")));
+        // Starting on a Saturday counts from Monday
+        assert_eq!(calendar.finish_date(day("2025-01-11"), 1), Some(day("2025-01-13")));
+    }
+
+    #[test]
+    fn holidays_and_custom_weeks_are_not_worked() {
+        let holidays = BTreeSet::from([day("2025-12-25"), day("2025-12-26")]);
+        let calendar = WorkingCalendar::new("Mon,Tue,Wed,Thu", holidays).unwrap();
+        let span = DateSpan::parse_inclusive(Some("2025-12-22"), Some("2025-12-28")).unwrap();
+        assert_eq!(calendar.working_days_in(&span), 3);
+        assert_eq!(calendar.finish_date(day("2025-12-24"), 2), Some(day("2025-12-29")));
+
+        assert!(parse_working_days("Mon,Funday").is_err());
+        assert!(parse_working_days("").is_err());
+    }
+}
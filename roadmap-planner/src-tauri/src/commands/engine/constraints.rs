@@ -0,0 +1,170 @@
+// Constraint engine - validates initiatives against linked constraints
+// Port of src/lib/constraintEngine.ts
+
+use super::dates::parse_date;
+use crate::db::{Constraint, Initiative};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeConstraintLink {
+    pub id: String,
+    pub initiative_id: String,
+    pub constraint_id: String,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstraintViolation {
+    pub constraint_id: String,
+    pub constraint_name: String,
+    pub constraint_type: String,
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub hardness: String,
+    pub message: String,
+}
+
+/// Check if an initiative violates a specific constraint
+pub fn check_constraint(initiative: &Initiative, constraint: &Constraint) -> Option<ConstraintViolation> {
+    // Skip if no dates to check
+    let start = parse_date(initiative.start_date.as_deref()?)?;
+    let end = parse_date(initiative.end_date.as_deref()?)?;
+
+    let effective = constraint.effective_date.as_deref().and_then(parse_date);
+    let expiry = constraint.expiry_date.as_deref().and_then(parse_date);
+
+    let message = if constraint.constraint_type == "Deadline" {
+        match effective {
+            Some(deadline) if end > deadline => Some(format!(
+                "\"{}\" ends after deadline \"{}\" ({})",
+                initiative.name, constraint.name, deadline
+            )),
+            _ => None,
+        }
+    } else {
+        // Non-deadline constraints must overlap their effective window
+        match (effective, expiry) {
+            (_, Some(expiry)) if start > expiry => Some(format!(
+                "\"{}\" starts after \"{}\" expires ({})",
+                initiative.name, constraint.name, expiry
+            )),
+            (Some(effective), _) if end < effective => Some(format!(
+                "\"{}\" ends before \"{}\" is effective ({})",
+                initiative.name, constraint.name, effective
+            )),
+            _ => None,
+        }
+    };
+
+    message.map(|message| ConstraintViolation {
+        constraint_id: constraint.id.clone(),
+        constraint_name: constraint.name.clone(),
+        constraint_type: constraint.constraint_type.clone(),
+        initiative_id: initiative.id.clone(),
+        initiative_name: initiative.name.clone(),
+        hardness: constraint.hardness.clone(),
+        message,
+    })
+}
+
+/// Check every initiative against the constraints linked to it
+pub fn check_all_constraints(
+    initiatives: &[Initiative],
+    constraints: &[Constraint],
+    links: &[InitiativeConstraintLink],
+) -> Vec<ConstraintViolation> {
+    let mut violations = Vec::new();
+
+    for initiative in initiatives {
+        for link in links.iter().filter(|l| l.initiative_id == initiative.id) {
+            let Some(constraint) = constraints.iter().find(|c| c.id == link.constraint_id) else {
+                continue;
+            };
+            if let Some(violation) = check_constraint(initiative, constraint) {
+                violations.push(violation);
+            }
+        }
+    }
+
+    violations
+}
+
+/// Only the violations of hard constraints, the ones that block a scenario outright
+pub fn check_hard_constraints(
+    initiatives: &[Initiative],
+    constraints: &[Constraint],
+    links: &[InitiativeConstraintLink],
+) -> Vec<ConstraintViolation> {
+    let hard: Vec<Constraint> = constraints.iter().filter(|c| c.hardness == "Hard").cloned().collect();
+    check_all_constraints(initiatives, &hard, links)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedInitiative {
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub violated: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioConstraint {
+    pub constraint_id: String,
+    pub constraint_name: String,
+    pub constraint_type: String,
+    pub hardness: String,
+    pub effective_date: Option<String>,
+    pub expiry_date: Option<String>,
+    pub affected: Vec<AffectedInitiative>,
+    pub violation_count: i64,
+}
+
+/// Every constraint linked to at least one of the initiatives, with the initiatives it
+/// applies to. Hard constraints come first, then the most violated, then by name.
+pub fn summarise_constraints(
+    initiatives: &[Initiative],
+    constraints: &[Constraint],
+    links: &[InitiativeConstraintLink],
+) -> Vec<ScenarioConstraint> {
+    let mut summaries: Vec<ScenarioConstraint> = constraints
+        .iter()
+        .filter_map(|constraint| {
+            let affected: Vec<AffectedInitiative> = initiatives
+                .iter()
+                .filter(|i| links.iter().any(|l| l.initiative_id == i.id && l.constraint_id == constraint.id))
+                .map(|initiative| {
+                    let violation = check_constraint(initiative, constraint);
+                    AffectedInitiative {
+                        initiative_id: initiative.id.clone(),
+                        initiative_name: initiative.name.clone(),
+                        violated: violation.is_some(),
+                        message: violation.map(|v| v.message),
+                    }
+                })
+                .collect();
+            if affected.is_empty() {
+                return None;
+            }
+
+            Some(ScenarioConstraint {
+                constraint_id: constraint.id.clone(),
+                constraint_name: constraint.name.clone(),
+                constraint_type: constraint.constraint_type.clone(),
+                hardness: constraint.hardness.clone(),
+                effective_date: constraint.effective_date.clone(),
+                expiry_date: constraint.expiry_date.clone(),
+                violation_count: affected.iter().filter(|a| a.violated).count() as i64,
+                affected,
+            })
+        })
+        .collect();
+
+    summaries.sort_by(|a, b| {
+        (a.hardness != "Hard")
+            .cmp(&(b.hardness != "Hard"))
+            .then_with(|| b.violation_count.cmp(&a.violation_count))
+            .then_with(|| a.constraint_name.cmp(&b.constraint_name))
+    });
+
+    summaries
+}
@@ -0,0 +1,115 @@
+// Currency engine - converts native amounts into the workspace reporting currency
+// Rates are effective-dated; the latest rate on or before a date applies
+
+use super::dates::{format_date, parse_date};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub id: String,
+    pub from_currency: String,
+    pub to_currency: String,
+    // One unit of from_currency buys this many units of to_currency
+    pub rate: f64,
+    pub effective_date: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+// An amount left out of a total because no rate was effective
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencyWarning {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub currency: String,
+    pub amount: f64,
+    pub on_date: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CurrencyConverter {
+    pub reporting_currency: String,
+    // (from, to) -> (effective date, rate), sorted by date
+    rates: HashMap<(String, String), Vec<(NaiveDate, f64)>>,
+}
+
+impl CurrencyConverter {
+    pub fn new(reporting_currency: &str, rates: &[ExchangeRate]) -> Self {
+        let mut by_pair: HashMap<(String, String), Vec<(NaiveDate, f64)>> = HashMap::new();
+        for rate in rates {
+            if let Some(date) = parse_date(&rate.effective_date) {
+                by_pair
+                    .entry((rate.from_currency.clone(), rate.to_currency.clone()))
+                    .or_default()
+                    .push((date, rate.rate));
+            }
+        }
+        for entries in by_pair.values_mut() {
+            entries.sort_by_key(|(date, _)| *date);
+        }
+
+        Self { reporting_currency: reporting_currency.to_string(), rates: by_pair }
+    }
+
+    /// The currency an amount is held in, treating an unset currency as the reporting currency
+    pub fn currency_of<'a>(&'a self, currency: Option<&'a str>) -> &'a str {
+        currency.unwrap_or(&self.reporting_currency)
+    }
+
+    fn latest(&self, from: &str, to: &str, on: NaiveDate) -> Option<(NaiveDate, f64)> {
+        self.rates
+            .get(&(from.to_string(), to.to_string()))?
+            .iter()
+            .rev()
+            .find(|(date, _)| *date <= on)
+            .copied()
+    }
+
+    /// Rate from a currency into the reporting currency on a date. A rate stored the
+    /// other way round is inverted; whichever took effect most recently wins.
+    pub fn rate(&self, from: &str, on: NaiveDate) -> Option<f64> {
+        if from == self.reporting_currency {
+            return Some(1.0);
+        }
+        let direct = self.latest(from, &self.reporting_currency, on);
+        let inverse = self.latest(&self.reporting_currency, from, on).map(|(date, rate)| (date, 1.0 / rate));
+        match (direct, inverse) {
+            (Some(d), Some(i)) => Some(if i.0 > d.0 { i.1 } else { d.1 }),
+            (d, i) => d.or(i).map(|(_, rate)| rate),
+        }
+    }
+
+    pub fn convert(&self, amount: f64, from: &str, on: NaiveDate) -> Option<f64> {
+        self.rate(from, on).map(|rate| amount * rate)
+    }
+
+    /// Convert an amount, recording a warning instead when no rate applies
+    pub fn convert_or_warn(
+        &self,
+        amount: f64,
+        from: &str,
+        on: NaiveDate,
+        entity_type: &str,
+        entity_id: &str,
+        warnings: &mut Vec<CurrencyWarning>,
+    ) -> Option<f64> {
+        let converted = self.convert(amount, from, on);
+        if converted.is_none() {
+            warnings.push(CurrencyWarning {
+                entity_type: entity_type.to_string(),
+                entity_id: entity_id.to_string(),
+                currency: from.to_string(),
+                amount,
+                on_date: format_date(on),
+                message: format!(
+                    "No {} to {} rate effective on {}; excluded from totals",
+                    from, self.reporting_currency, format_date(on)
+                ),
+            });
+        }
+        converted
+    }
+}
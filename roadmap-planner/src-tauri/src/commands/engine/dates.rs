@@ -0,0 +1,184 @@
+// Date helpers for the calculation engine
+// All spans are half-open: [start, end)
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateSpan {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateSpan {
+    /// Build a span from stored dates, where the end date is the last included day
+    pub fn from_inclusive(start: NaiveDate, end: NaiveDate) -> Option<Self> {
+        if end < start {
+            return None;
+        }
+        Some(Self { start, end: end + Duration::days(1) })
+    }
+
+    /// Build a span from optional stored date strings
+    pub fn parse_inclusive(start: Option<&str>, end: Option<&str>) -> Option<Self> {
+        Self::from_inclusive(parse_date(start?)?, parse_date(end?)?)
+    }
+
+    pub fn days(&self) -> i64 {
+        (self.end - self.start).num_days()
+    }
+
+    pub fn overlap_days(&self, other: &DateSpan) -> i64 {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        if start >= end { 0 } else { (end - start).num_days() }
+    }
+
+    pub fn intersect(&self, other: &DateSpan) -> Option<DateSpan> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        if start >= end { None } else { Some(DateSpan { start, end }) }
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date < self.end
+    }
+
+    /// Each day in the span, in order
+    pub fn iter_days(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.start.iter_days().take_while(move |d| *d < self.end)
+    }
+
+    /// The last included day, for writing back to the database
+    pub fn last_day(&self) -> NaiveDate {
+        self.end - Duration::days(1)
+    }
+}
+
+/// Parse a stored date, accepting full timestamps by taking the date part
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+pub fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+pub fn add_months(date: NaiveDate, months: i32) -> NaiveDate {
+    let total = date.year() * 12 + date.month0() as i32 + months;
+    let (year, month0) = (total.div_euclid(12), total.rem_euclid(12) as u32);
+    // Clamp the day to the length of the target month (31 Jan + 1 month = 28/29 Feb)
+    let mut day = date.day();
+    loop {
+        if let Some(d) = NaiveDate::from_ymd_opt(year, month0 + 1, day) {
+            return d;
+        }
+        day -= 1;
+    }
+}
+
+pub const FINANCIAL_PERIOD_TYPES: [&str; 4] = ["Year", "Half", "Quarter", "Month"];
+pub const POOL_PERIOD_TYPES: [&str; 3] = ["Month", "Quarter", "Year"];
+
+/// The stored spelling of a period type, accepting any case and the usual abbreviations
+pub fn normalise_period_type(value: &str, allowed: &[&'static str]) -> Result<&'static str, String> {
+    let canonical = match value.trim().to_ascii_lowercase().as_str() {
+        "year" | "yr" | "annual" | "yearly" => Some("Year"),
+        "half" | "half-year" | "halfyear" | "h" => Some("Half"),
+        "quarter" | "qtr" | "q" | "quarterly" => Some("Quarter"),
+        "month" | "mon" | "mth" | "monthly" => Some("Month"),
+        _ => None,
+    };
+
+    canonical
+        .and_then(|c| allowed.iter().find(|t| **t == c).copied())
+        .ok_or_else(|| format!("Unknown period type \"{}\", expected one of {}", value, allowed.join(", ")))
+}
+
+/// Start of the calendar period of the given type containing the date; weeks start on Monday
+pub fn period_start(date: NaiveDate, period_type: &str) -> NaiveDate {
+    if period_type == "Week" {
+        return date - Duration::days(date.weekday().num_days_from_monday() as i64);
+    }
+    let month0 = match period_type {
+        "Year" => 0,
+        "Half" => (date.month0() / 6) * 6,
+        "Quarter" => (date.month0() / 3) * 3,
+        _ => date.month0(),
+    };
+    NaiveDate::from_ymd_opt(date.year(), month0 + 1, 1).expect("first of month is always valid")
+}
+
+/// Label for the calendar period of the given type containing the date, e.g. 2025-Q1
+pub fn period_label(date: NaiveDate, period_type: &str) -> String {
+    match period_type {
+        "Week" => date.format("%G-W%V").to_string(),
+        "Year" => date.year().to_string(),
+        "Half" => format!("{}-H{}", date.year(), date.month0() / 6 + 1),
+        "Quarter" => format!("{}-Q{}", date.year(), date.month0() / 3 + 1),
+        _ => date.format("%Y-%m").to_string(),
+    }
+}
+
+pub fn period_months(period_type: &str) -> i32 {
+    match period_type {
+        "Year" => 12,
+        "Half" => 6,
+        "Quarter" => 3,
+        _ => 1,
+    }
+}
+
+/// Start of the period after the one starting on the given date
+pub fn next_period_start(start: NaiveDate, period_type: &str) -> NaiveDate {
+    match period_type {
+        "Week" => start + Duration::days(7),
+        _ => add_months(start, period_months(period_type)),
+    }
+}
+
+/// Calendar-aligned periods of the given type covering the span
+pub fn generate_periods(span: &DateSpan, period_type: &str) -> Vec<DateSpan> {
+    let mut periods = Vec::new();
+    let mut current = period_start(span.start, period_type);
+
+    while current < span.end {
+        let next = next_period_start(current, period_type);
+        periods.push(DateSpan { start: current, end: next });
+        current = next;
+    }
+
+    periods
+}
+
+/// Overall span of a set of optional start/end pairs, ignoring undated entries
+pub fn bounding_span<'a, I>(dates: I) -> Option<DateSpan>
+where
+    I: IntoIterator<Item = (Option<&'a str>, Option<&'a str>)>,
+{
+    dates
+        .into_iter()
+        .filter_map(|(start, end)| DateSpan::parse_inclusive(start, end))
+        .reduce(|a, b| DateSpan { start: a.start.min(b.start), end: a.end.max(b.end) })
+}
+
+/// Union of the spans, sorted by start; spans that overlap or touch become one
+pub fn merge_spans(mut spans: Vec<DateSpan>) -> Vec<DateSpan> {
+    spans.sort_by_key(|s| s.start);
+    let mut merged: Vec<DateSpan> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+/// Monday to Friday
+pub fn is_working_day(date: NaiveDate) -> bool {
+    date.weekday().num_days_from_monday() < 5
+}
+
+pub fn today() -> NaiveDate {
+    chrono::Local::now().date_naive()
+}
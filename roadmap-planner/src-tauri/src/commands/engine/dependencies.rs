@@ -0,0 +1,408 @@
+// Dependency engine - validates initiative dependencies
+// Port of src/lib/dependencyEngine.ts
+
+use super::dates::{format_date, parse_date};
+use crate::db::Initiative;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeDependency {
+    pub id: String,
+    pub predecessor_id: String,
+    pub successor_id: String,
+    pub dependency_type: String,
+    pub lag_days: Option<i64>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LagWarning {
+    pub dependency_id: String,
+    pub predecessor_id: String,
+    pub successor_id: String,
+    pub lag_days: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyViolation {
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub depends_on_id: String,
+    pub depends_on_name: String,
+    pub dependency_type: String,
+    pub message: String,
+    pub suggested_start_date: Option<String>,
+    pub suggested_end_date: Option<String>,
+}
+
+pub fn dates_of(initiative: &Initiative) -> Option<(NaiveDate, NaiveDate)> {
+    Some((
+        parse_date(initiative.start_date.as_deref()?)?,
+        parse_date(initiative.end_date.as_deref()?)?,
+    ))
+}
+
+// Leads and lags beyond a year are almost certainly data entry mistakes
+pub const MAX_LAG_DAYS: i64 = 365;
+
+pub fn validate_lag_days(lag_days: i64) -> Result<(), String> {
+    if lag_days.abs() > MAX_LAG_DAYS {
+        return Err(format!(
+            "Dependency lag must be between -{} and {} days, got {}",
+            MAX_LAG_DAYS, MAX_LAG_DAYS, lag_days
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RequiredStart {
+    pub date: NaiveDate,
+    // A lead (negative lag) would have put the successor before the predecessor starts
+    pub clamped: bool,
+}
+
+fn is_finish_driven(dependency_type: &str) -> bool {
+    matches!(dependency_type, "FinishToFinish" | "StartToFinish")
+}
+
+/// Earliest start the successor may have for the dependency to hold.
+/// A lead may overlap the two, but never moves the successor's constrained event (its
+/// start, or its finish for finish-driven types) before the predecessor starts.
+pub fn required_start(
+    dependency_type: &str,
+    lag_days: i64,
+    predecessor: (NaiveDate, NaiveDate),
+    successor_duration: Duration,
+) -> RequiredStart {
+    let (pred_start, pred_end) = predecessor;
+    let lag = Duration::days(lag_days);
+
+    let date = match dependency_type {
+        "StartToStart" => pred_start + lag,
+        "FinishToFinish" => pred_end + lag - successor_duration,
+        "StartToFinish" => pred_start + lag - successor_duration,
+        // FinishToStart is the default relationship
+        _ => pred_end + lag,
+    };
+
+    let floor = if is_finish_driven(dependency_type) { pred_start - successor_duration } else { pred_start };
+    if lag_days < 0 && date < floor {
+        return RequiredStart { date: floor, clamped: true };
+    }
+    RequiredStart { date, clamped: false }
+}
+
+fn format_violation_message(initiative_name: &str, predecessor_name: &str, dependency_type: &str) -> String {
+    match dependency_type {
+        "FinishToStart" => format!("\"{}\" starts before \"{}\" finishes", initiative_name, predecessor_name),
+        "StartToStart" => format!("\"{}\" starts before \"{}\" starts", initiative_name, predecessor_name),
+        "FinishToFinish" => format!("\"{}\" finishes before \"{}\" finishes", initiative_name, predecessor_name),
+        "StartToFinish" => format!("\"{}\" finishes before \"{}\" starts", initiative_name, predecessor_name),
+        _ => format!("Dependency violation between \"{}\" and \"{}\"", initiative_name, predecessor_name),
+    }
+}
+
+/// Check a single dependency, returning a violation with a suggested fix if unsatisfied
+pub fn check_dependency(
+    successor: &Initiative,
+    predecessor: &Initiative,
+    dependency: &InitiativeDependency,
+) -> Option<DependencyViolation> {
+    let (start, end) = dates_of(successor)?;
+    let predecessor_dates = dates_of(predecessor)?;
+    let duration = end - start;
+
+    let earliest = required_start(
+        &dependency.dependency_type,
+        dependency.lag_days.unwrap_or(0),
+        predecessor_dates,
+        duration,
+    )
+    .date;
+
+    if start >= earliest {
+        return None;
+    }
+
+    Some(DependencyViolation {
+        initiative_id: successor.id.clone(),
+        initiative_name: successor.name.clone(),
+        depends_on_id: predecessor.id.clone(),
+        depends_on_name: predecessor.name.clone(),
+        dependency_type: dependency.dependency_type.clone(),
+        message: format_violation_message(&successor.name, &predecessor.name, &dependency.dependency_type),
+        suggested_start_date: Some(format_date(earliest)),
+        suggested_end_date: Some(format_date(earliest + duration)),
+    })
+}
+
+/// Check all initiatives and return all dependency violations
+pub fn check_all_dependencies(
+    initiatives: &[Initiative],
+    dependencies: &[InitiativeDependency],
+) -> Vec<DependencyViolation> {
+    let by_id: HashMap<&str, &Initiative> = initiatives.iter().map(|i| (i.id.as_str(), i)).collect();
+
+    dependencies
+        .iter()
+        .filter_map(|dep| {
+            let successor = by_id.get(dep.successor_id.as_str())?;
+            let predecessor = by_id.get(dep.predecessor_id.as_str())?;
+            check_dependency(successor, predecessor, dep)
+        })
+        .collect()
+}
+
+/// Dependencies whose lead is longer than the planned dates allow, so scheduling clamps it
+pub fn lead_warnings(initiatives: &[Initiative], dependencies: &[InitiativeDependency]) -> Vec<LagWarning> {
+    let by_id: HashMap<&str, &Initiative> = initiatives.iter().map(|i| (i.id.as_str(), i)).collect();
+
+    dependencies
+        .iter()
+        .filter_map(|dep| {
+            let successor = by_id.get(dep.successor_id.as_str())?;
+            let predecessor = by_id.get(dep.predecessor_id.as_str())?;
+            let (start, end) = dates_of(successor)?;
+            let lag_days = dep.lag_days.unwrap_or(0);
+            let required = required_start(&dep.dependency_type, lag_days, dates_of(predecessor)?, end - start);
+            required.clamped.then(|| LagWarning {
+                dependency_id: dep.id.clone(),
+                predecessor_id: predecessor.id.clone(),
+                successor_id: successor.id.clone(),
+                lag_days,
+                message: format!(
+                    "A {} day lead on \"{}\" would move \"{}\" before \"{}\" starts; scheduling from {} instead",
+                    -lag_days,
+                    predecessor.name,
+                    successor.name,
+                    predecessor.name,
+                    format_date(required.date)
+                ),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyDateIssue {
+    pub dependency_id: String,
+    pub predecessor_id: String,
+    pub predecessor_name: String,
+    pub successor_id: String,
+    pub successor_name: String,
+    pub dependency_type: String,
+    // StartsTooEarly, SuccessorHasNoStart or PredecessorHasNoEnd
+    pub category: String,
+    pub message: String,
+    // Earliest start the dependency allows, when both ends are dated
+    pub required_start_date: Option<String>,
+}
+
+/// Every dependency whose dates don't hold together: the successor is scheduled too early for
+/// it, or one side is missing the date it would be checked against
+pub fn check_dependency_dates(initiatives: &[Initiative], dependencies: &[InitiativeDependency]) -> Vec<DependencyDateIssue> {
+    let by_id: HashMap<&str, &Initiative> = initiatives.iter().map(|i| (i.id.as_str(), i)).collect();
+    let mut issues = Vec::new();
+
+    for dep in dependencies {
+        let (Some(successor), Some(predecessor)) = (by_id.get(dep.successor_id.as_str()), by_id.get(dep.predecessor_id.as_str())) else {
+            continue;
+        };
+        let issue = |category: &str, message: String, required_start_date: Option<String>| DependencyDateIssue {
+            dependency_id: dep.id.clone(),
+            predecessor_id: predecessor.id.clone(),
+            predecessor_name: predecessor.name.clone(),
+            successor_id: successor.id.clone(),
+            successor_name: successor.name.clone(),
+            dependency_type: dep.dependency_type.clone(),
+            category: category.to_string(),
+            message,
+            required_start_date,
+        };
+
+        let has_start = successor.start_date.as_deref().and_then(parse_date).is_some();
+        let has_end = predecessor.end_date.as_deref().and_then(parse_date).is_some();
+        if !has_start {
+            issues.push(issue(
+                "SuccessorHasNoStart",
+                format!("\"{}\" depends on \"{}\" but has no start date", successor.name, predecessor.name),
+                None,
+            ));
+        }
+        if !has_end {
+            issues.push(issue(
+                "PredecessorHasNoEnd",
+                format!("\"{}\" has no end date, so \"{}\" can't be checked against it", predecessor.name, successor.name),
+                None,
+            ));
+        }
+        if let Some(violation) = check_dependency(successor, predecessor, dep) {
+            issues.push(issue("StartsTooEarly", violation.message, violation.suggested_start_date));
+        }
+    }
+
+    issues
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CriticalPath {
+    pub initiative_ids: HashSet<String>,
+    pub dependency_ids: HashSet<String>,
+}
+
+/// The chain of driving dependencies behind the latest-finishing initiatives.
+/// Works from the planned dates: a predecessor drives its successor when the successor
+/// starts no later than the dependency requires, so any slip in it delays the roadmap end.
+pub fn critical_path(initiatives: &[Initiative], dependencies: &[InitiativeDependency]) -> CriticalPath {
+    let dated: HashMap<&str, (NaiveDate, NaiveDate)> = initiatives
+        .iter()
+        .filter_map(|i| Some((i.id.as_str(), dates_of(i)?)))
+        .collect();
+
+    let mut path = CriticalPath::default();
+    let Some(roadmap_end) = dated.values().map(|(_, end)| *end).max() else {
+        return path;
+    };
+
+    let mut pending: Vec<&str> = dated
+        .iter()
+        .filter(|(_, (_, end))| *end == roadmap_end)
+        .map(|(id, _)| *id)
+        .collect();
+
+    while let Some(id) = pending.pop() {
+        if !path.initiative_ids.insert(id.to_string()) {
+            continue;
+        }
+        let (start, end) = dated[id];
+
+        for dep in dependencies.iter().filter(|d| d.successor_id == id) {
+            let Some(predecessor) = dated.get(dep.predecessor_id.as_str()) else {
+                continue;
+            };
+            let required = required_start(&dep.dependency_type, dep.lag_days.unwrap_or(0), *predecessor, end - start);
+            if start <= required.date {
+                path.dependency_ids.insert(dep.id.clone());
+                pending.push(dep.predecessor_id.as_str());
+            }
+        }
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::test_initiative;
+
+    fn day(value: &str) -> NaiveDate {
+        parse_date(value).unwrap()
+    }
+
+    #[test]
+    fn leads_and_lags_shift_the_required_start() {
+        let predecessor = (day("2025-03-01"), day("2025-03-31"));
+        let ten_days = Duration::days(10);
+
+        assert_eq!(required_start("FinishToStart", 14, predecessor, ten_days).date, day("2025-04-14"));
+        assert_eq!(required_start("FinishToStart", -14, predecessor, ten_days).date, day("2025-03-17"));
+        assert_eq!(required_start("StartToStart", 7, predecessor, ten_days).date, day("2025-03-08"));
+        assert_eq!(required_start("FinishToFinish", -5, predecessor, ten_days).date, day("2025-03-16"));
+    }
+
+    #[test]
+    fn lead_longer_than_a_short_predecessor_is_clamped() {
+        // Five-day predecessor with a two-week lead
+        let predecessor = (day("2025-03-10"), day("2025-03-15"));
+        let required = required_start("FinishToStart", -14, predecessor, Duration::days(10));
+        assert_eq!(required, RequiredStart { date: day("2025-03-10"), clamped: true });
+
+        let required = required_start("StartToStart", -3, predecessor, Duration::days(10));
+        assert_eq!(required, RequiredStart { date: day("2025-03-10"), clamped: true });
+
+        // A finish-driven successor may still finish no earlier than the predecessor starts
+        let required = required_start("FinishToFinish", -30, predecessor, Duration::days(10));
+        assert_eq!(required, RequiredStart { date: day("2025-02-28"), clamped: true });
+    }
+
+    fn dated(id: &str, start: &str, end: &str) -> Initiative {
+        Initiative {
+            start_date: Some(start.to_string()),
+            end_date: Some(end.to_string()),
+            priority: "Must".to_string(),
+            ..test_initiative(id)
+        }
+    }
+
+    fn finish_to_start(id: &str, predecessor_id: &str, successor_id: &str) -> InitiativeDependency {
+        InitiativeDependency {
+            id: id.to_string(),
+            predecessor_id: predecessor_id.to_string(),
+            successor_id: successor_id.to_string(),
+            dependency_type: "FinishToStart".to_string(),
+            lag_days: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn critical_path_follows_driving_dependencies_to_the_latest_finish() {
+        let initiatives = vec![
+            dated("design", "2025-01-01", "2025-01-31"),
+            dated("build", "2025-01-31", "2025-03-31"),
+            // Finishes well before build starts, so it has slack
+            dated("procure", "2025-01-01", "2025-01-10"),
+            dated("launch", "2025-03-31", "2025-04-30"),
+            // Undated initiatives never make the path
+            Initiative { start_date: None, end_date: None, ..dated("later", "2025-01-01", "2025-01-01") },
+        ];
+        let dependencies = vec![
+            finish_to_start("d1", "design", "build"),
+            finish_to_start("d2", "procure", "build"),
+            finish_to_start("d3", "build", "launch"),
+            finish_to_start("d4", "later", "launch"),
+        ];
+
+        let path = critical_path(&initiatives, &dependencies);
+
+        let ids: HashSet<String> = ["design", "build", "launch"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(path.initiative_ids, ids);
+        let deps: HashSet<String> = ["d1", "d3"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(path.dependency_ids, deps);
+    }
+
+    #[test]
+    fn lag_range_is_validated() {
+        assert!(validate_lag_days(-MAX_LAG_DAYS).is_ok());
+        assert!(validate_lag_days(MAX_LAG_DAYS + 1).is_err());
+    }
+
+    #[test]
+    fn dependency_dates_are_categorised() {
+        let initiatives = vec![
+            dated("design", "2025-01-01", "2025-01-31"),
+            dated("build", "2025-01-15", "2025-03-31"),
+            Initiative { start_date: None, ..dated("launch", "2025-04-01", "2025-04-30") },
+            Initiative { end_date: None, ..dated("procure", "2025-01-01", "2025-01-10") },
+            dated("train", "2025-04-01", "2025-04-30"),
+        ];
+        let dependencies = vec![
+            finish_to_start("d1", "design", "build"),
+            finish_to_start("d2", "build", "launch"),
+            finish_to_start("d3", "procure", "train"),
+            finish_to_start("d4", "build", "train"),
+        ];
+
+        let issues = check_dependency_dates(&initiatives, &dependencies);
+        let categories: Vec<(&str, &str)> = issues.iter().map(|i| (i.dependency_id.as_str(), i.category.as_str())).collect();
+        assert_eq!(categories, [("d1", "StartsTooEarly"), ("d2", "SuccessorHasNoStart"), ("d3", "PredecessorHasNoEnd")]);
+        assert_eq!(issues[0].required_start_date.as_deref(), Some("2025-01-31"));
+
+        assert!(check_dependency_dates(&initiatives, &dependencies[3..]).is_empty());
+    }
+}
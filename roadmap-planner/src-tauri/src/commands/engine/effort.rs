@@ -0,0 +1,193 @@
+// Effort units and profiles - converts initiative effort into a resource pool's
+// capacity unit and shapes how it is spread across the initiative's duration
+
+use super::dates::{DateSpan, period_months};
+use crate::db::ResourcePool;
+use serde::{Deserialize, Serialize};
+
+pub const WORKING_DAYS_PER_WEEK: f64 = 5.0;
+pub const WORKING_DAYS_PER_MONTH: f64 = 20.0;
+pub const WORKING_DAYS_PER_YEAR: f64 = WORKING_DAYS_PER_MONTH * 12.0;
+
+pub const EFFORT_UNITS: [&str; 4] = ["PersonDays", "PersonWeeks", "PersonMonths", "PersonYears"];
+
+/// Person-days in one unit of initiative effort
+pub fn effort_unit_days(unit: &str) -> Option<f64> {
+    match unit {
+        "PersonDays" => Some(1.0),
+        "PersonWeeks" => Some(WORKING_DAYS_PER_WEEK),
+        "PersonMonths" => Some(WORKING_DAYS_PER_MONTH),
+        "PersonYears" => Some(WORKING_DAYS_PER_YEAR),
+        _ => None,
+    }
+}
+
+pub fn validate_effort_unit(unit: &str) -> Result<(), String> {
+    match effort_unit_days(unit) {
+        Some(_) => Ok(()),
+        None => Err(format!("Unknown effort unit {}, expected one of {}", unit, EFFORT_UNITS.join(", "))),
+    }
+}
+
+/// Person-days one unit of pool capacity stands for over one of the pool's periods
+pub fn capacity_unit_days(capacity_unit: &str, period_type: &str) -> Option<f64> {
+    match capacity_unit {
+        // A full-time person for the whole period
+        "FTE" => Some(period_months(period_type) as f64 * WORKING_DAYS_PER_MONTH),
+        "PersonDays" => Some(1.0),
+        "PersonMonths" => Some(WORKING_DAYS_PER_MONTH),
+        _ => None,
+    }
+}
+
+/// Express effort in the pool's capacity unit. Effort without a unit is taken to be in that unit already.
+pub fn effort_in_pool_unit(effort: f64, effort_unit: Option<&str>, pool: &ResourcePool) -> f64 {
+    let Some(unit) = effort_unit else {
+        return effort;
+    };
+    match (effort_unit_days(unit), capacity_unit_days(&pool.capacity_unit, &pool.period_type)) {
+        (Some(from), Some(to)) => effort * from / to,
+        _ => effort,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffortProfile {
+    Flat,
+    FrontLoaded,
+    BackLoaded,
+    Bell,
+}
+
+impl EffortProfile {
+    pub const ALL: [EffortProfile; 4] = [
+        EffortProfile::Flat,
+        EffortProfile::FrontLoaded,
+        EffortProfile::BackLoaded,
+        EffortProfile::Bell,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EffortProfile::Flat => "Flat",
+            EffortProfile::FrontLoaded => "FrontLoaded",
+            EffortProfile::BackLoaded => "BackLoaded",
+            EffortProfile::Bell => "Bell",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name() == value)
+    }
+
+    /// Fraction of the total effort done by position x (0..=1) through the span.
+    /// Each curve is the integral of a weighting that sums to one, so shares of
+    /// adjacent windows always add back up to the whole.
+    pub fn cumulative(&self, x: f64) -> f64 {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            // weight 1
+            EffortProfile::Flat => x,
+            // weight 2(1 - x)
+            EffortProfile::FrontLoaded => 2.0 * x - x * x,
+            // weight 2x
+            EffortProfile::BackLoaded => x * x,
+            // weight 6x(1 - x)
+            EffortProfile::Bell => 3.0 * x * x - 2.0 * x * x * x,
+        }
+    }
+
+    /// Share of effort spread over `span` that falls within `window`
+    pub fn share(&self, span: &DateSpan, window: &DateSpan) -> f64 {
+        let start = span.start.max(window.start);
+        let end = span.end.min(window.end);
+        if start >= end {
+            return 0.0;
+        }
+        let days = span.days() as f64;
+        let from = (start - span.start).num_days() as f64 / days;
+        let to = (end - span.start).num_days() as f64 / days;
+        self.cumulative(to) - self.cumulative(from)
+    }
+}
+
+pub fn validate_effort_profile(profile: &str) -> Result<(), String> {
+    match EffortProfile::parse(profile) {
+        Some(_) => Ok(()),
+        None => {
+            let names: Vec<&str> = EffortProfile::ALL.iter().map(|p| p.name()).collect();
+            Err(format!("Unknown effort profile {}, expected one of {}", profile, names.join(", ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::dates::{generate_periods, parse_date};
+    use chrono::Duration;
+
+    fn span(start: &str, days: i64) -> DateSpan {
+        let start = parse_date(start).unwrap();
+        DateSpan { start, end: start + Duration::days(days) }
+    }
+
+    fn weekly(span: &DateSpan) -> Vec<DateSpan> {
+        let mut windows = Vec::new();
+        let mut current = span.start;
+        while current < span.end {
+            windows.push(DateSpan { start: current, end: current + Duration::days(7) });
+            current += Duration::days(7);
+        }
+        windows
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn every_profile_distributes_the_whole_estimate() {
+        let estimate = 137.5;
+        // 7 weeks from mid-month, a single day, a leap February, and a long odd span
+        let spans = [span("2025-03-12", 49), span("2025-06-30", 1), span("2024-02-10", 29), span("2025-01-17", 401)];
+
+        for profile in EffortProfile::ALL {
+            for s in &spans {
+                for windows in [generate_periods(s, "Month"), generate_periods(s, "Quarter"), weekly(s)] {
+                    let total: f64 = windows.iter().map(|w| estimate * profile.share(s, w)).sum();
+                    assert_close(total, estimate);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shares_follow_the_profile_shape() {
+        let s = span("2025-03-12", 49);
+        let weeks = weekly(&s);
+        let shares = |profile: EffortProfile| -> Vec<f64> { weeks.iter().map(|w| profile.share(&s, w)).collect() };
+
+        let flat = shares(EffortProfile::Flat);
+        assert!(flat.iter().all(|share| (share - 1.0 / 7.0).abs() < 1e-12));
+
+        let front = shares(EffortProfile::FrontLoaded);
+        assert!(front.windows(2).all(|pair| pair[0] > pair[1]));
+
+        let back = shares(EffortProfile::BackLoaded);
+        assert!(back.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let bell = shares(EffortProfile::Bell);
+        assert!(bell[3] > bell[0] && bell[3] > bell[6]);
+        assert_close(bell[0], bell[6]);
+    }
+
+    #[test]
+    fn windows_outside_the_span_get_nothing() {
+        let s = span("2025-03-12", 49);
+        let before = span("2025-01-01", 30);
+        for profile in EffortProfile::ALL {
+            assert_eq!(profile.share(&s, &before), 0.0);
+        }
+    }
+}
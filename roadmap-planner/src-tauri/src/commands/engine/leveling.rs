@@ -0,0 +1,293 @@
+// Leveling engine - proposes moving initiatives later to clear pool over-allocations
+// Suggestions only; nothing here changes the scenario
+
+use super::dates::{format_date, parse_date};
+use super::dependencies::{InitiativeDependency, dates_of, required_start};
+use super::resources::{OverAllocation, PoolPeriodAllocation, detect_overallocations};
+use crate::commands::kanban::priority_rank;
+use crate::db::Initiative;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+// Work already under way or finished stays where it is
+const MOVABLE_STATUSES: [&str; 2] = ["Proposed", "Planned"];
+
+// A move of more than a year is replanning, not leveling
+pub const MAX_SHIFT_DAYS: i64 = 365;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependentShift {
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub proposed_start_date: String,
+    pub proposed_end_date: String,
+    pub shift_days: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelingSuggestion {
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub priority: String,
+    // Dates before this suggestion, after any earlier suggestion has been applied
+    pub current_start_date: String,
+    pub current_end_date: String,
+    pub proposed_start_date: String,
+    pub proposed_end_date: String,
+    pub shift_days: i64,
+    // The over-allocation the move clears
+    pub resolves: OverAllocation,
+    // Successors that must move with it to keep their dependencies
+    pub dependents: Vec<DependentShift>,
+}
+
+fn is_movable(initiative: &Initiative) -> bool {
+    MOVABLE_STATUSES.contains(&initiative.status.as_str())
+}
+
+fn shift_dates(initiative: &mut Initiative, days: i64) {
+    let shift = |date: &Option<String>| {
+        date.as_deref().and_then(parse_date).map(|d| format_date(d + Duration::days(days)))
+    };
+    initiative.start_date = shift(&initiative.start_date);
+    initiative.end_date = shift(&initiative.end_date);
+}
+
+/// Move `id` later by `days`, then any successor the move pushes past its dependency, and so on.
+/// None if a successor that would have to move is under way or finished, or the links loop.
+pub fn shift_with_dependents(initiatives: &[Initiative], dependencies: &[InitiativeDependency], id: &str, days: i64) -> Option<Vec<Initiative>> {
+    let index: HashMap<&str, usize> = initiatives.iter().enumerate().map(|(n, i)| (i.id.as_str(), n)).collect();
+    let mut plan = initiatives.to_vec();
+    shift_dates(&mut plan[*index.get(id)?], days);
+
+    // Every move is strictly later, so only a cycle of positive lags can keep this going
+    let mut budget = initiatives.len() * (dependencies.len() + 1);
+    let mut queue = vec![id.to_string()];
+    while let Some(moved) = queue.pop() {
+        let predecessor = dates_of(&plan[index[moved.as_str()]])?;
+        for dependency in dependencies.iter().filter(|d| d.predecessor_id == moved) {
+            let Some(&n) = index.get(dependency.successor_id.as_str()) else {
+                continue;
+            };
+            let Some((start, end)) = dates_of(&plan[n]) else {
+                continue;
+            };
+            let earliest = required_start(&dependency.dependency_type, dependency.lag_days.unwrap_or(0), predecessor, end - start).date;
+            if start >= earliest {
+                continue;
+            }
+            if !is_movable(&plan[n]) || budget == 0 {
+                return None;
+            }
+            budget -= 1;
+            shift_dates(&mut plan[n], (earliest - start).num_days());
+            queue.push(plan[n].id.clone());
+        }
+    }
+
+    Some(plan)
+}
+
+// The pool or role over-allocations in a set of allocations, keyed by period and role
+fn over_allocated_keys(allocations: &[PoolPeriodAllocation], pool_id: &str) -> HashSet<(String, Option<String>)> {
+    detect_overallocations(allocations)
+        .into_iter()
+        .filter(|o| o.pool_id == pool_id && !o.is_blocker)
+        .map(|o| (o.period_start, o.role))
+        .collect()
+}
+
+fn key_of(conflict: &OverAllocation) -> (String, Option<String>) {
+    (conflict.period_start.clone(), conflict.role.clone())
+}
+
+// Lowest priority first, later-starting ones before earlier on a tie
+fn candidates<'a>(conflict: &OverAllocation, plan: &'a [Initiative]) -> Vec<&'a Initiative> {
+    let mut found: Vec<&Initiative> = plan
+        .iter()
+        .filter(|i| conflict.contributing_initiatives.iter().any(|c| c.id == i.id))
+        .filter(|i| is_movable(i) && dates_of(i).is_some())
+        .collect();
+    found.sort_by(|a, b| {
+        priority_rank(&b.priority)
+            .cmp(&priority_rank(&a.priority))
+            .then_with(|| b.start_date.cmp(&a.start_date))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    found
+}
+
+/// For each over-allocation, the smallest move later of its lowest-priority movable initiative
+/// that clears it without over-allocating another period of the same pool. `allocate` gives
+/// one pool's allocation for a rearranged plan. Suggestions build on each other in order;
+/// roles nobody in the pool has are left alone, as no move can clear them.
+pub fn suggest_leveling<F>(initiatives: &[Initiative], dependencies: &[InitiativeDependency], allocations: &[PoolPeriodAllocation], allocate: F) -> Vec<LevelingSuggestion>
+where
+    F: Fn(&[Initiative], &str) -> Vec<PoolPeriodAllocation>,
+{
+    let mut plan = initiatives.to_vec();
+    let mut suggestions = Vec::new();
+
+    for conflict in detect_overallocations(allocations).into_iter().filter(|o| !o.is_blocker) {
+        let before = over_allocated_keys(&allocate(&plan, &conflict.pool_id), &conflict.pool_id);
+        // An earlier suggestion may already have cleared it
+        if !before.contains(&key_of(&conflict)) {
+            continue;
+        }
+        let Some(period_end) = parse_date(&conflict.period_end) else {
+            continue;
+        };
+
+        let found = candidates(&conflict, &plan).into_iter().find_map(|candidate| {
+            let (start, end) = dates_of(candidate)?;
+            // Enough to take it clear of the period, which always removes its share there
+            let limit = ((period_end - start).num_days() + 1).min(MAX_SHIFT_DAYS);
+            for days in 1..=limit {
+                // Moving further only pushes successors further, so a refusal now is final
+                let moved = shift_with_dependents(&plan, dependencies, &candidate.id, days)?;
+                let after = over_allocated_keys(&allocate(&moved, &conflict.pool_id), &conflict.pool_id);
+                if !after.contains(&key_of(&conflict)) && after.is_subset(&before) {
+                    return Some((candidate.clone(), start, end, days, moved));
+                }
+            }
+            None
+        });
+        let Some((candidate, start, end, days, moved)) = found else {
+            continue;
+        };
+
+        let dependents = plan
+            .iter()
+            .zip(&moved)
+            .filter(|(old, new)| old.id != candidate.id && old.start_date != new.start_date)
+            .filter_map(|(old, new)| {
+                let (old_start, _) = dates_of(old)?;
+                let (new_start, new_end) = dates_of(new)?;
+                Some(DependentShift {
+                    initiative_id: new.id.clone(),
+                    initiative_name: new.name.clone(),
+                    proposed_start_date: format_date(new_start),
+                    proposed_end_date: format_date(new_end),
+                    shift_days: (new_start - old_start).num_days(),
+                })
+            })
+            .collect();
+
+        let shift = Duration::days(days);
+        suggestions.push(LevelingSuggestion {
+            initiative_id: candidate.id.clone(),
+            initiative_name: candidate.name.clone(),
+            priority: candidate.priority.clone(),
+            current_start_date: format_date(start),
+            current_end_date: format_date(end),
+            proposed_start_date: format_date(start + shift),
+            proposed_end_date: format_date(end + shift),
+            shift_days: days,
+            resolves: conflict,
+            dependents,
+        });
+        plan = moved;
+    }
+
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::test_initiative;
+    use crate::commands::engine::resources::{InitiativeResourceRequirement, calculate_resource_allocation};
+    use crate::db::ResourcePool;
+
+    fn initiative(id: &str, priority: &str, start: &str, end: &str) -> Initiative {
+        Initiative {
+            start_date: Some(start.to_string()),
+            end_date: Some(end.to_string()),
+            priority: priority.to_string(),
+            ..test_initiative(id)
+        }
+    }
+
+    fn requirement(initiative_id: &str, effort: f64) -> InitiativeResourceRequirement {
+        InitiativeResourceRequirement {
+            id: format!("req-{}", initiative_id),
+            initiative_id: initiative_id.to_string(),
+            resource_pool_id: "eng".to_string(),
+            effort_required: effort,
+            period_start: None,
+            period_end: None,
+            role: None,
+            approval_status: "Approved".to_string(),
+            approver_note: None,
+            generated: false,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn the_lower_priority_initiative_moves_and_takes_its_successor() {
+        let pools = vec![ResourcePool {
+            id: "eng".to_string(),
+            name: "Engineering".to_string(),
+            description: None,
+            capacity_per_period: Some(30.0),
+            capacity_unit: "PersonDays".to_string(),
+            period_type: "Month".to_string(),
+            colour: None,
+            created_at: None,
+            updated_at: None,
+        }];
+        // 20 days each in January against a capacity of 30
+        let initiatives = vec![
+            initiative("core", "Must", "2027-01-01", "2027-01-31"),
+            initiative("nice", "Could", "2027-01-01", "2027-01-31"),
+            initiative("after", "Could", "2027-02-01", "2027-02-28"),
+        ];
+        let requirements = vec![requirement("core", 20.0), requirement("nice", 20.0)];
+        let dependencies = vec![InitiativeDependency {
+            id: "dep".to_string(),
+            predecessor_id: "nice".to_string(),
+            successor_id: "after".to_string(),
+            dependency_type: "FinishToStart".to_string(),
+            lag_days: None,
+            created_at: None,
+        }];
+        let allocate = |plan: &[Initiative], _: &str| calculate_resource_allocation(plan, &requirements, &[], &pools, &[], &[]);
+
+        let suggestions = suggest_leveling(&initiatives, &dependencies, &allocate(&initiatives, "eng"), allocate);
+
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.initiative_id, "nice");
+        assert_eq!(suggestion.resolves.period_start, "2027-01-01");
+        // Moving "nice" into February must leave at most 10 of its 20 days in January
+        assert_eq!(suggestion.shift_days, 16);
+        assert_eq!((suggestion.proposed_start_date.as_str(), suggestion.proposed_end_date.as_str()), ("2027-01-17", "2027-02-16"));
+        assert_eq!(suggestion.dependents.len(), 1);
+        // Finish-to-start lets the successor start on the day its predecessor ends
+        assert_eq!(suggestion.dependents[0].proposed_start_date, "2027-02-16");
+    }
+
+    #[test]
+    fn a_successor_under_way_blocks_the_move() {
+        let mut started = initiative("after", "Could", "2027-02-01", "2027-02-28");
+        started.status = "InProgress".to_string();
+        let initiatives = vec![initiative("nice", "Could", "2027-01-01", "2027-01-31"), started];
+        let dependencies = vec![InitiativeDependency {
+            id: "dep".to_string(),
+            predecessor_id: "nice".to_string(),
+            successor_id: "after".to_string(),
+            dependency_type: "FinishToStart".to_string(),
+            lag_days: None,
+            created_at: None,
+        }];
+
+        // Ending on the day the successor starts still holds; a day later does not
+        assert!(shift_with_dependents(&initiatives, &dependencies, "nice", 1).is_some());
+        assert!(shift_with_dependents(&initiatives, &dependencies, "nice", 2).is_none());
+        // A move that still leaves room before the successor is fine
+        let dependencies = vec![InitiativeDependency { lag_days: Some(-10), ..dependencies[0].clone() }];
+        assert!(shift_with_dependents(&initiatives, &dependencies, "nice", 5).is_some());
+    }
+}
@@ -0,0 +1,49 @@
+// Calculation engine for Roadmap Planner commands
+// Pure functions ported from the frontend engines in src/lib
+
+pub mod assignments;
+pub mod budget;
+pub mod calendar;
+pub mod constraints;
+pub mod currency;
+pub mod dates;
+pub mod dependencies;
+pub mod effort;
+pub mod leveling;
+pub mod overrides;
+pub mod progress;
+pub mod resources;
+pub mod simulation;
+
+/// A planned, undated initiative in the baseline scenario, named after its id. Tests set the
+/// fields they care about and take the rest from here with `..test_initiative(id)`.
+#[cfg(test)]
+pub fn test_initiative(id: &str) -> crate::db::Initiative {
+    crate::db::Initiative {
+        id: id.to_string(),
+        name: id.to_string(),
+        description: None,
+        initiative_type: "New".to_string(),
+        status: "Planned".to_string(),
+        start_date: None,
+        end_date: None,
+        effort_estimate: None,
+        effort_uncertainty: None,
+        cost_estimate: None,
+        cost_uncertainty: None,
+        priority: "Should".to_string(),
+        scenario_id: "baseline".to_string(),
+        percent_complete: 0.0,
+        progress_from_milestones: false,
+        currency: None,
+        effort_unit: None,
+        effort_profile: "Flat".to_string(),
+        external_ref: None,
+        reference_code: None,
+        is_key_date: false,
+        colour: None,
+        icon: None,
+        created_at: None,
+        updated_at: None,
+    }
+}
@@ -0,0 +1,231 @@
+// Override engine - what-if values layered over a scenario's inputs
+// Applied to the loaded copies only, so the stored entities never change
+
+use super::dates::{format_date, parse_date};
+use super::resources::PoolPeriodAllocation;
+use crate::db::{FinancialPeriod, Initiative};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioOverride {
+    pub id: String,
+    pub scenario_id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub field: String,
+    pub override_value: String,
+    pub effective_from: Option<String>,
+    pub effective_to: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueKind {
+    // Zero or more
+    Amount,
+    Date,
+}
+
+struct OverrideField {
+    entity_type: &'static str,
+    field: &'static str,
+    kind: ValueKind,
+    // Whether the value can be limited to the periods between effective_from and effective_to
+    dated: bool,
+}
+
+const fn field(entity_type: &'static str, field: &'static str, kind: ValueKind, dated: bool) -> OverrideField {
+    OverrideField { entity_type, field, kind, dated }
+}
+
+// Every field a calculation reads overrides for
+const OVERRIDE_FIELDS: &[OverrideField] = &[
+    field("Initiative", "start_date", ValueKind::Date, false),
+    field("Initiative", "end_date", ValueKind::Date, false),
+    field("Initiative", "cost_estimate", ValueKind::Amount, false),
+    field("Initiative", "effort_estimate", ValueKind::Amount, false),
+    field("ResourcePool", "capacity_per_period", ValueKind::Amount, true),
+    field("FinancialPeriod", "budget_available", ValueKind::Amount, false),
+];
+
+/// "Initiative.start_date, Initiative.end_date, ..." for error messages
+pub fn supported_override_fields() -> Vec<String> {
+    OVERRIDE_FIELDS.iter().map(|f| format!("{}.{}", f.entity_type, f.field)).collect()
+}
+
+/// Check an override can be applied, returning the value in the form it's stored
+pub fn validate_override(entity_type: &str, field: &str, value: &str, effective_from: Option<&str>, effective_to: Option<&str>) -> Result<String, String> {
+    let Some(spec) = OVERRIDE_FIELDS.iter().find(|f| f.entity_type == entity_type && f.field == field) else {
+        return Err(format!(
+            "{}.{} can't be overridden; supported fields are {}",
+            entity_type,
+            field,
+            supported_override_fields().join(", ")
+        ));
+    };
+
+    let value = value.trim();
+    let stored = match spec.kind {
+        ValueKind::Amount => match value.parse::<f64>() {
+            Ok(amount) if amount.is_finite() && amount >= 0.0 => amount.to_string(),
+            _ => return Err(format!("{}.{} must be a number of zero or more, got \"{}\"", entity_type, field, value)),
+        },
+        ValueKind::Date => match parse_date(value) {
+            Some(date) => format_date(date),
+            None => return Err(format!("{}.{} must be a YYYY-MM-DD date, got \"{}\"", entity_type, field, value)),
+        },
+    };
+
+    if effective_from.is_some() || effective_to.is_some() {
+        if !spec.dated {
+            return Err(format!("{}.{} applies throughout, so it can't take effective dates", entity_type, field));
+        }
+        let from = effective_from.map(|d| parse_date(d).ok_or_else(|| format!("Invalid effective_from date {}", d))).transpose()?;
+        let to = effective_to.map(|d| parse_date(d).ok_or_else(|| format!("Invalid effective_to date {}", d))).transpose()?;
+        if let (Some(from), Some(to)) = (from, to) {
+            if to < from {
+                return Err("effective_to is before effective_from".to_string());
+            }
+        }
+    }
+
+    Ok(stored)
+}
+
+impl ScenarioOverride {
+    fn targets(&self, entity_type: &str, entity_id: &str, field: &str) -> bool {
+        self.entity_type == entity_type && self.entity_id == entity_id && self.field == field
+    }
+
+    /// True when a date falls inside the effective window, either end of which may be open
+    pub fn is_effective_on(&self, date: NaiveDate) -> bool {
+        let from = self.effective_from.as_deref().and_then(parse_date);
+        let to = self.effective_to.as_deref().and_then(parse_date);
+        from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to)
+    }
+
+    /// Whether two overrides of the same field would both apply somewhere
+    pub fn overlaps(&self, other: &ScenarioOverride) -> bool {
+        let starts = |o: &ScenarioOverride| o.effective_from.as_deref().and_then(parse_date).unwrap_or(NaiveDate::MIN);
+        let ends = |o: &ScenarioOverride| o.effective_to.as_deref().and_then(parse_date).unwrap_or(NaiveDate::MAX);
+        starts(self) <= ends(other) && starts(other) <= ends(self)
+    }
+}
+
+fn find<'a>(overrides: &'a [ScenarioOverride], entity_type: &str, entity_id: &str, field: &str) -> Option<&'a ScenarioOverride> {
+    overrides.iter().find(|o| o.targets(entity_type, entity_id, field))
+}
+
+fn amount(o: &ScenarioOverride) -> Option<f64> {
+    o.override_value.parse().ok()
+}
+
+/// Replace overridden dates, costs and effort on the scenario's initiatives
+pub fn apply_initiative_overrides(initiatives: &mut [Initiative], overrides: &[ScenarioOverride]) {
+    for initiative in initiatives {
+        let id = initiative.id.as_str();
+        if let Some(o) = find(overrides, "Initiative", id, "start_date") {
+            initiative.start_date = Some(o.override_value.clone());
+        }
+        if let Some(o) = find(overrides, "Initiative", id, "end_date") {
+            initiative.end_date = Some(o.override_value.clone());
+        }
+        if let Some(value) = find(overrides, "Initiative", id, "cost_estimate").and_then(amount) {
+            initiative.cost_estimate = Some(value);
+        }
+        if let Some(value) = find(overrides, "Initiative", id, "effort_estimate").and_then(amount) {
+            initiative.effort_estimate = Some(value);
+        }
+    }
+}
+
+/// Replace overridden budgets on financial periods
+pub fn apply_period_overrides(periods: &mut [FinancialPeriod], overrides: &[ScenarioOverride]) {
+    for period in periods {
+        if let Some(value) = find(overrides, "FinancialPeriod", &period.id, "budget_available").and_then(amount) {
+            period.budget_available = Some(value);
+        }
+    }
+}
+
+/// Replace pool capacity in the periods an override covers, judged by each period's start.
+/// Role capacities are left as they are.
+pub fn apply_capacity_overrides(allocations: &mut [PoolPeriodAllocation], overrides: &[ScenarioOverride]) {
+    for allocation in allocations {
+        let Some(start) = parse_date(&allocation.period_start) else {
+            continue;
+        };
+        let capacity = overrides
+            .iter()
+            .filter(|o| o.targets("ResourcePool", &allocation.pool_id, "capacity_per_period") && o.is_effective_on(start))
+            .find_map(amount);
+
+        if let Some(capacity) = capacity {
+            allocation.capacity = capacity;
+            allocation.utilisation = if capacity > 0.0 { allocation.demand / capacity * 100.0 } else { 0.0 };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capacity_override(value: &str, from: Option<&str>, to: Option<&str>) -> ScenarioOverride {
+        ScenarioOverride {
+            id: "o1".to_string(),
+            scenario_id: "what-if".to_string(),
+            entity_type: "ResourcePool".to_string(),
+            entity_id: "eng".to_string(),
+            field: "capacity_per_period".to_string(),
+            override_value: value.to_string(),
+            effective_from: from.map(str::to_string),
+            effective_to: to.map(str::to_string),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn allocation(period_start: &str, demand: f64) -> PoolPeriodAllocation {
+        PoolPeriodAllocation {
+            pool_id: "eng".to_string(),
+            pool_name: "Engineering".to_string(),
+            unit: "Days".to_string(),
+            period_start: period_start.to_string(),
+            period_end: period_start.to_string(),
+            demand,
+            capacity: 100.0,
+            utilisation: demand,
+            contributing_initiatives: Vec::new(),
+            roles: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn only_supported_fields_can_be_overridden() {
+        assert_eq!(validate_override("ResourcePool", "capacity_per_period", " 80 ", Some("2027-01-01"), None), Ok("80".to_string()));
+        assert_eq!(validate_override("Initiative", "end_date", "2027-06-30", None, None), Ok("2027-06-30".to_string()));
+
+        let unknown = validate_override("Initiative", "priority", "Must", None, None).unwrap_err();
+        assert!(unknown.contains("Initiative.cost_estimate"), "{}", unknown);
+        assert!(validate_override("Initiative", "cost_estimate", "-5", None, None).is_err());
+        assert!(validate_override("Initiative", "cost_estimate", "5", Some("2027-01-01"), None).is_err());
+        assert!(validate_override("ResourcePool", "capacity_per_period", "80", Some("2027-02-01"), Some("2027-01-01")).is_err());
+    }
+
+    #[test]
+    fn capacity_overrides_apply_within_their_window() {
+        let overrides = [capacity_override("80", Some("2027-01-01"), Some("2027-12-31"))];
+        let mut allocations = [allocation("2026-12-01", 90.0), allocation("2027-01-01", 90.0)];
+
+        apply_capacity_overrides(&mut allocations, &overrides);
+        assert_eq!(allocations[0].capacity, 100.0);
+        assert_eq!(allocations[1].capacity, 80.0);
+        assert!(allocations[1].is_over_allocated());
+
+        assert!(overrides[0].overlaps(&capacity_override("70", None, Some("2027-01-01"))));
+        assert!(!overrides[0].overlaps(&capacity_override("70", Some("2028-01-01"), None)));
+    }
+}
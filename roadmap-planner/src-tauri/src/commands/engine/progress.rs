@@ -0,0 +1,35 @@
+// Progress engine - earned-value style comparison of planned vs reported progress
+// Planned progress assumes linear delivery between start and end dates
+
+use super::dates::DateSpan;
+use crate::db::Initiative;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeProgress {
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub planned_percent: f64,
+    pub reported_percent: f64,
+    // reported - planned; negative means behind
+    pub variance: f64,
+}
+
+/// Share of the initiative's duration elapsed by the given date, as a percentage
+pub fn planned_percent(initiative: &Initiative, as_of: NaiveDate) -> Option<f64> {
+    let span = DateSpan::parse_inclusive(initiative.start_date.as_deref(), initiative.end_date.as_deref())?;
+    let elapsed = (as_of - span.start).num_days() + 1;
+    Some((elapsed as f64 / span.days() as f64 * 100.0).clamp(0.0, 100.0))
+}
+
+pub fn initiative_progress(initiative: &Initiative, as_of: NaiveDate) -> Option<InitiativeProgress> {
+    let planned = planned_percent(initiative, as_of)?;
+    Some(InitiativeProgress {
+        initiative_id: initiative.id.clone(),
+        initiative_name: initiative.name.clone(),
+        planned_percent: planned,
+        reported_percent: initiative.percent_complete,
+        variance: initiative.percent_complete - planned,
+    })
+}
@@ -0,0 +1,514 @@
+// Resource engine - calculates pool demand, utilisation and over-allocation
+// Port of src/lib/resourceEngine.ts
+
+use super::dates::{DateSpan, bounding_span, format_date, generate_periods, parse_date};
+use super::effort::{EffortProfile, capacity_unit_days, effort_in_pool_unit};
+use crate::db::{Initiative, Resource, ResourcePool};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeResourceRequirement {
+    pub id: String,
+    pub initiative_id: String,
+    pub resource_pool_id: String,
+    pub effort_required: f64,
+    pub period_start: Option<String>,
+    pub period_end: Option<String>,
+    // The role the effort needs; None draws on the pool as a whole
+    pub role: Option<String>,
+    // Requested, Approved or Rejected by the pool's manager
+    pub approval_status: String,
+    pub approver_note: Option<String>,
+    // Written by auto_allocate_initiative from capability default pools rather than by hand
+    pub generated: bool,
+    pub created_at: Option<String>,
+}
+
+pub const APPROVAL_STATUSES: [&str; 3] = ["Requested", "Approved", "Rejected"];
+
+impl InitiativeResourceRequirement {
+    pub fn is_approved(&self) -> bool {
+        self.approval_status == "Approved"
+    }
+}
+
+// A role's capacity within a pool, per period in the pool's capacity unit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolRoleCapacity {
+    pub id: String,
+    pub resource_pool_id: String,
+    pub role: String,
+    pub capacity_per_period: f64,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+// Share of an initiative's effort estimate drawn from a pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSplit {
+    pub id: String,
+    pub initiative_id: String,
+    pub resource_pool_id: String,
+    pub percentage: f64,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContributingInitiative {
+    pub id: String,
+    pub name: String,
+    pub effort: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolPeriodAllocation {
+    pub pool_id: String,
+    pub pool_name: String,
+    // The pool's capacity unit, which demand is converted into
+    pub unit: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub demand: f64,
+    pub capacity: f64,
+    // demand / capacity as a percentage
+    pub utilisation: f64,
+    pub contributing_initiatives: Vec<ContributingInitiative>,
+    // Per role when the pool has roles or the demand names them; unroled demand is pool-wide only
+    pub roles: Vec<RolePeriodAllocation>,
+}
+
+impl PoolPeriodAllocation {
+    pub fn is_over_allocated(&self) -> bool {
+        self.capacity > 0.0 && self.demand > self.capacity
+    }
+
+    pub fn over_allocation(&self) -> f64 {
+        (self.demand - self.capacity).max(0.0)
+    }
+
+    pub fn has_role_shortfall(&self) -> bool {
+        self.roles.iter().any(|r| r.is_blocked() || r.is_over_allocated())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolePeriodAllocation {
+    pub role: String,
+    pub demand: f64,
+    pub capacity: f64,
+    pub utilisation: f64,
+    pub contributing_initiatives: Vec<ContributingInitiative>,
+}
+
+impl RolePeriodAllocation {
+    // Nobody in the pool has the role, so the work can't be done however it is scheduled
+    pub fn is_blocked(&self) -> bool {
+        self.capacity <= 0.0 && self.demand > 0.0
+    }
+
+    pub fn is_over_allocated(&self) -> bool {
+        self.capacity > 0.0 && self.demand > self.capacity
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverAllocation {
+    pub pool_id: String,
+    pub pool_name: String,
+    // None for the pool as a whole
+    pub role: Option<String>,
+    pub unit: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub demand: f64,
+    pub capacity: f64,
+    pub excess: f64,
+    // Demand for a role with no capacity at all
+    pub is_blocker: bool,
+    pub contributing_initiatives: Vec<ContributingInitiative>,
+}
+
+fn add_contribution(contributing: &mut Vec<ContributingInitiative>, initiative: &Initiative, effort: f64) {
+    match contributing.iter_mut().find(|c| c.id == initiative.id) {
+        Some(existing) => existing.effort += effort,
+        None => contributing.push(ContributingInitiative {
+            id: initiative.id.clone(),
+            name: initiative.name.clone(),
+            effort,
+        }),
+    }
+}
+
+fn role_entry<'a>(roles: &'a mut Vec<RolePeriodAllocation>, role: &str) -> &'a mut RolePeriodAllocation {
+    let index = match roles.iter().position(|r| r.role.eq_ignore_ascii_case(role)) {
+        Some(index) => index,
+        None => {
+            roles.push(RolePeriodAllocation {
+                role: role.to_string(),
+                demand: 0.0,
+                capacity: 0.0,
+                utilisation: 0.0,
+                contributing_initiatives: Vec::new(),
+            });
+            roles.len() - 1
+        }
+    };
+    &mut roles[index]
+}
+
+/// One full-time member's capacity in the pool's unit for one of its periods
+fn member_capacity(pool: &ResourcePool) -> f64 {
+    match (capacity_unit_days("FTE", &pool.period_type), capacity_unit_days(&pool.capacity_unit, &pool.period_type)) {
+        (Some(fte_days), Some(unit_days)) if unit_days > 0.0 => fte_days / unit_days,
+        _ => 1.0,
+    }
+}
+
+/// Capacity per role in one period: the pool's own role capacities when it has any, otherwise
+/// its members' availability by role, pro-rated for members who join or leave mid-period
+pub fn role_capacity(pool: &ResourcePool, role_capacities: &[PoolRoleCapacity], members: &[Resource], period: &DateSpan) -> Vec<RolePeriodAllocation> {
+    let mut roles = Vec::new();
+
+    let manual: Vec<&PoolRoleCapacity> = role_capacities.iter().filter(|r| r.resource_pool_id == pool.id).collect();
+    if !manual.is_empty() {
+        for row in manual {
+            role_entry(&mut roles, row.role.trim()).capacity += row.capacity_per_period;
+        }
+        return roles;
+    }
+
+    let per_member = member_capacity(pool);
+    for member in members.iter().filter(|m| m.resource_pool_id.as_deref() == Some(pool.id.as_str())) {
+        let Some(role) = member.role.as_deref().map(str::trim).filter(|r| !r.is_empty()) else {
+            continue;
+        };
+        let start = member.start_date.as_deref().and_then(parse_date).unwrap_or(period.start).max(period.start);
+        // Stored end dates are the last day worked
+        let end = member.end_date.as_deref().and_then(parse_date).map(|d| d + Duration::days(1)).unwrap_or(period.end).min(period.end);
+        if start >= end {
+            continue;
+        }
+        let present = (end - start).num_days() as f64 / period.days() as f64;
+        role_entry(&mut roles, role).capacity += member.availability.unwrap_or(1.0) * per_member * present;
+    }
+    roles
+}
+
+/// Pool and role shortfalls, with roles nobody in the pool can cover listed first
+pub fn detect_overallocations(allocations: &[PoolPeriodAllocation]) -> Vec<OverAllocation> {
+    let mut found = Vec::new();
+
+    for allocation in allocations {
+        let entry = |role: Option<&str>, demand: f64, capacity: f64, is_blocker: bool, contributing: &[ContributingInitiative]| OverAllocation {
+            pool_id: allocation.pool_id.clone(),
+            pool_name: allocation.pool_name.clone(),
+            role: role.map(|r| r.to_string()),
+            unit: allocation.unit.clone(),
+            period_start: allocation.period_start.clone(),
+            period_end: allocation.period_end.clone(),
+            demand,
+            capacity,
+            excess: demand - capacity,
+            is_blocker,
+            contributing_initiatives: contributing.to_vec(),
+        };
+
+        if allocation.is_over_allocated() {
+            found.push(entry(None, allocation.demand, allocation.capacity, false, &allocation.contributing_initiatives));
+        }
+        for role in allocation.roles.iter().filter(|r| r.is_blocked() || r.is_over_allocated()) {
+            found.push(entry(Some(&role.role), role.demand, role.capacity, role.is_blocked(), &role.contributing_initiatives));
+        }
+    }
+
+    found.sort_by(|a, b| b.is_blocker.cmp(&a.is_blocker).then_with(|| b.excess.total_cmp(&a.excess)));
+    found
+}
+
+/// The span a requirement's effort is spread over: its own window, else the initiative's dates
+pub fn requirement_span(requirement: &InitiativeResourceRequirement, initiative: &Initiative) -> Option<DateSpan> {
+    DateSpan::parse_inclusive(requirement.period_start.as_deref(), requirement.period_end.as_deref())
+        .or_else(|| DateSpan::parse_inclusive(initiative.start_date.as_deref(), initiative.end_date.as_deref()))
+}
+
+/// Merge overlapping windows into a sorted, disjoint list
+fn merge_spans(mut spans: Vec<DateSpan>) -> Vec<DateSpan> {
+    spans.sort_by_key(|s| s.start);
+    let mut merged: Vec<DateSpan> = Vec::new();
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+/// Calculate demand against capacity for every pool, in each pool's own period type and unit.
+/// A pool split adds demand from the initiative's effort estimate, except where an explicit
+/// requirement for the same initiative and pool covers the period. Requirements naming a role
+/// are also counted against that role's capacity.
+pub fn calculate_resource_allocation(
+    initiatives: &[Initiative],
+    requirements: &[InitiativeResourceRequirement],
+    splits: &[PoolSplit],
+    pools: &[ResourcePool],
+    role_capacities: &[PoolRoleCapacity],
+    members: &[Resource],
+) -> Vec<PoolPeriodAllocation> {
+    let by_id: HashMap<&str, &Initiative> = initiatives.iter().map(|i| (i.id.as_str(), i)).collect();
+
+    // Requirements paired with their initiative and the span their effort covers
+    let spread: Vec<(&InitiativeResourceRequirement, &Initiative, DateSpan)> = requirements
+        .iter()
+        .filter_map(|r| {
+            let initiative = by_id.get(r.initiative_id.as_str())?;
+            let span = requirement_span(r, initiative)?;
+            Some((r, *initiative, span))
+        })
+        .collect();
+
+    // Splits paired with their initiative, its span, and the windows explicit requirements claim
+    let split_spread: Vec<(&PoolSplit, &Initiative, DateSpan, Vec<DateSpan>)> = splits
+        .iter()
+        .filter_map(|split| {
+            let initiative = by_id.get(split.initiative_id.as_str())?;
+            let span = DateSpan::parse_inclusive(initiative.start_date.as_deref(), initiative.end_date.as_deref())?;
+            let explicit = spread
+                .iter()
+                .filter(|(r, _, _)| r.initiative_id == split.initiative_id && r.resource_pool_id == split.resource_pool_id)
+                .map(|(_, _, window)| *window)
+                .collect();
+            Some((split, *initiative, span, merge_spans(explicit)))
+        })
+        .collect();
+
+    let Some(overall) = bounding_span(initiatives.iter().map(|i| (i.start_date.as_deref(), i.end_date.as_deref()))) else {
+        return Vec::new();
+    };
+
+    let mut allocations = Vec::new();
+
+    for pool in pools {
+        for period in generate_periods(&overall, &pool.period_type) {
+            let mut contributing = Vec::new();
+            let mut roles = role_capacity(pool, role_capacities, members, &period);
+
+            for (requirement, initiative, span) in spread.iter().filter(|(r, _, _)| r.resource_pool_id == pool.id) {
+                if span.overlap_days(&period) > 0 {
+                    // Distribute effort across the requirement's span following the initiative's profile
+                    let profile = EffortProfile::parse(&initiative.effort_profile).unwrap_or(EffortProfile::Flat);
+                    let required = effort_in_pool_unit(requirement.effort_required, initiative.effort_unit.as_deref(), pool);
+                    let effort = required * profile.share(span, &period);
+                    contributing.push(ContributingInitiative {
+                        id: initiative.id.clone(),
+                        name: initiative.name.clone(),
+                        effort,
+                    });
+
+                    if let Some(role) = requirement.role.as_deref().map(str::trim).filter(|r| !r.is_empty()) {
+                        let entry = role_entry(&mut roles, role);
+                        entry.demand += effort;
+                        add_contribution(&mut entry.contributing_initiatives, initiative, effort);
+                    }
+                }
+            }
+
+            for (split, initiative, span, explicit) in split_spread.iter().filter(|(s, _, _, _)| s.resource_pool_id == pool.id) {
+                let Some(estimate) = initiative.effort_estimate.filter(|e| *e > 0.0) else {
+                    continue;
+                };
+                let profile = EffortProfile::parse(&initiative.effort_profile).unwrap_or(EffortProfile::Flat);
+                let covered: f64 = explicit
+                    .iter()
+                    .filter_map(|window| window.intersect(&period))
+                    .map(|overlap| profile.share(span, &overlap))
+                    .sum();
+                let share = profile.share(span, &period) - covered;
+                if share <= 0.0 {
+                    continue;
+                }
+
+                let required = effort_in_pool_unit(estimate * split.percentage / 100.0, initiative.effort_unit.as_deref(), pool);
+                // One entry per initiative, whether its demand is explicit, split, or both
+                add_contribution(&mut contributing, initiative, required * share);
+            }
+
+            let demand: f64 = contributing.iter().map(|c| c.effort).sum();
+            let capacity = pool.capacity_per_period.unwrap_or(0.0);
+            let utilisation = if capacity > 0.0 { demand / capacity * 100.0 } else { 0.0 };
+            contributing.sort_by(|a, b| b.effort.total_cmp(&a.effort));
+            for role in &mut roles {
+                role.utilisation = if role.capacity > 0.0 { role.demand / role.capacity * 100.0 } else { 0.0 };
+                role.contributing_initiatives.sort_by(|a, b| b.effort.total_cmp(&a.effort));
+            }
+            roles.sort_by(|a, b| a.role.cmp(&b.role));
+
+            allocations.push(PoolPeriodAllocation {
+                pool_id: pool.id.clone(),
+                pool_name: pool.name.clone(),
+                unit: pool.capacity_unit.clone(),
+                period_start: format_date(period.start),
+                period_end: format_date(period.last_day()),
+                demand,
+                capacity,
+                utilisation,
+                contributing_initiatives: contributing,
+                roles,
+            });
+        }
+    }
+
+    allocations
+}
+
+/// Find periods where demand exceeds capacity, for the pool or any of its roles
+pub fn find_over_allocations(allocations: &[PoolPeriodAllocation]) -> Vec<PoolPeriodAllocation> {
+    allocations.iter().filter(|a| a.is_over_allocated() || a.has_role_shortfall()).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::test_initiative;
+
+    fn initiative() -> Initiative {
+        Initiative {
+            name: "Platform rebuild".to_string(),
+            // 31 + 28 + 31 days
+            start_date: Some("2025-01-01".to_string()),
+            end_date: Some("2025-03-31".to_string()),
+            effort_estimate: Some(90.0),
+            priority: "Must".to_string(),
+            ..test_initiative("init")
+        }
+    }
+
+    fn pool(id: &str) -> ResourcePool {
+        ResourcePool {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            capacity_per_period: Some(100.0),
+            capacity_unit: "PersonDays".to_string(),
+            period_type: "Month".to_string(),
+            colour: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn split(pool_id: &str, percentage: f64) -> PoolSplit {
+        PoolSplit {
+            id: format!("split-{}", pool_id),
+            initiative_id: "init".to_string(),
+            resource_pool_id: pool_id.to_string(),
+            percentage,
+            created_at: None,
+        }
+    }
+
+    fn demand(allocations: &[PoolPeriodAllocation], pool_id: &str) -> Vec<f64> {
+        allocations.iter().filter(|a| a.pool_id == pool_id).map(|a| a.demand).collect()
+    }
+
+    fn assert_all_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "expected {:?}, got {:?}", expected, actual);
+        }
+    }
+
+    #[test]
+    fn explicit_requirements_win_over_the_split_where_they_overlap() {
+        let initiatives = vec![initiative()];
+        let pools = vec![pool("eng"), pool("change")];
+        let splits = vec![split("eng", 50.0), split("change", 50.0)];
+        // Engineering is planned explicitly for February only
+        let requirements = vec![InitiativeResourceRequirement {
+            id: "req".to_string(),
+            initiative_id: "init".to_string(),
+            resource_pool_id: "eng".to_string(),
+            effort_required: 10.0,
+            period_start: Some("2025-02-01".to_string()),
+            period_end: Some("2025-02-28".to_string()),
+            role: None,
+            approval_status: "Approved".to_string(),
+            approver_note: None,
+            generated: false,
+            created_at: None,
+        }];
+
+        let allocations = calculate_resource_allocation(&initiatives, &requirements, &splits, &pools, &[], &[]);
+
+        // 45 days of split effort spread flat over 90 days, with February replaced by the explicit 10
+        assert_all_close(&demand(&allocations, "eng"), &[15.5, 10.0, 15.5]);
+        assert_all_close(&demand(&allocations, "change"), &[15.5, 14.0, 15.5]);
+        assert!(allocations.iter().all(|a| a.contributing_initiatives.len() == 1));
+    }
+
+    #[test]
+    fn splits_without_an_estimate_add_no_demand() {
+        let mut unestimated = initiative();
+        unestimated.effort_estimate = None;
+
+        let allocations = calculate_resource_allocation(&[unestimated], &[], &[split("eng", 100.0)], &[pool("eng")], &[], &[]);
+        assert_all_close(&demand(&allocations, "eng"), &[0.0, 0.0, 0.0]);
+    }
+
+    fn member(id: &str, role: &str, start_date: Option<&str>) -> Resource {
+        Resource {
+            id: id.to_string(),
+            name: id.to_string(),
+            role: Some(role.to_string()),
+            skills: None,
+            availability: Some(1.0),
+            resource_pool_id: Some("eng".to_string()),
+            start_date: start_date.map(|d| d.to_string()),
+            end_date: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn role_demand_is_checked_against_member_roles() {
+        let initiatives = vec![initiative()];
+        let mut eng = pool("eng");
+        eng.capacity_unit = "FTE".to_string();
+        // Two engineers, one of whom joins halfway through February
+        let members = vec![member("a", "Engineer", None), member("b", "Engineer", Some("2025-02-15"))];
+        let requirement = |id: &str, role: &str| InitiativeResourceRequirement {
+            id: id.to_string(),
+            initiative_id: "init".to_string(),
+            resource_pool_id: "eng".to_string(),
+            effort_required: 1.5,
+            period_start: Some("2025-02-01".to_string()),
+            period_end: Some("2025-02-28".to_string()),
+            role: Some(role.to_string()),
+            approval_status: "Approved".to_string(),
+            approver_note: None,
+            generated: false,
+            created_at: None,
+        };
+        let requirements = vec![requirement("build", "engineer"), requirement("design", "Architect")];
+
+        let allocations = calculate_resource_allocation(&initiatives, &requirements, &[], &[eng], &[], &members);
+        let february = &allocations[1];
+        assert_eq!(february.roles.len(), 2);
+
+        let architect = &february.roles[0];
+        assert!(architect.is_blocked());
+
+        let engineer = &february.roles[1];
+        assert_all_close(&[engineer.capacity], &[1.5]);
+        assert!(!engineer.is_over_allocated());
+
+        let found = detect_overallocations(&allocations);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].role.as_deref(), Some("Architect"));
+        assert!(found[0].is_blocker);
+    }
+}
@@ -0,0 +1,291 @@
+// Simulation engine - seeded sampling for Monte Carlo runs
+// Schedule simulation samples durations and pushes them through the dependency graph
+
+use super::constraints::InitiativeConstraintLink;
+use super::dates::{format_date, parse_date};
+use super::dependencies::{InitiativeDependency, required_start};
+use crate::db::{Constraint, Initiative};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+pub const DEFAULT_ITERATIONS: u32 = 1000;
+pub const MAX_ITERATIONS: u32 = 100_000;
+
+/// SplitMix64, so a seed reproduces the same run on every platform and release
+pub struct SimulationRng {
+    state: u64,
+}
+
+impl SimulationRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Triangular distribution by inverse transform
+    pub fn triangular(&mut self, min: f64, mode: f64, max: f64) -> f64 {
+        if max <= min {
+            return mode;
+        }
+        let u = self.next_f64();
+        let split = (mode - min) / (max - min);
+        if u < split {
+            min + (u * (max - min) * (mode - min)).sqrt()
+        } else {
+            max - ((1.0 - u) * (max - min) * (max - mode)).sqrt()
+        }
+    }
+}
+
+/// A seed for callers that don't ask for a reproducible run
+pub fn seed_from_clock() -> u64 {
+    chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64
+}
+
+/// (optimistic, most likely, pessimistic) multipliers on a planned value for an uncertainty level.
+/// Without a recorded uncertainty the planned value is used as is.
+pub fn uncertainty_range(uncertainty: Option<&str>) -> (f64, f64, f64) {
+    match uncertainty {
+        Some("Low") => (0.9, 1.0, 1.2),
+        Some("Medium") => (0.8, 1.0, 1.5),
+        Some("High") => (0.7, 1.0, 2.0),
+        _ => (1.0, 1.0, 1.0),
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice
+pub fn percentile<T: Copy>(sorted: &[T], p: f64) -> Option<T> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadlineOdds {
+    pub constraint_id: String,
+    pub constraint_name: String,
+    pub deadline: String,
+    pub hardness: String,
+    // Share of iterations finishing on or before the deadline
+    pub probability_met: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyDateForecast {
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub planned_end_date: String,
+    pub p10_end_date: String,
+    pub p50_end_date: String,
+    pub p80_end_date: String,
+    pub p90_end_date: String,
+    // Deadline constraints linked to the initiative
+    pub deadlines: Vec<DeadlineOdds>,
+    pub probability_all_deadlines_met: f64,
+}
+
+struct Planned<'a> {
+    initiative: &'a Initiative,
+    start: NaiveDate,
+    duration_days: i64,
+}
+
+// Predecessors before successors; initiatives caught in a cycle follow in input order
+fn schedule_order(planned: &[Planned], dependencies: &[InitiativeDependency]) -> Vec<usize> {
+    let index: HashMap<&str, usize> = planned.iter().enumerate().map(|(i, p)| (p.initiative.id.as_str(), i)).collect();
+    let mut incoming = vec![0usize; planned.len()];
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); planned.len()];
+
+    for dep in dependencies {
+        if let (Some(&p), Some(&s)) = (index.get(dep.predecessor_id.as_str()), index.get(dep.successor_id.as_str())) {
+            successors[p].push(s);
+            incoming[s] += 1;
+        }
+    }
+
+    let mut queue: VecDeque<usize> = (0..planned.len()).filter(|i| incoming[*i] == 0).collect();
+    let mut order = Vec::with_capacity(planned.len());
+    let mut placed = vec![false; planned.len()];
+
+    while let Some(i) = queue.pop_front() {
+        order.push(i);
+        placed[i] = true;
+        for &s in &successors[i] {
+            incoming[s] -= 1;
+            if incoming[s] == 0 {
+                queue.push_back(s);
+            }
+        }
+    }
+
+    order.extend((0..planned.len()).filter(|i| !placed[*i]));
+    order
+}
+
+/// Sample finish dates for key-date initiatives over many runs of the schedule.
+/// Each run draws every dated initiative's duration from its effort uncertainty, then
+/// starts it at its planned start or as soon as its predecessors allow, whichever is later.
+/// Returns the forecasts and the ids of key-date initiatives that have no dates.
+pub fn simulate_schedule(
+    initiatives: &[Initiative],
+    dependencies: &[InitiativeDependency],
+    constraints: &[Constraint],
+    links: &[InitiativeConstraintLink],
+    iterations: u32,
+    seed: u64,
+) -> (Vec<KeyDateForecast>, Vec<String>) {
+    let planned: Vec<Planned> = initiatives
+        .iter()
+        .filter_map(|initiative| {
+            let start = parse_date(initiative.start_date.as_deref()?)?;
+            let end = parse_date(initiative.end_date.as_deref()?)?;
+            Some(Planned { initiative, start, duration_days: (end - start).num_days().max(0) })
+        })
+        .collect();
+
+    let undated: Vec<String> = initiatives
+        .iter()
+        .filter(|i| i.is_key_date && !planned.iter().any(|p| p.initiative.id == i.id))
+        .map(|i| i.id.clone())
+        .collect();
+
+    let key: Vec<usize> = (0..planned.len()).filter(|i| planned[*i].initiative.is_key_date).collect();
+    if key.is_empty() {
+        return (Vec::new(), undated);
+    }
+
+    let order = schedule_order(&planned, dependencies);
+    let index: HashMap<&str, usize> = planned.iter().enumerate().map(|(i, p)| (p.initiative.id.as_str(), i)).collect();
+    let mut predecessors: Vec<Vec<(usize, &InitiativeDependency)>> = vec![Vec::new(); planned.len()];
+    for dep in dependencies {
+        if let (Some(&p), Some(&s)) = (index.get(dep.predecessor_id.as_str()), index.get(dep.successor_id.as_str())) {
+            predecessors[s].push((p, dep));
+        }
+    }
+
+    let mut rng = SimulationRng::new(seed);
+    let mut finishes: Vec<Vec<NaiveDate>> = vec![Vec::with_capacity(iterations as usize); key.len()];
+    let mut dates: Vec<Option<(NaiveDate, NaiveDate)>> = vec![None; planned.len()];
+
+    for _ in 0..iterations {
+        dates.iter_mut().for_each(|d| *d = None);
+
+        for &i in &order {
+            let item = &planned[i];
+            let (low, mode, high) = uncertainty_range(item.initiative.effort_uncertainty.as_deref());
+            let duration = Duration::days((item.duration_days as f64 * rng.triangular(low, mode, high)).round() as i64);
+
+            // Predecessors still unscheduled in this run (a cycle) don't constrain the start
+            let start = predecessors[i]
+                .iter()
+                .filter_map(|(p, dep)| {
+                    let predecessor = dates[*p]?;
+                    Some(required_start(&dep.dependency_type, dep.lag_days.unwrap_or(0), predecessor, duration).date)
+                })
+                .fold(item.start, NaiveDate::max);
+
+            dates[i] = Some((start, start + duration));
+        }
+
+        for (slot, &i) in key.iter().enumerate() {
+            if let Some((_, end)) = dates[i] {
+                finishes[slot].push(end);
+            }
+        }
+    }
+
+    let forecasts = key
+        .iter()
+        .zip(finishes.iter_mut())
+        .map(|(&i, runs)| {
+            runs.sort_unstable();
+            let item = &planned[i];
+            let at = |p: f64| percentile(runs, p).map(format_date).unwrap_or_default();
+            let met_share = |deadline: NaiveDate| runs.iter().filter(|d| **d <= deadline).count() as f64 / runs.len().max(1) as f64;
+
+            let linked: Vec<(&Constraint, NaiveDate)> = links
+                .iter()
+                .filter(|l| l.initiative_id == item.initiative.id)
+                .filter_map(|l| constraints.iter().find(|c| c.id == l.constraint_id))
+                .filter(|c| c.constraint_type == "Deadline")
+                .filter_map(|c| Some((c, parse_date(c.effective_date.as_deref()?)?)))
+                .collect();
+
+            // Every deadline is met exactly when the earliest one is
+            let probability_all_deadlines_met = linked.iter().map(|(_, d)| *d).min().map(met_share).unwrap_or(1.0);
+
+            KeyDateForecast {
+                initiative_id: item.initiative.id.clone(),
+                initiative_name: item.initiative.name.clone(),
+                planned_end_date: format_date(item.start + Duration::days(item.duration_days)),
+                p10_end_date: at(10.0),
+                p50_end_date: at(50.0),
+                p80_end_date: at(80.0),
+                p90_end_date: at(90.0),
+                deadlines: linked
+                    .iter()
+                    .map(|(c, deadline)| DeadlineOdds {
+                        constraint_id: c.id.clone(),
+                        constraint_name: c.name.clone(),
+                        deadline: format_date(*deadline),
+                        hardness: c.hardness.clone(),
+                        probability_met: met_share(*deadline),
+                    })
+                    .collect(),
+                probability_all_deadlines_met,
+            }
+        })
+        .collect();
+
+    (forecasts, undated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_the_same_draws() {
+        let mut a = SimulationRng::new(42);
+        let mut b = SimulationRng::new(42);
+        let draws_a: Vec<f64> = (0..5).map(|_| a.next_f64()).collect();
+        let draws_b: Vec<f64> = (0..5).map(|_| b.next_f64()).collect();
+        assert_eq!(draws_a, draws_b);
+        assert!(draws_a.iter().all(|d| (0.0..1.0).contains(d)));
+    }
+
+    #[test]
+    fn triangular_draws_stay_within_bounds() {
+        let mut rng = SimulationRng::new(7);
+        for _ in 0..1000 {
+            let x = rng.triangular(0.8, 1.0, 1.5);
+            assert!((0.8..=1.5).contains(&x));
+        }
+        assert_eq!(rng.triangular(1.0, 1.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let values: Vec<i32> = (1..=10).collect();
+        assert_eq!(percentile(&values, 10.0), Some(1));
+        assert_eq!(percentile(&values, 50.0), Some(5));
+        assert_eq!(percentile(&values, 80.0), Some(8));
+        assert_eq!(percentile(&values, 100.0), Some(10));
+        assert_eq!(percentile::<i32>(&[], 50.0), None);
+    }
+}
@@ -0,0 +1,54 @@
+// Entity types shared by commands that operate on any table
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EntityType {
+    Capability,
+    System,
+    Initiative,
+    Scenario,
+    ResourcePool,
+    Resource,
+    Constraint,
+    FinancialPeriod,
+}
+
+impl EntityType {
+    pub const ALL: [EntityType; 8] = [
+        EntityType::Capability,
+        EntityType::System,
+        EntityType::Initiative,
+        EntityType::Scenario,
+        EntityType::ResourcePool,
+        EntityType::Resource,
+        EntityType::Constraint,
+        EntityType::FinancialPeriod,
+    ];
+
+    pub fn table(&self) -> &'static str {
+        match self {
+            EntityType::Capability => "capabilities",
+            EntityType::System => "systems",
+            EntityType::Initiative => "initiatives",
+            EntityType::Scenario => "scenarios",
+            EntityType::ResourcePool => "resource_pools",
+            EntityType::Resource => "resources",
+            EntityType::Constraint => "constraints",
+            EntityType::FinancialPeriod => "financial_periods",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EntityType::Capability => "Capability",
+            EntityType::System => "System",
+            EntityType::Initiative => "Initiative",
+            EntityType::Scenario => "Scenario",
+            EntityType::ResourcePool => "ResourcePool",
+            EntityType::Resource => "Resource",
+            EntityType::Constraint => "Constraint",
+            EntityType::FinancialPeriod => "FinancialPeriod",
+        }
+    }
+}
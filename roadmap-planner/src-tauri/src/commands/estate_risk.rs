@@ -0,0 +1,228 @@
+// Tauri commands for the estate risk profile
+// Support exposure of every live system over time, weighted by criticality, with the baseline's replacements applied
+
+use crate::commands::engine::dates::{DateSpan, FINANCIAL_PERIOD_TYPES, format_date, generate_periods, normalise_period_type, parse_date, period_label};
+use crate::commands::get_systems;
+use crate::commands::scenario_data::load_scenario_data;
+use crate::commands::stale_data::criticality_weight;
+use crate::db::System;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SupportStatus {
+    InSupport,
+    // Past mainstream support but within extended support
+    ExtendedOnly,
+    OutOfSupport,
+}
+
+impl SupportStatus {
+    // Share of a system's criticality weight counted as risk
+    pub fn factor(self) -> f64 {
+        match self {
+            SupportStatus::InSupport => 0.0,
+            SupportStatus::ExtendedOnly => 0.5,
+            SupportStatus::OutOfSupport => 1.0,
+        }
+    }
+}
+
+/// Support status on the given day. Each end date is the last supported day; a system with
+/// no support end date recorded has no known cliff and counts as in support.
+pub fn support_status(system: &System, on: NaiveDate) -> SupportStatus {
+    let supported_until = |date: &Option<String>| date.as_deref().and_then(parse_date).map(|d| on <= d);
+    match supported_until(&system.support_end_date) {
+        None | Some(true) => SupportStatus::InSupport,
+        Some(false) if supported_until(&system.extended_support_end_date) == Some(true) => SupportStatus::ExtendedOnly,
+        Some(false) => SupportStatus::OutOfSupport,
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SupportBandCounts {
+    pub in_support: i64,
+    pub extended_only: i64,
+    pub out_of_support: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstateRiskPoint {
+    pub label: String,
+    pub start_date: String,
+    // Last day of the period, the day support status is taken on
+    pub end_date: String,
+    pub score: f64,
+    pub counts: SupportBandCounts,
+    // With systems the baseline replaces by the period's end taken out
+    pub projected_score: f64,
+    pub projected_counts: SupportBandCounts,
+    pub replaced_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstateRiskProfile {
+    pub from: String,
+    pub to: String,
+    pub granularity: String,
+    // None when no scenario is marked baseline; the projection then matches the current score
+    pub baseline_scenario_id: Option<String>,
+    pub points: Vec<EstateRiskPoint>,
+}
+
+/// Score the estate on the last day of each period. `replaced_on` maps a system to the day the
+/// baseline initiative replacing it finishes; from the period that day falls in on, the system
+/// no longer counts towards the projection.
+pub fn estate_risk_points(systems: &[System], replaced_on: &HashMap<String, NaiveDate>, periods: &[DateSpan], period_type: &str) -> Vec<EstateRiskPoint> {
+    let live: Vec<&System> = systems.iter().filter(|s| s.lifecycle_stage != "Retired").collect();
+
+    periods
+        .iter()
+        .map(|period| {
+            let on = period.last_day();
+            let mut point = EstateRiskPoint {
+                label: period_label(period.start, period_type),
+                start_date: format_date(period.start),
+                end_date: format_date(on),
+                score: 0.0,
+                counts: SupportBandCounts::default(),
+                projected_score: 0.0,
+                projected_counts: SupportBandCounts::default(),
+                replaced_count: 0,
+            };
+
+            for system in &live {
+                let status = support_status(system, on);
+                let score = criticality_weight(&system.criticality) * status.factor();
+                point.score += score;
+                add_to_band(&mut point.counts, status);
+
+                if replaced_on.get(&system.id).is_some_and(|d| *d <= on) {
+                    point.replaced_count += 1;
+                } else {
+                    point.projected_score += score;
+                    add_to_band(&mut point.projected_counts, status);
+                }
+            }
+            point
+        })
+        .collect()
+}
+
+fn add_to_band(counts: &mut SupportBandCounts, status: SupportStatus) {
+    match status {
+        SupportStatus::InSupport => counts.in_support += 1,
+        SupportStatus::ExtendedOnly => counts.extended_only += 1,
+        SupportStatus::OutOfSupport => counts.out_of_support += 1,
+    }
+}
+
+// ============================================
+// ESTATE RISK COMMANDS
+// ============================================
+
+/// Criticality-weighted support risk across the estate for each calendar period of
+/// `granularity` between `from` and `to`, alongside what the baseline does to it. A system
+/// leaves the projection when an initiative it is linked to as Replaced finishes, or one of
+/// type Decommission or Replacement that targets it.
+#[tauri::command]
+pub async fn get_estate_risk_profile(db: State<'_, tauri_plugin_sql::DbInstances>, from: String, to: String, granularity: String) -> Result<EstateRiskProfile, String> {
+    let window = DateSpan::parse_inclusive(Some(&from), Some(&to))
+        .ok_or_else(|| format!("Invalid date range {} to {}", from, to))?;
+    let period_type = normalise_period_type(&granularity, &FINANCIAL_PERIOD_TYPES)?;
+
+    let systems = get_systems(db.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let baseline_scenario_id = sqlx::query_scalar!("SELECT id FROM scenarios WHERE is_baseline = 1 LIMIT 1")
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut replaced_on: HashMap<String, NaiveDate> = HashMap::new();
+    if let Some(baseline_id) = &baseline_scenario_id {
+        let data = load_scenario_data(db.clone(), baseline_id).await?;
+        let links = sqlx::query!(
+            r#"SELECT si.system_id, si.initiative_id FROM system_initiatives si
+            JOIN initiatives i ON i.id = si.initiative_id
+            WHERE i.scenario_id = ?
+                AND (si.relationship_type = 'Replaced'
+                    OR (si.relationship_type = 'Target' AND i.type IN ('Decommission', 'Replacement')))"#,
+            baseline_id
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        // Dates as the baseline's overrides leave them
+        let finishes: HashMap<&str, NaiveDate> = data
+            .initiatives
+            .iter()
+            .filter(|i| i.status != "Cancelled")
+            .filter_map(|i| Some((i.id.as_str(), i.end_date.as_deref().and_then(parse_date)?)))
+            .collect();
+        for link in &links {
+            if let Some(finish) = finishes.get(link.initiative_id.as_str()) {
+                replaced_on
+                    .entry(link.system_id.clone())
+                    .and_modify(|d| *d = (*d).min(*finish))
+                    .or_insert(*finish);
+            }
+        }
+    }
+
+    Ok(EstateRiskProfile {
+        from,
+        to,
+        granularity: period_type.to_string(),
+        baseline_scenario_id,
+        points: estate_risk_points(&systems, &replaced_on, &generate_periods(&window, period_type), period_type),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system(id: &str, criticality: &str, support_end: Option<&str>, extended_end: Option<&str>) -> System {
+        System {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            owner: None,
+            vendor: None,
+            technology_stack: None,
+            lifecycle_stage: "Production".to_string(),
+            criticality: criticality.to_string(),
+            support_end_date: support_end.map(str::to_string),
+            extended_support_end_date: extended_end.map(str::to_string),
+            capability_id: None,
+            external_ref: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn systems_replaced_before_the_cliff_leave_the_projection() {
+        let systems = vec![
+            system("ledger", "Critical", Some("2027-03-31"), Some("2027-06-30")),
+            system("crm", "Medium", Some("2027-03-31"), None),
+            system("intranet", "Low", None, None),
+        ];
+        let replaced_on: HashMap<String, NaiveDate> = [("ledger".to_string(), parse_date("2027-05-15").unwrap())].into();
+        let window = DateSpan::parse_inclusive(Some("2027-01-01"), Some("2027-09-30")).unwrap();
+
+        let points = estate_risk_points(&systems, &replaced_on, &generate_periods(&window, "Quarter"), "Quarter");
+        let scores: Vec<(&str, f64, f64)> = points.iter().map(|p| (p.label.as_str(), p.score, p.projected_score)).collect();
+        // Q2: ledger on extended support (4 × 0.5) and crm out of support (2 × 1), ledger replaced in May
+        assert_eq!(scores, [("2027-Q1", 0.0, 0.0), ("2027-Q2", 4.0, 2.0), ("2027-Q3", 6.0, 2.0)]);
+        assert_eq!(points[1].end_date, "2027-06-30");
+        assert_eq!((points[1].counts.in_support, points[1].counts.extended_only, points[1].counts.out_of_support), (1, 1, 1));
+        assert_eq!((points[1].projected_counts.extended_only, points[1].replaced_count), (0, 1));
+    }
+}
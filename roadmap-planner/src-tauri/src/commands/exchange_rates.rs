@@ -0,0 +1,154 @@
+// Tauri commands for exchange rates
+// Effective-dated rates used to convert costs and budgets into the reporting currency
+
+use crate::commands::engine::currency::{CurrencyConverter, ExchangeRate};
+use crate::commands::engine::dates::parse_date;
+use crate::commands::settings::read_reporting_currency;
+use crate::db::get_current_timestamp;
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// Currencies are stored as three-letter ISO 4217 codes
+pub fn validate_currency_code(code: &str) -> Result<(), String> {
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(format!("Currency must be a three-letter ISO code such as GBP, got {}", code));
+    }
+    Ok(())
+}
+
+fn validate_exchange_rate(rate: &ExchangeRate) -> Result<(), String> {
+    validate_currency_code(&rate.from_currency)?;
+    validate_currency_code(&rate.to_currency)?;
+    if rate.from_currency == rate.to_currency {
+        return Err("An exchange rate must be between two different currencies".to_string());
+    }
+    if !rate.rate.is_finite() || rate.rate <= 0.0 {
+        return Err(format!("Exchange rate must be greater than zero, got {}", rate.rate));
+    }
+    if parse_date(&rate.effective_date).is_none() {
+        return Err(format!("Invalid effective date {}", rate.effective_date));
+    }
+    Ok(())
+}
+
+/// Converter over every stored rate, targeting the workspace reporting currency
+pub async fn load_currency_converter(pool: &SqlitePool) -> Result<CurrencyConverter, String> {
+    let reporting_currency = read_reporting_currency(pool).await?;
+
+    let rates: Vec<ExchangeRate> = sqlx::query_as!(
+        ExchangeRate,
+        r#"SELECT id, from_currency, to_currency, rate, effective_date, created_at, updated_at
+        FROM exchange_rates"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(CurrencyConverter::new(&reporting_currency, &rates))
+}
+
+// ============================================
+// EXCHANGE RATE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_exchange_rates(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<Vec<ExchangeRate>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<ExchangeRate> = sqlx::query_as!(
+        ExchangeRate,
+        r#"SELECT id, from_currency, to_currency, rate, effective_date, created_at, updated_at
+        FROM exchange_rates ORDER BY from_currency, to_currency, effective_date DESC"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_exchange_rate(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<ExchangeRate, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let row: ExchangeRate = sqlx::query_as!(
+        ExchangeRate,
+        r#"SELECT id, from_currency, to_currency, rate, effective_date, created_at, updated_at
+        FROM exchange_rates WHERE id = ?"#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn create_exchange_rate(db: State<'_, tauri_plugin_sql::DbInstances>, rate: ExchangeRate) -> Result<ExchangeRate, String> {
+    validate_exchange_rate(&rate)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO exchange_rates (id, from_currency, to_currency, rate, effective_date, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+        rate.id,
+        rate.from_currency,
+        rate.to_currency,
+        rate.rate,
+        rate.effective_date,
+        now,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_exchange_rate(db, rate.id).await
+}
+
+#[tauri::command]
+pub async fn update_exchange_rate(db: State<'_, tauri_plugin_sql::DbInstances>, rate: ExchangeRate) -> Result<ExchangeRate, String> {
+    validate_exchange_rate(&rate)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"UPDATE exchange_rates SET
+            from_currency = ?, to_currency = ?, rate = ?, effective_date = ?, updated_at = ?
+        WHERE id = ?"#,
+        rate.from_currency,
+        rate.to_currency,
+        rate.rate,
+        rate.effective_date,
+        now,
+        rate.id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_exchange_rate(db, rate.id).await
+}
+
+#[tauri::command]
+pub async fn delete_exchange_rate(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM exchange_rates WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,245 @@
+// Whole-row fetches by id for every entity type
+// Each entity's column list is written once here; the single-row getters and get_by_ids share it
+
+use crate::commands::entities::EntityType;
+use crate::commands::rows::ids_json;
+use crate::db::{Capability, Constraint, FinancialPeriod, Initiative, Resource, ResourcePool, Scenario, System};
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "entity_type", content = "rows")]
+pub enum EntityRows {
+    Capability(Vec<Capability>),
+    System(Vec<System>),
+    Initiative(Vec<Initiative>),
+    Scenario(Vec<Scenario>),
+    ResourcePool(Vec<ResourcePool>),
+    Resource(Vec<Resource>),
+    Constraint(Vec<Constraint>),
+    FinancialPeriod(Vec<FinancialPeriod>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFetch {
+    // In the order the ids were requested
+    pub found: EntityRows,
+    pub not_found: Vec<String>,
+}
+
+/// Take the only row of a single-id fetch, or report the entity as missing
+pub fn single<T>(mut rows: Vec<T>, entity_type: EntityType, id: &str) -> Result<T, String> {
+    rows.pop().ok_or_else(|| format!("{} {} not found", entity_type.name(), id))
+}
+
+// Every fetch binds its ids as one JSON array expanded with json_each, so a batch of any
+// size is a single statement and never runs into SQLite's bound parameter limit
+
+pub async fn fetch_capabilities(conn: &mut SqliteConnection, ids: &[String]) -> Result<Vec<Capability>, String> {
+    let ids = ids_json(ids);
+    sqlx::query_as!(
+        Capability,
+        r#"SELECT
+            id, name, description,
+            type as "capability_type",
+            parent_id, colour, sort_order,
+            created_at, updated_at
+        FROM capabilities WHERE id IN (SELECT value FROM json_each(?))"#,
+        ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn fetch_systems(conn: &mut SqliteConnection, ids: &[String]) -> Result<Vec<System>, String> {
+    let ids = ids_json(ids);
+    sqlx::query_as!(
+        System,
+        r#"SELECT
+            id, name, description, owner, vendor, technology_stack,
+            lifecycle_stage, criticality, support_end_date, extended_support_end_date,
+            capability_id, external_ref, created_at, updated_at
+        FROM systems WHERE id IN (SELECT value FROM json_each(?))"#,
+        ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn fetch_initiatives(conn: &mut SqliteConnection, ids: &[String]) -> Result<Vec<Initiative>, String> {
+    let ids = ids_json(ids);
+    sqlx::query_as!(
+        Initiative,
+        r#"SELECT
+            id, name, description, type as "initiative_type", status,
+            start_date, end_date, effort_estimate, effort_uncertainty,
+            cost_estimate, cost_uncertainty, priority, scenario_id,
+            percent_complete, progress_from_milestones as "progress_from_milestones: bool", currency, effort_unit, effort_profile, external_ref, reference_code, is_key_date as "is_key_date: bool", colour, icon,
+            created_at, updated_at
+        FROM initiatives WHERE id IN (SELECT value FROM json_each(?))"#,
+        ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn fetch_scenarios(conn: &mut SqliteConnection, ids: &[String]) -> Result<Vec<Scenario>, String> {
+    let ids = ids_json(ids);
+    sqlx::query_as!(
+        Scenario,
+        r#"SELECT
+            id, name, description, type as "scenario_type",
+            is_baseline as "is_baseline: bool", parent_scenario_id, is_locked as "is_locked: bool", created_at, updated_at
+        FROM scenarios WHERE id IN (SELECT value FROM json_each(?))"#,
+        ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn fetch_resource_pools(conn: &mut SqliteConnection, ids: &[String]) -> Result<Vec<ResourcePool>, String> {
+    let ids = ids_json(ids);
+    sqlx::query_as!(
+        ResourcePool,
+        r#"SELECT
+            id, name, description, capacity_per_period,
+            capacity_unit, period_type, colour, created_at, updated_at
+        FROM resource_pools WHERE id IN (SELECT value FROM json_each(?))"#,
+        ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn fetch_resources(conn: &mut SqliteConnection, ids: &[String]) -> Result<Vec<Resource>, String> {
+    let ids = ids_json(ids);
+    sqlx::query_as!(
+        Resource,
+        r#"SELECT
+            id, name, role, skills, availability,
+            resource_pool_id, start_date, end_date, created_at, updated_at
+        FROM resources WHERE id IN (SELECT value FROM json_each(?))"#,
+        ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn fetch_constraints(conn: &mut SqliteConnection, ids: &[String]) -> Result<Vec<Constraint>, String> {
+    let ids = ids_json(ids);
+    sqlx::query_as!(
+        Constraint,
+        r#"SELECT
+            id, name, description, type as "constraint_type",
+            hardness, effective_date, expiry_date, created_at, updated_at
+        FROM constraints WHERE id IN (SELECT value FROM json_each(?))"#,
+        ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn fetch_financial_periods(conn: &mut SqliteConnection, ids: &[String]) -> Result<Vec<FinancialPeriod>, String> {
+    let ids = ids_json(ids);
+    sqlx::query_as!(
+        FinancialPeriod,
+        r#"SELECT
+            id, name, type as "period_type",
+            start_date, end_date, budget_available, currency, closed as "closed: bool", created_at, updated_at
+        FROM financial_periods WHERE id IN (SELECT value FROM json_each(?))"#,
+        ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+// Put rows back in request order; a repeated id is reported once, at its first position
+fn in_request_order<T>(rows: Vec<T>, ids: &[String], id_of: impl Fn(&T) -> String) -> (Vec<T>, Vec<String>) {
+    let mut by_id: HashMap<String, T> = rows.into_iter().map(|r| (id_of(&r), r)).collect();
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut found = Vec::with_capacity(by_id.len());
+    let mut not_found = Vec::new();
+
+    for id in ids.iter().filter(|id| seen.insert(id.as_str())) {
+        match by_id.remove(id) {
+            Some(row) => found.push(row),
+            None => not_found.push(id.clone()),
+        }
+    }
+
+    (found, not_found)
+}
+
+// ============================================
+// BATCH FETCH COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_by_ids(db: State<'_, tauri_plugin_sql::DbInstances>, entity_type: EntityType, ids: Vec<String>) -> Result<BatchFetch, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+
+    let (found, not_found) = match entity_type {
+        EntityType::Capability => {
+            let (rows, missing) = in_request_order(fetch_capabilities(&mut conn, &ids).await?, &ids, |r| r.id.clone());
+            (EntityRows::Capability(rows), missing)
+        }
+        EntityType::System => {
+            let (rows, missing) = in_request_order(fetch_systems(&mut conn, &ids).await?, &ids, |r| r.id.clone());
+            (EntityRows::System(rows), missing)
+        }
+        EntityType::Initiative => {
+            let (rows, missing) = in_request_order(fetch_initiatives(&mut conn, &ids).await?, &ids, |r| r.id.clone());
+            (EntityRows::Initiative(rows), missing)
+        }
+        EntityType::Scenario => {
+            let (rows, missing) = in_request_order(fetch_scenarios(&mut conn, &ids).await?, &ids, |r| r.id.clone());
+            (EntityRows::Scenario(rows), missing)
+        }
+        EntityType::ResourcePool => {
+            let (rows, missing) = in_request_order(fetch_resource_pools(&mut conn, &ids).await?, &ids, |r| r.id.clone());
+            (EntityRows::ResourcePool(rows), missing)
+        }
+        EntityType::Resource => {
+            let (rows, missing) = in_request_order(fetch_resources(&mut conn, &ids).await?, &ids, |r| r.id.clone());
+            (EntityRows::Resource(rows), missing)
+        }
+        EntityType::Constraint => {
+            let (rows, missing) = in_request_order(fetch_constraints(&mut conn, &ids).await?, &ids, |r| r.id.clone());
+            (EntityRows::Constraint(rows), missing)
+        }
+        EntityType::FinancialPeriod => {
+            let (rows, missing) = in_request_order(fetch_financial_periods(&mut conn, &ids).await?, &ids, |r| r.id.clone());
+            (EntityRows::FinancialPeriod(rows), missing)
+        }
+    };
+
+    Ok(BatchFetch { found, not_found })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_follow_request_order_and_missing_ids_are_reported_once() {
+        let ids: Vec<String> = ["c", "missing", "a", "c", "missing", "b"].iter().map(|s| s.to_string()).collect();
+        let rows = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+
+        let (found, not_found) = in_request_order(rows, &ids, |r| r.clone());
+        assert_eq!(found, vec!["c", "a", "b"]);
+        assert_eq!(not_found, vec!["missing"]);
+    }
+}
@@ -0,0 +1,465 @@
+// Tauri commands for rolling forecast snapshots and the forward spend curve
+// Captures the budget report's figures over time so movements in the forecast can be charted
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::budget::{calculate_budget_report, cost_in_span, phased_cost};
+use crate::commands::engine::currency::{CurrencyConverter, CurrencyWarning};
+use crate::commands::engine::dates::{DateSpan, format_date, next_period_start, parse_date, period_label, period_start, today};
+use crate::commands::exchange_rates::load_currency_converter;
+use crate::commands::{get_initiatives, get_scenario, require_scenario};
+use crate::commands::scenario_data::load_scenario_data;
+use crate::db::{Initiative, get_current_timestamp};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastSnapshot {
+    pub id: String,
+    pub scenario_id: String,
+    pub scenario_name: String,
+    pub label: String,
+    pub currency: String,
+    pub captured_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastCapture {
+    pub snapshot: ForecastSnapshot,
+    pub period_count: i64,
+    pub line_count: i64,
+    // Amounts left out of the figures for want of an exchange rate
+    pub warnings: Vec<CurrencyWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastHistoryPoint {
+    pub snapshot_id: String,
+    pub label: String,
+    pub scenario_id: String,
+    pub scenario_name: String,
+    pub captured_at: String,
+    pub currency: String,
+    pub budget_available: Option<f64>,
+    pub planned_spend: f64,
+    // Against the previous snapshot of the same scenario
+    pub change: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastMovement {
+    // Period or initiative, depending on the list it appears in
+    pub id: String,
+    pub name: String,
+    pub planned_a: f64,
+    pub planned_b: f64,
+    pub change: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastComparison {
+    pub snapshot_a: ForecastSnapshot,
+    pub snapshot_b: ForecastSnapshot,
+    pub total_change: f64,
+    // Largest movements first
+    pub periods: Vec<ForecastMovement>,
+    pub initiatives: Vec<ForecastMovement>,
+}
+
+// Longest spend forecast, ten years out
+const MAX_FORECAST_QUARTERS: u32 = 40;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarterSpend {
+    // e.g. 2027-Q1
+    pub label: String,
+    // The first quarter starts today rather than on its calendar start
+    pub start_date: String,
+    pub end_date: String,
+    pub currency: String,
+    pub spend: f64,
+    // Spend from today to the end of this quarter
+    pub cumulative_spend: f64,
+    pub warnings: Vec<CurrencyWarning>,
+}
+
+/// Initiative costs pro-rated by day into the next `quarters` calendar quarters, starting with
+/// the rest of the current one. Days before `as_of` are already spent and left out, as are
+/// cancelled and complete initiatives.
+pub fn spend_forecast(initiatives: &[Initiative], converter: &CurrencyConverter, as_of: NaiveDate, quarters: u32) -> Vec<QuarterSpend> {
+    let mut forecast = Vec::with_capacity(quarters as usize);
+    let mut cumulative_spend = 0.0;
+    let mut start = period_start(as_of, "Quarter");
+
+    for _ in 0..quarters {
+        let end = next_period_start(start, "Quarter");
+        let window = DateSpan { start: start.max(as_of), end };
+        let mut warnings = Vec::new();
+        let mut spend = 0.0;
+
+        for initiative in initiatives.iter().filter(|i| i.status != "Cancelled" && i.status != "Complete") {
+            let cost = cost_in_span(initiative, &window);
+            if cost == 0.0 {
+                continue;
+            }
+            let currency = converter.currency_of(initiative.currency.as_deref());
+            if let Some(converted) = converter.convert_or_warn(cost, currency, window.start, "Initiative", &initiative.id, &mut warnings) {
+                spend += converted;
+            }
+        }
+
+        cumulative_spend += spend;
+        forecast.push(QuarterSpend {
+            label: period_label(start, "Quarter"),
+            start_date: format_date(window.start),
+            end_date: format_date(window.last_day()),
+            currency: converter.reporting_currency.clone(),
+            spend,
+            cumulative_spend,
+            warnings,
+        });
+        start = end;
+    }
+
+    forecast
+}
+
+// Figures as stored: (id, name, planned spend) for a period or initiative line
+type Figure = (String, String, f64);
+
+/// Totals per id in each snapshot, with anything present in only one counted as zero in the other
+pub fn compare_figures(a: &[Figure], b: &[Figure]) -> Vec<ForecastMovement> {
+    let mut totals: BTreeMap<&str, (&str, f64, f64)> = BTreeMap::new();
+    for (id, name, amount) in a {
+        totals.entry(id).or_insert((name, 0.0, 0.0)).1 += amount;
+    }
+    for (id, name, amount) in b {
+        let entry = totals.entry(id).or_insert((name, 0.0, 0.0));
+        // The later name wins, in case of a rename between snapshots
+        entry.0 = name;
+        entry.2 += amount;
+    }
+
+    let mut movements: Vec<ForecastMovement> = totals
+        .into_iter()
+        .map(|(id, (name, planned_a, planned_b))| ForecastMovement {
+            id: id.to_string(),
+            name: name.to_string(),
+            planned_a,
+            planned_b,
+            change: planned_b - planned_a,
+        })
+        .filter(|m| m.change != 0.0)
+        .collect();
+    movements.sort_by(|x, y| y.change.abs().total_cmp(&x.change.abs()).then_with(|| x.name.cmp(&y.name)));
+    movements
+}
+
+async fn get_forecast_snapshot(pool: &sqlx::SqlitePool, id: &str) -> Result<ForecastSnapshot, String> {
+    sqlx::query_as!(
+        ForecastSnapshot,
+        r#"SELECT id as "id!", scenario_id, scenario_name, label, currency, captured_at
+        FROM forecast_snapshots WHERE id = ?"#,
+        id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Forecast snapshot {} not found", id))
+}
+
+// ============================================
+// FORECAST SNAPSHOT COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn capture_forecast(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, label: String) -> Result<ForecastCapture, String> {
+    let label = label.trim().to_string();
+    if label.is_empty() {
+        return Err("A label is required to capture a forecast".to_string());
+    }
+
+    let scenario = get_scenario(db.clone(), scenario_id.clone()).await?;
+    let data = load_scenario_data(db.clone(), &scenario_id).await?;
+    let report = calculate_budget_report(&data.initiatives, &data.periods, &data.converter);
+    let warnings: Vec<CurrencyWarning> = report.iter().flat_map(|p| p.warnings.iter().cloned()).collect();
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let snapshot = ForecastSnapshot {
+        id: uuid::Uuid::new_v4().to_string(),
+        scenario_id,
+        scenario_name: scenario.name,
+        label,
+        currency: data.converter.reporting_currency.clone(),
+        captured_at: get_current_timestamp(),
+    };
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    sqlx::query!(
+        r#"INSERT INTO forecast_snapshots (id, scenario_id, scenario_name, label, currency, captured_at)
+        VALUES (?, ?, ?, ?, ?, ?)"#,
+        snapshot.id,
+        snapshot.scenario_id,
+        snapshot.scenario_name,
+        snapshot.label,
+        snapshot.currency,
+        snapshot.captured_at
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut line_count = 0;
+    for figures in &report {
+        sqlx::query!(
+            r#"INSERT INTO forecast_snapshot_periods (snapshot_id, financial_period_id, period_name, start_date, budget_available, planned_spend)
+            VALUES (?, ?, ?, ?, ?, ?)"#,
+            snapshot.id,
+            figures.period_id,
+            figures.period_name,
+            figures.start_date,
+            figures.budget_available,
+            figures.planned_spend
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        // Same phasing and conversion as the report total, so lines add up to it
+        let Some(period) = data.periods.iter().find(|p| p.id == figures.period_id) else {
+            continue;
+        };
+        let Some(on) = parse_date(&period.start_date) else {
+            continue;
+        };
+        for initiative in &data.initiatives {
+            let cost = phased_cost(initiative, period);
+            if cost == 0.0 {
+                continue;
+            }
+            let currency = data.converter.currency_of(initiative.currency.as_deref());
+            // Missing rates are already among the report's warnings
+            let Some(planned_spend) = data.converter.convert(cost, currency, on) else {
+                continue;
+            };
+
+            sqlx::query!(
+                r#"INSERT INTO forecast_snapshot_lines (snapshot_id, financial_period_id, initiative_id, initiative_name, planned_spend)
+                VALUES (?, ?, ?, ?, ?)"#,
+                snapshot.id,
+                period.id,
+                initiative.id,
+                initiative.name,
+                planned_spend
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+            line_count += 1;
+        }
+    }
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: "Scenario".to_string(),
+        entity_id: Some(snapshot.scenario_id.clone()),
+        action: "CaptureForecast".to_string(),
+        description: Some(format!("Captured forecast \"{}\" for {}", snapshot.label, snapshot.scenario_name)),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(ForecastCapture {
+        snapshot,
+        period_count: report.len() as i64,
+        line_count,
+        warnings,
+    })
+}
+
+#[tauri::command]
+pub async fn get_forecast_snapshots(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: Option<String>) -> Result<Vec<ForecastSnapshot>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<ForecastSnapshot> = sqlx::query_as!(
+        ForecastSnapshot,
+        r#"SELECT id as "id!", scenario_id, scenario_name, label, currency, captured_at
+        FROM forecast_snapshots
+        WHERE ? IS NULL OR scenario_id = ?
+        ORDER BY captured_at DESC"#,
+        scenario_id,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+/// How one period's planned spend moved across snapshots, oldest first
+#[tauri::command]
+pub async fn get_forecast_history(db: State<'_, tauri_plugin_sql::DbInstances>, period_id: String) -> Result<Vec<ForecastHistoryPoint>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows = sqlx::query!(
+        r#"SELECT s.id as "snapshot_id!", s.label, s.scenario_id, s.scenario_name, s.captured_at, s.currency,
+            p.budget_available, p.planned_spend
+        FROM forecast_snapshot_periods p
+        JOIN forecast_snapshots s ON s.id = p.snapshot_id
+        WHERE p.financial_period_id = ?
+        ORDER BY s.captured_at, s.id"#,
+        period_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut previous: BTreeMap<String, f64> = BTreeMap::new();
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let change = previous.insert(r.scenario_id.clone(), r.planned_spend).map(|before| r.planned_spend - before);
+            ForecastHistoryPoint {
+                snapshot_id: r.snapshot_id,
+                label: r.label,
+                scenario_id: r.scenario_id,
+                scenario_name: r.scenario_name,
+                captured_at: r.captured_at,
+                currency: r.currency,
+                budget_available: r.budget_available,
+                planned_spend: r.planned_spend,
+                change,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn compare_forecasts(db: State<'_, tauri_plugin_sql::DbInstances>, snapshot_a: String, snapshot_b: String) -> Result<ForecastComparison, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let a = get_forecast_snapshot(pool, &snapshot_a).await?;
+    let b = get_forecast_snapshot(pool, &snapshot_b).await?;
+    if a.currency != b.currency {
+        return Err(format!(
+            "Snapshots were captured in different reporting currencies ({} and {})",
+            a.currency, b.currency
+        ));
+    }
+
+    let mut periods: Vec<Vec<Figure>> = Vec::with_capacity(2);
+    let mut initiatives: Vec<Vec<Figure>> = Vec::with_capacity(2);
+    for snapshot_id in [&a.id, &b.id] {
+        let period_rows = sqlx::query!(
+            r#"SELECT financial_period_id, period_name, planned_spend
+            FROM forecast_snapshot_periods WHERE snapshot_id = ?"#,
+            snapshot_id
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        periods.push(period_rows.into_iter().map(|r| (r.financial_period_id, r.period_name, r.planned_spend)).collect());
+
+        let line_rows = sqlx::query!(
+            r#"SELECT initiative_id, initiative_name, planned_spend
+            FROM forecast_snapshot_lines WHERE snapshot_id = ?"#,
+            snapshot_id
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        initiatives.push(line_rows.into_iter().map(|r| (r.initiative_id, r.initiative_name, r.planned_spend)).collect());
+    }
+
+    let total = |figures: &[Figure]| figures.iter().map(|(_, _, amount)| amount).sum::<f64>();
+
+    Ok(ForecastComparison {
+        total_change: total(&periods[1]) - total(&periods[0]),
+        periods: compare_figures(&periods[0], &periods[1]),
+        initiatives: compare_figures(&initiatives[0], &initiatives[1]),
+        snapshot_a: a,
+        snapshot_b: b,
+    })
+}
+
+/// Forward spend curve for cash-flow planning: per-quarter and cumulative spend over the next
+/// `quarters` quarters, in the reporting currency
+#[tauri::command]
+pub async fn get_spend_forecast(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, quarters: u32) -> Result<Vec<QuarterSpend>, String> {
+    if quarters == 0 || quarters > MAX_FORECAST_QUARTERS {
+        return Err(format!("Quarters must be between 1 and {}", MAX_FORECAST_QUARTERS));
+    }
+    require_scenario(db.clone(), &scenario_id).await?;
+    let initiatives = get_initiatives(db.clone(), Some(scenario_id)).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+    let converter = load_currency_converter(pool).await?;
+
+    Ok(spend_forecast(&initiatives, &converter, today(), quarters))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::test_initiative;
+
+    fn figure(id: &str, amount: f64) -> Figure {
+        (id.to_string(), id.to_string(), amount)
+    }
+
+    #[test]
+    fn biggest_movements_come_first() {
+        // Lines for "a" span two periods and are totalled per initiative
+        let before = vec![figure("a", 100.0), figure("a", 50.0), figure("b", 200.0), figure("gone", 30.0)];
+        let after = vec![figure("a", 150.0), figure("b", 120.0), figure("new", 40.0)];
+
+        let movements = compare_figures(&before, &after);
+        let summary: Vec<(&str, f64)> = movements.iter().map(|m| (m.id.as_str(), m.change)).collect();
+        // Unchanged "a" is left out
+        assert_eq!(summary, vec![("b", -80.0), ("new", 40.0), ("gone", -30.0)]);
+    }
+
+    fn initiative(id: &str, status: &str, start: &str, end: &str, cost: f64) -> Initiative {
+        Initiative {
+            status: status.to_string(),
+            start_date: Some(start.to_string()),
+            end_date: Some(end.to_string()),
+            cost_estimate: Some(cost),
+            ..test_initiative(id)
+        }
+    }
+
+    #[test]
+    fn spend_is_pro_rated_into_quarters_from_today() {
+        let initiatives = vec![
+            // 100 days at 10 a day, 40 of them already gone
+            initiative("running", "InProgress", "2027-01-01", "2027-04-10", 1000.0),
+            initiative("later", "Planned", "2027-07-01", "2027-09-29", 910.0),
+            initiative("past", "InProgress", "2026-01-01", "2026-12-31", 500.0),
+            initiative("dropped", "Cancelled", "2027-01-01", "2027-12-31", 800.0),
+        ];
+        let converter = CurrencyConverter::new("GBP", &[]);
+        let as_of = parse_date("2027-02-10").unwrap();
+
+        let forecast = spend_forecast(&initiatives, &converter, as_of, 3);
+        let summary: Vec<(&str, &str, f64, f64)> = forecast
+            .iter()
+            .map(|q| (q.label.as_str(), q.start_date.as_str(), q.spend.round(), q.cumulative_spend.round()))
+            .collect();
+        assert_eq!(
+            summary,
+            [("2027-Q1", "2027-02-10", 500.0, 500.0), ("2027-Q2", "2027-04-01", 100.0, 600.0), ("2027-Q3", "2027-07-01", 910.0, 1510.0)]
+        );
+        assert_eq!(forecast[2].end_date, "2027-09-30");
+    }
+}
@@ -0,0 +1,443 @@
+// Tauri commands for funding sources and derived period budgets
+// A derived period's budget_available is the sum of the funding attributed to it
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::currency::{CurrencyConverter, CurrencyWarning};
+use crate::commands::engine::dates::parse_date;
+use crate::commands::exchange_rates::{load_currency_converter, validate_currency_code};
+use crate::commands::period_close::ensure_period_open;
+use crate::commands::settings::read_setting;
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use tauri::State;
+
+pub const BUDGET_MODES: [&str; 2] = ["Manual", "Derived"];
+
+// Percentage of the stored budget a derived figure may differ by before it is reported
+const TOLERANCE_SETTING: &str = "budget.derived_tolerance_percent";
+const DEFAULT_TOLERANCE_PERCENT: f64 = 1.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingSource {
+    pub id: String,
+    pub name: String,
+    pub financial_period_id: String,
+    pub capex_amount: f64,
+    pub opex_amount: f64,
+    // Unset means the reporting currency
+    pub currency: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+// What recalculation needs to know about a period
+#[derive(Debug, Clone)]
+pub struct PeriodFunding {
+    pub id: String,
+    pub name: String,
+    pub start_date: String,
+    pub currency: Option<String>,
+    pub budget_available: Option<f64>,
+    pub budget_mode: String,
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedBudget {
+    pub period_id: String,
+    pub period_name: String,
+    pub budget_mode: String,
+    // The period's own currency, which every figure here is in
+    pub currency: String,
+    pub stored_budget: Option<f64>,
+    pub capex: f64,
+    pub opex: f64,
+    pub derived_budget: f64,
+    pub source_count: i64,
+    // A closed period's budget is frozen, so it is reported but never written
+    pub closed: bool,
+    // Derived less stored; None when nothing is stored
+    pub difference: Option<f64>,
+    pub exceeds_tolerance: bool,
+    pub written: bool,
+    // Funding left out of the figures for want of an exchange rate
+    pub warnings: Vec<CurrencyWarning>,
+}
+
+fn validate_funding_source(source: &FundingSource) -> Result<(), String> {
+    if source.name.trim().is_empty() {
+        return Err("A funding source needs a name".to_string());
+    }
+    for (field, amount) in [("Capex", source.capex_amount), ("Opex", source.opex_amount)] {
+        if !amount.is_finite() || amount < 0.0 {
+            return Err(format!("{} amount must be zero or more, got {}", field, amount));
+        }
+    }
+    if let Some(code) = source.currency.as_deref() {
+        validate_currency_code(code)?;
+    }
+    Ok(())
+}
+
+/// Convert between any two currencies through the reporting currency
+fn convert_between(converter: &CurrencyConverter, amount: f64, from: &str, to: &str, on: chrono::NaiveDate) -> Option<f64> {
+    if from == to {
+        return Some(amount);
+    }
+    Some(converter.convert(amount, from, on)? / converter.rate(to, on)?)
+}
+
+/// Each period's funding, summed in the period's currency at the rate effective when it
+/// starts. A stored budget further than `tolerance_percent` from the derived one is flagged.
+pub fn derive_period_budgets(periods: &[PeriodFunding], sources: &[FundingSource], converter: &CurrencyConverter, tolerance_percent: f64) -> Vec<DerivedBudget> {
+    let mut by_period: HashMap<&str, Vec<&FundingSource>> = HashMap::new();
+    for source in sources {
+        by_period.entry(source.financial_period_id.as_str()).or_default().push(source);
+    }
+
+    periods
+        .iter()
+        .filter_map(|period| {
+            let on = parse_date(&period.start_date)?;
+            let currency = converter.currency_of(period.currency.as_deref()).to_string();
+            let funding = by_period.get(period.id.as_str()).map(Vec::as_slice).unwrap_or_default();
+            let mut warnings = Vec::new();
+            let (mut capex, mut opex) = (0.0, 0.0);
+
+            for source in funding {
+                let from = converter.currency_of(source.currency.as_deref());
+                for (amount, total) in [(source.capex_amount, &mut capex), (source.opex_amount, &mut opex)] {
+                    if amount == 0.0 {
+                        continue;
+                    }
+                    match convert_between(converter, amount, from, &currency, on) {
+                        Some(converted) => *total += converted,
+                        None => warnings.push(CurrencyWarning {
+                            entity_type: "FundingSource".to_string(),
+                            entity_id: source.id.clone(),
+                            currency: from.to_string(),
+                            amount,
+                            on_date: period.start_date.clone(),
+                            message: format!("No {} to {} rate effective on {}; excluded from the derived budget", from, currency, period.start_date),
+                        }),
+                    }
+                }
+            }
+
+            let derived_budget = capex + opex;
+            let difference = period.budget_available.map(|stored| derived_budget - stored);
+            let exceeds_tolerance = match period.budget_available {
+                Some(stored) => (derived_budget - stored).abs() > stored.abs() * tolerance_percent / 100.0,
+                None => derived_budget != 0.0,
+            };
+
+            Some(DerivedBudget {
+                period_id: period.id.clone(),
+                period_name: period.name.clone(),
+                budget_mode: period.budget_mode.clone(),
+                currency,
+                stored_budget: period.budget_available,
+                capex,
+                opex,
+                derived_budget,
+                source_count: funding.len() as i64,
+                closed: period.closed,
+                difference,
+                exceeds_tolerance,
+                written: false,
+                warnings,
+            })
+        })
+        .collect()
+}
+
+async fn read_tolerance_percent(pool: &SqlitePool) -> Result<f64, String> {
+    match read_setting(pool, TOLERANCE_SETTING).await? {
+        Some(value) => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|v| *v >= 0.0)
+            .ok_or_else(|| format!("Setting {} must be a number of zero or more, got {}", TOLERANCE_SETTING, value)),
+        None => Ok(DEFAULT_TOLERANCE_PERCENT),
+    }
+}
+
+async fn fetch_funding_sources(pool: &SqlitePool, period_id: Option<&str>) -> Result<Vec<FundingSource>, String> {
+    sqlx::query_as!(
+        FundingSource,
+        r#"SELECT id as "id!", name, financial_period_id, capex_amount, opex_amount, currency,
+            created_at as "created_at?", updated_at as "updated_at?"
+        FROM funding_sources WHERE ?1 IS NULL OR financial_period_id = ?1
+        ORDER BY financial_period_id, name"#,
+        period_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Derived budgets for every period, without writing anything; validate_workspace compares them
+pub async fn load_derived_budgets(pool: &SqlitePool) -> Result<Vec<DerivedBudget>, String> {
+    let periods = sqlx::query_as!(
+        PeriodFunding,
+        r#"SELECT id as "id!", name, start_date, currency, budget_available, budget_mode, closed as "closed: bool"
+        FROM financial_periods ORDER BY start_date"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let sources = fetch_funding_sources(pool, None).await?;
+    let converter = load_currency_converter(pool).await?;
+    let tolerance_percent = read_tolerance_percent(pool).await?;
+
+    Ok(derive_period_budgets(&periods, &sources, &converter, tolerance_percent))
+}
+
+// ============================================
+// FUNDING SOURCE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_funding_sources(db: State<'_, tauri_plugin_sql::DbInstances>, period_id: Option<String>) -> Result<Vec<FundingSource>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    fetch_funding_sources(pool, period_id.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn create_funding_source(db: State<'_, tauri_plugin_sql::DbInstances>, source: FundingSource) -> Result<FundingSource, String> {
+    validate_funding_source(&source)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+    ensure_period_open(pool, &source.financial_period_id, "funding").await?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO funding_sources (id, name, financial_period_id, capex_amount, opex_amount, currency, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+        source.id,
+        source.name,
+        source.financial_period_id,
+        source.capex_amount,
+        source.opex_amount,
+        source.currency,
+        now,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(FundingSource { created_at: Some(now.clone()), updated_at: Some(now), ..source })
+}
+
+#[tauri::command]
+pub async fn update_funding_source(db: State<'_, tauri_plugin_sql::DbInstances>, source: FundingSource) -> Result<FundingSource, String> {
+    validate_funding_source(&source)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+    ensure_period_open(pool, &source.financial_period_id, "funding").await?;
+
+    let now = get_current_timestamp();
+
+    let updated = sqlx::query!(
+        r#"UPDATE funding_sources SET
+            name = ?, financial_period_id = ?, capex_amount = ?, opex_amount = ?, currency = ?, updated_at = ?
+        WHERE id = ?"#,
+        source.name,
+        source.financial_period_id,
+        source.capex_amount,
+        source.opex_amount,
+        source.currency,
+        now,
+        source.id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(format!("Funding source {} not found", source.id));
+    }
+    Ok(FundingSource { updated_at: Some(now), ..source })
+}
+
+#[tauri::command]
+pub async fn delete_funding_source(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM funding_sources WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Choose whether a period's budget is typed in (Manual) or summed from its funding (Derived)
+#[tauri::command]
+pub async fn set_budget_mode(db: State<'_, tauri_plugin_sql::DbInstances>, period_id: String, budget_mode: String) -> Result<(), String> {
+    if !BUDGET_MODES.contains(&budget_mode.as_str()) {
+        return Err(format!("Unknown budget mode \"{}\", expected one of {}", budget_mode, BUDGET_MODES.join(", ")));
+    }
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let updated = sqlx::query!(
+        "UPDATE financial_periods SET budget_mode = ?, updated_at = ? WHERE id = ?",
+        budget_mode,
+        now,
+        period_id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(format!("FinancialPeriod {} not found", period_id));
+    }
+    Ok(())
+}
+
+/// Sum each period's funding sources and compare with its stored budget_available. With
+/// `write_back` unset only Derived periods are overwritten; true overwrites every period that
+/// has funding and false only reports. Closed periods are never written.
+#[tauri::command]
+pub async fn recalculate_period_budgets(db: State<'_, tauri_plugin_sql::DbInstances>, write_back: Option<bool>) -> Result<Vec<DerivedBudget>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut budgets = load_derived_budgets(pool).await?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let group_id = uuid::Uuid::new_v4().to_string();
+    let now = get_current_timestamp();
+
+    for budget in budgets.iter_mut().filter(|b| !b.closed) {
+        let write = match write_back {
+            Some(write_back) => write_back && budget.source_count > 0,
+            None => budget.budget_mode == "Derived",
+        };
+        if !write || !budget.warnings.is_empty() {
+            continue;
+        }
+
+        sqlx::query!(
+            "UPDATE financial_periods SET budget_available = ?, budget_capex = ?, budget_opex = ?, updated_at = ? WHERE id = ?",
+            budget.derived_budget,
+            budget.capex,
+            budget.opex,
+            now,
+            budget.period_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        record_audit(&mut tx, NewAuditEntry {
+            group_id: Some(group_id.clone()),
+            entity_type: "FinancialPeriod".to_string(),
+            entity_id: Some(budget.period_id.clone()),
+            action: "RecalculateBudget".to_string(),
+            description: Some(format!(
+                "Set the budget of {} to {:.2} {} from {} funding source(s)",
+                budget.period_name, budget.derived_budget, budget.currency, budget.source_count
+            )),
+            before: Some(serde_json::json!({ "budget_available": budget.stored_budget })),
+            after: Some(serde_json::json!({
+                "budget_available": budget.derived_budget,
+                "budget_capex": budget.capex,
+                "budget_opex": budget.opex,
+            })),
+        })
+        .await?;
+        budget.written = true;
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(budgets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::currency::ExchangeRate;
+
+    fn period(id: &str, budget_available: Option<f64>, currency: &str) -> PeriodFunding {
+        PeriodFunding {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            start_date: "2027-01-01".to_string(),
+            currency: Some(currency.to_string()),
+            budget_available,
+            budget_mode: "Manual".to_string(),
+            closed: false,
+        }
+    }
+
+    fn source(id: &str, period_id: &str, capex: f64, opex: f64, currency: Option<&str>) -> FundingSource {
+        FundingSource {
+            id: id.to_string(),
+            name: id.to_string(),
+            financial_period_id: period_id.to_string(),
+            capex_amount: capex,
+            opex_amount: opex,
+            currency: currency.map(|c| c.to_string()),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn funding_is_summed_per_period_with_the_split_kept() {
+        let rate = ExchangeRate {
+            id: "usd".to_string(),
+            from_currency: "USD".to_string(),
+            to_currency: "GBP".to_string(),
+            rate: 0.8,
+            effective_date: "2026-01-01".to_string(),
+            created_at: None,
+            updated_at: None,
+        };
+        let converter = CurrencyConverter::new("GBP", &[rate]);
+        let periods = vec![period("q1", Some(1000.0), "GBP"), period("q2", Some(1000.0), "GBP"), period("q3", None, "EUR")];
+        let sources = vec![
+            source("core", "q1", 600.0, 200.0, None),
+            // 250 USD is 200 GBP
+            source("grant", "q1", 0.0, 250.0, Some("USD")),
+            source("small", "q2", 995.0, 0.0, None),
+            source("euro", "q3", 100.0, 0.0, None),
+        ];
+
+        let budgets = derive_period_budgets(&periods, &sources, &converter, 1.0);
+
+        assert_eq!((budgets[0].capex, budgets[0].opex, budgets[0].derived_budget), (600.0, 400.0, 1000.0));
+        assert_eq!(budgets[0].source_count, 2);
+        assert!(!budgets[0].exceeds_tolerance);
+
+        // Within 1% of the stored figure
+        assert_eq!(budgets[1].difference, Some(-5.0));
+        assert!(!budgets[1].exceeds_tolerance);
+
+        // No GBP to EUR rate, so the funding is left out and flagged
+        assert_eq!(budgets[2].derived_budget, 0.0);
+        assert_eq!(budgets[2].warnings.len(), 1);
+        assert!(!budgets[2].exceeds_tolerance);
+
+        let strict = derive_period_budgets(&periods, &sources, &converter, 0.1);
+        assert!(strict[1].exceeds_tolerance);
+    }
+}
@@ -0,0 +1,365 @@
+// Tauri commands for derived initiative health
+// Red/Amber/Green worked out from schedule, progress, constraint and allocation signals
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::constraints::check_hard_constraints;
+use crate::commands::engine::dates::{parse_date, today};
+use crate::commands::engine::progress::planned_percent;
+use crate::commands::entities::EntityType;
+use crate::commands::fetch::{fetch_initiatives, single};
+use crate::commands::scenario_data::load_scenario_data;
+use crate::commands::settings::read_setting;
+use crate::commands::{ensure_scenarios_unlocked, get_initiatives};
+use crate::db::{Initiative, get_current_timestamp};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+pub const HEALTH_LEVELS: [&str; 3] = ["Green", "Amber", "Red"];
+
+// Statuses an initiative is committed to, so it should have people against it
+const COMMITTED_STATUSES: [&str; 2] = ["Planned", "InProgress"];
+
+// Settings holding the thresholds, with their defaults
+const SLIP_AMBER_SETTING: &str = "health.slip_amber_days";
+const SLIP_RED_SETTING: &str = "health.slip_red_days";
+const PROGRESS_AMBER_SETTING: &str = "health.progress_amber_points";
+const PROGRESS_RED_SETTING: &str = "health.progress_red_points";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HealthThresholds {
+    // Days the end date has moved out from the baseline's
+    pub slip_amber_days: f64,
+    pub slip_red_days: f64,
+    // Percentage points reported progress trails planned progress
+    pub progress_amber_points: f64,
+    pub progress_red_points: f64,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self { slip_amber_days: 14.0, slip_red_days: 30.0, progress_amber_points: 10.0, progress_red_points: 20.0 }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReason {
+    // Slippage, Progress, HardConstraint or Allocation
+    pub signal: String,
+    pub level: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeHealth {
+    pub initiative_id: String,
+    pub initiative_name: String,
+    // The manual status when one is set, otherwise the derived one
+    pub status: String,
+    pub derived_status: String,
+    pub reasons: Vec<HealthReason>,
+    pub is_manual: bool,
+    pub manual_reason: Option<String>,
+}
+
+// What is known about one initiative when judging its health
+#[derive(Debug, Clone, Default)]
+pub struct HealthSignals {
+    pub status: String,
+    // End date compared with the same initiative in the baseline, when there is one
+    pub slip_days: Option<i64>,
+    pub planned_percent: Option<f64>,
+    pub reported_percent: f64,
+    pub hard_violations: Vec<String>,
+    pub has_allocations: bool,
+}
+
+fn rank(level: &str) -> usize {
+    HEALTH_LEVELS.iter().position(|l| *l == level).unwrap_or(0)
+}
+
+fn graded(value: f64, amber: f64, red: f64) -> Option<&'static str> {
+    if value >= red {
+        Some("Red")
+    } else if value >= amber {
+        Some("Amber")
+    } else {
+        None
+    }
+}
+
+/// Judge health from the signals. The worst reason sets the status:
+/// - an end date slipped past the baseline's by the amber or red number of days
+/// - reported progress behind planned progress by the amber or red number of points
+/// - any hard constraint violation is Red
+/// - a Planned or In Progress initiative with no pool requirement or named allocation is Amber
+///
+/// Complete initiatives are Green; nothing is left to go wrong.
+pub fn assess_health(signals: &HealthSignals, thresholds: &HealthThresholds) -> (String, Vec<HealthReason>) {
+    let mut reasons = Vec::new();
+    if signals.status == "Complete" {
+        return ("Green".to_string(), reasons);
+    }
+    let mut add = |signal: &str, level: &str, message: String| {
+        reasons.push(HealthReason { signal: signal.to_string(), level: level.to_string(), message });
+    };
+
+    if let Some(days) = signals.slip_days.filter(|d| *d > 0) {
+        if let Some(level) = graded(days as f64, thresholds.slip_amber_days, thresholds.slip_red_days) {
+            add("Slippage", level, format!("{} days behind baseline", days));
+        }
+    }
+    if let Some(planned) = signals.planned_percent {
+        let behind = planned - signals.reported_percent;
+        if let Some(level) = graded(behind, thresholds.progress_amber_points, thresholds.progress_red_points) {
+            add(
+                "Progress",
+                level,
+                format!("{:.0}% complete against {:.0}% planned", signals.reported_percent, planned),
+            );
+        }
+    }
+    for violation in &signals.hard_violations {
+        add("HardConstraint", "Red", violation.clone());
+    }
+    if COMMITTED_STATUSES.contains(&signals.status.as_str()) && !signals.has_allocations {
+        add("Allocation", "Amber", "No resources allocated".to_string());
+    }
+
+    reasons.sort_by_key(|r| std::cmp::Reverse(rank(&r.level)));
+    let status = reasons.first().map(|r| r.level.clone()).unwrap_or_else(|| "Green".to_string());
+    (status, reasons)
+}
+
+async fn read_threshold(pool: &SqlitePool, key: &str, default: f64) -> Result<f64, String> {
+    match read_setting(pool, key).await? {
+        Some(value) => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|v| *v >= 0.0)
+            .ok_or_else(|| format!("Setting {} must be a number of zero or more, got {}", key, value)),
+        None => Ok(default),
+    }
+}
+
+pub async fn read_health_thresholds(pool: &SqlitePool) -> Result<HealthThresholds, String> {
+    let defaults = HealthThresholds::default();
+    let thresholds = HealthThresholds {
+        slip_amber_days: read_threshold(pool, SLIP_AMBER_SETTING, defaults.slip_amber_days).await?,
+        slip_red_days: read_threshold(pool, SLIP_RED_SETTING, defaults.slip_red_days).await?,
+        progress_amber_points: read_threshold(pool, PROGRESS_AMBER_SETTING, defaults.progress_amber_points).await?,
+        progress_red_points: read_threshold(pool, PROGRESS_RED_SETTING, defaults.progress_red_points).await?,
+    };
+    if thresholds.slip_red_days < thresholds.slip_amber_days || thresholds.progress_red_points < thresholds.progress_amber_points {
+        return Err("Red health thresholds must be at least their amber thresholds".to_string());
+    }
+    Ok(thresholds)
+}
+
+/// The scenario health is judged against: its latest locked snapshot, or failing that the
+/// baseline scenario. Initiatives are matched by reference code, which copies keep.
+async fn comparison_scenario_id(pool: &SqlitePool, scenario_id: &str) -> Result<Option<String>, String> {
+    let snapshot = sqlx::query_scalar!(
+        "SELECT id FROM scenarios WHERE parent_scenario_id = ? AND is_locked = 1 ORDER BY created_at DESC LIMIT 1",
+        scenario_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    if snapshot.is_some() {
+        return Ok(snapshot);
+    }
+
+    sqlx::query_scalar!(
+        "SELECT id FROM scenarios WHERE is_baseline = 1 AND id != ? ORDER BY created_at LIMIT 1",
+        scenario_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+fn end_date(initiative: &Initiative) -> Option<NaiveDate> {
+    initiative.end_date.as_deref().and_then(parse_date)
+}
+
+// ============================================
+// INITIATIVE HEALTH COMMANDS
+// ============================================
+
+/// Derived health for each of the scenario's initiatives, with the reasons behind it.
+/// Cancelled initiatives are left out.
+#[tauri::command]
+pub async fn get_initiative_health(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<InitiativeHealth>, String> {
+    let data = load_scenario_data(db.clone(), &scenario_id).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let thresholds = read_health_thresholds(pool).await?;
+
+    let baseline_ends: HashMap<String, NaiveDate> = match comparison_scenario_id(pool, &scenario_id).await? {
+        Some(baseline_id) => get_initiatives(db.clone(), Some(baseline_id))
+            .await?
+            .iter()
+            .filter_map(|i| Some((i.reference_code.as_deref()?.to_lowercase(), end_date(i)?)))
+            .collect(),
+        None => HashMap::new(),
+    };
+
+    let mut hard_violations: HashMap<String, Vec<String>> = HashMap::new();
+    for v in check_hard_constraints(&data.initiatives, &data.constraints, &data.constraint_links) {
+        hard_violations.entry(v.initiative_id).or_default().push(v.message);
+    }
+
+    let named: Vec<String> = sqlx::query_scalar!(
+        r#"SELECT DISTINCT ir.initiative_id FROM initiative_resources ir
+        JOIN initiatives i ON i.id = ir.initiative_id WHERE i.scenario_id = ?"#,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let allocated: HashSet<&str> = named
+        .iter()
+        .map(String::as_str)
+        .chain(data.requirements.iter().map(|r| r.initiative_id.as_str()))
+        .collect();
+
+    let manual: HashMap<String, (Option<String>, Option<String>)> = sqlx::query!(
+        r#"SELECT id as "id!", health_override, health_override_reason FROM initiatives
+        WHERE scenario_id = ? AND health_override IS NOT NULL"#,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .into_iter()
+    .map(|r| (r.id, (r.health_override, r.health_override_reason)))
+    .collect();
+
+    let as_of = today();
+    let health = data
+        .initiatives
+        .iter()
+        .filter(|i| i.status != "Cancelled")
+        .map(|initiative| {
+            let slip_days = initiative
+                .reference_code
+                .as_deref()
+                .and_then(|code| baseline_ends.get(&code.to_lowercase()))
+                .zip(end_date(initiative))
+                .map(|(baseline_end, end)| (end - *baseline_end).num_days());
+            let signals = HealthSignals {
+                status: initiative.status.clone(),
+                slip_days,
+                planned_percent: planned_percent(initiative, as_of),
+                reported_percent: initiative.percent_complete,
+                hard_violations: hard_violations.remove(&initiative.id).unwrap_or_default(),
+                has_allocations: allocated.contains(initiative.id.as_str()),
+            };
+            let (derived_status, reasons) = assess_health(&signals, &thresholds);
+            let (manual_status, manual_reason) = manual.get(&initiative.id).cloned().unwrap_or_default();
+
+            InitiativeHealth {
+                initiative_id: initiative.id.clone(),
+                initiative_name: initiative.name.clone(),
+                status: manual_status.clone().unwrap_or_else(|| derived_status.clone()),
+                derived_status,
+                reasons,
+                is_manual: manual_status.is_some(),
+                manual_reason,
+            }
+        })
+        .collect();
+
+    Ok(health)
+}
+
+/// Set a manual health status that wins over the derived one, or clear it with `None`
+#[tauri::command]
+pub async fn set_initiative_health_override(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, status: Option<String>, reason: Option<String>) -> Result<(), String> {
+    if let Some(status) = &status {
+        if !HEALTH_LEVELS.contains(&status.as_str()) {
+            return Err(format!("Health must be one of {}, got {}", HEALTH_LEVELS.join(", "), status));
+        }
+    }
+    let reason = reason.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let initiative = single(fetch_initiatives(&mut tx, std::slice::from_ref(&id)).await?, EntityType::Initiative, &id)?;
+    ensure_scenarios_unlocked(&mut tx, &[], std::slice::from_ref(&id)).await?;
+
+    let now = get_current_timestamp();
+    let reason = if status.is_some() { reason } else { None };
+    sqlx::query!(
+        "UPDATE initiatives SET health_override = ?, health_override_reason = ?, updated_at = ? WHERE id = ?",
+        status,
+        reason,
+        now,
+        id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let description = match &status {
+        Some(status) => format!("Set health of \"{}\" to {} manually", initiative.name, status),
+        None => format!("Cleared the manual health of \"{}\"", initiative.name),
+    };
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Initiative.name().to_string(),
+        entity_id: Some(id),
+        action: "SetHealthOverride".to_string(),
+        description: Some(description),
+        after: Some(serde_json::json!({ "health_override": status, "health_override_reason": reason })),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signals(status: &str) -> HealthSignals {
+        HealthSignals { status: status.to_string(), has_allocations: true, ..Default::default() }
+    }
+
+    #[test]
+    fn the_worst_signal_sets_the_status() {
+        let thresholds = HealthThresholds::default();
+
+        let (status, reasons) = assess_health(&signals("InProgress"), &thresholds);
+        assert_eq!(status, "Green");
+        assert!(reasons.is_empty());
+
+        let slipping = HealthSignals { slip_days: Some(21), has_allocations: false, ..signals("InProgress") };
+        let (status, reasons) = assess_health(&slipping, &thresholds);
+        assert_eq!(status, "Amber");
+        let messages: Vec<&str> = reasons.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, ["21 days behind baseline", "No resources allocated"]);
+
+        let behind = HealthSignals { planned_percent: Some(60.0), reported_percent: 35.0, ..signals("InProgress") };
+        assert_eq!(assess_health(&behind, &thresholds).0, "Red");
+
+        let blocked = HealthSignals { hard_violations: vec!["ends after deadline".to_string()], slip_days: Some(15), ..signals("Planned") };
+        let (status, reasons) = assess_health(&blocked, &thresholds);
+        assert_eq!(status, "Red");
+        assert_eq!(reasons[0].signal, "HardConstraint");
+
+        // Finished work has nothing left to flag
+        assert_eq!(assess_health(&HealthSignals { slip_days: Some(90), ..signals("Complete") }, &thresholds).0, "Green");
+    }
+}
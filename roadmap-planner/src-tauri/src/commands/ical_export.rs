@@ -0,0 +1,163 @@
+// Tauri commands for iCalendar exports
+// One all-day event per dated initiative, for subscribing to a roadmap from a calendar app
+
+use crate::commands::engine::dates::parse_date;
+use crate::commands::{get_initiatives, get_scenario};
+use crate::db::{Initiative, Scenario};
+use chrono::{Duration, NaiveDate, Utc};
+use tauri::State;
+
+// RFC 5545 limit on line length, in octets, before folding
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Escape TEXT values per RFC 5545: backslash, semicolon, comma and line breaks
+pub fn escape_ical_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => escaped.push_str("\\n"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+/// Fold a content line so no physical line is longer than 75 octets, never splitting a character
+pub fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / MAX_LINE_OCTETS * 3);
+    let mut octets = 0;
+    for ch in line.chars() {
+        // Continuation lines start with a space, which counts towards their length
+        if octets + ch.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(ch);
+        octets += ch.len_utf8();
+    }
+    folded
+}
+
+fn ical_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+// An initiative with only one date is shown as a single day on it
+fn event_days(initiative: &Initiative) -> Option<(NaiveDate, NaiveDate)> {
+    let start = initiative.start_date.as_deref().and_then(parse_date);
+    let end = initiative.end_date.as_deref().and_then(parse_date);
+    match (start, end) {
+        (Some(start), Some(end)) if end >= start => Some((start, end)),
+        (Some(day), _) | (None, Some(day)) => Some((day, day)),
+        (None, None) => None,
+    }
+}
+
+/// A VCALENDAR with an all-day VEVENT per dated initiative; undated initiatives are skipped.
+/// `stamp` is the DTSTAMP, in UTC as YYYYMMDDTHHMMSSZ.
+pub fn render_ical(scenario: &Scenario, initiatives: &[Initiative], stamp: &str) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Roadmap Planner//Scenario Export//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", escape_ical_text(&scenario.name)),
+    ];
+
+    for initiative in initiatives {
+        let Some((start, end)) = event_days(initiative) else {
+            continue;
+        };
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@roadmap-planner", initiative.id));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", ical_date(start)));
+        // All-day end dates are exclusive
+        lines.push(format!("DTEND;VALUE=DATE:{}", ical_date(end + Duration::days(1))));
+        lines.push(format!("SUMMARY:{}", escape_ical_text(&initiative.name)));
+        if let Some(description) = initiative.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            lines.push(format!("DESCRIPTION:{}", escape_ical_text(description)));
+        }
+        lines.push(format!("STATUS:{}", if initiative.status == "Cancelled" { "CANCELLED" } else { "CONFIRMED" }));
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ical: String = lines.iter().map(|line| fold_line(line)).collect::<Vec<_>>().join("\r\n");
+    ical.push_str("\r\n");
+    ical
+}
+
+// ============================================
+// ICALENDAR EXPORT COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn export_scenario_ical(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<String, String> {
+    let scenario = get_scenario(db.clone(), scenario_id.clone()).await?;
+    let initiatives = get_initiatives(db, Some(scenario_id)).await?;
+
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    Ok(render_ical(&scenario, &initiatives, &stamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::test_initiative;
+
+    fn initiative(id: &str, start: Option<&str>, end: Option<&str>, description: Option<&str>) -> Initiative {
+        Initiative {
+            name: format!("{}, phase 1", id),
+            description: description.map(str::to_string),
+            start_date: start.map(str::to_string),
+            end_date: end.map(str::to_string),
+            ..test_initiative(id)
+        }
+    }
+
+    #[test]
+    fn special_characters_are_escaped_and_long_lines_folded() {
+        assert_eq!(escape_ical_text("a;b,c\\d\r\ne"), "a\\;b\\,c\\\\d\\ne");
+
+        let long = format!("SUMMARY:{}", "é".repeat(50));
+        let folded = fold_line(&long);
+        assert!(folded.split("\r\n").all(|line| line.len() <= MAX_LINE_OCTETS));
+        assert_eq!(folded.replace("\r\n ", ""), long);
+    }
+
+    #[test]
+    fn dated_initiatives_become_all_day_events() {
+        let scenario = Scenario {
+            id: "baseline".to_string(),
+            name: "Baseline".to_string(),
+            description: None,
+            scenario_type: None,
+            is_baseline: true,
+            parent_scenario_id: None,
+            is_locked: false,
+            created_at: None,
+            updated_at: None,
+        };
+        let initiatives = vec![
+            initiative("ledger", Some("2027-01-01"), Some("2027-03-31"), Some("Move off\nthe mainframe")),
+            initiative("undated", None, None, None),
+            initiative("cutover", None, Some("2027-04-15"), None),
+        ];
+
+        let ical = render_ical(&scenario, &initiatives, "20261016T090000Z");
+        assert!(ical.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ical.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ical.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ical.contains("DTSTART;VALUE=DATE:20270101\r\nDTEND;VALUE=DATE:20270401\r\n"));
+        assert!(ical.contains("SUMMARY:ledger\\, phase 1\r\nDESCRIPTION:Move off\\nthe mainframe\r\n"));
+        assert!(ical.contains("DTSTART;VALUE=DATE:20270415\r\nDTEND;VALUE=DATE:20270416\r\n"));
+        assert!(!ical.contains("undated"));
+    }
+}
@@ -0,0 +1,293 @@
+// Id remapping for rows copied between workspaces
+// Assigns fresh ids and rewrites references, table-driven so new tables only need a rule
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+pub struct ForeignKeyRule {
+    pub column: &'static str,
+    pub table: &'static str,
+    // Rows whose required reference can't be resolved are dropped; optional ones are cleared
+    pub required: bool,
+}
+
+pub struct TableRule {
+    pub table: &'static str,
+    pub foreign_keys: &'static [ForeignKeyRule],
+}
+
+const fn required(column: &'static str, table: &'static str) -> ForeignKeyRule {
+    ForeignKeyRule { column, table, required: true }
+}
+
+const fn optional(column: &'static str, table: &'static str) -> ForeignKeyRule {
+    ForeignKeyRule { column, table, required: false }
+}
+
+// Every table that can travel in a payload. initiatives.scenario_id is set by the
+// importer rather than remapped, so it has no rule here.
+pub const TABLE_RULES: &[TableRule] = &[
+    TableRule { table: "capabilities", foreign_keys: &[optional("parent_id", "capabilities")] },
+    TableRule { table: "capability_assessments", foreign_keys: &[required("capability_id", "capabilities")] },
+    TableRule { table: "systems", foreign_keys: &[optional("capability_id", "capabilities")] },
+    TableRule {
+        table: "system_dependencies",
+        foreign_keys: &[required("source_system_id", "systems"), required("target_system_id", "systems")],
+    },
+    TableRule {
+        table: "interfaces",
+        foreign_keys: &[optional("source_system_id", "systems"), optional("target_system_id", "systems")],
+    },
+    TableRule { table: "resource_pools", foreign_keys: &[] },
+    TableRule { table: "pool_role_capacities", foreign_keys: &[required("resource_pool_id", "resource_pools")] },
+    TableRule { table: "resources", foreign_keys: &[optional("resource_pool_id", "resource_pools")] },
+    TableRule { table: "resource_time_off", foreign_keys: &[required("resource_id", "resources")] },
+    TableRule { table: "constraints", foreign_keys: &[] },
+    TableRule { table: "financial_periods", foreign_keys: &[] },
+    TableRule { table: "initiatives", foreign_keys: &[] },
+    TableRule { table: "milestones", foreign_keys: &[required("initiative_id", "initiatives")] },
+    TableRule {
+        table: "initiative_dependencies",
+        foreign_keys: &[required("predecessor_id", "initiatives"), required("successor_id", "initiatives")],
+    },
+    TableRule {
+        table: "initiative_capabilities",
+        foreign_keys: &[required("initiative_id", "initiatives"), required("capability_id", "capabilities")],
+    },
+    TableRule {
+        table: "initiative_resource_requirements",
+        foreign_keys: &[required("initiative_id", "initiatives"), required("resource_pool_id", "resource_pools")],
+    },
+    TableRule {
+        table: "initiative_pool_splits",
+        foreign_keys: &[required("initiative_id", "initiatives"), required("resource_pool_id", "resource_pools")],
+    },
+    TableRule {
+        table: "initiative_resources",
+        foreign_keys: &[required("initiative_id", "initiatives"), required("resource_id", "resources")],
+    },
+    TableRule {
+        table: "system_initiatives",
+        foreign_keys: &[required("system_id", "systems"), required("initiative_id", "initiatives")],
+    },
+    TableRule {
+        table: "initiative_constraints",
+        foreign_keys: &[required("initiative_id", "initiatives"), required("constraint_id", "constraints")],
+    },
+    TableRule {
+        table: "initiative_objectives",
+        foreign_keys: &[required("initiative_id", "initiatives"), required("objective_id", "objectives")],
+    },
+];
+
+pub fn table_rule(table: &str) -> Option<&'static TableRule> {
+    TABLE_RULES.iter().find(|r| r.table == table)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnresolvedAction {
+    // The reference was optional and set to NULL
+    Cleared,
+    // The reference was required, so the row was not imported
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedReference {
+    pub table: String,
+    pub row_id: String,
+    pub column: String,
+    pub target_table: String,
+    pub target_id: String,
+    // Filled in by the importer from the payload's external references
+    pub target_name: Option<String>,
+    pub action: UnresolvedAction,
+}
+
+#[derive(Debug, Clone)]
+pub struct RemappedRow {
+    pub table: String,
+    pub old_id: String,
+    pub row: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RemapOutcome {
+    // In TABLE_RULES order, so parents come before the rows that reference them
+    pub rows: Vec<RemappedRow>,
+    pub unresolved: Vec<UnresolvedReference>,
+}
+
+fn row_id(row: &Map<String, Value>) -> Option<String> {
+    row.get("id").and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+/// Give every row a new id and rewrite its references. A reference resolves to the new id
+/// of a row in the same payload, else to `external[(table, old id)]`, else it is unresolved.
+/// Ids are assigned before any reference is rewritten, so cycles inside the payload
+/// (mutual dependencies, a capability parented under its own child) remap cleanly.
+pub fn remap_rows(
+    tables: &BTreeMap<String, Vec<Map<String, Value>>>,
+    external: &HashMap<(String, String), String>,
+    mut new_id: impl FnMut() -> String,
+) -> RemapOutcome {
+    let mut ids: HashMap<(String, String), String> = HashMap::new();
+    for rule in TABLE_RULES {
+        for row in tables.get(rule.table).into_iter().flatten() {
+            if let Some(old) = row_id(row) {
+                ids.insert((rule.table.to_string(), old), new_id());
+            }
+        }
+    }
+
+    // Only link tables have required references and nothing references a link row,
+    // so a dropped row never strands another
+    let mut outcome = RemapOutcome::default();
+
+    for rule in TABLE_RULES {
+        for row in tables.get(rule.table).into_iter().flatten() {
+            let Some(old_id) = row_id(row) else {
+                continue;
+            };
+
+            let mut remapped = row.clone();
+            remapped.insert("id".to_string(), Value::String(ids[&(rule.table.to_string(), old_id.clone())].clone()));
+            let mut unresolved = Vec::new();
+
+            for fk in rule.foreign_keys {
+                let Some(target) = row.get(fk.column).and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let target_key = (fk.table.to_string(), target.to_string());
+
+                match ids.get(&target_key).or_else(|| external.get(&target_key)) {
+                    Some(id) => {
+                        remapped.insert(fk.column.to_string(), Value::String(id.clone()));
+                    }
+                    None => {
+                        remapped.insert(fk.column.to_string(), Value::Null);
+                        unresolved.push(UnresolvedReference {
+                            table: rule.table.to_string(),
+                            row_id: old_id.clone(),
+                            column: fk.column.to_string(),
+                            target_table: fk.table.to_string(),
+                            target_id: target.to_string(),
+                            target_name: None,
+                            action: if fk.required { UnresolvedAction::Skipped } else { UnresolvedAction::Cleared },
+                        });
+                    }
+                }
+            }
+
+            if unresolved.iter().any(|u| u.action == UnresolvedAction::Skipped) {
+                // Report only the missing references that forced the drop
+                outcome.unresolved.extend(unresolved.into_iter().filter(|u| u.action == UnresolvedAction::Skipped));
+            } else {
+                outcome.unresolved.extend(unresolved);
+                outcome.rows.push(RemappedRow { table: rule.table.to_string(), old_id, row: remapped });
+            }
+        }
+    }
+
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rows(values: Vec<Value>) -> Vec<Map<String, Value>> {
+        values.into_iter().map(|v| v.as_object().unwrap().clone()).collect()
+    }
+
+    fn sequential_ids() -> impl FnMut() -> String {
+        let mut next = 0;
+        move || {
+            next += 1;
+            format!("new-{}", next)
+        }
+    }
+
+    fn find<'a>(outcome: &'a RemapOutcome, table: &str, old_id: &str) -> Option<&'a Map<String, Value>> {
+        outcome.rows.iter().find(|r| r.table == table && r.old_id == old_id).map(|r| &r.row)
+    }
+
+    #[test]
+    fn cyclic_dependencies_inside_the_payload_are_remapped() {
+        let mut tables = BTreeMap::new();
+        tables.insert("initiatives".to_string(), rows(vec![json!({"id": "a", "name": "A"}), json!({"id": "b", "name": "B"})]));
+        tables.insert(
+            "initiative_dependencies".to_string(),
+            rows(vec![
+                json!({"id": "ab", "predecessor_id": "a", "successor_id": "b"}),
+                json!({"id": "ba", "predecessor_id": "b", "successor_id": "a"}),
+            ]),
+        );
+
+        let outcome = remap_rows(&tables, &HashMap::new(), sequential_ids());
+        assert!(outcome.unresolved.is_empty());
+
+        let a = find(&outcome, "initiatives", "a").unwrap()["id"].clone();
+        let b = find(&outcome, "initiatives", "b").unwrap()["id"].clone();
+        assert_ne!(a, json!("a"));
+        assert_ne!(b, json!("b"));
+
+        let ab = find(&outcome, "initiative_dependencies", "ab").unwrap();
+        assert_eq!((ab["predecessor_id"].clone(), ab["successor_id"].clone()), (a.clone(), b.clone()));
+        let ba = find(&outcome, "initiative_dependencies", "ba").unwrap();
+        assert_eq!((ba["predecessor_id"].clone(), ba["successor_id"].clone()), (b, a));
+    }
+
+    #[test]
+    fn self_referencing_parent_cycle_is_remapped() {
+        let mut tables = BTreeMap::new();
+        tables.insert(
+            "capabilities".to_string(),
+            rows(vec![json!({"id": "x", "parent_id": "y"}), json!({"id": "y", "parent_id": "x"})]),
+        );
+
+        let outcome = remap_rows(&tables, &HashMap::new(), sequential_ids());
+        let x = find(&outcome, "capabilities", "x").unwrap();
+        let y = find(&outcome, "capabilities", "y").unwrap();
+        assert_eq!(x["parent_id"], y["id"]);
+        assert_eq!(y["parent_id"], x["id"]);
+    }
+
+    #[test]
+    fn external_references_resolve_or_are_reported() {
+        let mut tables = BTreeMap::new();
+        tables.insert("initiatives".to_string(), rows(vec![json!({"id": "a"})]));
+        tables.insert(
+            "initiative_capabilities".to_string(),
+            rows(vec![
+                json!({"id": "l1", "initiative_id": "a", "capability_id": "known"}),
+                json!({"id": "l2", "initiative_id": "a", "capability_id": "missing"}),
+            ]),
+        );
+        tables.insert("systems".to_string(), rows(vec![json!({"id": "s", "capability_id": "missing"})]));
+
+        let mut external = HashMap::new();
+        external.insert(("capabilities".to_string(), "known".to_string()), "target-cap".to_string());
+
+        let outcome = remap_rows(&tables, &external, sequential_ids());
+
+        assert_eq!(find(&outcome, "initiative_capabilities", "l1").unwrap()["capability_id"], json!("target-cap"));
+        assert!(find(&outcome, "initiative_capabilities", "l2").is_none());
+        assert_eq!(find(&outcome, "systems", "s").unwrap()["capability_id"], Value::Null);
+
+        let actions: Vec<(&str, &UnresolvedAction)> = outcome.unresolved.iter().map(|u| (u.row_id.as_str(), &u.action)).collect();
+        assert!(actions.contains(&("l2", &UnresolvedAction::Skipped)));
+        assert!(actions.contains(&("s", &UnresolvedAction::Cleared)));
+    }
+
+    #[test]
+    fn every_required_table_is_a_leaf() {
+        // remap_rows relies on this to drop rows in a single pass
+        for rule in TABLE_RULES.iter().filter(|r| r.foreign_keys.iter().any(|fk| fk.required)) {
+            let referenced = TABLE_RULES.iter().flat_map(|r| r.foreign_keys).any(|fk| fk.table == rule.table);
+            assert!(!referenced, "{} has required references and is referenced", rule.table);
+        }
+    }
+}
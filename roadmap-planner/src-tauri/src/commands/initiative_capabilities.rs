@@ -0,0 +1,128 @@
+// Tauri commands for initiative to capability links
+// Link CRUD plus impact analysis derived from the capability hierarchy
+
+use crate::commands::get_initiative;
+use crate::db::{System, get_current_timestamp};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeCapability {
+    pub id: String,
+    pub initiative_id: String,
+    pub capability_id: String,
+    pub created_at: Option<String>,
+}
+
+// ============================================
+// INITIATIVE CAPABILITY LINK COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_initiative_capabilities(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String) -> Result<Vec<InitiativeCapability>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<InitiativeCapability> = sqlx::query_as!(
+        InitiativeCapability,
+        r#"SELECT id, initiative_id, capability_id, created_at
+        FROM initiative_capabilities WHERE initiative_id = ?"#,
+        initiative_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn link_initiative_capability(db: State<'_, tauri_plugin_sql::DbInstances>, link: InitiativeCapability) -> Result<InitiativeCapability, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO initiative_capabilities (id, initiative_id, capability_id, created_at)
+        VALUES (?, ?, ?, ?)"#,
+        link.id,
+        link.initiative_id,
+        link.capability_id,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let row: InitiativeCapability = sqlx::query_as!(
+        InitiativeCapability,
+        r#"SELECT id, initiative_id, capability_id, created_at
+        FROM initiative_capabilities WHERE id = ?"#,
+        link.id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn unlink_initiative_capability(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM initiative_capabilities WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================
+// IMPACT ANALYSIS COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_systems_impacted_by_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String, include_descendants: Option<bool>) -> Result<Vec<System>, String> {
+    // Fail clearly for an unknown initiative rather than returning an empty list
+    get_initiative(db.clone(), initiative_id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let include_descendants = include_descendants.unwrap_or(false);
+
+    // The recursive step only runs when descendants are requested
+    let rows: Vec<System> = sqlx::query_as!(
+        System,
+        r#"WITH RECURSIVE targeted(id) AS (
+            SELECT capability_id FROM initiative_capabilities WHERE initiative_id = ?
+            UNION
+            SELECT c.id FROM capabilities c
+            JOIN targeted t ON c.parent_id = t.id
+            WHERE ?
+        )
+        SELECT DISTINCT
+            s.id, s.name, s.description, s.owner, s.vendor, s.technology_stack,
+            s.lifecycle_stage, s.criticality, s.support_end_date, s.extended_support_end_date,
+            s.capability_id, s.external_ref, s.created_at, s.updated_at
+        FROM systems s
+        WHERE s.capability_id IN (SELECT id FROM targeted)
+        ORDER BY CASE s.criticality
+            WHEN 'Critical' THEN 0
+            WHEN 'High' THEN 1
+            WHEN 'Medium' THEN 2
+            ELSE 3
+        END, s.name"#,
+        initiative_id,
+        include_descendants
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
@@ -0,0 +1,133 @@
+// Tauri commands for the initiative detail panel
+// Loads an initiative and everything linked to it in a single call
+
+use crate::commands::allocations::InitiativeResource;
+use crate::commands::engine::dependencies::InitiativeDependency;
+use crate::commands::engine::resources::InitiativeResourceRequirement;
+use crate::commands::entities::EntityType;
+use crate::commands::fetch::{fetch_initiatives, single};
+use crate::db::{Constraint, Initiative};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkedSystem {
+    pub id: String,
+    pub system_id: String,
+    pub system_name: String,
+    pub lifecycle_stage: String,
+    pub criticality: String,
+    pub relationship_type: String,
+    pub weight: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeDetail {
+    pub initiative: Initiative,
+    // Edges where this initiative is the successor
+    pub predecessors: Vec<InitiativeDependency>,
+    // Edges where this initiative is the predecessor
+    pub successors: Vec<InitiativeDependency>,
+    pub systems: Vec<LinkedSystem>,
+    pub pool_allocations: Vec<InitiativeResourceRequirement>,
+    pub named_allocations: Vec<InitiativeResource>,
+    pub constraints: Vec<Constraint>,
+}
+
+// ============================================
+// INITIATIVE DETAIL COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_initiative_detail(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<InitiativeDetail, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // One read transaction so every part comes from the same snapshot
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let initiative = single(fetch_initiatives(&mut tx, std::slice::from_ref(&id)).await?, EntityType::Initiative, &id)?;
+
+    let predecessors: Vec<InitiativeDependency> = sqlx::query_as!(
+        InitiativeDependency,
+        r#"SELECT id, predecessor_id, successor_id, dependency_type, lag_days, created_at
+        FROM initiative_dependencies WHERE successor_id = ?"#,
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let successors: Vec<InitiativeDependency> = sqlx::query_as!(
+        InitiativeDependency,
+        r#"SELECT id, predecessor_id, successor_id, dependency_type, lag_days, created_at
+        FROM initiative_dependencies WHERE predecessor_id = ?"#,
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let systems: Vec<LinkedSystem> = sqlx::query_as!(
+        LinkedSystem,
+        r#"SELECT
+            si.id, si.system_id, s.name as system_name, s.lifecycle_stage, s.criticality,
+            si.relationship_type, si.weight
+        FROM system_initiatives si
+        JOIN systems s ON s.id = si.system_id
+        WHERE si.initiative_id = ?
+        ORDER BY s.name"#,
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let pool_allocations: Vec<InitiativeResourceRequirement> = sqlx::query_as!(
+        InitiativeResourceRequirement,
+        r#"SELECT id, initiative_id, resource_pool_id, effort_required, period_start, period_end, role,
+            approval_status, approver_note, generated as "generated: bool", created_at
+        FROM initiative_resource_requirements WHERE initiative_id = ?"#,
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let named_allocations: Vec<InitiativeResource> = sqlx::query_as!(
+        InitiativeResource,
+        r#"SELECT id, initiative_id, resource_id, allocation_percent, start_date, end_date, created_at, updated_at
+        FROM initiative_resources WHERE initiative_id = ?"#,
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let constraints: Vec<Constraint> = sqlx::query_as!(
+        Constraint,
+        r#"SELECT
+            c.id, c.name, c.description, c.type as "constraint_type",
+            c.hardness, c.effective_date, c.expiry_date, c.created_at, c.updated_at
+        FROM constraints c
+        JOIN initiative_constraints ic ON ic.constraint_id = c.id
+        WHERE ic.initiative_id = ?
+        ORDER BY c.name"#,
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(InitiativeDetail {
+        initiative,
+        predecessors,
+        successors,
+        systems,
+        pool_allocations,
+        named_allocations,
+        constraints,
+    })
+}
@@ -0,0 +1,219 @@
+// Tauri commands for system interfaces
+// Integration inventory between systems and complexity reporting
+
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interface {
+    pub id: String,
+    pub source_system_id: Option<String>,
+    pub target_system_id: Option<String>,
+    pub name: String,
+    pub protocol: Option<String>,
+    pub frequency: Option<String>,
+    pub data_description: Option<String>,
+    pub criticality: String,
+    pub is_orphaned: bool,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+// What happens to a system's interfaces when the system is deleted
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum InterfaceDeleteStrategy {
+    #[default]
+    Delete,
+    Flag,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationComplexity {
+    pub system_id: String,
+    pub system_name: String,
+    pub system_criticality: String,
+    pub inbound_count: i64,
+    pub outbound_count: i64,
+    pub interface_count: i64,
+    pub weighted_score: i64,
+}
+
+// ============================================
+// INTERFACES COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_interfaces(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<Vec<Interface>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<Interface> = sqlx::query_as!(
+        Interface,
+        r#"SELECT
+            id, source_system_id, target_system_id, name, protocol, frequency,
+            data_description, criticality, is_orphaned as "is_orphaned: bool",
+            created_at, updated_at
+        FROM interfaces ORDER BY name"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_interface(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<Interface, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let row: Interface = sqlx::query_as!(
+        Interface,
+        r#"SELECT
+            id, source_system_id, target_system_id, name, protocol, frequency,
+            data_description, criticality, is_orphaned as "is_orphaned: bool",
+            created_at, updated_at
+        FROM interfaces WHERE id = ?"#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn get_interfaces_for_system(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<Vec<Interface>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // Both inbound and outbound interfaces
+    let rows: Vec<Interface> = sqlx::query_as!(
+        Interface,
+        r#"SELECT
+            id, source_system_id, target_system_id, name, protocol, frequency,
+            data_description, criticality, is_orphaned as "is_orphaned: bool",
+            created_at, updated_at
+        FROM interfaces
+        WHERE source_system_id = ? OR target_system_id = ?
+        ORDER BY name"#,
+        id,
+        id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn create_interface(db: State<'_, tauri_plugin_sql::DbInstances>, interface: Interface) -> Result<Interface, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO interfaces (id, source_system_id, target_system_id, name, protocol, frequency,
+            data_description, criticality, is_orphaned, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0, ?, ?)"#,
+        interface.id,
+        interface.source_system_id,
+        interface.target_system_id,
+        interface.name,
+        interface.protocol,
+        interface.frequency,
+        interface.data_description,
+        interface.criticality,
+        now,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_interface(db, interface.id).await
+}
+
+#[tauri::command]
+pub async fn update_interface(db: State<'_, tauri_plugin_sql::DbInstances>, interface: Interface) -> Result<Interface, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"UPDATE interfaces SET
+            source_system_id = ?, target_system_id = ?, name = ?, protocol = ?, frequency = ?,
+            data_description = ?, criticality = ?, is_orphaned = ?, updated_at = ?
+        WHERE id = ?"#,
+        interface.source_system_id,
+        interface.target_system_id,
+        interface.name,
+        interface.protocol,
+        interface.frequency,
+        interface.data_description,
+        interface.criticality,
+        interface.is_orphaned,
+        now,
+        interface.id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_interface(db, interface.id).await
+}
+
+#[tauri::command]
+pub async fn delete_interface(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM interfaces WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================
+// INTEGRATION REPORTING COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_integration_complexity_report(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<Vec<IntegrationComplexity>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // Each interface is weighted by its criticality (Critical 4 .. Low 1)
+    let rows: Vec<IntegrationComplexity> = sqlx::query_as!(
+        IntegrationComplexity,
+        r#"SELECT
+            s.id as "system_id!", s.name as system_name, s.criticality as system_criticality,
+            COUNT(CASE WHEN i.target_system_id = s.id THEN 1 END) as "inbound_count!: i64",
+            COUNT(CASE WHEN i.source_system_id = s.id THEN 1 END) as "outbound_count!: i64",
+            COUNT(i.id) as "interface_count!: i64",
+            COALESCE(SUM(CASE i.criticality
+                WHEN 'Critical' THEN 4
+                WHEN 'High' THEN 3
+                WHEN 'Medium' THEN 2
+                WHEN 'Low' THEN 1
+                ELSE 0
+            END), 0) as "weighted_score!: i64"
+        FROM systems s
+        LEFT JOIN interfaces i
+            ON (i.source_system_id = s.id OR i.target_system_id = s.id) AND i.is_orphaned = 0
+        GROUP BY s.id, s.name, s.criticality
+        ORDER BY 7 DESC, 6 DESC, s.name"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
@@ -0,0 +1,489 @@
+// Tauri commands for capability investment analysis
+// Attributes phased initiative cost to the capability model
+
+use crate::commands::engine::budget::{PeriodCost, cost_in_span};
+use crate::commands::engine::currency::{CurrencyConverter, CurrencyWarning};
+use crate::commands::engine::dates::{DateSpan, FINANCIAL_PERIOD_TYPES, merge_spans};
+use crate::commands::entities::EntityType;
+use crate::commands::exchange_rates::load_currency_converter;
+use crate::commands::fetch::{fetch_capabilities, single};
+use crate::commands::{get_capabilities, get_financial_periods, get_initiatives, require_scenario};
+use crate::db::{Capability, FinancialPeriod, Initiative};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityInvestment {
+    pub capability_id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    pub depth: u32,
+    // Cost attributed to this capability's own systems
+    pub direct_amount: f64,
+    // Direct amount plus everything attributed to descendants
+    pub rollup_amount: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityInvestmentHeatmap {
+    pub scenario_id: String,
+    pub from: String,
+    pub to: String,
+    // Reporting currency of every amount; rates are taken as of the window start
+    pub currency: String,
+    pub total_amount_native: BTreeMap<String, f64>,
+    // Sum of direct amounts plus the unmapped bucket; matches the budget report for the window
+    pub total_amount: f64,
+    pub unmapped_amount: f64,
+    pub unmapped_initiative_ids: Vec<String>,
+    pub capabilities: Vec<CapabilityInvestment>,
+    pub warnings: Vec<CurrencyWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NeglectedCapability {
+    pub capability_id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageStats {
+    pub scenario_id: String,
+    // True when only capabilities without children are counted
+    pub leaves_only: bool,
+    pub capability_count: i64,
+    pub covered_count: i64,
+    // covered_count / capability_count as a percentage; 0 when there are no capabilities
+    pub coverage_percent: f64,
+    // Counted capabilities no active initiative touches, by name
+    pub neglected: Vec<NeglectedCapability>,
+}
+
+// An initiative's link to a capability through one of the systems it touches
+pub struct CapabilityLink {
+    pub initiative_id: String,
+    pub capability_id: String,
+    pub weight: Option<f64>,
+}
+
+/// Links from the scenario's initiatives to the capabilities of their systems
+pub async fn fetch_capability_links(pool: &SqlitePool, scenario_id: &str) -> Result<Vec<CapabilityLink>, String> {
+    let links: Vec<CapabilityLink> = sqlx::query_as!(
+        CapabilityLink,
+        r#"SELECT si.initiative_id, s.capability_id as "capability_id!", si.weight
+        FROM system_initiatives si
+        JOIN systems s ON s.id = si.system_id
+        JOIN initiatives i ON i.id = si.initiative_id
+        WHERE i.scenario_id = ? AND s.capability_id IS NOT NULL"#,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(links)
+}
+
+/// Share of an initiative attributed to each capability. Weights are used when every
+/// link carries one; otherwise the initiative is split evenly across its capabilities.
+pub fn capability_shares(links: &[&CapabilityLink]) -> HashMap<String, f64> {
+    let mut shares: HashMap<String, f64> = HashMap::new();
+
+    if links.iter().all(|l| l.weight.is_some()) {
+        let total: f64 = links.iter().filter_map(|l| l.weight).sum();
+        for link in links {
+            *shares.entry(link.capability_id.clone()).or_default() += link.weight.unwrap_or(0.0) / total;
+        }
+    } else {
+        let distinct: HashSet<&str> = links.iter().map(|l| l.capability_id.as_str()).collect();
+        for capability_id in &distinct {
+            shares.insert(capability_id.to_string(), 1.0 / distinct.len() as f64);
+        }
+    }
+
+    shares
+}
+
+// ============================================
+// CAPABILITY INVESTMENT COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_capability_investment_heatmap(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, from: String, to: String) -> Result<CapabilityInvestmentHeatmap, String> {
+    let window = DateSpan::parse_inclusive(Some(&from), Some(&to))
+        .ok_or_else(|| format!("Invalid date range {} to {}", from, to))?;
+
+    require_scenario(db.clone(), &scenario_id).await?;
+    let capabilities = get_capabilities(db.clone()).await?;
+    let initiatives = get_initiatives(db.clone(), Some(scenario_id.clone())).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let links = fetch_capability_links(pool, &scenario_id).await?;
+    let converter = load_currency_converter(pool).await?;
+
+    let mut links_by_initiative: HashMap<&str, Vec<&CapabilityLink>> = HashMap::new();
+    for link in &links {
+        links_by_initiative.entry(link.initiative_id.as_str()).or_default().push(link);
+    }
+
+    let mut direct: HashMap<String, f64> = HashMap::new();
+    let mut unmapped_amount = 0.0;
+    let mut unmapped_initiative_ids = Vec::new();
+    let mut total_amount_native: BTreeMap<String, f64> = BTreeMap::new();
+    let mut warnings = Vec::new();
+
+    for initiative in &initiatives {
+        let native = cost_in_span(initiative, &window);
+        if native == 0.0 {
+            continue;
+        }
+        let currency = converter.currency_of(initiative.currency.as_deref());
+        *total_amount_native.entry(currency.to_string()).or_default() += native;
+        let Some(amount) = converter.convert_or_warn(native, currency, window.start, "Initiative", &initiative.id, &mut warnings) else {
+            continue;
+        };
+        match links_by_initiative.get(initiative.id.as_str()) {
+            Some(initiative_links) => {
+                for (capability_id, share) in capability_shares(initiative_links) {
+                    *direct.entry(capability_id).or_default() += amount * share;
+                }
+            }
+            None => {
+                unmapped_amount += amount;
+                unmapped_initiative_ids.push(initiative.id.clone());
+            }
+        }
+    }
+
+    let total_amount = direct.values().sum::<f64>() + unmapped_amount;
+
+    Ok(CapabilityInvestmentHeatmap {
+        scenario_id,
+        from,
+        to,
+        currency: converter.reporting_currency.clone(),
+        total_amount_native,
+        total_amount,
+        unmapped_amount,
+        unmapped_initiative_ids,
+        capabilities: build_rollup(&capabilities, &direct),
+        warnings,
+    })
+}
+
+// Flattens the capability tree depth-first, rolling direct amounts up to ancestors
+fn build_rollup(capabilities: &[Capability], direct: &HashMap<String, f64>) -> Vec<CapabilityInvestment> {
+    let known: HashSet<&str> = capabilities.iter().map(|c| c.id.as_str()).collect();
+    let mut children: HashMap<&str, Vec<&Capability>> = HashMap::new();
+    let mut roots: Vec<&Capability> = Vec::new();
+
+    for capability in capabilities {
+        match capability.parent_id.as_deref() {
+            Some(parent) if known.contains(parent) => children.entry(parent).or_default().push(capability),
+            _ => roots.push(capability),
+        }
+    }
+
+    let mut rows = Vec::with_capacity(capabilities.len());
+    let mut visited = HashSet::new();
+    for root in roots {
+        add_rollup_rows(root, 0, &children, direct, &mut visited, &mut rows);
+    }
+    rows
+}
+
+fn add_rollup_rows<'a>(
+    capability: &'a Capability,
+    depth: u32,
+    children: &HashMap<&str, Vec<&'a Capability>>,
+    direct: &HashMap<String, f64>,
+    visited: &mut HashSet<&'a str>,
+    rows: &mut Vec<CapabilityInvestment>,
+) -> f64 {
+    visited.insert(capability.id.as_str());
+
+    let direct_amount = direct.get(&capability.id).copied().unwrap_or(0.0);
+    let index = rows.len();
+    rows.push(CapabilityInvestment {
+        capability_id: capability.id.clone(),
+        name: capability.name.clone(),
+        parent_id: capability.parent_id.clone(),
+        depth,
+        direct_amount,
+        rollup_amount: direct_amount,
+    });
+
+    let mut rollup_amount = direct_amount;
+    for kid in children.get(capability.id.as_str()).into_iter().flatten() {
+        if !visited.contains(kid.id.as_str()) {
+            rollup_amount += add_rollup_rows(kid, depth + 1, children, direct, visited, rows);
+        }
+    }
+
+    rows[index].rollup_amount = rollup_amount;
+    rollup_amount
+}
+
+/// Summed cost of `initiatives` in each period of the finest type defined, in period order and
+/// the reporting currency at each period's start. Periods nothing falls in are kept at zero so
+/// gaps in investment show. Fails rather than leave out cost it has no exchange rate for.
+pub fn investment_timeline(initiatives: &[&Initiative], periods: &[FinancialPeriod], converter: &CurrencyConverter) -> Result<Vec<PeriodCost>, String> {
+    let Some(finest) = FINANCIAL_PERIOD_TYPES
+        .iter()
+        .rev()
+        .find(|t| periods.iter().any(|p| p.period_type == **t))
+    else {
+        return Ok(Vec::new());
+    };
+
+    let worked = merge_spans(
+        initiatives
+            .iter()
+            .filter(|i| i.cost_estimate.is_some())
+            .filter_map(|i| DateSpan::parse_inclusive(i.start_date.as_deref(), i.end_date.as_deref()))
+            .collect(),
+    );
+
+    let mut timeline = Vec::new();
+    for period in periods.iter().filter(|p| p.period_type == *finest) {
+        let Some(period_span) = DateSpan::parse_inclusive(Some(&period.start_date), Some(&period.end_date)) else {
+            continue;
+        };
+        let mut cost = 0.0;
+        for initiative in initiatives {
+            let native = cost_in_span(initiative, &period_span);
+            if native == 0.0 {
+                continue;
+            }
+            let currency = converter.currency_of(initiative.currency.as_deref());
+            cost += converter.convert(native, currency, period_span.start).ok_or_else(|| {
+                format!(
+                    "No exchange rate from {} to {} on {} for initiative {}",
+                    currency, converter.reporting_currency, period.start_date, initiative.name
+                )
+            })?;
+        }
+        timeline.push(PeriodCost {
+            period_id: period.id.clone(),
+            period_name: period.name.clone(),
+            period_type: period.period_type.clone(),
+            start_date: period.start_date.clone(),
+            end_date: period.end_date.clone(),
+            overlap_days: worked.iter().map(|span| span.overlap_days(&period_span)).sum(),
+            cost,
+        });
+    }
+    Ok(timeline)
+}
+
+// ============================================
+// INVESTMENT TIMELINE COMMANDS
+// ============================================
+
+/// Spend on a capability per financial period, from the scenario's initiatives linked to it and,
+/// with `include_descendants`, to any capability below it. An initiative linked to several of
+/// them is counted once.
+#[tauri::command]
+pub async fn get_capability_investment_timeline(db: State<'_, tauri_plugin_sql::DbInstances>, capability_id: String, scenario_id: String, include_descendants: bool) -> Result<Vec<PeriodCost>, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+    let initiatives = get_initiatives(db.clone(), Some(scenario_id.clone())).await?;
+    let periods = get_financial_periods(db.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    single(fetch_capabilities(&mut conn, std::slice::from_ref(&capability_id)).await?, EntityType::Capability, &capability_id)?;
+
+    let linked: HashSet<String> = sqlx::query_scalar!(
+        r#"WITH RECURSIVE targeted(id) AS (
+            SELECT ?
+            UNION
+            SELECT c.id FROM capabilities c JOIN targeted t ON c.parent_id = t.id WHERE ?
+        )
+        SELECT DISTINCT ic.initiative_id as "initiative_id!"
+        FROM initiative_capabilities ic
+        WHERE ic.capability_id IN (SELECT id FROM targeted)"#,
+        capability_id,
+        include_descendants
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .into_iter()
+    .collect();
+
+    let converter = load_currency_converter(pool).await?;
+    let contributing: Vec<&Initiative> = initiatives.iter().filter(|i| linked.contains(&i.id)).collect();
+    investment_timeline(&contributing, &periods, &converter)
+}
+
+/// Share of capabilities at least one active initiative is linked to. Leaves only unless
+/// `include_all`, since a parent is covered whenever any of its children is.
+pub fn investment_coverage(scenario_id: &str, capabilities: &[Capability], covered: &HashSet<String>, include_all: bool) -> CoverageStats {
+    let known: HashSet<&str> = capabilities.iter().map(|c| c.id.as_str()).collect();
+    let parents: HashSet<&str> = capabilities
+        .iter()
+        .filter_map(|c| c.parent_id.as_deref())
+        .filter(|parent| known.contains(parent))
+        .collect();
+
+    let counted: Vec<&Capability> = capabilities
+        .iter()
+        .filter(|c| include_all || !parents.contains(c.id.as_str()))
+        .collect();
+    let mut neglected: Vec<NeglectedCapability> = counted
+        .iter()
+        .filter(|c| !covered.contains(&c.id))
+        .map(|c| NeglectedCapability { capability_id: c.id.clone(), name: c.name.clone(), parent_id: c.parent_id.clone() })
+        .collect();
+    neglected.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let capability_count = counted.len() as i64;
+    let covered_count = capability_count - neglected.len() as i64;
+    CoverageStats {
+        scenario_id: scenario_id.to_string(),
+        leaves_only: !include_all,
+        capability_count,
+        covered_count,
+        coverage_percent: if capability_count > 0 { covered_count as f64 * 100.0 / capability_count as f64 } else { 0.0 },
+        neglected,
+    }
+}
+
+// ============================================
+// INVESTMENT COVERAGE COMMANDS
+// ============================================
+
+/// How many capabilities the scenario's active initiatives touch, linked directly or through
+/// their systems. Complete and Cancelled initiatives don't count.
+#[tauri::command]
+pub async fn get_investment_coverage(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, include_all: Option<bool>) -> Result<CoverageStats, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+    let capabilities = get_capabilities(db.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let covered: HashSet<String> = sqlx::query_scalar!(
+        r#"SELECT ic.capability_id as "capability_id!"
+        FROM initiative_capabilities ic
+        JOIN initiatives i ON i.id = ic.initiative_id
+        WHERE i.scenario_id = ? AND i.status NOT IN ('Complete', 'Cancelled')
+        UNION
+        SELECT s.capability_id as "capability_id!"
+        FROM system_initiatives si
+        JOIN systems s ON s.id = si.system_id
+        JOIN initiatives i ON i.id = si.initiative_id
+        WHERE i.scenario_id = ? AND i.status NOT IN ('Complete', 'Cancelled') AND s.capability_id IS NOT NULL"#,
+        scenario_id,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .into_iter()
+    .collect();
+
+    Ok(investment_coverage(&scenario_id, &capabilities, &covered, include_all.unwrap_or(false)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::test_initiative;
+    use crate::commands::engine::currency::ExchangeRate;
+
+    fn capability(id: &str, parent_id: Option<&str>) -> Capability {
+        Capability {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            capability_type: "Business".to_string(),
+            parent_id: parent_id.map(|p| p.to_string()),
+            colour: None,
+            sort_order: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn coverage_counts_leaves_unless_asked_for_all() {
+        let capabilities = vec![
+            capability("payments", None),
+            capability("cards", Some("payments")),
+            capability("transfers", Some("payments")),
+            capability("onboarding", None),
+        ];
+        let covered: HashSet<String> = ["cards".to_string()].into();
+
+        let leaves = investment_coverage("baseline", &capabilities, &covered, false);
+        assert_eq!((leaves.capability_count, leaves.covered_count), (3, 1));
+        assert!((leaves.coverage_percent - 100.0 / 3.0).abs() < 1e-9);
+        let neglected: Vec<&str> = leaves.neglected.iter().map(|n| n.capability_id.as_str()).collect();
+        assert_eq!(neglected, ["onboarding", "transfers"]);
+
+        let all = investment_coverage("baseline", &capabilities, &covered, true);
+        assert_eq!((all.capability_count, all.covered_count), (4, 1));
+        assert_eq!(investment_coverage("baseline", &[], &covered, false).coverage_percent, 0.0);
+    }
+
+    fn initiative(id: &str, start: &str, end: &str, cost: f64, currency: &str) -> Initiative {
+        Initiative {
+            start_date: Some(start.to_string()),
+            end_date: Some(end.to_string()),
+            cost_estimate: Some(cost),
+            currency: Some(currency.to_string()),
+            ..test_initiative(id)
+        }
+    }
+
+    fn period(id: &str, period_type: &str, start: &str, end: &str) -> FinancialPeriod {
+        FinancialPeriod {
+            id: id.to_string(),
+            name: id.to_string(),
+            period_type: period_type.to_string(),
+            start_date: start.to_string(),
+            end_date: end.to_string(),
+            budget_available: None,
+            currency: None,
+            closed: false,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn timeline_sums_every_finest_period_in_the_reporting_currency() {
+        let rate = ExchangeRate {
+            id: "usd".to_string(),
+            from_currency: "USD".to_string(),
+            to_currency: "GBP".to_string(),
+            rate: 0.8,
+            effective_date: "2026-01-01".to_string(),
+            created_at: None,
+            updated_at: None,
+        };
+        let converter = CurrencyConverter::new("GBP", &[rate]);
+        let periods = vec![
+            period("fy27", "Year", "2027-01-01", "2027-12-31"),
+            period("q1", "Quarter", "2027-01-01", "2027-03-31"),
+            period("q2", "Quarter", "2027-04-01", "2027-06-30"),
+        ];
+        let migration = initiative("migration", "2027-01-01", "2027-01-10", 1000.0, "GBP");
+        let licences = initiative("licences", "2027-01-06", "2027-01-15", 500.0, "USD");
+
+        let timeline = investment_timeline(&[&migration, &licences], &periods, &converter).unwrap();
+        let rows: Vec<(&str, i64, f64)> = timeline.iter().map(|p| (p.period_id.as_str(), p.overlap_days, p.cost)).collect();
+        assert_eq!(rows, [("q1", 15, 1400.0), ("q2", 0, 0.0)]);
+
+        let unconvertible = initiative("expansion", "2027-04-01", "2027-04-30", 300.0, "EUR");
+        assert!(investment_timeline(&[&unconvertible], &periods, &converter).is_err());
+    }
+}
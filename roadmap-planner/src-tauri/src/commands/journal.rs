@@ -0,0 +1,244 @@
+// Operation journal for crash recovery
+// Compound commands record their intent before they run, so an interrupted one can be reported
+
+use crate::commands::settings::read_setting;
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::sync::OnceLock;
+use tauri::State;
+
+// Finished entries kept before the oldest are pruned
+pub const JOURNAL_LIMIT_SETTING: &str = "journal.max_finished_entries";
+pub const DEFAULT_JOURNAL_LIMIT: i64 = 500;
+
+static SESSION_ID: OnceLock<String> = OnceLock::new();
+
+// Identifies this run of the app, so its own operations in flight are never reported
+fn session_id() -> &'static str {
+    SESSION_ID.get_or_init(|| uuid::Uuid::new_v4().to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: String,
+    pub command: String,
+    // The arguments the command was invoked with, to run it again as it was
+    pub args: serde_json::Value,
+    pub status: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
+/// A journaled run of a command. Start it before the command's transaction opens and complete it
+/// on that transaction just before the commit, so it reads Completed exactly when the work did.
+pub struct JournaledOperation {
+    pool: SqlitePool,
+    id: String,
+    completed: bool,
+}
+
+impl JournaledOperation {
+    /// Record that `command` is starting. Written straight to the pool, outside any transaction,
+    /// so the record outlives a crash that loses the command's own work.
+    pub async fn start(pool: &SqlitePool, command: &str, args: serde_json::Value) -> Result<Self, String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let session = session_id();
+        let args_json = args.to_string();
+        let now = get_current_timestamp();
+
+        sqlx::query!(
+            "INSERT INTO operation_journal (id, session_id, command, args_json, status, started_at) VALUES (?, ?, ?, ?, 'Started', ?)",
+            id,
+            session,
+            command,
+            args_json,
+            now
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let limit = read_journal_limit(pool).await?;
+        let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+        prune_journal(&mut conn, limit).await?;
+
+        Ok(Self { pool: pool.clone(), id, completed: false })
+    }
+
+    pub async fn complete(mut self, conn: &mut SqliteConnection) -> Result<(), String> {
+        let now = get_current_timestamp();
+        sqlx::query!(
+            "UPDATE operation_journal SET status = 'Completed', finished_at = ? WHERE id = ?",
+            now,
+            self.id
+        )
+        .execute(conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        self.completed = true;
+        Ok(())
+    }
+}
+
+impl Drop for JournaledOperation {
+    // Dropped without completing means the command returned an error and its transaction rolled
+    // back: a clean failure, not an interruption to recover from
+    fn drop(&mut self) {
+        if self.completed {
+            return;
+        }
+        let pool = self.pool.clone();
+        let id = std::mem::take(&mut self.id);
+        tauri::async_runtime::spawn(async move {
+            let now = get_current_timestamp();
+            let _ = sqlx::query!(
+                "UPDATE operation_journal SET status = 'Failed', finished_at = ? WHERE id = ? AND status = 'Started'",
+                now,
+                id
+            )
+            .execute(&pool)
+            .await;
+        });
+    }
+}
+
+async fn read_journal_limit(pool: &SqlitePool) -> Result<i64, String> {
+    match read_setting(pool, JOURNAL_LIMIT_SETTING).await? {
+        Some(value) => value
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .filter(|v| *v >= 0)
+            .ok_or_else(|| format!("Setting {} must be a whole number of zero or more, got {}", JOURNAL_LIMIT_SETTING, value)),
+        None => Ok(DEFAULT_JOURNAL_LIMIT),
+    }
+}
+
+/// Delete all but the newest `limit` finished entries. Started entries are never pruned.
+pub async fn prune_journal(conn: &mut SqliteConnection, limit: i64) -> Result<u64, String> {
+    let pruned = sqlx::query!(
+        r#"DELETE FROM operation_journal
+        WHERE status <> 'Started' AND id NOT IN (
+            SELECT id FROM operation_journal WHERE status <> 'Started'
+            ORDER BY started_at DESC, id DESC LIMIT ?
+        )"#,
+        limit
+    )
+    .execute(conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected();
+
+    Ok(pruned)
+}
+
+/// Operations an earlier run of the app started and never finished, oldest first
+pub async fn interrupted_operations(conn: &mut SqliteConnection, session_id: &str) -> Result<Vec<JournalEntry>, String> {
+    let rows = sqlx::query!(
+        r#"SELECT id, command, args_json, status, started_at, finished_at FROM operation_journal
+        WHERE status = 'Started' AND session_id <> ?
+        ORDER BY started_at, id"#,
+        session_id
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| JournalEntry {
+            id: r.id,
+            command: r.command,
+            args: serde_json::from_str(&r.args_json).unwrap_or(serde_json::Value::Null),
+            status: r.status,
+            started_at: r.started_at,
+            finished_at: r.finished_at,
+        })
+        .collect())
+}
+
+// ============================================
+// JOURNAL COMMANDS
+// ============================================
+
+/// Operations interrupted by a crash or forced close, for the startup warning. None of their
+/// work was saved; re-run one by invoking its command with its args, then discard the entry.
+#[tauri::command]
+pub async fn recover_incomplete_operations(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<Vec<JournalEntry>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    interrupted_operations(&mut conn, session_id()).await
+}
+
+#[tauri::command]
+pub async fn discard_operation(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let discarded = sqlx::query!(
+        "UPDATE operation_journal SET status = 'Discarded', finished_at = ? WHERE id = ? AND status = 'Started'",
+        now,
+        id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected();
+
+    if discarded == 0 {
+        return Err(format!("No interrupted operation {}", id));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::Connection;
+
+    async fn journal(entries: &[(&str, &str, &str)]) -> SqliteConnection {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        crate::db::migrations::migrate_if_empty(&mut conn).await.unwrap();
+        for (id, session, status) in entries {
+            let started_at = format!("2027-01-01 00:00:0{}", id);
+            sqlx::query!(
+                "INSERT INTO operation_journal (id, session_id, command, args_json, status, started_at) VALUES (?, ?, 'bulk_delete', '{\"ids\":[\"a\"]}', ?, ?)",
+                id,
+                session,
+                status,
+                started_at
+            )
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        }
+        conn
+    }
+
+    #[tokio::test]
+    async fn only_other_runs_unfinished_operations_are_reported() {
+        let mut conn = journal(&[("1", "earlier", "Started"), ("2", "earlier", "Completed"), ("3", "now", "Started")]).await;
+
+        let interrupted = interrupted_operations(&mut conn, "now").await.unwrap();
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].id, "1");
+        assert_eq!(interrupted[0].args, serde_json::json!({ "ids": ["a"] }));
+    }
+
+    #[tokio::test]
+    async fn pruning_keeps_the_newest_finished_entries_and_anything_unfinished() {
+        let mut conn = journal(&[("1", "s", "Completed"), ("2", "s", "Started"), ("3", "s", "Failed"), ("4", "s", "Completed")]).await;
+
+        assert_eq!(prune_journal(&mut conn, 1).await.unwrap(), 2);
+        let left: Vec<String> = sqlx::query_scalar!("SELECT id FROM operation_journal ORDER BY id")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(left, ["2", "4"]);
+    }
+}
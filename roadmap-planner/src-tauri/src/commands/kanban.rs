@@ -0,0 +1,55 @@
+// Tauri commands for the kanban board
+// Initiatives grouped into status columns
+
+use crate::commands::{get_initiatives, require_scenario};
+use crate::db::Initiative;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+// Column order on the board; matches the initiatives.status CHECK constraint
+pub const INITIATIVE_STATUSES: [&str; 5] = ["Proposed", "Planned", "InProgress", "Complete", "Cancelled"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusColumn {
+    pub status: String,
+    pub count: i64,
+    pub initiatives: Vec<Initiative>,
+}
+
+// MoSCoW order, unknown values last
+pub fn priority_rank(priority: &str) -> u8 {
+    match priority {
+        "Must" => 0,
+        "Should" => 1,
+        "Could" => 2,
+        "Wont" => 3,
+        _ => 4,
+    }
+}
+
+// ============================================
+// KANBAN COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_kanban(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<StatusColumn>, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    // Already ordered by start date then name, which the stable sort keeps within a priority
+    let mut initiatives = get_initiatives(db, Some(scenario_id)).await?;
+    initiatives.sort_by_key(|i| priority_rank(&i.priority));
+
+    let columns = INITIATIVE_STATUSES
+        .iter()
+        .map(|status| {
+            let initiatives: Vec<Initiative> = initiatives.iter().filter(|i| i.status == *status).cloned().collect();
+            StatusColumn {
+                status: status.to_string(),
+                count: initiatives.len() as i64,
+                initiatives,
+            }
+        })
+        .collect();
+
+    Ok(columns)
+}
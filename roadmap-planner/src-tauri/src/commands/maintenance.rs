@@ -0,0 +1,155 @@
+// Tauri commands for data maintenance
+// Repairs the user runs on request; nothing here runs automatically
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairReport {
+    // Sibling groups, one per parent plus the roots
+    pub groups_checked: i64,
+    pub groups_repaired: i64,
+    pub capabilities_renumbered: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiblingOrder {
+    pub id: String,
+    pub parent_id: Option<String>,
+    pub name: String,
+    pub sort_order: Option<i64>,
+}
+
+/// New sort_order values for groups whose siblings collide or leave gaps.
+/// Siblings keep their current relative order and are numbered from 1.
+pub fn plan_sort_order_repair(capabilities: &[SiblingOrder]) -> (i64, Vec<Vec<(String, i64)>>) {
+    let mut groups: BTreeMap<Option<&str>, Vec<&SiblingOrder>> = BTreeMap::new();
+    for capability in capabilities {
+        groups.entry(capability.parent_id.as_deref()).or_default().push(capability);
+    }
+
+    let mut repairs = Vec::new();
+    for siblings in groups.values_mut() {
+        // Unset orders sort last, as they would in the tree
+        siblings.sort_by(|a, b| {
+            (a.sort_order.is_none(), a.sort_order, &a.name, &a.id).cmp(&(b.sort_order.is_none(), b.sort_order, &b.name, &b.id))
+        });
+
+        let healthy = siblings.iter().enumerate().all(|(i, s)| s.sort_order == Some(i as i64 + 1));
+        if !healthy {
+            repairs.push(
+                siblings
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, s)| s.sort_order != Some(*i as i64 + 1))
+                    .map(|(i, s)| (s.id.clone(), i as i64 + 1))
+                    .collect(),
+            );
+        }
+    }
+
+    (groups.len() as i64, repairs)
+}
+
+// ============================================
+// MAINTENANCE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn repair_sort_orders(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<RepairReport, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let capabilities: Vec<SiblingOrder> = sqlx::query_as!(
+        SiblingOrder,
+        "SELECT id, parent_id, name, sort_order FROM capabilities"
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let (groups_checked, repairs) = plan_sort_order_repair(&capabilities);
+    let changes: Vec<&(String, i64)> = repairs.iter().flatten().collect();
+
+    if !changes.is_empty() {
+        let now = get_current_timestamp();
+        for (id, sort_order) in &changes {
+            sqlx::query!("UPDATE capabilities SET sort_order = ?, updated_at = ? WHERE id = ?", sort_order, now, id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+
+        let before: serde_json::Map<String, serde_json::Value> = capabilities
+            .iter()
+            .filter(|c| changes.iter().any(|(id, _)| id == &c.id))
+            .map(|c| (c.id.clone(), serde_json::json!(c.sort_order)))
+            .collect();
+        let after: serde_json::Map<String, serde_json::Value> =
+            changes.iter().map(|(id, sort_order)| (id.clone(), serde_json::json!(sort_order))).collect();
+
+        record_audit(&mut tx, NewAuditEntry {
+            entity_type: "Capability".to_string(),
+            action: "RepairSortOrder".to_string(),
+            description: Some(format!("Renumbered {} capabilities in {} sibling groups", changes.len(), repairs.len())),
+            before: Some(serde_json::Value::Object(before)),
+            after: Some(serde_json::Value::Object(after)),
+            ..Default::default()
+        })
+        .await?;
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(RepairReport {
+        groups_checked,
+        groups_repaired: repairs.len() as i64,
+        capabilities_renumbered: changes.len() as i64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capability(id: &str, parent_id: Option<&str>, sort_order: Option<i64>) -> SiblingOrder {
+        SiblingOrder {
+            id: id.to_string(),
+            parent_id: parent_id.map(|p| p.to_string()),
+            name: id.to_string(),
+            sort_order,
+        }
+    }
+
+    #[test]
+    fn collisions_and_gaps_are_renumbered_within_their_group() {
+        let capabilities = vec![
+            // Roots are healthy
+            capability("r1", None, Some(1)),
+            capability("r2", None, Some(2)),
+            // Collision at 1 and a gap before 7
+            capability("a", Some("r1"), Some(1)),
+            capability("b", Some("r1"), Some(1)),
+            capability("c", Some("r1"), Some(7)),
+            // Unset order goes last
+            capability("x", Some("r2"), None),
+            capability("y", Some("r2"), Some(1)),
+        ];
+
+        let (groups_checked, repairs) = plan_sort_order_repair(&capabilities);
+        assert_eq!(groups_checked, 3);
+        assert_eq!(repairs.len(), 2);
+
+        let changes: Vec<(String, i64)> = repairs.into_iter().flatten().collect();
+        assert!(changes.contains(&("b".to_string(), 2)));
+        assert!(changes.contains(&("c".to_string(), 3)));
+        assert!(changes.contains(&("x".to_string(), 2)));
+        // Already in place, so untouched
+        assert!(!changes.iter().any(|(id, _)| id == "a" || id == "y"));
+    }
+}
@@ -0,0 +1,141 @@
+// Tauri commands for Markdown exports
+// Text meant for pasting into wikis and documents
+
+use crate::commands::get_capabilities;
+use crate::db::Capability;
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+const INDENT: &str = "  ";
+
+/// Backslash-escape characters Markdown would otherwise treat as formatting
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut at_start = true;
+    let mut leading_digits = false;
+
+    for ch in text.chars() {
+        let special = match ch {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~' | '#' => true,
+            // Would start a nested list or heading
+            '+' | '-' | '=' => at_start,
+            // "1." or "1)" at the start reads as an ordered list
+            '.' | ')' => leading_digits,
+            _ => false,
+        };
+        if special {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+
+        leading_digits = ch.is_ascii_digit() && (at_start || leading_digits);
+        at_start = false;
+    }
+    escaped
+}
+
+/// Nested bullets in sibling order, with each description as an indented line below its name
+pub fn render_capability_tree(capabilities: &[Capability], include_descriptions: bool) -> String {
+    let known: HashSet<&str> = capabilities.iter().map(|c| c.id.as_str()).collect();
+    let mut children: HashMap<&str, Vec<&Capability>> = HashMap::new();
+    let mut roots: Vec<&Capability> = Vec::new();
+
+    // Capabilities arrive in sort order, so sibling order is preserved
+    for capability in capabilities {
+        match capability.parent_id.as_deref() {
+            Some(parent) if known.contains(parent) => children.entry(parent).or_default().push(capability),
+            _ => roots.push(capability),
+        }
+    }
+
+    let mut lines = Vec::with_capacity(capabilities.len());
+    let mut visited = HashSet::new();
+    for root in roots {
+        add_bullets(root, 0, &children, include_descriptions, &mut visited, &mut lines);
+    }
+
+    let mut markdown = lines.join("\n");
+    markdown.push('\n');
+    markdown
+}
+
+fn add_bullets<'a>(
+    capability: &'a Capability,
+    depth: usize,
+    children: &HashMap<&str, Vec<&'a Capability>>,
+    include_descriptions: bool,
+    visited: &mut HashSet<&'a str>,
+    lines: &mut Vec<String>,
+) {
+    visited.insert(capability.id.as_str());
+
+    let indent = INDENT.repeat(depth);
+    lines.push(format!("{}- {}", indent, escape_markdown(&capability.name)));
+
+    if include_descriptions {
+        // Line breaks would end the list item
+        let description = capability.description.as_deref().unwrap_or_default().split_whitespace().collect::<Vec<_>>().join(" ");
+        if !description.is_empty() {
+            lines.push(format!("{}{}{}", indent, INDENT, escape_markdown(&description)));
+        }
+    }
+
+    for kid in children.get(capability.id.as_str()).into_iter().flatten() {
+        if !visited.contains(kid.id.as_str()) {
+            add_bullets(kid, depth + 1, children, include_descriptions, visited, lines);
+        }
+    }
+}
+
+// ============================================
+// MARKDOWN EXPORT COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn export_capability_tree_markdown(db: State<'_, tauri_plugin_sql::DbInstances>, include_descriptions: Option<bool>) -> Result<String, String> {
+    let capabilities = get_capabilities(db).await?;
+
+    Ok(render_capability_tree(&capabilities, include_descriptions.unwrap_or(false)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capability(id: &str, parent_id: Option<&str>, name: &str, description: Option<&str>) -> Capability {
+        Capability {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.map(|d| d.to_string()),
+            capability_type: "Business".to_string(),
+            parent_id: parent_id.map(|p| p.to_string()),
+            colour: None,
+            sort_order: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn tree_is_nested_in_sibling_order() {
+        let capabilities = vec![
+            capability("a", None, "Customer", Some("Everything\nfacing the customer")),
+            capability("b", Some("a"), "Onboarding", None),
+            capability("c", Some("a"), "Billing", None),
+            capability("d", None, "Operations", None),
+        ];
+
+        assert_eq!(
+            render_capability_tree(&capabilities, true),
+            "- Customer\n  Everything facing the customer\n  - Onboarding\n  - Billing\n- Operations\n"
+        );
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        assert_eq!(escape_markdown("CRM_v2 *core* [beta]"), "CRM\\_v2 \\*core\\* \\[beta\\]");
+        assert_eq!(escape_markdown("- Legacy"), "\\- Legacy");
+        assert_eq!(escape_markdown("2024. Plan"), "2024\\. Plan");
+        assert_eq!(escape_markdown("Plan 2024. Next"), "Plan 2024. Next");
+    }
+}
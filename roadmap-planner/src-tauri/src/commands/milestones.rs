@@ -0,0 +1,176 @@
+// Tauri commands for initiative milestones
+// Milestone CRUD and milestone-driven progress
+
+use crate::commands::get_initiative;
+use crate::db::{Initiative, get_current_timestamp};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    pub id: String,
+    pub initiative_id: String,
+    pub name: String,
+    pub target_date: Option<String>,
+    pub is_complete: bool,
+    pub completed_at: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+// ============================================
+// MILESTONES COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_milestones(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String) -> Result<Vec<Milestone>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<Milestone> = sqlx::query_as!(
+        Milestone,
+        r#"SELECT
+            id, initiative_id, name, target_date, is_complete as "is_complete: bool",
+            completed_at, created_at, updated_at
+        FROM milestones WHERE initiative_id = ? ORDER BY target_date, name"#,
+        initiative_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_milestone(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<Milestone, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let row: Milestone = sqlx::query_as!(
+        Milestone,
+        r#"SELECT
+            id, initiative_id, name, target_date, is_complete as "is_complete: bool",
+            completed_at, created_at, updated_at
+        FROM milestones WHERE id = ?"#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn create_milestone(db: State<'_, tauri_plugin_sql::DbInstances>, milestone: Milestone) -> Result<Milestone, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO milestones (id, initiative_id, name, target_date, is_complete, completed_at, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+        milestone.id,
+        milestone.initiative_id,
+        milestone.name,
+        milestone.target_date,
+        milestone.is_complete,
+        milestone.completed_at,
+        now,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_milestone(db, milestone.id).await
+}
+
+#[tauri::command]
+pub async fn update_milestone(db: State<'_, tauri_plugin_sql::DbInstances>, milestone: Milestone) -> Result<Milestone, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"UPDATE milestones SET
+            initiative_id = ?, name = ?, target_date = ?, is_complete = ?,
+            completed_at = ?, updated_at = ?
+        WHERE id = ?"#,
+        milestone.initiative_id,
+        milestone.name,
+        milestone.target_date,
+        milestone.is_complete,
+        milestone.completed_at,
+        now,
+        milestone.id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_milestone(db, milestone.id).await
+}
+
+#[tauri::command]
+pub async fn delete_milestone(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM milestones WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================
+// PROGRESS COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn recalculate_progress(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String) -> Result<Initiative, String> {
+    let initiative = get_initiative(db.clone(), initiative_id.clone()).await?;
+
+    // Manually reported progress is left alone
+    if !initiative.progress_from_milestones {
+        return Ok(initiative);
+    }
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let counts = sqlx::query!(
+        r#"SELECT
+            COUNT(*) as "total!: i64",
+            COALESCE(SUM(is_complete), 0) as "completed!: i64"
+        FROM milestones WHERE initiative_id = ?"#,
+        initiative_id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if counts.total == 0 {
+        return Err(format!("Initiative {} has no milestones to derive progress from", initiative_id));
+    }
+
+    let percent_complete = counts.completed as f64 / counts.total as f64 * 100.0;
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        "UPDATE initiatives SET percent_complete = ?, updated_at = ? WHERE id = ?",
+        percent_complete,
+        now,
+        initiative_id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_initiative(db, initiative_id).await
+}
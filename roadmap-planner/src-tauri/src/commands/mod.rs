@@ -1,10 +1,111 @@
 // Tauri commands for Roadmap Planner
 // All CRUD operations for entities
 
+pub mod activity;
+pub mod actuals;
+pub mod allocation_approvals;
+pub mod allocations;
+pub mod audit;
+pub mod auto_allocation;
+pub mod backup;
+pub mod bulk;
+pub mod calendars;
+pub mod capability_assessments;
+pub mod capability_import;
+pub mod capability_roadmap;
+pub mod capability_tree;
+pub mod capacity;
+pub mod clipboard;
+pub mod colour_contrast;
+pub mod comments;
+pub mod comparison;
+pub mod compliance;
+pub mod csv_import;
+pub mod data_gaps;
+pub mod dependencies;
+pub mod dependency_graph;
+pub mod digest;
+pub mod dot_export;
+pub mod engine;
+pub mod entities;
+pub mod estate_risk;
+pub mod exchange_rates;
+pub mod fetch;
+pub mod forecasts;
+pub mod funding;
+pub mod health;
+pub mod ical_export;
+pub mod id_remap;
+pub mod initiative_capabilities;
+pub mod initiative_detail;
+pub mod interfaces;
+pub mod investment;
+pub mod journal;
+pub mod kanban;
+pub mod maintenance;
+pub mod markdown;
+pub mod milestones;
+pub mod objectives;
+pub mod ownership;
+pub mod period_close;
+pub mod period_structure;
+pub mod pool_delete;
+pub mod pool_roles;
+pub mod pool_splits;
+pub mod projection;
+pub mod reference_codes;
+pub mod risk;
+pub mod rows;
+pub mod scenario_approvals;
+pub mod scenario_data;
+pub mod scenario_growth;
+pub mod scenario_overrides;
+pub mod scenario_stats;
+pub mod scheduling;
+pub mod schema;
+pub mod settings;
+pub mod simulation;
+pub mod stale_data;
+pub mod summaries;
+pub mod time_off;
+pub mod timeline;
+pub mod tsv;
+pub mod upsert;
+pub mod validation;
+pub mod workspace_diff;
+pub mod workspace_merge;
+pub mod xlsx_export;
+
 use crate::db::{
     Capability, Constraint, FinancialPeriod, Initiative, Resource, ResourcePool, Scenario, System,
     get_current_timestamp,
 };
+use audit::{NewAuditEntry, record_audit};
+use clipboard::{collect_entity_rows, insert_remapped_rows};
+use engine::budget::{HorizonOverrun, horizon_overrun, planning_horizon_end};
+use engine::dates::{
+    FINANCIAL_PERIOD_TYPES, POOL_PERIOD_TYPES, add_months, format_date, normalise_period_type, parse_date, period_label, period_months, today,
+};
+use engine::effort::{validate_effort_profile, validate_effort_unit};
+use entities::EntityType;
+use exchange_rates::validate_currency_code;
+use fetch::{
+    fetch_capabilities, fetch_constraints, fetch_financial_periods, fetch_initiatives, fetch_resource_pools, fetch_resources,
+    fetch_scenarios, fetch_systems, single,
+};
+use id_remap::{RemappedRow, remap_rows, table_rule};
+use interfaces::InterfaceDeleteStrategy;
+use journal::JournaledOperation;
+use period_close::PeriodClosedError;
+use period_structure::{PeriodDeleteSummary, PeriodInUseError, count_period_references, plan_period_split};
+use ownership::{ResourceOwnsError, count_owned, transfer_ownership};
+use pool_delete::{PoolDeleteStrategy, PoolDeleteSummary, PoolInUseError, count_pool_references, move_pool_splits};
+use reference_codes::{claim_reference_code, next_reference_code, normalise_reference_code};
+use rows::{ids_json, row_to_json};
+use serde::{Deserialize, Serialize};
+use settings::read_reporting_currency;
+use sqlx::SqliteConnection;
+use std::collections::HashMap;
 use tauri::State;
 use tauri_plugin_sql::{Migration, MigrationKind};
 
@@ -41,21 +142,9 @@ pub async fn get_capability(db: State<'_, tauri_plugin_sql::DbInstances>, id: St
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
-    let row: Capability = sqlx::query_as!(
-        Capability,
-        r#"SELECT
-            id, name, description,
-            type as "capability_type",
-            parent_id, colour, sort_order,
-            created_at, updated_at
-        FROM capabilities WHERE id = ?"#,
-        id
-    )
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    Ok(row)
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let rows = fetch_capabilities(&mut conn, std::slice::from_ref(&id)).await?;
+    single(rows, EntityType::Capability, &id)
 }
 
 #[tauri::command]
@@ -126,6 +215,210 @@ pub async fn delete_capability(db: State<'_, tauri_plugin_sql::DbInstances>, id:
     Ok(())
 }
 
+#[tauri::command]
+pub async fn merge_capabilities(db: State<'_, tauri_plugin_sql::DbInstances>, keep_id: String, merge_id: String) -> Result<(), String> {
+    if keep_id == merge_id {
+        return Err("Cannot merge a capability into itself".to_string());
+    }
+
+    get_capability(db.clone(), keep_id.clone()).await
+        .map_err(|_| format!("Capability {} not found", keep_id))?;
+    get_capability(db.clone(), merge_id.clone()).await
+        .map_err(|_| format!("Capability {} not found", merge_id))?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // The merged capability's children move under the kept one, so the kept one
+    // must not sit beneath the merged one
+    let is_descendant = sqlx::query_scalar!(
+        r#"WITH RECURSIVE ancestors(id, parent_id) AS (
+            SELECT id, parent_id FROM capabilities WHERE id = ?
+            UNION
+            SELECT c.id, c.parent_id FROM capabilities c
+            JOIN ancestors a ON c.id = a.parent_id
+        )
+        SELECT COUNT(*) FROM ancestors WHERE id = ?"#,
+        keep_id,
+        merge_id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if is_descendant > 0 {
+        return Err(format!(
+            "Cannot merge {} into {}: {} is a descendant of {} and the merge would create a cycle",
+            merge_id, keep_id, keep_id, merge_id
+        ));
+    }
+
+    let now = get_current_timestamp();
+    let operation = JournaledOperation::start(pool, "merge_capabilities", serde_json::json!({ "keepId": keep_id, "mergeId": merge_id })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Links held by a locked snapshot's initiatives can't be moved
+    let linked: Vec<String> = sqlx::query_scalar!("SELECT initiative_id FROM initiative_capabilities WHERE capability_id = ?", merge_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    ensure_scenarios_unlocked(&mut tx, &[], &linked).await?;
+
+    sqlx::query!(
+        "UPDATE systems SET capability_id = ?, updated_at = ? WHERE capability_id = ?",
+        keep_id,
+        now,
+        merge_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Initiatives already linked to both only keep their existing link
+    sqlx::query!(
+        r#"DELETE FROM initiative_capabilities
+        WHERE capability_id = ? AND initiative_id IN (
+            SELECT initiative_id FROM initiative_capabilities WHERE capability_id = ?
+        )"#,
+        merge_id,
+        keep_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query!(
+        "UPDATE initiative_capabilities SET capability_id = ? WHERE capability_id = ?",
+        keep_id,
+        merge_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query!(
+        "UPDATE capabilities SET parent_id = ?, updated_at = ? WHERE parent_id = ?",
+        keep_id,
+        now,
+        merge_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query!("DELETE FROM capabilities WHERE id = ?", merge_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilitySystemCount {
+    pub capability_id: String,
+    // Systems whose capability_id is this capability, not its descendants
+    pub system_count: i64,
+}
+
+#[tauri::command]
+pub async fn get_capability_system_counts(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<Vec<CapabilitySystemCount>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<CapabilitySystemCount> = sqlx::query_as!(
+        CapabilitySystemCount,
+        r#"SELECT c.id as "capability_id!", COUNT(s.id) as "system_count!: i64"
+        FROM capabilities c
+        LEFT JOIN systems s ON s.capability_id = c.id
+        GROUP BY c.id
+        ORDER BY c.id"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityExposure {
+    pub capability_id: String,
+    pub capability_name: String,
+    pub system_count: i64,
+    // Earliest support_end_date among the systems, None when none has one
+    pub soonest_support_end_date: Option<String>,
+    // Negative once that date has passed
+    pub days_to_end_of_support: Option<i64>,
+    pub systems_past_support: i64,
+}
+
+#[tauri::command]
+pub async fn get_capability_eol_exposure(db: State<'_, tauri_plugin_sql::DbInstances>, include_descendants: Option<bool>) -> Result<Vec<CapabilityExposure>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let include_descendants = include_descendants.unwrap_or(false);
+    let today = today();
+    let today_str = format_date(today);
+
+    // Every capability covers itself; the recursive step only runs when descendants roll up
+    let rows = sqlx::query!(
+        r#"WITH RECURSIVE covered(root_id, id) AS (
+            SELECT id, id FROM capabilities
+            UNION
+            SELECT cv.root_id, c.id FROM capabilities c
+            JOIN covered cv ON c.parent_id = cv.id
+            WHERE ?
+        )
+        SELECT c.id as "capability_id!", c.name as "capability_name!",
+            COUNT(s.id) as "system_count!: i64",
+            MIN(s.support_end_date) as "soonest_support_end_date: String",
+            COALESCE(SUM(s.support_end_date < ?), 0) as "systems_past_support!: i64"
+        FROM capabilities c
+        JOIN covered cv ON cv.root_id = c.id
+        LEFT JOIN systems s ON s.capability_id = cv.id
+        GROUP BY c.id, c.name"#,
+        include_descendants,
+        today_str
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut exposure: Vec<CapabilityExposure> = rows
+        .into_iter()
+        .map(|r| CapabilityExposure {
+            days_to_end_of_support: r
+                .soonest_support_end_date
+                .as_deref()
+                .and_then(parse_date)
+                .map(|d| (d - today).num_days()),
+            capability_id: r.capability_id,
+            capability_name: r.capability_name,
+            system_count: r.system_count,
+            soonest_support_end_date: r.soonest_support_end_date,
+            systems_past_support: r.systems_past_support,
+        })
+        .collect();
+
+    // Soonest end of support first; capabilities with no dated systems last
+    exposure.sort_by(|a, b| {
+        match (&a.soonest_support_end_date, &b.soonest_support_end_date) {
+            (Some(x), Some(y)) => x.cmp(y),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+        .then_with(|| a.capability_name.cmp(&b.capability_name))
+    });
+
+    Ok(exposure)
+}
+
 // ============================================
 // SYSTEMS COMMANDS
 // ============================================
@@ -140,7 +433,7 @@ pub async fn get_systems(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result
         r#"SELECT
             id, name, description, owner, vendor, technology_stack,
             lifecycle_stage, criticality, support_end_date, extended_support_end_date,
-            capability_id, created_at, updated_at
+            capability_id, external_ref, created_at, updated_at
         FROM systems ORDER BY name"#
     )
     .fetch_all(pool)
@@ -155,20 +448,9 @@ pub async fn get_system(db: State<'_, tauri_plugin_sql::DbInstances>, id: String
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
-    let row: System = sqlx::query_as!(
-        System,
-        r#"SELECT
-            id, name, description, owner, vendor, technology_stack,
-            lifecycle_stage, criticality, support_end_date, extended_support_end_date,
-            capability_id, created_at, updated_at
-        FROM systems WHERE id = ?"#,
-        id
-    )
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    Ok(row)
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let rows = fetch_systems(&mut conn, std::slice::from_ref(&id)).await?;
+    single(rows, EntityType::System, &id)
 }
 
 #[tauri::command]
@@ -181,7 +463,7 @@ pub async fn get_systems_by_capability(db: State<'_, tauri_plugin_sql::DbInstanc
         r#"SELECT
             id, name, description, owner, vendor, technology_stack,
             lifecycle_stage, criticality, support_end_date, extended_support_end_date,
-            capability_id, created_at, updated_at
+            capability_id, external_ref, created_at, updated_at
         FROM systems WHERE capability_id = ? ORDER BY name"#,
         capability_id
     )
@@ -197,6 +479,11 @@ pub async fn create_system(db: State<'_, tauri_plugin_sql::DbInstances>, system:
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    if let Some(capability_id) = &system.capability_id {
+        let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+        ensure_exists(&mut conn, EntityType::Capability, capability_id).await?;
+    }
+
     let now = get_current_timestamp();
     let tech_stack_json = system.technology_stack.as_ref()
         .map(|ts| serde_json::to_string(ts).unwrap_or_default());
@@ -204,8 +491,8 @@ pub async fn create_system(db: State<'_, tauri_plugin_sql::DbInstances>, system:
     sqlx::query!(
         r#"INSERT INTO systems (id, name, description, owner, vendor, technology_stack,
             lifecycle_stage, criticality, support_end_date, extended_support_end_date,
-            capability_id, created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            capability_id, external_ref, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         system.id,
         system.name,
         system.description,
@@ -217,6 +504,7 @@ pub async fn create_system(db: State<'_, tauri_plugin_sql::DbInstances>, system:
         system.support_end_date,
         system.extended_support_end_date,
         system.capability_id,
+        system.external_ref,
         now,
         now
     )
@@ -232,6 +520,11 @@ pub async fn update_system(db: State<'_, tauri_plugin_sql::DbInstances>, system:
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    if let Some(capability_id) = &system.capability_id {
+        let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+        ensure_exists(&mut conn, EntityType::Capability, capability_id).await?;
+    }
+
     let now = get_current_timestamp();
     let tech_stack_json = system.technology_stack.as_ref()
         .map(|ts| serde_json::to_string(ts).unwrap_or_default());
@@ -240,7 +533,7 @@ pub async fn update_system(db: State<'_, tauri_plugin_sql::DbInstances>, system:
         r#"UPDATE systems SET
             name = ?, description = ?, owner = ?, vendor = ?, technology_stack = ?,
             lifecycle_stage = ?, criticality = ?, support_end_date = ?, extended_support_end_date = ?,
-            capability_id = ?, updated_at = ?
+            capability_id = ?, external_ref = ?, updated_at = ?
         WHERE id = ?"#,
         system.name,
         system.description,
@@ -252,6 +545,7 @@ pub async fn update_system(db: State<'_, tauri_plugin_sql::DbInstances>, system:
         system.support_end_date,
         system.extended_support_end_date,
         system.capability_id,
+        system.external_ref,
         now,
         system.id
     )
@@ -263,15 +557,45 @@ pub async fn update_system(db: State<'_, tauri_plugin_sql::DbInstances>, system:
 }
 
 #[tauri::command]
-pub async fn delete_system(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+pub async fn delete_system(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, interface_strategy: Option<InterfaceDeleteStrategy>) -> Result<(), String> {
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Interfaces are either removed with the system or kept and flagged as orphaned
+    match interface_strategy.unwrap_or_default() {
+        InterfaceDeleteStrategy::Delete => {
+            sqlx::query!(
+                "DELETE FROM interfaces WHERE source_system_id = ? OR target_system_id = ?",
+                id,
+                id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+        InterfaceDeleteStrategy::Flag => {
+            let now = get_current_timestamp();
+            sqlx::query!(
+                "UPDATE interfaces SET is_orphaned = 1, updated_at = ? WHERE source_system_id = ? OR target_system_id = ?",
+                now,
+                id,
+                id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
     sqlx::query!("DELETE FROM systems WHERE id = ?", id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
 
+    tx.commit().await.map_err(|e| e.to_string())?;
+
     Ok(())
 }
 
@@ -279,6 +603,65 @@ pub async fn delete_system(db: State<'_, tauri_plugin_sql::DbInstances>, id: Str
 // INITIATIVES COMMANDS
 // ============================================
 
+fn validate_percent_complete(percent_complete: f64) -> Result<(), String> {
+    if !(0.0..=100.0).contains(&percent_complete) {
+        return Err(format!("Percent complete must be between 0 and 100, got {}", percent_complete));
+    }
+    Ok(())
+}
+
+pub const INITIATIVE_ICONS: [&str; 3] = ["risk", "regulatory", "customer-facing"];
+
+/// Accepts #RGB or #RRGGBB
+pub fn validate_hex_colour(colour: &str) -> Result<(), String> {
+    let digits = colour.strip_prefix('#').unwrap_or_default();
+    if !matches!(digits.len(), 3 | 6) || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Colour must be a hex value like #1E90FF, got {}", colour));
+    }
+    Ok(())
+}
+
+pub fn validate_initiative_appearance(colour: Option<&str>, icon: Option<&str>) -> Result<(), String> {
+    if let Some(colour) = colour {
+        validate_hex_colour(colour)?;
+    }
+    if let Some(icon) = icon {
+        if !INITIATIVE_ICONS.contains(&icon) {
+            return Err(format!("Unknown icon {}, expected one of {}", icon, INITIATIVE_ICONS.join(", ")));
+        }
+    }
+    Ok(())
+}
+
+// An unset currency defaults to the workspace reporting currency
+async fn resolve_currency(pool: &sqlx::SqlitePool, currency: Option<&str>) -> Result<String, String> {
+    match currency {
+        Some(code) => {
+            validate_currency_code(code)?;
+            Ok(code.to_string())
+        }
+        None => read_reporting_currency(pool).await,
+    }
+}
+
+// A created or updated initiative, with warnings that don't block the save
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedInitiative {
+    #[serde(flatten)]
+    pub initiative: Initiative,
+    pub horizon_warnings: Vec<HorizonOverrun>,
+}
+
+async fn saved_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<SavedInitiative, String> {
+    let initiative = get_initiative(db.clone(), id).await?;
+    let periods = get_financial_periods(db).await?;
+
+    Ok(SavedInitiative {
+        horizon_warnings: horizon_overrun(&initiative, planning_horizon_end(&periods)).into_iter().collect(),
+        initiative,
+    })
+}
+
 #[tauri::command]
 pub async fn get_initiatives(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: Option<String>) -> Result<Vec<Initiative>, String> {
     let pool = db.0.get("sqlite:roadmap.db")
@@ -291,6 +674,7 @@ pub async fn get_initiatives(db: State<'_, tauri_plugin_sql::DbInstances>, scena
                 id, name, description, type as "initiative_type", status,
                 start_date, end_date, effort_estimate, effort_uncertainty,
                 cost_estimate, cost_uncertainty, priority, scenario_id,
+                percent_complete, progress_from_milestones as "progress_from_milestones: bool", currency, effort_unit, effort_profile, external_ref, reference_code, is_key_date as "is_key_date: bool", colour, icon,
                 created_at, updated_at
             FROM initiatives WHERE scenario_id = ? ORDER BY start_date, name"#,
             sid
@@ -304,6 +688,7 @@ pub async fn get_initiatives(db: State<'_, tauri_plugin_sql::DbInstances>, scena
                 id, name, description, type as "initiative_type", status,
                 start_date, end_date, effort_estimate, effort_uncertainty,
                 cost_estimate, cost_uncertainty, priority, scenario_id,
+                percent_complete, progress_from_milestones as "progress_from_milestones: bool", currency, effort_unit, effort_profile, external_ref, reference_code, is_key_date as "is_key_date: bool", colour, icon,
                 created_at, updated_at
             FROM initiatives ORDER BY start_date, name"#
         )
@@ -320,36 +705,145 @@ pub async fn get_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, id: St
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
-    let row: Initiative = sqlx::query_as!(
-        Initiative,
-        r#"SELECT
-            id, name, description, type as "initiative_type", status,
-            start_date, end_date, effort_estimate, effort_uncertainty,
-            cost_estimate, cost_uncertainty, priority, scenario_id,
-            created_at, updated_at
-        FROM initiatives WHERE id = ?"#,
-        id
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let rows = fetch_initiatives(&mut conn, std::slice::from_ref(&id)).await?;
+    single(rows, EntityType::Initiative, &id)
+}
+
+/// The initiative whose external_ref is exactly `external_ref`, ignoring case. For a partial
+/// key, find_initiatives_by_external_ref returns every candidate.
+#[tauri::command]
+pub async fn get_initiative_by_external_ref(db: State<'_, tauri_plugin_sql::DbInstances>, external_ref: String) -> Result<Option<Initiative>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let external_ref = external_ref.trim();
+    if external_ref.is_empty() {
+        return Ok(None);
+    }
+
+    // Scenario copies share the ref; prefer the baseline's, then the most recently edited
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let id = sqlx::query_scalar!(
+        r#"SELECT i.id as "id!" FROM initiatives i
+        JOIN scenarios s ON s.id = i.scenario_id
+        WHERE i.external_ref = ? COLLATE NOCASE
+        ORDER BY s.is_baseline DESC, i.updated_at DESC
+        LIMIT 1"#,
+        external_ref
     )
-    .fetch_one(pool)
+    .fetch_optional(&mut *conn)
     .await
     .map_err(|e| e.to_string())?;
 
-    Ok(row)
+    match id {
+        Some(id) => Ok(fetch_initiatives(&mut conn, &[id]).await?.pop()),
+        None => Ok(None),
+    }
+}
+
+/// Every initiative whose external_ref starts with `prefix`, ignoring case, e.g. all tickets
+/// under a Jira project key. Ordered by ref; scenario copies of one ref come baseline first,
+/// then the most recently edited.
+#[tauri::command]
+pub async fn find_initiatives_by_external_ref(db: State<'_, tauri_plugin_sql::DbInstances>, prefix: String) -> Result<Vec<Initiative>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let prefix = prefix.trim();
+    if prefix.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Bound as a single pattern, LIKE can use the NOCASE index; wildcards in the prefix are literal
+    let pattern = format!("{}%", prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let ids = sqlx::query_scalar!(
+        r#"SELECT i.id as "id!" FROM initiatives i
+        JOIN scenarios s ON s.id = i.scenario_id
+        WHERE i.external_ref LIKE ? ESCAPE '\'
+        ORDER BY i.external_ref COLLATE NOCASE, s.is_baseline DESC, i.updated_at DESC"#,
+        pattern
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut initiatives = fetch_initiatives(&mut conn, &ids).await?;
+    let position: HashMap<&str, usize> = ids.iter().enumerate().map(|(i, id)| (id.as_str(), i)).collect();
+    initiatives.sort_by_key(|i| position[i.id.as_str()]);
+    Ok(initiatives)
+}
+
+/// Look up an initiative by its reference code, e.g. RM-0142
+#[tauri::command]
+pub async fn get_initiative_by_reference(db: State<'_, tauri_plugin_sql::DbInstances>, code: String) -> Result<Option<Initiative>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let Some(code) = normalise_reference_code(&code) else {
+        return Ok(None);
+    };
+
+    // Scenario copies share the code; prefer the baseline's, then the most recently edited
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let id = sqlx::query_scalar!(
+        r#"SELECT i.id as "id!" FROM initiatives i
+        JOIN scenarios s ON s.id = i.scenario_id
+        WHERE i.reference_code = ? COLLATE NOCASE
+        ORDER BY s.is_baseline DESC, i.updated_at DESC
+        LIMIT 1"#,
+        code
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    match id {
+        Some(id) => Ok(fetch_initiatives(&mut conn, &[id]).await?.pop()),
+        None => Ok(None),
+    }
 }
 
 #[tauri::command]
-pub async fn create_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, initiative: Initiative) -> Result<Initiative, String> {
+pub async fn create_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, initiative: Initiative) -> Result<SavedInitiative, String> {
+    validate_percent_complete(initiative.percent_complete)?;
+    if let Some(unit) = &initiative.effort_unit {
+        validate_effort_unit(unit)?;
+    }
+    validate_effort_profile(&initiative.effort_profile)?;
+    validate_initiative_appearance(initiative.colour.as_deref(), initiative.icon.as_deref())?;
+
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    ensure_exists(&mut conn, EntityType::Scenario, &initiative.scenario_id).await?;
+    ensure_scenarios_unlocked(&mut conn, std::slice::from_ref(&initiative.scenario_id), &[]).await?;
+    drop(conn);
+
+    let currency = resolve_currency(pool, initiative.currency.as_deref()).await?;
     let now = get_current_timestamp();
 
+    // The counter and the insert commit together so a failed create doesn't burn a number
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Imports carry their code; everything else is numbered from the counter
+    let reference_code = match initiative.reference_code.as_deref().and_then(normalise_reference_code) {
+        Some(code) => match claim_reference_code(&mut tx, &code).await? {
+            Some(code) => code,
+            None => return Err(format!("Reference code {} is already in use", code)),
+        },
+        None => next_reference_code(&mut tx).await?,
+    };
+
     sqlx::query!(
         r#"INSERT INTO initiatives (id, name, description, type, status,
             start_date, end_date, effort_estimate, effort_uncertainty,
             cost_estimate, cost_uncertainty, priority, scenario_id,
+            percent_complete, progress_from_milestones, currency, effort_unit, effort_profile, external_ref, reference_code, is_key_date, colour, icon,
             created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         initiative.id,
         initiative.name,
         initiative.description,
@@ -363,29 +857,86 @@ pub async fn create_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, ini
         initiative.cost_uncertainty,
         initiative.priority,
         initiative.scenario_id,
+        initiative.percent_complete,
+        initiative.progress_from_milestones,
+        currency,
+        initiative.effort_unit,
+        initiative.effort_profile,
+        initiative.external_ref,
+        reference_code,
+        initiative.is_key_date,
+        initiative.colour,
+        initiative.icon,
         now,
         now
     )
-    .execute(pool)
+    .execute(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
 
-    get_initiative(db, initiative.id).await
+    let created = single(fetch_initiatives(&mut tx, std::slice::from_ref(&initiative.id)).await?, EntityType::Initiative, &initiative.id)?;
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Initiative.name().to_string(),
+        entity_id: Some(initiative.id.clone()),
+        action: "Create".to_string(),
+        description: Some(format!("Created {}", created.name)),
+        after: serde_json::to_value(&created).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    saved_initiative(db, initiative.id).await
 }
 
 #[tauri::command]
-pub async fn update_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, initiative: Initiative) -> Result<Initiative, String> {
+pub async fn update_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, initiative: Initiative) -> Result<SavedInitiative, String> {
+    validate_percent_complete(initiative.percent_complete)?;
+    if let Some(unit) = &initiative.effort_unit {
+        validate_effort_unit(unit)?;
+    }
+    validate_effort_profile(&initiative.effort_profile)?;
+    validate_initiative_appearance(initiative.colour.as_deref(), initiative.icon.as_deref())?;
+
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    // Covers moving an initiative out of a locked scenario as well as into one
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    ensure_exists(&mut conn, EntityType::Scenario, &initiative.scenario_id).await?;
+    ensure_scenarios_unlocked(&mut conn, std::slice::from_ref(&initiative.scenario_id), std::slice::from_ref(&initiative.id)).await?;
+    drop(conn);
+
+    let currency = resolve_currency(pool, initiative.currency.as_deref()).await?;
     let now = get_current_timestamp();
 
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let before = single(fetch_initiatives(&mut tx, std::slice::from_ref(&initiative.id)).await?, EntityType::Initiative, &initiative.id)?;
+
+    // A dependency cannot cross scenarios, so a linked initiative moves through move_initiative
+    if before.scenario_id != initiative.scenario_id {
+        let dependency_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM initiative_dependencies WHERE predecessor_id = ?1 OR successor_id = ?1"#,
+            initiative.id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        if dependency_count > 0 {
+            return Err(format!(
+                "\"{}\" has {} dependency(ies); use move_initiative to move or break them when changing its scenario",
+                before.name, dependency_count
+            ));
+        }
+    }
+
     sqlx::query!(
         r#"UPDATE initiatives SET
             name = ?, description = ?, type = ?, status = ?,
             start_date = ?, end_date = ?, effort_estimate = ?, effort_uncertainty = ?,
             cost_estimate = ?, cost_uncertainty = ?, priority = ?, scenario_id = ?,
-            updated_at = ?
+            percent_complete = ?, progress_from_milestones = ?, currency = ?, effort_unit = ?, effort_profile = ?, external_ref = ?, is_key_date = ?, colour = ?, icon = ?, updated_at = ?
         WHERE id = ?"#,
         initiative.name,
         initiative.description,
@@ -399,14 +950,37 @@ pub async fn update_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, ini
         initiative.cost_uncertainty,
         initiative.priority,
         initiative.scenario_id,
+        initiative.percent_complete,
+        initiative.progress_from_milestones,
+        currency,
+        initiative.effort_unit,
+        initiative.effort_profile,
+        initiative.external_ref,
+        initiative.is_key_date,
+        initiative.colour,
+        initiative.icon,
         now,
         initiative.id
     )
-    .execute(pool)
+    .execute(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
 
-    get_initiative(db, initiative.id).await
+    let after = single(fetch_initiatives(&mut tx, std::slice::from_ref(&initiative.id)).await?, EntityType::Initiative, &initiative.id)?;
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Initiative.name().to_string(),
+        entity_id: Some(initiative.id.clone()),
+        action: "Update".to_string(),
+        description: Some(format!("Updated {}", after.name)),
+        before: serde_json::to_value(&before).ok(),
+        after: serde_json::to_value(&after).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    saved_initiative(db, initiative.id).await
 }
 
 #[tauri::command]
@@ -414,11 +988,29 @@ pub async fn delete_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, id:
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    ensure_scenarios_unlocked(&mut tx, &[], std::slice::from_ref(&id)).await?;
+    let before = fetch_initiatives(&mut tx, std::slice::from_ref(&id)).await?.pop();
+
     sqlx::query!("DELETE FROM initiatives WHERE id = ?", id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
 
+    if let Some(before) = before {
+        record_audit(&mut tx, NewAuditEntry {
+            entity_type: EntityType::Initiative.name().to_string(),
+            entity_id: Some(id.clone()),
+            action: "Delete".to_string(),
+            description: Some(format!("Deleted {}", before.name)),
+            before: serde_json::to_value(&before).ok(),
+            ..Default::default()
+        })
+        .await?;
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
     Ok(())
 }
 
@@ -435,7 +1027,7 @@ pub async fn get_scenarios(db: State<'_, tauri_plugin_sql::DbInstances>) -> Resu
         Scenario,
         r#"SELECT
             id, name, description, type as "scenario_type",
-            is_baseline, parent_scenario_id, created_at, updated_at
+            is_baseline as "is_baseline: bool", parent_scenario_id, is_locked as "is_locked: bool", created_at, updated_at
         FROM scenarios ORDER BY is_baseline DESC, name"#
     )
     .fetch_all(pool)
@@ -450,18 +1042,120 @@ pub async fn get_scenario(db: State<'_, tauri_plugin_sql::DbInstances>, id: Stri
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let rows = fetch_scenarios(&mut conn, std::slice::from_ref(&id)).await?;
+    single(rows, EntityType::Scenario, &id)
+}
+
+/// Fail clearly for an unknown scenario before a read that would otherwise come back empty
+pub async fn require_scenario(db: State<'_, tauri_plugin_sql::DbInstances>, id: &str) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    ensure_exists(&mut conn, EntityType::Scenario, id).await
+}
+
+pub const SCENARIO_LOCKED: &str = "Scenario is locked";
+
+/// Triggers reject writes to a locked scenario anyway; checking first gives a plain error
+pub fn ensure_unlocked(scenario: &Scenario) -> Result<(), String> {
+    if scenario.is_locked {
+        return Err(SCENARIO_LOCKED.to_string());
+    }
+    Ok(())
+}
+
+/// Refuse a write referencing a row that isn't there, which would otherwise be saved pointing
+/// nowhere and drop out of every view that joins on it
+pub async fn ensure_exists(conn: &mut SqliteConnection, entity_type: EntityType, id: &str) -> Result<(), String> {
+    let found: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {} WHERE id = ?", entity_type.table()))
+        .bind(id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if found == 0 {
+        return Err(format!("{} {} does not exist", entity_type.name(), id));
+    }
+    Ok(())
+}
+
+/// Refuse a write touching a locked scenario, named directly or through one of its initiatives
+pub async fn ensure_scenarios_unlocked(conn: &mut SqliteConnection, scenario_ids: &[String], initiative_ids: &[String]) -> Result<(), String> {
+    let scenario_ids = ids_json(scenario_ids);
+    let initiative_ids = ids_json(initiative_ids);
+
+    let locked = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM scenarios
+        WHERE is_locked = 1 AND (
+            id IN (SELECT value FROM json_each(?))
+            OR id IN (SELECT scenario_id FROM initiatives WHERE id IN (SELECT value FROM json_each(?)))
+        )"#,
+        scenario_ids,
+        initiative_ids
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if locked > 0 {
+        return Err(SCENARIO_LOCKED.to_string());
+    }
+    Ok(())
+}
+
+// Must match the row seeded by the initial migration
+const BASELINE_SCENARIO_ID: &str = "baseline";
+
+#[tauri::command]
+pub async fn ensure_baseline(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<Scenario, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // A surviving "baseline" row that lost its flag is restored rather than duplicated
+    sqlx::query!(
+        r#"UPDATE scenarios SET is_baseline = 1, updated_at = ?
+        WHERE id = ? AND NOT EXISTS (SELECT 1 FROM scenarios WHERE is_baseline = 1)"#,
+        now,
+        BASELINE_SCENARIO_ID
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Any flagged scenario counts, whatever its id
+    sqlx::query!(
+        r#"INSERT INTO scenarios (id, name, description, type, is_baseline, parent_scenario_id, created_at, updated_at)
+        SELECT ?, 'Baseline', 'The current known state and committed plans', NULL, 1, NULL, ?, ?
+        WHERE NOT EXISTS (SELECT 1 FROM scenarios WHERE is_baseline = 1)"#,
+        BASELINE_SCENARIO_ID,
+        now,
+        now
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
     let row: Scenario = sqlx::query_as!(
         Scenario,
         r#"SELECT
             id, name, description, type as "scenario_type",
-            is_baseline, parent_scenario_id, created_at, updated_at
-        FROM scenarios WHERE id = ?"#,
-        id
+            is_baseline as "is_baseline: bool", parent_scenario_id, is_locked as "is_locked: bool", created_at, updated_at
+        FROM scenarios WHERE is_baseline = 1
+        ORDER BY id = ? DESC, created_at
+        LIMIT 1"#,
+        BASELINE_SCENARIO_ID
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
 
+    tx.commit().await.map_err(|e| e.to_string())?;
+
     Ok(row)
 }
 
@@ -493,6 +1187,8 @@ pub async fn create_scenario(db: State<'_, tauri_plugin_sql::DbInstances>, scena
 
 #[tauri::command]
 pub async fn update_scenario(db: State<'_, tauri_plugin_sql::DbInstances>, scenario: Scenario) -> Result<Scenario, String> {
+    ensure_unlocked(&get_scenario(db.clone(), scenario.id.clone()).await?)?;
+
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
@@ -531,6 +1227,7 @@ pub async fn delete_scenario(db: State<'_, tauri_plugin_sql::DbInstances>, id: S
     if scenario.is_baseline {
         return Err("Cannot delete the baseline scenario".to_string());
     }
+    ensure_unlocked(&scenario)?;
 
     sqlx::query!("DELETE FROM scenarios WHERE id = ?", id)
         .execute(pool)
@@ -540,6 +1237,229 @@ pub async fn delete_scenario(db: State<'_, tauri_plugin_sql::DbInstances>, id: S
     Ok(())
 }
 
+/// Discard a scenario's initiatives and replace them with fresh copies of the baseline's,
+/// along with their milestones, links and the dependencies between them
+#[tauri::command]
+pub async fn reset_scenario_to_baseline(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<(), String> {
+    let scenario = get_scenario(db.clone(), scenario_id.clone()).await?;
+    if scenario.is_baseline || scenario_id == BASELINE_SCENARIO_ID {
+        return Err("Cannot reset the baseline scenario".to_string());
+    }
+    ensure_unlocked(&scenario)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let operation = JournaledOperation::start(pool, "reset_scenario_to_baseline", serde_json::json!({ "scenarioId": scenario_id })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let baseline_id = sqlx::query_scalar!(
+        "SELECT id FROM scenarios WHERE is_baseline = 1 ORDER BY id = ? DESC, created_at LIMIT 1",
+        BASELINE_SCENARIO_ID
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "No baseline scenario to reset from".to_string())?;
+
+    // Copies reference each other in any order
+    sqlx::query("PRAGMA defer_foreign_keys = ON")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let discarded: Vec<serde_json::Value> = sqlx::query("SELECT * FROM initiatives WHERE scenario_id = ?")
+        .bind(&scenario_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(row_to_json)
+        .collect();
+
+    let baseline_ids: Vec<String> = sqlx::query_scalar!("SELECT id FROM initiatives WHERE scenario_id = ?", baseline_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query!("DELETE FROM initiatives WHERE scenario_id = ?", scenario_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let inserted = copy_initiatives(&mut tx, &baseline_ids, &scenario_id).await?;
+
+    let violations = sqlx::query("PRAGMA foreign_key_check")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !violations.is_empty() {
+        return Err("Reset would leave rows referencing missing rows".to_string());
+    }
+
+    let copied: Vec<serde_json::Value> = inserted
+        .into_iter()
+        .map(|r| serde_json::json!({ "table": r.table, "row": r.row }))
+        .collect();
+
+    record_audit(&mut tx, NewAuditEntry {
+        group_id: Some(uuid::Uuid::new_v4().to_string()),
+        entity_type: EntityType::Scenario.name().to_string(),
+        entity_id: Some(scenario_id.clone()),
+        action: "ResetToBaseline".to_string(),
+        description: Some(format!("Replaced {} initiatives with copies from {}", discarded.len(), baseline_id)),
+        before: Some(serde_json::Value::Array(discarded)),
+        after: Some(serde_json::Value::Array(copied)),
+    })
+    .await?;
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Copy initiatives into a scenario under new ids, along with their milestones, links
+/// and the dependencies between them
+async fn copy_initiatives(conn: &mut SqliteConnection, initiative_ids: &[String], scenario_id: &str) -> Result<Vec<RemappedRow>, String> {
+    let mut tables = collect_entity_rows(&mut *conn, EntityType::Initiative, initiative_ids).await?;
+    for row in tables.get_mut("initiatives").into_iter().flatten() {
+        row.insert("scenario_id".to_string(), serde_json::Value::String(scenario_id.to_string()));
+    }
+
+    // Capabilities, systems, pools and the like are shared, so references to them stay as they are
+    let mut shared: HashMap<(String, String), String> = HashMap::new();
+    for (table, rows) in &tables {
+        for fk in table_rule(table).map(|r| r.foreign_keys).unwrap_or_default() {
+            for target in rows.iter().filter_map(|r| r.get(fk.column)?.as_str()) {
+                shared.insert((fk.table.to_string(), target.to_string()), target.to_string());
+            }
+        }
+    }
+
+    let outcome = remap_rows(&tables, &shared, || uuid::Uuid::new_v4().to_string());
+    insert_remapped_rows(conn, outcome.rows).await
+}
+
+/// Freeze a copy of a scenario and its initiatives as a locked scenario; the
+/// database rejects any later change to it
+#[tauri::command]
+pub async fn snapshot_scenario(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, label: String) -> Result<Scenario, String> {
+    let label = label.trim().to_string();
+    if label.is_empty() {
+        return Err("A label is required to snapshot a scenario".to_string());
+    }
+
+    let source = get_scenario(db.clone(), scenario_id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let snapshot_id = uuid::Uuid::new_v4().to_string();
+    let now = get_current_timestamp();
+    let description = format!("Snapshot of {} taken {}", source.name, now);
+
+    let operation = JournaledOperation::start(pool, "snapshot_scenario", serde_json::json!({ "scenarioId": scenario_id, "label": label })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Copies reference each other in any order
+    sqlx::query("PRAGMA defer_foreign_keys = ON")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Inserted unlocked so the copies can be written, then locked in the same transaction
+    sqlx::query!(
+        r#"INSERT INTO scenarios (id, name, description, type, is_baseline, parent_scenario_id, created_at, updated_at)
+        VALUES (?, ?, ?, ?, 0, ?, ?, ?)"#,
+        snapshot_id,
+        label,
+        description,
+        source.scenario_type,
+        source.id,
+        now,
+        now
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let initiative_ids: Vec<String> = sqlx::query_scalar!("SELECT id FROM initiatives WHERE scenario_id = ?", scenario_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let inserted = copy_initiatives(&mut tx, &initiative_ids, &snapshot_id).await?;
+
+    let violations = sqlx::query("PRAGMA foreign_key_check")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !violations.is_empty() {
+        return Err("Snapshot would leave rows referencing missing rows".to_string());
+    }
+
+    sqlx::query!("UPDATE scenarios SET is_locked = 1 WHERE id = ?", snapshot_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let copied: Vec<serde_json::Value> = inserted
+        .into_iter()
+        .map(|r| serde_json::json!({ "table": r.table, "row": r.row }))
+        .collect();
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Scenario.name().to_string(),
+        entity_id: Some(snapshot_id.clone()),
+        action: "Snapshot".to_string(),
+        description: Some(format!("Locked snapshot \"{}\" of {} with {} initiatives", label, source.name, initiative_ids.len())),
+        after: Some(serde_json::Value::Array(copied)),
+        ..Default::default()
+    })
+    .await?;
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    get_scenario(db, snapshot_id).await
+}
+
+#[tauri::command]
+pub async fn unlock_scenario(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<Scenario, String> {
+    let scenario = get_scenario(db.clone(), id.clone()).await?;
+    if scenario.is_baseline || id == BASELINE_SCENARIO_ID {
+        return Err("Cannot unlock the baseline scenario".to_string());
+    }
+    if !scenario.is_locked {
+        return Ok(scenario);
+    }
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    sqlx::query!("UPDATE scenarios SET is_locked = 0, updated_at = ? WHERE id = ?", now, id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Scenario.name().to_string(),
+        entity_id: Some(id.clone()),
+        action: "Unlock".to_string(),
+        description: Some(format!("Unlocked {}", scenario.name)),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    get_scenario(db, id).await
+}
+
 // ============================================
 // RESOURCE POOLS COMMANDS
 // ============================================
@@ -568,19 +1488,9 @@ pub async fn get_resource_pool(db: State<'_, tauri_plugin_sql::DbInstances>, id:
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
-    let row: ResourcePool = sqlx::query_as!(
-        ResourcePool,
-        r#"SELECT
-            id, name, description, capacity_per_period,
-            capacity_unit, period_type, colour, created_at, updated_at
-        FROM resource_pools WHERE id = ?"#,
-        id
-    )
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    Ok(row)
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let rows = fetch_resource_pools(&mut conn, std::slice::from_ref(&id)).await?;
+    single(rows, EntityType::ResourcePool, &id)
 }
 
 #[tauri::command]
@@ -588,6 +1498,7 @@ pub async fn create_resource_pool(db: State<'_, tauri_plugin_sql::DbInstances>,
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    let period_type = normalise_period_type(&pool_data.period_type, &POOL_PERIOD_TYPES)?;
     let now = get_current_timestamp();
 
     sqlx::query!(
@@ -598,7 +1509,7 @@ pub async fn create_resource_pool(db: State<'_, tauri_plugin_sql::DbInstances>,
         pool_data.description,
         pool_data.capacity_per_period,
         pool_data.capacity_unit,
-        pool_data.period_type,
+        period_type,
         pool_data.colour,
         now,
         now
@@ -615,6 +1526,7 @@ pub async fn update_resource_pool(db: State<'_, tauri_plugin_sql::DbInstances>,
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    let period_type = normalise_period_type(&pool_data.period_type, &POOL_PERIOD_TYPES)?;
     let now = get_current_timestamp();
 
     sqlx::query!(
@@ -626,7 +1538,7 @@ pub async fn update_resource_pool(db: State<'_, tauri_plugin_sql::DbInstances>,
         pool_data.description,
         pool_data.capacity_per_period,
         pool_data.capacity_unit,
-        pool_data.period_type,
+        period_type,
         pool_data.colour,
         now,
         pool_data.id
@@ -639,16 +1551,121 @@ pub async fn update_resource_pool(db: State<'_, tauri_plugin_sql::DbInstances>,
 }
 
 #[tauri::command]
-pub async fn delete_resource_pool(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+pub async fn delete_resource_pool(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, strategy: Option<PoolDeleteStrategy>) -> Result<PoolDeleteSummary, String> {
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let rows = fetch_resource_pools(&mut tx, std::slice::from_ref(&id)).await?;
+    let resource_pool = single(rows, EntityType::ResourcePool, &id)?;
+    let references = count_pool_references(&mut tx, &id).await?;
+
+    let mut summary = PoolDeleteSummary {
+        pool_id: id.clone(),
+        pool_name: resource_pool.name.clone(),
+        reassigned_to: None,
+        resources_moved: 0,
+        allocations_moved: 0,
+        splits_moved: 0,
+        resources_deleted: 0,
+        allocations_deleted: 0,
+        named_allocations_deleted: 0,
+        splits_deleted: 0,
+        message: format!("Deleted resource pool {}", resource_pool.name),
+    };
+
+    // A pool in use is never deleted implicitly, so resources are not left without a pool
+    match strategy {
+        _ if references.is_empty() => {}
+        None => return Err(PoolInUseError::new(&id, references).to_string()),
+        Some(PoolDeleteStrategy::ReassignTo(target_id)) => {
+            if target_id == id {
+                return Err("Cannot reassign a resource pool's members to itself".to_string());
+            }
+            let targets = fetch_resource_pools(&mut tx, std::slice::from_ref(&target_id)).await?;
+            let target = single(targets, EntityType::ResourcePool, &target_id)?;
+            let now = get_current_timestamp();
+
+            summary.resources_moved = sqlx::query!(
+                "UPDATE resources SET resource_pool_id = ?, updated_at = ? WHERE resource_pool_id = ?",
+                target.id,
+                now,
+                id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?
+            .rows_affected() as i64;
+
+            summary.allocations_moved = sqlx::query!(
+                "UPDATE initiative_resource_requirements SET resource_pool_id = ? WHERE resource_pool_id = ?",
+                target.id,
+                id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?
+            .rows_affected() as i64;
+
+            summary.splits_moved = move_pool_splits(&mut tx, &id, &target.id).await?;
+
+            summary.message = format!(
+                "Deleted resource pool {}; moved {} resource(s), {} allocation(s) and {} pool split(s) to {}",
+                resource_pool.name, summary.resources_moved, summary.allocations_moved, summary.splits_moved, target.name
+            );
+            summary.reassigned_to = Some(target.id);
+        }
+        Some(PoolDeleteStrategy::Cascade) => {
+            // Named allocations go with their resources through ON DELETE CASCADE
+            summary.named_allocations_deleted = references.named_allocation_count;
+
+            summary.allocations_deleted = sqlx::query!(
+                "DELETE FROM initiative_resource_requirements WHERE resource_pool_id = ?",
+                id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?
+            .rows_affected() as i64;
+
+            summary.splits_deleted = sqlx::query!("DELETE FROM initiative_pool_splits WHERE resource_pool_id = ?", id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?
+                .rows_affected() as i64;
+
+            summary.resources_deleted = sqlx::query!("DELETE FROM resources WHERE resource_pool_id = ?", id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?
+                .rows_affected() as i64;
+
+            summary.message = format!(
+                "Deleted resource pool {} with {} resource(s), {} allocation(s), {} named allocation(s) and {} pool split(s)",
+                resource_pool.name, summary.resources_deleted, summary.allocations_deleted, summary.named_allocations_deleted, summary.splits_deleted
+            );
+        }
+    }
+
     sqlx::query!("DELETE FROM resource_pools WHERE id = ?", id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
 
-    Ok(())
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: "ResourcePool".to_string(),
+        entity_id: Some(id.clone()),
+        action: "Delete".to_string(),
+        description: Some(summary.message.clone()),
+        before: serde_json::to_value(&resource_pool).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(summary)
 }
 
 // ============================================
@@ -692,19 +1709,9 @@ pub async fn get_resource(db: State<'_, tauri_plugin_sql::DbInstances>, id: Stri
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
-    let row: Resource = sqlx::query_as!(
-        Resource,
-        r#"SELECT
-            id, name, role, skills, availability,
-            resource_pool_id, start_date, end_date, created_at, updated_at
-        FROM resources WHERE id = ?"#,
-        id
-    )
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    Ok(row)
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let rows = fetch_resources(&mut conn, std::slice::from_ref(&id)).await?;
+    single(rows, EntityType::Resource, &id)
 }
 
 #[tauri::command]
@@ -769,15 +1776,30 @@ pub async fn update_resource(db: State<'_, tauri_plugin_sql::DbInstances>, resou
 }
 
 #[tauri::command]
-pub async fn delete_resource(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+pub async fn delete_resource(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, replacement_id: Option<String>) -> Result<(), String> {
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Owned systems and initiatives are handed on rather than silently left without an owner
+    let owned = count_owned(&mut tx, &id).await?;
+    if !owned.is_empty() {
+        match replacement_id {
+            Some(replacement_id) => {
+                transfer_ownership(&mut tx, &id, &replacement_id).await?;
+            }
+            None => return Err(ResourceOwnsError::new(&id, owned).to_string()),
+        }
+    }
+
     sqlx::query!("DELETE FROM resources WHERE id = ?", id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
 
+    tx.commit().await.map_err(|e| e.to_string())?;
+
     Ok(())
 }
 
@@ -809,19 +1831,9 @@ pub async fn get_constraint(db: State<'_, tauri_plugin_sql::DbInstances>, id: St
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
-    let row: Constraint = sqlx::query_as!(
-        Constraint,
-        r#"SELECT
-            id, name, description, type as "constraint_type",
-            hardness, effective_date, expiry_date, created_at, updated_at
-        FROM constraints WHERE id = ?"#,
-        id
-    )
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    Ok(row)
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let rows = fetch_constraints(&mut conn, std::slice::from_ref(&id)).await?;
+    single(rows, EntityType::Constraint, &id)
 }
 
 #[tauri::command]
@@ -905,7 +1917,7 @@ pub async fn get_financial_periods(db: State<'_, tauri_plugin_sql::DbInstances>)
         FinancialPeriod,
         r#"SELECT
             id, name, type as "period_type",
-            start_date, end_date, budget_available, created_at, updated_at
+            start_date, end_date, budget_available, currency, closed as "closed: bool", created_at, updated_at
         FROM financial_periods ORDER BY start_date"#
     )
     .fetch_all(pool)
@@ -915,22 +1927,39 @@ pub async fn get_financial_periods(db: State<'_, tauri_plugin_sql::DbInstances>)
     Ok(rows)
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodTypes {
+    pub financial_periods: Vec<String>,
+    pub resource_pools: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn get_period_types() -> Result<PeriodTypes, String> {
+    Ok(PeriodTypes {
+        financial_periods: FINANCIAL_PERIOD_TYPES.iter().map(|t| t.to_string()).collect(),
+        resource_pools: POOL_PERIOD_TYPES.iter().map(|t| t.to_string()).collect(),
+    })
+}
+
 #[tauri::command]
 pub async fn create_financial_period(db: State<'_, tauri_plugin_sql::DbInstances>, period: FinancialPeriod) -> Result<FinancialPeriod, String> {
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    let period_type = normalise_period_type(&period.period_type, &FINANCIAL_PERIOD_TYPES)?;
+    let currency = resolve_currency(pool, period.currency.as_deref()).await?;
     let now = get_current_timestamp();
 
     sqlx::query!(
-        r#"INSERT INTO financial_periods (id, name, type, start_date, end_date, budget_available, created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+        r#"INSERT INTO financial_periods (id, name, type, start_date, end_date, budget_available, currency, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         period.id,
         period.name,
-        period.period_type,
+        period_type,
         period.start_date,
         period.end_date,
         period.budget_available,
+        currency,
         now,
         now
     )
@@ -939,19 +1968,9 @@ pub async fn create_financial_period(db: State<'_, tauri_plugin_sql::DbInstances
     .map_err(|e| e.to_string())?;
 
     // Return the created period
-    let row: FinancialPeriod = sqlx::query_as!(
-        FinancialPeriod,
-        r#"SELECT
-            id, name, type as "period_type",
-            start_date, end_date, budget_available, created_at, updated_at
-        FROM financial_periods WHERE id = ?"#,
-        period.id
-    )
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    Ok(row)
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let rows = fetch_financial_periods(&mut conn, std::slice::from_ref(&period.id)).await?;
+    single(rows, EntityType::FinancialPeriod, &period.id)
 }
 
 #[tauri::command]
@@ -959,17 +1978,37 @@ pub async fn update_financial_period(db: State<'_, tauri_plugin_sql::DbInstances
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
+    let period_type = normalise_period_type(&period.period_type, &FINANCIAL_PERIOD_TYPES)?;
+    let currency = resolve_currency(pool, period.currency.as_deref()).await?;
+
+    // A closed period's budget is frozen, including the currency it is expressed in
+    let existing = sqlx::query!(
+        r#"SELECT closed as "closed: bool", budget_available, currency FROM financial_periods WHERE id = ?"#,
+        period.id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if existing.closed && existing.budget_available != period.budget_available {
+        return Err(PeriodClosedError::new(&period.id, "budget_available").to_string());
+    }
+    if existing.closed && existing.currency.as_deref() != Some(currency.as_str()) {
+        return Err(PeriodClosedError::new(&period.id, "currency").to_string());
+    }
+
     let now = get_current_timestamp();
 
     sqlx::query!(
         r#"UPDATE financial_periods SET
-            name = ?, type = ?, start_date = ?, end_date = ?, budget_available = ?, updated_at = ?
+            name = ?, type = ?, start_date = ?, end_date = ?, budget_available = ?, currency = ?, updated_at = ?
         WHERE id = ?"#,
         period.name,
-        period.period_type,
+        period_type,
         period.start_date,
         period.end_date,
         period.budget_available,
+        currency,
         now,
         period.id
     )
@@ -978,30 +2017,350 @@ pub async fn update_financial_period(db: State<'_, tauri_plugin_sql::DbInstances
     .map_err(|e| e.to_string())?;
 
     // Return the updated period
-    let row: FinancialPeriod = sqlx::query_as!(
-        FinancialPeriod,
-        r#"SELECT
-            id, name, type as "period_type",
-            start_date, end_date, budget_available, created_at, updated_at
-        FROM financial_periods WHERE id = ?"#,
-        period.id
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let rows = fetch_financial_periods(&mut conn, std::slice::from_ref(&period.id)).await?;
+    single(rows, EntityType::FinancialPeriod, &period.id)
+}
+
+/// Delete a period. One with actuals, close reports or budget overrides is refused unless
+/// `cascade` is set, in which case they are deleted with it.
+#[tauri::command]
+pub async fn delete_financial_period(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, cascade: Option<bool>) -> Result<PeriodDeleteSummary, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let rows = fetch_financial_periods(&mut tx, std::slice::from_ref(&id)).await?;
+    let period = single(rows, EntityType::FinancialPeriod, &id)?;
+    let references = count_period_references(&mut tx, &id).await?;
+
+    if !references.is_empty() && !cascade.unwrap_or(false) {
+        return Err(PeriodInUseError::new(&id, references).to_string());
+    }
+
+    let overrides_deleted = sqlx::query!(
+        "DELETE FROM scenario_overrides WHERE entity_type = 'FinancialPeriod' AND entity_id = ?",
+        id
     )
-    .fetch_one(pool)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected() as i64;
+
+    // Actuals, close reports and funding sources go through ON DELETE CASCADE
+    sqlx::query!("DELETE FROM financial_periods WHERE id = ?", id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let message = if references.is_empty() {
+        format!("Deleted financial period {}", period.name)
+    } else {
+        format!(
+            "Deleted financial period {} with {} actual(s), {} close report(s), {} scenario override(s) and {} funding source(s)",
+            period.name, references.actual_count, references.close_report_count, overrides_deleted, references.funding_source_count
+        )
+    };
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::FinancialPeriod.name().to_string(),
+        entity_id: Some(id.clone()),
+        action: "Delete".to_string(),
+        description: Some(message.clone()),
+        before: serde_json::to_value(&period).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(PeriodDeleteSummary {
+        period_id: id,
+        period_name: period.name,
+        actuals_deleted: references.actual_count,
+        close_reports_deleted: references.close_report_count,
+        overrides_deleted,
+        funding_sources_deleted: references.funding_source_count,
+        message,
+    })
+}
+
+/// Replace a period with two contiguous ones, the second starting on `split_date`. The budget,
+/// actuals, funding sources and budget overrides are divided between them by day count. The
+/// first part keeps the period's id.
+#[tauri::command]
+pub async fn split_financial_period(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, split_date: String) -> Result<Vec<FinancialPeriod>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let operation = JournaledOperation::start(pool, "split_financial_period", serde_json::json!({ "id": id, "splitDate": split_date })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let rows = fetch_financial_periods(&mut tx, std::slice::from_ref(&id)).await?;
+    let period = single(rows, EntityType::FinancialPeriod, &id)?;
+    if period.closed {
+        return Err(PeriodClosedError::new(&id, "dates").to_string());
+    }
+    let split = plan_period_split(&period.start_date, &period.end_date, &split_date)?;
+    let budget = sqlx::query!("SELECT budget_mode, budget_capex, budget_opex FROM financial_periods WHERE id = ?", id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let now = get_current_timestamp();
+    let second_id = uuid::Uuid::new_v4().to_string();
+    let first_name = format!("{} (part 1)", period.name);
+    let second_name = format!("{} (part 2)", period.name);
+    let first_end = format_date(split.first.last_day());
+    let second_start = format_date(split.second.start);
+    let divide = |amount: Option<f64>| match amount {
+        Some(amount) => {
+            let (first, second) = split.divide(amount);
+            (Some(first), Some(second))
+        }
+        None => (None, None),
+    };
+    let (first_budget, second_budget) = divide(period.budget_available);
+    let (first_capex, second_capex) = divide(budget.budget_capex);
+    let (first_opex, second_opex) = divide(budget.budget_opex);
+
+    sqlx::query!(
+        r#"UPDATE financial_periods SET name = ?, end_date = ?, budget_available = ?, budget_capex = ?, budget_opex = ?, updated_at = ?
+        WHERE id = ?"#,
+        first_name,
+        first_end,
+        first_budget,
+        first_capex,
+        first_opex,
+        now,
+        id
+    )
+    .execute(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
 
-    Ok(row)
+    sqlx::query!(
+        r#"INSERT INTO financial_periods
+            (id, name, type, start_date, end_date, budget_available, currency, budget_mode, budget_capex, budget_opex, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        second_id,
+        second_name,
+        period.period_type,
+        second_start,
+        period.end_date,
+        second_budget,
+        period.currency,
+        budget.budget_mode,
+        second_capex,
+        second_opex,
+        now,
+        now
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let actuals = sqlx::query!(
+        "SELECT id, initiative_id, amount, notes FROM period_actuals WHERE financial_period_id = ?",
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for actual in &actuals {
+        let (first, second) = split.divide(actual.amount);
+        sqlx::query!("UPDATE period_actuals SET amount = ?, updated_at = ? WHERE id = ?", first, now, actual.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let actual_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query!(
+            r#"INSERT INTO period_actuals (id, financial_period_id, initiative_id, amount, notes, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+            actual_id,
+            second_id,
+            actual.initiative_id,
+            second,
+            actual.notes,
+            now,
+            now
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    // A derived budget is recalculated from its funding sources, so they divide the same way
+    let sources = sqlx::query!(
+        "SELECT id, name, capex_amount, opex_amount, currency FROM funding_sources WHERE financial_period_id = ?",
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for source in &sources {
+        let (first_capex, second_capex) = split.divide(source.capex_amount);
+        let (first_opex, second_opex) = split.divide(source.opex_amount);
+        sqlx::query!(
+            "UPDATE funding_sources SET capex_amount = ?, opex_amount = ?, updated_at = ? WHERE id = ?",
+            first_capex,
+            first_opex,
+            now,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let source_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query!(
+            r#"INSERT INTO funding_sources (id, name, financial_period_id, capex_amount, opex_amount, currency, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+            source_id,
+            source.name,
+            second_id,
+            second_capex,
+            second_opex,
+            source.currency,
+            now,
+            now
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    let overrides = sqlx::query!(
+        r#"SELECT id, scenario_id, field, override_value FROM scenario_overrides
+        WHERE entity_type = 'FinancialPeriod' AND entity_id = ?"#,
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for o in &overrides {
+        let Ok(amount) = o.override_value.parse::<f64>() else {
+            continue;
+        };
+        let (first, second) = split.divide(amount);
+        let (first, second) = (first.to_string(), second.to_string());
+        sqlx::query!("UPDATE scenario_overrides SET override_value = ?, updated_at = ? WHERE id = ?", first, now, o.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let override_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query!(
+            r#"INSERT INTO scenario_overrides (id, scenario_id, entity_type, entity_id, field, override_value, created_at, updated_at)
+            VALUES (?, ?, 'FinancialPeriod', ?, ?, ?, ?, ?)"#,
+            override_id,
+            o.scenario_id,
+            second_id,
+            o.field,
+            second,
+            now,
+            now
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    let mut parts = fetch_financial_periods(&mut tx, &[id.clone(), second_id]).await?;
+    parts.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::FinancialPeriod.name().to_string(),
+        entity_id: Some(id.clone()),
+        action: "Split".to_string(),
+        description: Some(format!(
+            "Split financial period {} on {}, dividing {} actual(s), {} funding source(s) and {} override(s)",
+            period.name, second_start, actuals.len(), sources.len(), overrides.len()
+        )),
+        before: serde_json::to_value(&period).ok(),
+        after: serde_json::to_value(&parts).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(parts)
 }
 
 #[tauri::command]
-pub async fn delete_financial_period(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+pub async fn generate_financial_periods(db: State<'_, tauri_plugin_sql::DbInstances>, start_date: String, period_type: String, count: u32, budget_per_period: Option<f64>) -> Result<Vec<FinancialPeriod>, String> {
+    let start = parse_date(&start_date).ok_or_else(|| format!("Invalid start date {}", start_date))?;
+    let period_type = normalise_period_type(&period_type, &FINANCIAL_PERIOD_TYPES)?;
+    if count == 0 {
+        return Err("Count must be at least 1".to_string());
+    }
+
     let pool = db.0.get("sqlite:roadmap.db")
         .ok_or_else(|| "Database not found".to_string())?;
 
-    sqlx::query!("DELETE FROM financial_periods WHERE id = ?", id)
-        .execute(pool)
+    let currency = resolve_currency(pool, None).await?;
+    let now = get_current_timestamp();
+    let months = period_months(period_type);
+
+    let operation = JournaledOperation::start(pool, "generate_financial_periods", serde_json::json!({ "startDate": start_date, "periodType": period_type, "count": count, "budgetPerPeriod": budget_per_period })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let mut ids = Vec::new();
+
+    for i in 0..count as i32 {
+        // Step from the original start so a 31st doesn't drift to the 28th after February
+        let period_start = add_months(start, i * months);
+        let period_end = add_months(start, (i + 1) * months) - chrono::Duration::days(1);
+        let (start_str, end_str) = (format_date(period_start), format_date(period_end));
+
+        let existing = sqlx::query_scalar!(
+            "SELECT name FROM financial_periods WHERE type = ? AND start_date <= ? AND end_date >= ? LIMIT 1",
+            period_type,
+            end_str,
+            start_str
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        if let Some(name) = existing {
+            return Err(format!("{} period {} already covers part of {} to {}", period_type, name, start_str, end_str));
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let name = period_label(period_start, period_type);
+
+        sqlx::query!(
+            r#"INSERT INTO financial_periods (id, name, type, start_date, end_date, budget_available, currency, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            id,
+            name,
+            period_type,
+            start_str,
+            end_str,
+            budget_per_period,
+            currency,
+            now,
+            now
+        )
+        .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
 
-    Ok(())
+        ids.push(id);
+    }
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let mut rows = fetch_financial_periods(&mut conn, &ids).await?;
+    rows.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+
+    Ok(rows)
 }
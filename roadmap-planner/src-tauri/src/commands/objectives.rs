@@ -0,0 +1,372 @@
+// Tauri commands for strategic objectives (OKRs)
+// Objective CRUD, initiative alignment links and the alignment report
+
+use crate::commands::engine::currency::CurrencyWarning;
+use crate::commands::engine::dates::{parse_date, today};
+use crate::commands::exchange_rates::load_currency_converter;
+use crate::commands::{get_initiatives, require_scenario};
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Objective {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub target_date: Option<String>,
+    pub owner: Option<String>,
+    pub parent_objective_id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeObjective {
+    pub id: String,
+    pub initiative_id: String,
+    pub objective_id: String,
+    pub contribution_weight: f64,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlignedInitiative {
+    pub initiative_id: String,
+    pub name: String,
+    pub status: String,
+    pub contribution_weight: f64,
+    pub percent_complete: f64,
+    pub cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectiveAlignment {
+    pub objective: Objective,
+    pub initiatives: Vec<AlignedInitiative>,
+    // Full cost of every linked initiative; one serving several objectives counts towards each
+    pub total_investment: f64,
+    // percent_complete averaged by contribution weight
+    pub weighted_progress: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectiveAlignmentReport {
+    pub scenario_id: String,
+    pub currency: String,
+    pub objectives: Vec<ObjectiveAlignment>,
+    // Objectives no initiative in the scenario contributes to
+    pub gaps: Vec<Objective>,
+    pub warnings: Vec<CurrencyWarning>,
+}
+
+// Reparenting under itself or one of its descendants would make the hierarchy cyclic
+async fn validate_objective_parent(pool: &sqlx::SqlitePool, id: &str, parent_id: Option<&str>) -> Result<(), String> {
+    let Some(parent_id) = parent_id else {
+        return Ok(());
+    };
+    if parent_id == id {
+        return Err("An objective cannot be its own parent".to_string());
+    }
+
+    let is_descendant = sqlx::query_scalar!(
+        r#"WITH RECURSIVE ancestors(id, parent_id) AS (
+            SELECT id, parent_objective_id FROM objectives WHERE id = ?
+            UNION
+            SELECT o.id, o.parent_objective_id FROM objectives o
+            JOIN ancestors a ON o.id = a.parent_id
+        )
+        SELECT COUNT(*) FROM ancestors WHERE id = ?"#,
+        parent_id,
+        id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if is_descendant > 0 {
+        return Err(format!(
+            "Cannot move {} under {}: {} is a descendant of {} and the move would create a cycle",
+            id, parent_id, parent_id, id
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_contribution_weight(weight: f64) -> Result<(), String> {
+    if !weight.is_finite() || weight <= 0.0 {
+        return Err(format!("Contribution weight must be greater than 0, got {}", weight));
+    }
+    Ok(())
+}
+
+// ============================================
+// OBJECTIVES COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_objectives(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<Vec<Objective>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<Objective> = sqlx::query_as!(
+        Objective,
+        r#"SELECT id, name, description, target_date, owner, parent_objective_id, created_at, updated_at
+        FROM objectives ORDER BY name"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_objective(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<Objective, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let row: Objective = sqlx::query_as!(
+        Objective,
+        r#"SELECT id, name, description, target_date, owner, parent_objective_id, created_at, updated_at
+        FROM objectives WHERE id = ?"#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn create_objective(db: State<'_, tauri_plugin_sql::DbInstances>, objective: Objective) -> Result<Objective, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    validate_objective_parent(pool, &objective.id, objective.parent_objective_id.as_deref()).await?;
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO objectives (id, name, description, target_date, owner, parent_objective_id, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+        objective.id,
+        objective.name,
+        objective.description,
+        objective.target_date,
+        objective.owner,
+        objective.parent_objective_id,
+        now,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_objective(db, objective.id).await
+}
+
+#[tauri::command]
+pub async fn update_objective(db: State<'_, tauri_plugin_sql::DbInstances>, objective: Objective) -> Result<Objective, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    validate_objective_parent(pool, &objective.id, objective.parent_objective_id.as_deref()).await?;
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"UPDATE objectives SET
+            name = ?, description = ?, target_date = ?, owner = ?,
+            parent_objective_id = ?, updated_at = ?
+        WHERE id = ?"#,
+        objective.name,
+        objective.description,
+        objective.target_date,
+        objective.owner,
+        objective.parent_objective_id,
+        now,
+        objective.id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_objective(db, objective.id).await
+}
+
+#[tauri::command]
+pub async fn delete_objective(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM objectives WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================
+// INITIATIVE OBJECTIVE LINK COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_initiative_objectives(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String) -> Result<Vec<InitiativeObjective>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<InitiativeObjective> = sqlx::query_as!(
+        InitiativeObjective,
+        r#"SELECT id, initiative_id, objective_id, contribution_weight, created_at
+        FROM initiative_objectives WHERE initiative_id = ?"#,
+        initiative_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn link_initiative_objective(db: State<'_, tauri_plugin_sql::DbInstances>, link: InitiativeObjective) -> Result<InitiativeObjective, String> {
+    validate_contribution_weight(link.contribution_weight)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    // Linking again updates the weight rather than failing on the unique pair
+    sqlx::query!(
+        r#"INSERT INTO initiative_objectives (id, initiative_id, objective_id, contribution_weight, created_at)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT(initiative_id, objective_id) DO UPDATE SET contribution_weight = excluded.contribution_weight"#,
+        link.id,
+        link.initiative_id,
+        link.objective_id,
+        link.contribution_weight,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let row: InitiativeObjective = sqlx::query_as!(
+        InitiativeObjective,
+        r#"SELECT id, initiative_id, objective_id, contribution_weight, created_at
+        FROM initiative_objectives WHERE initiative_id = ? AND objective_id = ?"#,
+        link.initiative_id,
+        link.objective_id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn unlink_initiative_objective(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM initiative_objectives WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================
+// ALIGNMENT REPORT COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_objective_alignment_report(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<ObjectiveAlignmentReport, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let as_of = today();
+    let objectives = get_objectives(db.clone()).await?;
+    let initiatives = get_initiatives(db.clone(), Some(scenario_id.clone())).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+    let converter = load_currency_converter(pool).await?;
+
+    let links: Vec<InitiativeObjective> = sqlx::query_as!(
+        InitiativeObjective,
+        r#"SELECT l.id, l.initiative_id, l.objective_id, l.contribution_weight, l.created_at
+        FROM initiative_objectives l
+        JOIN initiatives i ON i.id = l.initiative_id
+        WHERE i.scenario_id = ?"#,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Converted once per initiative, at the rate effective when it starts
+    let mut warnings = Vec::new();
+    let mut costs: HashMap<&str, f64> = HashMap::new();
+    for initiative in &initiatives {
+        let on = initiative.start_date.as_deref().and_then(parse_date).unwrap_or(as_of);
+        let currency = converter.currency_of(initiative.currency.as_deref());
+        let cost = initiative
+            .cost_estimate
+            .and_then(|native| converter.convert_or_warn(native, currency, on, "Initiative", &initiative.id, &mut warnings))
+            .unwrap_or(0.0);
+        costs.insert(initiative.id.as_str(), cost);
+    }
+    let by_id: HashMap<&str, _> = initiatives.iter().map(|i| (i.id.as_str(), i)).collect();
+
+    let mut aligned: Vec<ObjectiveAlignment> = Vec::new();
+    let mut gaps = Vec::new();
+
+    for objective in objectives {
+        let mut linked: Vec<AlignedInitiative> = links
+            .iter()
+            .filter(|l| l.objective_id == objective.id)
+            .filter_map(|l| {
+                let initiative = by_id.get(l.initiative_id.as_str())?;
+                Some(AlignedInitiative {
+                    initiative_id: initiative.id.clone(),
+                    name: initiative.name.clone(),
+                    status: initiative.status.clone(),
+                    contribution_weight: l.contribution_weight,
+                    percent_complete: initiative.percent_complete,
+                    cost: costs.get(initiative.id.as_str()).copied().unwrap_or(0.0),
+                })
+            })
+            .collect();
+
+        if linked.is_empty() {
+            gaps.push(objective);
+            continue;
+        }
+        linked.sort_by(|a, b| b.contribution_weight.total_cmp(&a.contribution_weight).then_with(|| a.name.cmp(&b.name)));
+
+        let total_weight: f64 = linked.iter().map(|i| i.contribution_weight).sum();
+        let weighted_progress = (total_weight > 0.0)
+            .then(|| linked.iter().map(|i| i.percent_complete * i.contribution_weight).sum::<f64>() / total_weight);
+
+        aligned.push(ObjectiveAlignment {
+            objective,
+            total_investment: linked.iter().map(|i| i.cost).sum(),
+            weighted_progress,
+            initiatives: linked,
+        });
+    }
+
+    Ok(ObjectiveAlignmentReport {
+        scenario_id,
+        currency: converter.reporting_currency.clone(),
+        objectives: aligned,
+        gaps,
+        warnings,
+    })
+}
@@ -0,0 +1,279 @@
+// Tauri commands for resource ownership of systems and initiatives
+// The owner is a resource; the legacy free-text systems.owner is only a display fallback
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::entities::EntityType;
+use crate::commands::ensure_scenarios_unlocked;
+use crate::commands::fetch::{fetch_initiatives, fetch_resources, fetch_systems, single};
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+use tauri::State;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OwnershipReferences {
+    pub system_count: i64,
+    pub initiative_count: i64,
+}
+
+impl OwnershipReferences {
+    pub fn is_empty(&self) -> bool {
+        self.system_count == 0 && self.initiative_count == 0
+    }
+}
+
+// Returned (serialised as JSON) when a resource who owns things is deleted without a replacement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceOwnsError {
+    pub code: String,
+    pub resource_id: String,
+    pub references: OwnershipReferences,
+    pub message: String,
+}
+
+impl ResourceOwnsError {
+    pub fn new(resource_id: &str, references: OwnershipReferences) -> Self {
+        Self {
+            code: "ResourceOwnsEntities".to_string(),
+            resource_id: resource_id.to_string(),
+            message: format!(
+                "Resource {} owns {} system(s) and {} initiative(s); supply a replacement owner to delete it",
+                resource_id, references.system_count, references.initiative_count
+            ),
+            references,
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceOwnsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).unwrap_or_else(|_| self.message.clone()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnedEntity {
+    pub entity_type: EntityType,
+    pub entity_id: String,
+    pub name: String,
+    // Only initiatives belong to a scenario
+    pub scenario_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnershipTransfer {
+    pub from_resource_id: String,
+    pub to_resource_id: String,
+    pub systems_moved: i64,
+    pub initiatives_moved: i64,
+    // One line for the confirmation toast
+    pub message: String,
+}
+
+pub async fn count_owned(conn: &mut SqliteConnection, resource_id: &str) -> Result<OwnershipReferences, String> {
+    let system_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM systems WHERE owner_resource_id = ?"#,
+        resource_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let initiative_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM initiatives WHERE owner_resource_id = ?"#,
+        resource_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(OwnershipReferences { system_count, initiative_count })
+}
+
+/// Hand everything `from` owns to `to`, recording one audit entry for the move. Initiatives in a
+/// locked scenario keep their owner, so the whole transfer is refused if any are affected.
+pub async fn transfer_ownership(conn: &mut SqliteConnection, from_resource_id: &str, to_resource_id: &str) -> Result<OwnershipTransfer, String> {
+    if from_resource_id == to_resource_id {
+        return Err("Cannot reassign a resource's ownership to itself".to_string());
+    }
+    let from = single(fetch_resources(&mut *conn, &[from_resource_id.to_string()]).await?, EntityType::Resource, from_resource_id)?;
+    let to = single(fetch_resources(&mut *conn, &[to_resource_id.to_string()]).await?, EntityType::Resource, to_resource_id)?;
+
+    let owned_initiatives = sqlx::query_scalar!("SELECT id FROM initiatives WHERE owner_resource_id = ?", from.id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    ensure_scenarios_unlocked(&mut *conn, &[], &owned_initiatives).await?;
+
+    let now = get_current_timestamp();
+    let systems_moved = sqlx::query!(
+        "UPDATE systems SET owner_resource_id = ?, updated_at = ? WHERE owner_resource_id = ?",
+        to.id,
+        now,
+        from.id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected() as i64;
+
+    let initiatives_moved = sqlx::query!(
+        "UPDATE initiatives SET owner_resource_id = ?, updated_at = ? WHERE owner_resource_id = ?",
+        to.id,
+        now,
+        from.id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected() as i64;
+
+    let message = format!(
+        "Moved {} system(s) and {} initiative(s) from {} to {}",
+        systems_moved, initiatives_moved, from.name, to.name
+    );
+
+    record_audit(&mut *conn, NewAuditEntry {
+        entity_type: EntityType::Resource.name().to_string(),
+        entity_id: Some(from.id.clone()),
+        action: "ReassignOwnership".to_string(),
+        description: Some(message.clone()),
+        after: Some(serde_json::json!({ "owner_resource_id": to.id })),
+        ..Default::default()
+    })
+    .await?;
+
+    Ok(OwnershipTransfer {
+        from_resource_id: from.id,
+        to_resource_id: to.id,
+        systems_moved,
+        initiatives_moved,
+        message,
+    })
+}
+
+async fn set_owner(db: State<'_, tauri_plugin_sql::DbInstances>, entity_type: EntityType, entity_id: String, resource_id: Option<String>) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let owner = match &resource_id {
+        Some(id) => Some(single(fetch_resources(&mut tx, std::slice::from_ref(id)).await?, EntityType::Resource, id)?),
+        None => None,
+    };
+    let now = get_current_timestamp();
+
+    let (name, before) = match entity_type {
+        EntityType::System => {
+            let system = single(fetch_systems(&mut tx, std::slice::from_ref(&entity_id)).await?, EntityType::System, &entity_id)?;
+            let before = sqlx::query_scalar!("SELECT owner_resource_id FROM systems WHERE id = ?", system.id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            sqlx::query!("UPDATE systems SET owner_resource_id = ?, updated_at = ? WHERE id = ?", resource_id, now, system.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            (system.name, before)
+        }
+        EntityType::Initiative => {
+            let initiative = single(fetch_initiatives(&mut tx, std::slice::from_ref(&entity_id)).await?, EntityType::Initiative, &entity_id)?;
+            ensure_scenarios_unlocked(&mut tx, &[], std::slice::from_ref(&initiative.id)).await?;
+            let before = sqlx::query_scalar!("SELECT owner_resource_id FROM initiatives WHERE id = ?", initiative.id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            sqlx::query!("UPDATE initiatives SET owner_resource_id = ?, updated_at = ? WHERE id = ?", resource_id, now, initiative.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            (initiative.name, before)
+        }
+        other => return Err(format!("{} has no owner", other.name())),
+    };
+
+    let (action, description) = match &owner {
+        Some(owner) => ("AssignOwner", format!("Made {} the owner of \"{}\"", owner.name, name)),
+        None => ("UnassignOwner", format!("Removed the owner of \"{}\"", name)),
+    };
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: entity_type.name().to_string(),
+        entity_id: Some(entity_id),
+        action: action.to_string(),
+        description: Some(description),
+        before: Some(serde_json::json!({ "owner_resource_id": before })),
+        after: Some(serde_json::json!({ "owner_resource_id": resource_id })),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// ============================================
+// OWNERSHIP COMMANDS
+// ============================================
+
+/// Make a resource the owner of a system or initiative, replacing any current owner
+#[tauri::command]
+pub async fn assign_owner(db: State<'_, tauri_plugin_sql::DbInstances>, entity_type: EntityType, entity_id: String, resource_id: String) -> Result<(), String> {
+    set_owner(db, entity_type, entity_id, Some(resource_id)).await
+}
+
+#[tauri::command]
+pub async fn unassign_owner(db: State<'_, tauri_plugin_sql::DbInstances>, entity_type: EntityType, entity_id: String) -> Result<(), String> {
+    set_owner(db, entity_type, entity_id, None).await
+}
+
+/// Systems then initiatives the resource owns, each by name
+#[tauri::command]
+pub async fn get_entities_owned_by(db: State<'_, tauri_plugin_sql::DbInstances>, resource_id: String) -> Result<Vec<OwnedEntity>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    single(fetch_resources(&mut conn, std::slice::from_ref(&resource_id)).await?, EntityType::Resource, &resource_id)?;
+
+    let systems = sqlx::query!("SELECT id, name FROM systems WHERE owner_resource_id = ? ORDER BY name", resource_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let initiatives = sqlx::query!(
+        "SELECT id, name, scenario_id FROM initiatives WHERE owner_resource_id = ? ORDER BY name",
+        resource_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut owned: Vec<OwnedEntity> = systems
+        .into_iter()
+        .map(|s| OwnedEntity { entity_type: EntityType::System, entity_id: s.id, name: s.name, scenario_id: None })
+        .collect();
+    owned.extend(initiatives.into_iter().map(|i| OwnedEntity {
+        entity_type: EntityType::Initiative,
+        entity_id: i.id,
+        name: i.name,
+        scenario_id: Some(i.scenario_id),
+    }));
+
+    Ok(owned)
+}
+
+/// Move every system and initiative one resource owns to another, e.g. when someone leaves
+#[tauri::command]
+pub async fn reassign_ownership(db: State<'_, tauri_plugin_sql::DbInstances>, from_resource_id: String, to_resource_id: String) -> Result<OwnershipTransfer, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let transfer = transfer_ownership(&mut tx, &from_resource_id, &to_resource_id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(transfer)
+}
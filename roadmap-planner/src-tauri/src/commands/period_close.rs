@@ -0,0 +1,313 @@
+// Tauri commands for closing financial periods
+// Closing freezes budget and actuals and snapshots budget vs actual figures
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::budget::phased_cost;
+use crate::commands::engine::dates::parse_date;
+use crate::commands::entities::EntityType;
+use crate::commands::exchange_rates::load_currency_converter;
+use crate::commands::fetch::{fetch_financial_periods, single};
+use crate::commands::journal::JournaledOperation;
+use crate::commands::settings::read_bool_setting;
+use crate::db::{Initiative, get_current_timestamp};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use tauri::State;
+
+// Setting that controls whether closing requires actuals for in-flight initiatives
+const REQUIRE_ACTUALS_SETTING: &str = "period_close.require_actuals";
+
+// Returned (serialised as JSON) when a write targets a closed period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodClosedError {
+    pub code: String,
+    pub period_id: String,
+    pub field: String,
+    pub message: String,
+}
+
+impl PeriodClosedError {
+    pub fn new(period_id: &str, field: &str) -> Self {
+        Self {
+            code: "PeriodClosed".to_string(),
+            period_id: period_id.to_string(),
+            field: field.to_string(),
+            message: format!("Financial period {} is closed; {} cannot be changed", period_id, field),
+        }
+    }
+}
+
+impl std::fmt::Display for PeriodClosedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).unwrap_or_else(|_| self.message.clone()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodCloseLine {
+    pub initiative_id: String,
+    pub initiative_name: String,
+    // Planned spend is native to the initiative, actuals to the period
+    pub currency: String,
+    pub planned_spend_native: f64,
+    pub actual_spend_native: Option<f64>,
+    // Converted into the report currency; None when no rate was effective
+    pub planned_spend: Option<f64>,
+    pub actual_spend: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodCloseReport {
+    pub id: String,
+    pub financial_period_id: String,
+    pub budget_available: Option<f64>,
+    pub planned_spend: f64,
+    pub actual_spend: f64,
+    pub variance: Option<f64>,
+    pub lines_json: String,
+    pub closed_at: Option<String>,
+    pub currency: Option<String>,
+    pub warnings_json: Option<String>,
+}
+
+/// Reject writes against a closed period
+pub async fn ensure_period_open(pool: &SqlitePool, period_id: &str, field: &str) -> Result<(), String> {
+    let closed = sqlx::query_scalar!(
+        r#"SELECT closed as "closed: bool" FROM financial_periods WHERE id = ?"#,
+        period_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Financial period {} does not exist", period_id))?;
+
+    if closed {
+        return Err(PeriodClosedError::new(period_id, field).to_string());
+    }
+    Ok(())
+}
+
+// ============================================
+// PERIOD CLOSE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn close_financial_period(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<PeriodCloseReport, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let period = {
+        let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+        single(fetch_financial_periods(&mut conn, std::slice::from_ref(&id)).await?, EntityType::FinancialPeriod, &id)?
+    };
+
+    if period.closed {
+        return Err(format!("Financial period {} is already closed", id));
+    }
+
+    // Actual spend happens against the committed plan, so the baseline is what gets closed
+    let initiatives: Vec<Initiative> = sqlx::query_as!(
+        Initiative,
+        r#"SELECT
+            i.id, i.name, i.description, i.type as "initiative_type", i.status,
+            i.start_date, i.end_date, i.effort_estimate, i.effort_uncertainty,
+            i.cost_estimate, i.cost_uncertainty, i.priority, i.scenario_id,
+            i.percent_complete, i.progress_from_milestones as "progress_from_milestones: bool", i.currency, i.effort_unit, i.effort_profile, i.external_ref, i.reference_code, i.is_key_date as "is_key_date: bool", i.colour, i.icon,
+            i.created_at, i.updated_at
+        FROM initiatives i
+        JOIN scenarios s ON s.id = i.scenario_id
+        WHERE s.is_baseline = 1
+            AND i.status IN ('InProgress', 'Complete')
+            AND i.start_date <= ? AND i.end_date >= ?
+        ORDER BY i.name"#,
+        period.end_date,
+        period.start_date
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let actuals: HashMap<String, f64> = sqlx::query!(
+        "SELECT initiative_id, amount FROM period_actuals WHERE financial_period_id = ?",
+        id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .into_iter()
+    .map(|r| (r.initiative_id, r.amount))
+    .collect();
+
+    if read_bool_setting(pool, REQUIRE_ACTUALS_SETTING, true).await? {
+        let missing: Vec<&str> = initiatives
+            .iter()
+            .filter(|i| !actuals.contains_key(&i.id))
+            .map(|i| i.name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "Cannot close {}: missing actuals for {}",
+                period.name,
+                missing.join(", ")
+            ));
+        }
+    }
+
+    // Figures are frozen in the reporting currency at the rate effective at the period start
+    let converter = load_currency_converter(pool).await?;
+    let on = parse_date(&period.start_date)
+        .ok_or_else(|| format!("Financial period {} has an invalid start date", id))?;
+    let period_currency = converter.currency_of(period.currency.as_deref()).to_string();
+    let mut warnings = Vec::new();
+
+    let lines: Vec<PeriodCloseLine> = initiatives
+        .iter()
+        .map(|i| {
+            let currency = converter.currency_of(i.currency.as_deref());
+            let planned_spend_native = phased_cost(i, &period);
+            let actual_spend_native = actuals.get(&i.id).copied();
+            PeriodCloseLine {
+                initiative_id: i.id.clone(),
+                initiative_name: i.name.clone(),
+                currency: currency.to_string(),
+                planned_spend_native,
+                actual_spend_native,
+                planned_spend: converter.convert_or_warn(planned_spend_native, currency, on, "Initiative", &i.id, &mut warnings),
+                actual_spend: actual_spend_native.and_then(|a| {
+                    converter.convert_or_warn(a, &period_currency, on, "PeriodActual", &i.id, &mut warnings)
+                }),
+            }
+        })
+        .collect();
+
+    let planned_spend: f64 = lines.iter().filter_map(|l| l.planned_spend).sum();
+    let actual_spend: f64 = match converter.rate(&period_currency, on) {
+        Some(rate) => actuals.values().sum::<f64>() * rate,
+        None => 0.0,
+    };
+    let budget_available = period.budget_available.and_then(|b| {
+        converter.convert_or_warn(b, &period_currency, on, "FinancialPeriod", &id, &mut warnings)
+    });
+    let variance = budget_available.map(|b| b - actual_spend);
+    let lines_json = serde_json::to_string(&lines).map_err(|e| e.to_string())?;
+    let warnings_json = serde_json::to_string(&warnings).map_err(|e| e.to_string())?;
+    let currency = converter.reporting_currency.clone();
+    let report_id = uuid::Uuid::new_v4().to_string();
+    let now = get_current_timestamp();
+
+    let operation = JournaledOperation::start(pool, "close_financial_period", serde_json::json!({ "id": id })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    sqlx::query!(
+        r#"INSERT INTO period_close_reports (id, financial_period_id, budget_available, planned_spend,
+            actual_spend, variance, lines_json, closed_at, currency, warnings_json)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        report_id,
+        id,
+        budget_available,
+        planned_spend,
+        actual_spend,
+        variance,
+        lines_json,
+        now,
+        currency,
+        warnings_json
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query!(
+        "UPDATE financial_periods SET closed = 1, updated_at = ? WHERE id = ?",
+        now,
+        id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: "FinancialPeriod".to_string(),
+        entity_id: Some(id.clone()),
+        action: "Close".to_string(),
+        description: Some(format!("Closed {}", period.name)),
+        ..Default::default()
+    })
+    .await?;
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(PeriodCloseReport {
+        id: report_id,
+        financial_period_id: id,
+        budget_available,
+        planned_spend,
+        actual_spend,
+        variance,
+        lines_json,
+        closed_at: Some(now),
+        currency: Some(currency),
+        warnings_json: Some(warnings_json),
+    })
+}
+
+#[tauri::command]
+pub async fn reopen_financial_period(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, reason: String) -> Result<(), String> {
+    if reason.trim().is_empty() {
+        return Err("A reason is required to reopen a financial period".to_string());
+    }
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let result = sqlx::query!(
+        "UPDATE financial_periods SET closed = 0, updated_at = ? WHERE id = ? AND closed = 1",
+        now,
+        id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if result.rows_affected() == 0 {
+        return Err(format!("Financial period {} is not closed", id));
+    }
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: "FinancialPeriod".to_string(),
+        entity_id: Some(id),
+        action: "Reopen".to_string(),
+        description: Some(reason),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_period_close_reports(db: State<'_, tauri_plugin_sql::DbInstances>, period_id: String) -> Result<Vec<PeriodCloseReport>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<PeriodCloseReport> = sqlx::query_as!(
+        PeriodCloseReport,
+        r#"SELECT
+            id, financial_period_id, budget_available, planned_spend, actual_spend,
+            variance, lines_json, closed_at, currency, warnings_json
+        FROM period_close_reports WHERE financial_period_id = ? ORDER BY closed_at DESC"#,
+        period_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
@@ -0,0 +1,167 @@
+// Guard rails for deleting and splitting financial periods
+// Actuals, close reports and overrides hang off a period, so they go or divide with it
+
+use crate::commands::engine::dates::{DateSpan, parse_date};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PeriodReferences {
+    pub actual_count: i64,
+    pub close_report_count: i64,
+    // Scenario overrides of the period's budget
+    pub override_count: i64,
+    pub funding_source_count: i64,
+    // Forecast snapshots copy their figures, so these are kept and never block a delete
+    pub snapshot_count: i64,
+}
+
+impl PeriodReferences {
+    pub fn is_empty(&self) -> bool {
+        self.actual_count == 0 && self.close_report_count == 0 && self.override_count == 0 && self.funding_source_count == 0
+    }
+}
+
+// Returned (serialised as JSON) when a period in use is deleted without cascading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodInUseError {
+    pub code: String,
+    pub period_id: String,
+    pub references: PeriodReferences,
+    pub message: String,
+}
+
+impl PeriodInUseError {
+    pub fn new(period_id: &str, references: PeriodReferences) -> Self {
+        Self {
+            code: "PeriodInUse".to_string(),
+            period_id: period_id.to_string(),
+            message: format!(
+                "Financial period {} has {} actual(s), {} close report(s), {} scenario override(s) and {} funding source(s); cascade to delete it",
+                period_id, references.actual_count, references.close_report_count, references.override_count, references.funding_source_count
+            ),
+            references,
+        }
+    }
+}
+
+impl std::fmt::Display for PeriodInUseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).unwrap_or_else(|_| self.message.clone()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodDeleteSummary {
+    pub period_id: String,
+    pub period_name: String,
+    pub actuals_deleted: i64,
+    pub close_reports_deleted: i64,
+    pub overrides_deleted: i64,
+    pub funding_sources_deleted: i64,
+    // One line for the confirmation toast
+    pub message: String,
+}
+
+pub async fn count_period_references(conn: &mut SqliteConnection, period_id: &str) -> Result<PeriodReferences, String> {
+    let actual_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM period_actuals WHERE financial_period_id = ?"#,
+        period_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let close_report_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM period_close_reports WHERE financial_period_id = ?"#,
+        period_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let override_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM scenario_overrides
+        WHERE entity_type = 'FinancialPeriod' AND entity_id = ?"#,
+        period_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let funding_source_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM funding_sources WHERE financial_period_id = ?"#,
+        period_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let snapshot_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM forecast_snapshot_periods WHERE financial_period_id = ?"#,
+        period_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(PeriodReferences { actual_count, close_report_count, override_count, funding_source_count, snapshot_count })
+}
+
+// Where a period divides, and how much of each amount stays with the first part
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeriodSplit {
+    pub first: DateSpan,
+    pub second: DateSpan,
+    pub first_share: f64,
+}
+
+impl PeriodSplit {
+    /// Divide an amount by day count; the parts always add back up to the whole
+    pub fn divide(&self, amount: f64) -> (f64, f64) {
+        let first = amount * self.first_share;
+        (first, amount - first)
+    }
+}
+
+/// Split a period so the second part starts on `split_date`; both parts must keep at least a day
+pub fn plan_period_split(start_date: &str, end_date: &str, split_date: &str) -> Result<PeriodSplit, String> {
+    let span = DateSpan::parse_inclusive(Some(start_date), Some(end_date))
+        .ok_or_else(|| format!("Period has invalid dates {} to {}", start_date, end_date))?;
+    let split: NaiveDate = parse_date(split_date).ok_or_else(|| format!("Invalid split date {}", split_date))?;
+
+    if split <= span.start || split >= span.end {
+        return Err(format!(
+            "Split date {} must fall after {} and no later than {}",
+            split_date, start_date, end_date
+        ));
+    }
+
+    let first = DateSpan { start: span.start, end: split };
+    let second = DateSpan { start: split, end: span.end };
+    Ok(PeriodSplit { first, second, first_share: first.days() as f64 / span.days() as f64 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_divide_by_day_count() {
+        let split = plan_period_split("2027-01-01", "2027-03-31", "2027-03-01").unwrap();
+        assert_eq!(split.first.last_day(), parse_date("2027-02-28").unwrap());
+        assert_eq!(split.second.start, parse_date("2027-03-01").unwrap());
+        assert_eq!(split.first.days(), 59);
+        assert_eq!(split.second.days(), 31);
+
+        let (first, second) = split.divide(900.0);
+        assert!((first - 590.0).abs() < 1e-9);
+        assert_eq!(first + second, 900.0);
+
+        // Each part keeps at least one day
+        assert!(plan_period_split("2027-01-01", "2027-03-31", "2027-01-01").is_err());
+        assert!(plan_period_split("2027-01-01", "2027-03-31", "2027-04-01").is_err());
+        assert!(plan_period_split("2027-01-01", "2027-03-31", "2027-03-31").is_ok());
+    }
+}
@@ -0,0 +1,206 @@
+// Guard rails for deleting resource pools
+// A pool still in use is only removed once its resources and allocations have somewhere to go
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+
+// What happens to a pool's resources and pool allocations when the pool is deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PoolDeleteStrategy {
+    // Move them to another pool
+    ReassignTo(String),
+    // Delete them, along with the resources' named allocations
+    Cascade,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PoolReferences {
+    pub resource_count: i64,
+    // Pool-level effort requirements
+    pub allocation_count: i64,
+    // Named allocations held by the pool's resources
+    pub named_allocation_count: i64,
+    pub split_count: i64,
+}
+
+impl PoolReferences {
+    pub fn is_empty(&self) -> bool {
+        self.resource_count == 0 && self.allocation_count == 0 && self.split_count == 0
+    }
+}
+
+// Returned (serialised as JSON) when a pool in use is deleted without a strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolInUseError {
+    pub code: String,
+    pub pool_id: String,
+    pub references: PoolReferences,
+    pub message: String,
+}
+
+impl PoolInUseError {
+    pub fn new(pool_id: &str, references: PoolReferences) -> Self {
+        Self {
+            code: "PoolInUse".to_string(),
+            pool_id: pool_id.to_string(),
+            message: format!(
+                "Resource pool {} has {} resource(s), {} allocation(s) and {} pool split(s); reassign or cascade to delete it",
+                pool_id, references.resource_count, references.allocation_count, references.split_count
+            ),
+            references,
+        }
+    }
+}
+
+impl std::fmt::Display for PoolInUseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).unwrap_or_else(|_| self.message.clone()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolDeleteSummary {
+    pub pool_id: String,
+    pub pool_name: String,
+    pub reassigned_to: Option<String>,
+    pub resources_moved: i64,
+    pub allocations_moved: i64,
+    pub splits_moved: i64,
+    pub resources_deleted: i64,
+    pub allocations_deleted: i64,
+    pub named_allocations_deleted: i64,
+    pub splits_deleted: i64,
+    // One line for the confirmation toast
+    pub message: String,
+}
+
+pub async fn count_pool_references(conn: &mut SqliteConnection, pool_id: &str) -> Result<PoolReferences, String> {
+    let resource_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM resources WHERE resource_pool_id = ?"#,
+        pool_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let allocation_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM initiative_resource_requirements WHERE resource_pool_id = ?"#,
+        pool_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let named_allocation_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM initiative_resources
+        WHERE resource_id IN (SELECT id FROM resources WHERE resource_pool_id = ?)"#,
+        pool_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let split_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM initiative_pool_splits WHERE resource_pool_id = ?"#,
+        pool_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(PoolReferences { resource_count, allocation_count, named_allocation_count, split_count })
+}
+
+/// Move a pool's splits onto `to_pool_id`, returning how many moved. An initiative already
+/// split onto the target keeps that one row with both shares added together.
+pub async fn move_pool_splits(conn: &mut SqliteConnection, from_pool_id: &str, to_pool_id: &str) -> Result<i64, String> {
+    let merged = sqlx::query!(
+        r#"UPDATE initiative_pool_splits
+        SET percentage = percentage + (
+            SELECT source.percentage FROM initiative_pool_splits source
+            WHERE source.resource_pool_id = ?1 AND source.initiative_id = initiative_pool_splits.initiative_id
+        )
+        WHERE resource_pool_id = ?2
+            AND initiative_id IN (SELECT initiative_id FROM initiative_pool_splits WHERE resource_pool_id = ?1)"#,
+        from_pool_id,
+        to_pool_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected() as i64;
+
+    sqlx::query!(
+        r#"DELETE FROM initiative_pool_splits
+        WHERE resource_pool_id = ?1
+            AND initiative_id IN (SELECT initiative_id FROM initiative_pool_splits WHERE resource_pool_id = ?2)"#,
+        from_pool_id,
+        to_pool_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let repointed = sqlx::query!(
+        "UPDATE initiative_pool_splits SET resource_pool_id = ? WHERE resource_pool_id = ?",
+        to_pool_id,
+        from_pool_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected() as i64;
+
+    Ok(merged + repointed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::Connection;
+
+    #[tokio::test]
+    async fn splits_merge_into_the_target_or_move_across() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        crate::db::migrations::migrate_if_empty(&mut conn).await.unwrap();
+        sqlx::query!(
+            r#"INSERT INTO resource_pools (id, name, capacity_unit, period_type)
+            VALUES ('old', 'Old', 'FTE', 'Month'), ('new', 'New', 'FTE', 'Month'), ('ops', 'Ops', 'FTE', 'Month')"#
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+        sqlx::query!("INSERT INTO scenarios (id, name) VALUES ('s', 'Baseline')").execute(&mut conn).await.unwrap();
+        sqlx::query!(
+            r#"INSERT INTO initiatives (id, name, type, status, priority, scenario_id)
+            VALUES ('both', 'Both', 'New', 'Planned', 'Must', 's'), ('only', 'Only', 'New', 'Planned', 'Must', 's')"#
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+        sqlx::query!(
+            r#"INSERT INTO initiative_pool_splits (id, initiative_id, resource_pool_id, percentage)
+            VALUES ('a', 'both', 'old', 30), ('b', 'both', 'new', 50), ('c', 'both', 'ops', 20), ('d', 'only', 'old', 100)"#
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+        assert_eq!(move_pool_splits(&mut conn, "old", "new").await.unwrap(), 2);
+
+        let splits: Vec<(String, String, String, f64)> = sqlx::query_as(
+            "SELECT id, initiative_id, resource_pool_id, percentage FROM initiative_pool_splits ORDER BY id",
+        )
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+        assert_eq!(
+            splits,
+            [
+                ("b".to_string(), "both".to_string(), "new".to_string(), 80.0),
+                ("c".to_string(), "both".to_string(), "ops".to_string(), 20.0),
+                ("d".to_string(), "only".to_string(), "new".to_string(), 100.0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+// Tauri commands for role capacity within resource pools
+// Set by hand per pool, or derived from the roles of the pool's members
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::dates::{DateSpan, next_period_start, period_start, today};
+use crate::commands::engine::resources::{PoolRoleCapacity, role_capacity};
+use crate::commands::entities::EntityType;
+use crate::commands::{get_resource_pool, get_resources};
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleCapacity {
+    pub role: String,
+    pub capacity_per_period: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolRoles {
+    pub pool_id: String,
+    // True when no role capacities are set and the members' roles are used instead
+    pub derived: bool,
+    pub roles: Vec<RoleCapacity>,
+}
+
+/// Each role named once, case-insensitively, with a capacity of zero or more
+pub fn validate_role_capacities(roles: &[(String, f64)]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for (role, capacity) in roles {
+        let role = role.trim();
+        if role.is_empty() {
+            return Err("Role names can't be blank".to_string());
+        }
+        if !seen.insert(role.to_lowercase()) {
+            return Err(format!("Role {} appears more than once", role));
+        }
+        if capacity.is_nan() || *capacity < 0.0 {
+            return Err(format!("Capacity for {} must be zero or more, got {}", role, capacity));
+        }
+    }
+    Ok(())
+}
+
+// ============================================
+// POOL ROLE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_pool_role_capacities(db: State<'_, tauri_plugin_sql::DbInstances>, pool_id: String) -> Result<PoolRoles, String> {
+    let resource_pool = get_resource_pool(db.clone(), pool_id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<PoolRoleCapacity> = sqlx::query_as!(
+        PoolRoleCapacity,
+        r#"SELECT id, resource_pool_id, role, capacity_per_period, created_at, updated_at
+        FROM pool_role_capacities WHERE resource_pool_id = ? ORDER BY role"#,
+        pool_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if !rows.is_empty() {
+        let roles = rows
+            .into_iter()
+            .map(|r| RoleCapacity { role: r.role, capacity_per_period: r.capacity_per_period })
+            .collect();
+        return Ok(PoolRoles { pool_id, derived: false, roles });
+    }
+
+    // The current period, so members who have left or not yet joined don't count
+    let start = period_start(today(), &resource_pool.period_type);
+    let current = DateSpan { start, end: next_period_start(start, &resource_pool.period_type) };
+    let members = get_resources(db, Some(pool_id.clone())).await?;
+
+    let roles = role_capacity(&resource_pool, &[], &members, &current)
+        .into_iter()
+        .map(|r| RoleCapacity { role: r.role, capacity_per_period: r.capacity })
+        .collect();
+
+    Ok(PoolRoles { pool_id, derived: true, roles })
+}
+
+/// Replace a pool's role capacities; an empty list goes back to deriving them from members
+#[tauri::command]
+pub async fn set_pool_role_capacities(db: State<'_, tauri_plugin_sql::DbInstances>, pool_id: String, roles: Vec<(String, f64)>) -> Result<PoolRoles, String> {
+    validate_role_capacities(&roles)?;
+    let before = get_pool_role_capacities(db.clone(), pool_id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    sqlx::query!("DELETE FROM pool_role_capacities WHERE resource_pool_id = ?", pool_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for (role, capacity) in &roles {
+        let id = uuid::Uuid::new_v4().to_string();
+        let role = role.trim();
+        sqlx::query!(
+            r#"INSERT INTO pool_role_capacities (id, resource_pool_id, role, capacity_per_period, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)"#,
+            id,
+            pool_id,
+            role,
+            capacity,
+            now,
+            now
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    let after: Vec<RoleCapacity> = roles
+        .iter()
+        .map(|(role, capacity)| RoleCapacity { role: role.trim().to_string(), capacity_per_period: *capacity })
+        .collect();
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::ResourcePool.name().to_string(),
+        entity_id: Some(pool_id.clone()),
+        action: "SetRoleCapacities".to_string(),
+        description: Some(format!("Set capacity for {} role(s)", roles.len())),
+        before: serde_json::to_value(&before).ok(),
+        after: serde_json::to_value(&after).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    get_pool_role_capacities(db, pool_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles(parts: &[(&str, f64)]) -> Vec<(String, f64)> {
+        parts.iter().map(|(role, capacity)| (role.to_string(), *capacity)).collect()
+    }
+
+    #[test]
+    fn roles_must_be_named_once() {
+        assert!(validate_role_capacities(&roles(&[("Engineer", 12.0), ("Architect", 0.0)])).is_ok());
+        assert!(validate_role_capacities(&roles(&[("Engineer", 12.0), ("engineer ", 3.0)])).is_err());
+        assert!(validate_role_capacities(&roles(&[("  ", 1.0)])).is_err());
+        assert!(validate_role_capacities(&roles(&[("BA", -1.0)])).is_err());
+    }
+}
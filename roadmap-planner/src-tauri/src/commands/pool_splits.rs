@@ -0,0 +1,134 @@
+// Tauri commands for initiative pool splits
+// Proportional shares of an initiative's effort across resource pools
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::resources::PoolSplit;
+use crate::commands::entities::EntityType;
+use crate::commands::{ensure_scenarios_unlocked, get_initiative};
+use crate::db::get_current_timestamp;
+use std::collections::HashSet;
+use tauri::State;
+
+// Allows for rounding in shares like 33.33 + 33.33 + 33.34
+const SPLIT_TOTAL_TOLERANCE: f64 = 0.01;
+
+/// A split must name each pool once with a positive share, and the shares must total 100
+pub fn validate_pool_split(splits: &[(String, f64)]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for (pool_id, percentage) in splits {
+        if !seen.insert(pool_id.as_str()) {
+            return Err(format!("Resource pool {} appears more than once in the split", pool_id));
+        }
+        if !(*percentage > 0.0 && *percentage <= 100.0) {
+            return Err(format!("Split percentages must be between 0 and 100, got {} for {}", percentage, pool_id));
+        }
+    }
+
+    let total: f64 = splits.iter().map(|(_, percentage)| percentage).sum();
+    if !splits.is_empty() && (total - 100.0).abs() > SPLIT_TOTAL_TOLERANCE {
+        return Err(format!("Split percentages must total 100, got {}", total));
+    }
+    Ok(())
+}
+
+// ============================================
+// POOL SPLIT COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_initiative_pool_split(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String) -> Result<Vec<PoolSplit>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<PoolSplit> = sqlx::query_as!(
+        PoolSplit,
+        r#"SELECT id, initiative_id, resource_pool_id, percentage, created_at
+        FROM initiative_pool_splits WHERE initiative_id = ? ORDER BY percentage DESC"#,
+        initiative_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+/// Replace an initiative's split; an empty list removes it
+#[tauri::command]
+pub async fn set_initiative_pool_split(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String, splits: Vec<(String, f64)>) -> Result<Vec<PoolSplit>, String> {
+    validate_pool_split(&splits)?;
+    get_initiative(db.clone(), initiative_id.clone()).await?;
+    let before = get_initiative_pool_split(db.clone(), initiative_id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    ensure_scenarios_unlocked(&mut tx, &[], std::slice::from_ref(&initiative_id)).await?;
+
+    sqlx::query!("DELETE FROM initiative_pool_splits WHERE initiative_id = ?", initiative_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for (pool_id, percentage) in &splits {
+        let id = uuid::Uuid::new_v4().to_string();
+        sqlx::query!(
+            r#"INSERT INTO initiative_pool_splits (id, initiative_id, resource_pool_id, percentage, created_at)
+            VALUES (?, ?, ?, ?, ?)"#,
+            id,
+            initiative_id,
+            pool_id,
+            percentage,
+            now
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e.as_database_error().map(|d| d.is_foreign_key_violation()) {
+            Some(true) => format!("Resource pool {} not found", pool_id),
+            _ => e.to_string(),
+        })?;
+    }
+
+    let after: Vec<serde_json::Value> = splits
+        .iter()
+        .map(|(pool_id, percentage)| serde_json::json!({ "resource_pool_id": pool_id, "percentage": percentage }))
+        .collect();
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Initiative.name().to_string(),
+        entity_id: Some(initiative_id.clone()),
+        action: "SetPoolSplit".to_string(),
+        description: Some(format!("Split effort across {} pool(s)", splits.len())),
+        before: serde_json::to_value(&before).ok(),
+        after: Some(serde_json::Value::Array(after)),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    get_initiative_pool_split(db, initiative_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(parts: &[(&str, f64)]) -> Vec<(String, f64)> {
+        parts.iter().map(|(id, pct)| (id.to_string(), *pct)).collect()
+    }
+
+    #[test]
+    fn splits_must_total_one_hundred() {
+        assert!(validate_pool_split(&split(&[("eng", 60.0), ("change", 40.0)])).is_ok());
+        assert!(validate_pool_split(&split(&[("a", 33.33), ("b", 33.33), ("c", 33.34)])).is_ok());
+        assert!(validate_pool_split(&[]).is_ok());
+
+        assert!(validate_pool_split(&split(&[("eng", 60.0), ("change", 30.0)])).is_err());
+        assert!(validate_pool_split(&split(&[("eng", 50.0), ("eng", 50.0)])).is_err());
+        assert!(validate_pool_split(&split(&[("eng", 120.0), ("change", -20.0)])).is_err());
+    }
+}
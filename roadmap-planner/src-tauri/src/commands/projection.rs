@@ -0,0 +1,194 @@
+// Tauri commands for projected entity lists
+// Returns only the requested fields, to keep large lists small over IPC; the typed getters are unchanged
+
+use crate::commands::rows::row_to_json;
+use serde_json::Value;
+use tauri::State;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    Plain,
+    // Stored as 0 or 1, returned as true or false as the typed rows have it
+    Bool,
+    // Stored as JSON text, returned parsed
+    Json,
+}
+
+pub struct ProjectedField {
+    // Field name as the typed struct serialises it
+    name: &'static str,
+    column: &'static str,
+    kind: FieldKind,
+}
+
+const fn field(name: &'static str, column: &'static str, kind: FieldKind) -> ProjectedField {
+    ProjectedField { name, column, kind }
+}
+
+// The only fields a projection may name; anything else is rejected before any SQL is built
+const INITIATIVE_FIELDS: &[ProjectedField] = &[
+    field("id", "id", FieldKind::Plain),
+    field("name", "name", FieldKind::Plain),
+    field("description", "description", FieldKind::Plain),
+    field("initiative_type", "type", FieldKind::Plain),
+    field("status", "status", FieldKind::Plain),
+    field("start_date", "start_date", FieldKind::Plain),
+    field("end_date", "end_date", FieldKind::Plain),
+    field("effort_estimate", "effort_estimate", FieldKind::Plain),
+    field("effort_uncertainty", "effort_uncertainty", FieldKind::Plain),
+    field("cost_estimate", "cost_estimate", FieldKind::Plain),
+    field("cost_uncertainty", "cost_uncertainty", FieldKind::Plain),
+    field("priority", "priority", FieldKind::Plain),
+    field("scenario_id", "scenario_id", FieldKind::Plain),
+    field("percent_complete", "percent_complete", FieldKind::Plain),
+    field("progress_from_milestones", "progress_from_milestones", FieldKind::Bool),
+    field("currency", "currency", FieldKind::Plain),
+    field("effort_unit", "effort_unit", FieldKind::Plain),
+    field("effort_profile", "effort_profile", FieldKind::Plain),
+    field("external_ref", "external_ref", FieldKind::Plain),
+    field("reference_code", "reference_code", FieldKind::Plain),
+    field("is_key_date", "is_key_date", FieldKind::Bool),
+    field("colour", "colour", FieldKind::Plain),
+    field("icon", "icon", FieldKind::Plain),
+    field("owner_resource_id", "owner_resource_id", FieldKind::Plain),
+    field("created_at", "created_at", FieldKind::Plain),
+    field("updated_at", "updated_at", FieldKind::Plain),
+];
+
+const SYSTEM_FIELDS: &[ProjectedField] = &[
+    field("id", "id", FieldKind::Plain),
+    field("name", "name", FieldKind::Plain),
+    field("description", "description", FieldKind::Plain),
+    field("owner", "owner", FieldKind::Plain),
+    field("owner_resource_id", "owner_resource_id", FieldKind::Plain),
+    field("vendor", "vendor", FieldKind::Plain),
+    field("technology_stack", "technology_stack", FieldKind::Json),
+    field("lifecycle_stage", "lifecycle_stage", FieldKind::Plain),
+    field("criticality", "criticality", FieldKind::Plain),
+    field("support_end_date", "support_end_date", FieldKind::Plain),
+    field("extended_support_end_date", "extended_support_end_date", FieldKind::Plain),
+    field("capability_id", "capability_id", FieldKind::Plain),
+    field("external_ref", "external_ref", FieldKind::Plain),
+    field("created_at", "created_at", FieldKind::Plain),
+    field("updated_at", "updated_at", FieldKind::Plain),
+];
+
+/// The whitelisted fields named, in the order given and always starting with id. No fields
+/// means all of them.
+fn resolve_fields<'a>(allowed: &'a [ProjectedField], entity: &str, fields: Option<&[String]>) -> Result<Vec<&'a ProjectedField>, String> {
+    let Some(fields) = fields.filter(|f| !f.is_empty()) else {
+        return Ok(allowed.iter().collect());
+    };
+
+    let mut resolved = vec![&allowed[0]];
+    for name in fields {
+        let field = allowed
+            .iter()
+            .find(|f| f.name == name)
+            .ok_or_else(|| format!("Unknown {} field \"{}\"", entity, name))?;
+        if !resolved.iter().any(|f| f.name == field.name) {
+            resolved.push(field);
+        }
+    }
+    Ok(resolved)
+}
+
+fn select_list(fields: &[&ProjectedField]) -> String {
+    fields
+        .iter()
+        .map(|f| if f.name == f.column { f.column.to_string() } else { format!("{} AS {}", f.column, f.name) })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Give booleans and JSON columns the shape the typed rows serialise them in
+fn shape_row(mut row: Value, fields: &[&ProjectedField]) -> Value {
+    if let Value::Object(object) = &mut row {
+        for field in fields.iter().filter(|f| f.kind != FieldKind::Plain) {
+            let Some(value) = object.get_mut(field.name) else {
+                continue;
+            };
+            *value = match (field.kind, value.take()) {
+                (FieldKind::Bool, Value::Number(n)) => Value::Bool(n.as_i64() == Some(1)),
+                (FieldKind::Json, Value::String(text)) => serde_json::from_str(&text).unwrap_or(Value::String(text)),
+                (_, other) => other,
+            };
+        }
+    }
+    row
+}
+
+// ============================================
+// PROJECTION COMMANDS
+// ============================================
+
+/// Initiatives with only the named fields, e.g. ["name", "start_date", "end_date", "status",
+/// "colour"] for the roadmap; ordered as get_initiatives orders them
+#[tauri::command]
+pub async fn query_initiatives(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: Option<String>, fields: Option<Vec<String>>) -> Result<Vec<Value>, String> {
+    let fields = resolve_fields(INITIATIVE_FIELDS, "initiative", fields.as_deref())?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM initiatives WHERE ?1 IS NULL OR scenario_id = ?1 ORDER BY start_date, name",
+        select_list(&fields)
+    ))
+    .bind(scenario_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows.iter().map(|r| shape_row(row_to_json(r), &fields)).collect())
+}
+
+/// Systems with only the named fields, ordered by name
+#[tauri::command]
+pub async fn query_systems(db: State<'_, tauri_plugin_sql::DbInstances>, fields: Option<Vec<String>>) -> Result<Vec<Value>, String> {
+    let fields = resolve_fields(SYSTEM_FIELDS, "system", fields.as_deref())?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows = sqlx::query(&format!("SELECT {} FROM systems ORDER BY name", select_list(&fields)))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(rows.iter().map(|r| shape_row(row_to_json(r), &fields)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn names(fields: &[&ProjectedField]) -> Vec<&'static str> {
+        fields.iter().map(|f| f.name).collect()
+    }
+
+    #[test]
+    fn only_whitelisted_fields_are_selected() {
+        let requested = vec!["name".to_string(), "initiative_type".to_string(), "name".to_string()];
+        let fields = resolve_fields(INITIATIVE_FIELDS, "initiative", Some(&requested)).unwrap();
+        assert_eq!(names(&fields), ["id", "name", "initiative_type"]);
+        assert_eq!(select_list(&fields), "id, name, type AS initiative_type");
+
+        let injected = vec!["name FROM initiatives; DROP TABLE initiatives; --".to_string()];
+        assert!(resolve_fields(INITIATIVE_FIELDS, "initiative", Some(&injected)).is_err());
+
+        assert_eq!(resolve_fields(SYSTEM_FIELDS, "system", None).unwrap().len(), SYSTEM_FIELDS.len());
+    }
+
+    #[test]
+    fn rows_take_the_typed_shape() {
+        let fields = resolve_fields(SYSTEM_FIELDS, "system", None).unwrap();
+        let row = shape_row(json!({ "id": "crm", "technology_stack": "[\"Java\",\"Oracle\"]" }), &fields);
+        assert_eq!(row, json!({ "id": "crm", "technology_stack": ["Java", "Oracle"] }));
+
+        let fields = resolve_fields(INITIATIVE_FIELDS, "initiative", Some(&["is_key_date".to_string()])).unwrap();
+        let row = shape_row(json!({ "id": "a", "is_key_date": 1 }), &fields);
+        assert_eq!(row, json!({ "id": "a", "is_key_date": true }));
+    }
+}
@@ -0,0 +1,144 @@
+// Reference codes for initiatives
+// Short public identifiers like RM-0142, numbered from a per-workspace counter
+
+use sqlx::SqliteConnection;
+
+// Prefix for newly assigned codes; existing codes keep the prefix they were given
+pub const REFERENCE_PREFIX_SETTING: &str = "reference_code_prefix";
+const DEFAULT_REFERENCE_PREFIX: &str = "RM";
+const MAX_PREFIX_LENGTH: usize = 10;
+
+const INITIATIVE_SEQUENCE: &str = "initiatives";
+
+pub fn validate_reference_prefix(prefix: &str) -> Result<(), String> {
+    if prefix.is_empty() || prefix.len() > MAX_PREFIX_LENGTH || !prefix.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!(
+            "Reference code prefix must be 1 to {} letters or digits, got \"{}\"",
+            MAX_PREFIX_LENGTH, prefix
+        ));
+    }
+    Ok(())
+}
+
+pub fn format_reference_code(prefix: &str, number: i64) -> String {
+    format!("{}-{:04}", prefix.to_ascii_uppercase(), number)
+}
+
+/// Trimmed and upper-cased, or None when blank
+pub fn normalise_reference_code(code: &str) -> Option<String> {
+    let code = code.trim();
+    (!code.is_empty()).then(|| code.to_ascii_uppercase())
+}
+
+/// The number after the last hyphen, which the counter must stay ahead of
+pub fn reference_number(code: &str) -> Option<i64> {
+    let (_, digits) = code.rsplit_once('-')?;
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+async fn read_reference_prefix(conn: &mut SqliteConnection) -> Result<String, String> {
+    let prefix = sqlx::query_scalar!("SELECT value FROM settings WHERE key = ?", REFERENCE_PREFIX_SETTING)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .unwrap_or_else(|| DEFAULT_REFERENCE_PREFIX.to_string());
+
+    let prefix = prefix.trim().to_string();
+    validate_reference_prefix(&prefix)?;
+    Ok(prefix)
+}
+
+/// Move the counter past an imported code so later codes don't collide with it
+async fn advance_reference_counter(conn: &mut SqliteConnection, code: &str) -> Result<(), String> {
+    let Some(number) = reference_number(code) else {
+        return Ok(());
+    };
+
+    sqlx::query!(
+        r#"INSERT INTO reference_sequences (name, last_value) VALUES (?1, ?2)
+        ON CONFLICT(name) DO UPDATE SET last_value = MAX(last_value, ?2)"#,
+        INITIATIVE_SEQUENCE,
+        number
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+async fn reference_code_in_use(conn: &mut SqliteConnection, code: &str) -> Result<bool, String> {
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM initiatives WHERE reference_code = ? COLLATE NOCASE"#,
+        code
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(count > 0)
+}
+
+/// Take the next code from the workspace counter
+pub async fn next_reference_code(conn: &mut SqliteConnection) -> Result<String, String> {
+    let prefix = read_reference_prefix(&mut *conn).await?;
+
+    // Codes written outside the counter are skipped rather than reused
+    loop {
+        let number = sqlx::query_scalar!(
+            r#"INSERT INTO reference_sequences (name, last_value) VALUES (?, 1)
+            ON CONFLICT(name) DO UPDATE SET last_value = last_value + 1
+            RETURNING last_value"#,
+            INITIATIVE_SEQUENCE
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let code = format_reference_code(&prefix, number);
+        if !reference_code_in_use(&mut *conn, &code).await? {
+            return Ok(code);
+        }
+    }
+}
+
+/// Keep a code carried in from elsewhere if it's free in this workspace, advancing the counter
+/// past it; None when it's blank or already taken
+pub async fn claim_reference_code(conn: &mut SqliteConnection, code: &str) -> Result<Option<String>, String> {
+    let Some(code) = normalise_reference_code(code) else {
+        return Ok(None);
+    };
+    if reference_code_in_use(&mut *conn, &code).await? {
+        return Ok(None);
+    }
+
+    advance_reference_counter(&mut *conn, &code).await?;
+    Ok(Some(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_zero_padded_and_numbers_read_back() {
+        assert_eq!(format_reference_code("rm", 142), "RM-0142");
+        assert_eq!(format_reference_code("RM", 12345), "RM-12345");
+
+        assert_eq!(reference_number("RM-0142"), Some(142));
+        assert_eq!(reference_number("OPS-2024-0007"), Some(7));
+        assert_eq!(reference_number("RM-"), None);
+        assert_eq!(reference_number("PROJ-12a"), None);
+
+        assert_eq!(normalise_reference_code("  rm-0001 "), Some("RM-0001".to_string()));
+        assert_eq!(normalise_reference_code("   "), None);
+
+        assert!(validate_reference_prefix("OPS").is_ok());
+        assert!(validate_reference_prefix("").is_err());
+        assert!(validate_reference_prefix("R M").is_err());
+    }
+}
@@ -0,0 +1,104 @@
+// Tauri commands for scenario risk scoring
+// Combines the constraint, dependency, resource and budget engines into one indicator
+
+use crate::commands::engine::budget::calculate_budget_report;
+use crate::commands::engine::constraints::check_hard_constraints;
+use crate::commands::engine::dependencies::check_all_dependencies;
+use crate::commands::engine::resources::find_over_allocations;
+use crate::commands::scenario_data::{ScenarioData, load_scenario_data};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+// Score thresholds for the traffic-light level
+const AMBER_THRESHOLD: f64 = 10.0;
+const RED_THRESHOLD: f64 = 30.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskWeights {
+    pub hard_constraint_violation: f64,
+    pub schedule_violation: f64,
+    pub over_allocation: f64,
+    pub budget_overrun: f64,
+}
+
+impl Default for RiskWeights {
+    fn default() -> Self {
+        Self {
+            hard_constraint_violation: 10.0,
+            schedule_violation: 5.0,
+            over_allocation: 3.0,
+            budget_overrun: 4.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskCategory {
+    pub category: String,
+    pub count: i64,
+    pub weight: f64,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskScore {
+    pub scenario_id: String,
+    pub score: f64,
+    // Green, Amber or Red
+    pub level: String,
+    pub breakdown: Vec<RiskCategory>,
+}
+
+pub fn calculate_risk_score(scenario_id: &str, data: &ScenarioData, weights: &RiskWeights) -> RiskScore {
+    let hard_violations = check_hard_constraints(&data.initiatives, &data.constraints, &data.constraint_links).len();
+    let schedule_violations = check_all_dependencies(&data.initiatives, &data.dependencies).len();
+    let allocations = data.resource_allocation();
+    let over_allocations = find_over_allocations(&allocations).len();
+    let budget_overruns = calculate_budget_report(&data.initiatives, &data.periods, &data.converter)
+        .iter()
+        .filter(|p| p.is_overrun)
+        .count();
+
+    let breakdown: Vec<RiskCategory> = [
+        ("HardConstraintViolations", hard_violations, weights.hard_constraint_violation),
+        ("ScheduleViolations", schedule_violations, weights.schedule_violation),
+        ("OverAllocations", over_allocations, weights.over_allocation),
+        ("BudgetOverruns", budget_overruns, weights.budget_overrun),
+    ]
+    .into_iter()
+    .map(|(category, count, weight)| RiskCategory {
+        category: category.to_string(),
+        count: count as i64,
+        weight,
+        score: count as f64 * weight,
+    })
+    .collect();
+
+    let score: f64 = breakdown.iter().map(|c| c.score).sum();
+    let level = if score >= RED_THRESHOLD {
+        "Red"
+    } else if score >= AMBER_THRESHOLD {
+        "Amber"
+    } else {
+        "Green"
+    };
+
+    RiskScore {
+        scenario_id: scenario_id.to_string(),
+        score,
+        level: level.to_string(),
+        breakdown,
+    }
+}
+
+// ============================================
+// RISK SCORING COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_scenario_risk_score(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, weights: Option<RiskWeights>) -> Result<RiskScore, String> {
+    let data = load_scenario_data(db, &scenario_id).await?;
+
+    Ok(calculate_risk_score(&scenario_id, &data, &weights.unwrap_or_default()))
+}
@@ -0,0 +1,48 @@
+// Generic row helpers for commands that work across tables
+// Converts untyped SQLite rows into JSON values
+
+use serde_json::{Map, Value};
+use sqlx::query::Query;
+use sqlx::sqlite::{SqliteArguments, SqliteRow};
+use sqlx::{Column, Row, Sqlite, TypeInfo, ValueRef};
+
+pub fn row_to_json(row: &SqliteRow) -> Value {
+    let mut object = Map::new();
+
+    for column in row.columns() {
+        let index = column.ordinal();
+        let value = match row.try_get_raw(index) {
+            Ok(raw) if raw.is_null() => Value::Null,
+            Ok(raw) => match raw.type_info().name() {
+                "INTEGER" => row.try_get::<i64, _>(index).map(Value::from).unwrap_or(Value::Null),
+                "REAL" => row.try_get::<f64, _>(index).map(Value::from).unwrap_or(Value::Null),
+                "TEXT" => row.try_get::<String, _>(index).map(Value::from).unwrap_or(Value::Null),
+                // No tables store blobs; anything else is surfaced as null
+                _ => Value::Null,
+            },
+            Err(_) => Value::Null,
+        };
+        object.insert(column.name().to_string(), value);
+    }
+
+    Value::Object(object)
+}
+
+/// Bind value for `IN (SELECT value FROM json_each(?))` clauses
+pub fn ids_json(ids: &[String]) -> String {
+    serde_json::to_string(ids).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Bind a JSON value produced by row_to_json back into a query
+pub fn bind_json<'q>(query: Query<'q, Sqlite, SqliteArguments<'q>>, value: &Value) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+    match value {
+        Value::Null => query.bind(None::<String>),
+        Value::Bool(b) => query.bind(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => query.bind(i),
+            None => query.bind(n.as_f64()),
+        },
+        Value::String(s) => query.bind(s.clone()),
+        other => query.bind(other.to_string()),
+    }
+}
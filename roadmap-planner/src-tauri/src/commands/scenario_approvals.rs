@@ -0,0 +1,299 @@
+// Tauri commands for scenario sign-off
+// Named approvers record decisions by role; full approval locks the scenario
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::entities::EntityType;
+use crate::commands::settings::read_setting;
+use crate::commands::{ensure_unlocked, get_scenario, require_scenario};
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+pub const APPROVAL_DECISIONS: [&str; 2] = ["Approve", "Reject"];
+
+// Comma-separated roles that must all approve, e.g. "Sponsor, Architecture Board"
+pub const REQUIRED_ROLES_SETTING: &str = "approvals.required_roles";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioApproval {
+    pub id: String,
+    pub scenario_id: String,
+    pub approver_name: String,
+    pub role: String,
+    // Approve or Reject
+    pub decision: String,
+    pub comment: Option<String>,
+    pub approval_round: i64,
+    pub decided_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioApprovalStatus {
+    pub scenario_id: String,
+    // NotSubmitted, Pending, Rejected, Approved or ApprovedWithChanges
+    pub approval_state: String,
+    pub approval_round: i64,
+    pub required_roles: Vec<String>,
+    // Required roles yet to approve in the current round
+    pub outstanding_roles: Vec<String>,
+    pub is_locked: bool,
+    // Every decision across all rounds, oldest first
+    pub decisions: Vec<ScenarioApproval>,
+}
+
+pub async fn read_required_roles(pool: &SqlitePool) -> Result<Vec<String>, String> {
+    let mut seen = HashSet::new();
+    Ok(read_setting(pool, REQUIRED_ROLES_SETTING)
+        .await?
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|r| !r.is_empty() && seen.insert(r.to_lowercase()))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Judge a round from its decisions, oldest first, where each role's latest decision stands:
+/// - any role's rejection makes it Rejected
+/// - approval by every required role (or by anyone, when no roles are required) makes it Approved
+/// - otherwise a scenario approved in an earlier round has changed since, so ApprovedWithChanges
+/// - otherwise Pending once anyone has decided, and NotSubmitted before that
+///
+/// Returns the state and the required roles still to approve.
+pub fn approval_state(decisions: &[(&str, &str)], required_roles: &[String], previously_approved: bool) -> (String, Vec<String>) {
+    let mut latest: HashMap<String, &str> = HashMap::new();
+    for (role, decision) in decisions {
+        latest.insert(role.trim().to_lowercase(), decision);
+    }
+
+    let outstanding: Vec<String> = required_roles
+        .iter()
+        .filter(|role| latest.get(&role.trim().to_lowercase()) != Some(&"Approve"))
+        .cloned()
+        .collect();
+
+    let state = if latest.values().any(|d| *d == "Reject") {
+        "Rejected"
+    } else if outstanding.is_empty() && latest.values().any(|d| *d == "Approve") {
+        "Approved"
+    } else if previously_approved {
+        "ApprovedWithChanges"
+    } else if !latest.is_empty() {
+        "Pending"
+    } else {
+        "NotSubmitted"
+    };
+    (state.to_string(), outstanding)
+}
+
+struct ApprovalRound {
+    approval_round: i64,
+    approved_round: Option<i64>,
+    is_locked: bool,
+}
+
+async fn fetch_round(conn: &mut SqliteConnection, scenario_id: &str) -> Result<ApprovalRound, String> {
+    let row = sqlx::query!(
+        r#"SELECT approval_round, approved_round, is_locked as "is_locked: bool" FROM scenarios WHERE id = ?"#,
+        scenario_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Scenario not found: {}", scenario_id))?;
+
+    Ok(ApprovalRound { approval_round: row.approval_round, approved_round: row.approved_round, is_locked: row.is_locked })
+}
+
+async fn fetch_decisions(conn: &mut SqliteConnection, scenario_id: &str) -> Result<Vec<ScenarioApproval>, String> {
+    sqlx::query_as!(
+        ScenarioApproval,
+        r#"SELECT id, scenario_id, approver_name, role, decision, comment, approval_round, decided_at
+        FROM scenario_approvals WHERE scenario_id = ? ORDER BY decided_at, rowid"#,
+        scenario_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+fn round_state(round: &ApprovalRound, decisions: &[ScenarioApproval], required_roles: &[String]) -> (String, Vec<String>) {
+    let current: Vec<(&str, &str)> = decisions
+        .iter()
+        .filter(|d| d.approval_round == round.approval_round)
+        .map(|d| (d.role.as_str(), d.decision.as_str()))
+        .collect();
+    let previously_approved = round.approved_round.is_some_and(|r| r < round.approval_round);
+    approval_state(&current, required_roles, previously_approved)
+}
+
+async fn approval_status(conn: &mut SqliteConnection, scenario_id: &str, required_roles: Vec<String>) -> Result<ScenarioApprovalStatus, String> {
+    let round = fetch_round(conn, scenario_id).await?;
+    let decisions = fetch_decisions(conn, scenario_id).await?;
+    let (approval_state, outstanding_roles) = round_state(&round, &decisions, &required_roles);
+
+    Ok(ScenarioApprovalStatus {
+        scenario_id: scenario_id.to_string(),
+        approval_state,
+        approval_round: round.approval_round,
+        required_roles,
+        outstanding_roles,
+        is_locked: round.is_locked,
+        decisions,
+    })
+}
+
+/// Approval state of every scenario, for the summaries
+pub async fn load_approval_states(pool: &SqlitePool) -> Result<HashMap<String, String>, String> {
+    let required_roles = read_required_roles(pool).await?;
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+
+    let scenario_ids: Vec<String> = sqlx::query_scalar!(r#"SELECT id as "id!" FROM scenarios"#)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut states = HashMap::with_capacity(scenario_ids.len());
+    for scenario_id in scenario_ids {
+        let round = fetch_round(&mut conn, &scenario_id).await?;
+        let decisions = fetch_decisions(&mut conn, &scenario_id).await?;
+        states.insert(scenario_id, round_state(&round, &decisions, &required_roles).0);
+    }
+    Ok(states)
+}
+
+// ============================================
+// SCENARIO APPROVAL COMMANDS
+// ============================================
+
+/// Record an approver's decision in the current round. The decision that completes approval
+/// locks the scenario; unlocking it and changing an initiative starts a new round.
+#[tauri::command]
+pub async fn record_scenario_decision(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, approver_name: String, role: String, decision: String, comment: Option<String>) -> Result<ScenarioApprovalStatus, String> {
+    let approver_name = approver_name.trim().to_string();
+    let role = role.trim().to_string();
+    if approver_name.is_empty() || role.is_empty() {
+        return Err("An approver name and role are required".to_string());
+    }
+    if !APPROVAL_DECISIONS.contains(&decision.as_str()) {
+        return Err(format!("Decision must be one of {}, got {}", APPROVAL_DECISIONS.join(", "), decision));
+    }
+    let comment = comment.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+
+    let scenario = get_scenario(db.clone(), scenario_id.clone()).await?;
+    ensure_unlocked(&scenario)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let required_roles = read_required_roles(pool).await?;
+    let now = get_current_timestamp();
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let round = fetch_round(&mut tx, &scenario_id).await?;
+    let before = round_state(&round, &fetch_decisions(&mut tx, &scenario_id).await?, &required_roles).0;
+
+    let approval = ScenarioApproval {
+        id: uuid::Uuid::new_v4().to_string(),
+        scenario_id: scenario_id.clone(),
+        approver_name,
+        role,
+        decision,
+        comment,
+        approval_round: round.approval_round,
+        decided_at: now.clone(),
+    };
+    sqlx::query!(
+        r#"INSERT INTO scenario_approvals (id, scenario_id, approver_name, role, decision, comment, approval_round, decided_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+        approval.id,
+        approval.scenario_id,
+        approval.approver_name,
+        approval.role,
+        approval.decision,
+        approval.comment,
+        approval.approval_round,
+        approval.decided_at
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let verb = if approval.decision == "Approve" { "approved" } else { "rejected" };
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Scenario.name().to_string(),
+        entity_id: Some(scenario_id.clone()),
+        action: approval.decision.clone(),
+        description: Some(format!("{} ({}) {} {}", approval.approver_name, approval.role, verb, scenario.name)),
+        after: serde_json::to_value(&approval).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    let after = round_state(&round, &fetch_decisions(&mut tx, &scenario_id).await?, &required_roles).0;
+    if after == "Approved" && before != "Approved" {
+        sqlx::query!(
+            "UPDATE scenarios SET is_locked = 1, approved_round = approval_round, updated_at = ? WHERE id = ?",
+            now,
+            scenario_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        record_audit(&mut tx, NewAuditEntry {
+            entity_type: EntityType::Scenario.name().to_string(),
+            entity_id: Some(scenario_id.clone()),
+            action: "Lock".to_string(),
+            description: Some(format!("Locked {} on approval (round {})", scenario.name, round.approval_round)),
+            ..Default::default()
+        })
+        .await?;
+    }
+
+    let status = approval_status(&mut tx, &scenario_id, required_roles).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn get_scenario_approvals(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<ScenarioApprovalStatus, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let required_roles = read_required_roles(pool).await?;
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    approval_status(&mut conn, &scenario_id, required_roles).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_required_role_must_approve() {
+        let required = vec!["Sponsor".to_string(), "Architecture".to_string()];
+
+        assert_eq!(approval_state(&[], &required, false).0, "NotSubmitted");
+
+        let (state, outstanding) = approval_state(&[("sponsor", "Approve")], &required, false);
+        assert_eq!(state, "Pending");
+        assert_eq!(outstanding, ["Architecture"]);
+
+        // A role's latest decision stands
+        let decisions = [("Sponsor", "Approve"), ("Architecture", "Reject"), ("Architecture", "Approve")];
+        assert_eq!(approval_state(&decisions, &required, false).0, "Approved");
+        assert_eq!(approval_state(&decisions[..2], &required, false).0, "Rejected");
+
+        // Changed since an earlier approval and not yet signed off again
+        assert_eq!(approval_state(&[("Sponsor", "Approve")], &required, true).0, "ApprovedWithChanges");
+
+        // With no roles configured, one approval is enough
+        assert_eq!(approval_state(&[("Finance", "Approve")], &[], false).0, "Approved");
+    }
+}
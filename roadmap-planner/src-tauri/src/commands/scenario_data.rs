@@ -0,0 +1,164 @@
+// Shared loaders for scenario-wide calculations
+// Fetches everything the calculation engine needs for one scenario
+
+use crate::commands::engine::constraints::InitiativeConstraintLink;
+use crate::commands::engine::currency::CurrencyConverter;
+use crate::commands::engine::dependencies::InitiativeDependency;
+use crate::commands::engine::overrides::{ScenarioOverride, apply_capacity_overrides, apply_initiative_overrides, apply_period_overrides};
+use crate::commands::engine::resources::{InitiativeResourceRequirement, PoolPeriodAllocation, PoolRoleCapacity, PoolSplit, calculate_resource_allocation};
+use crate::commands::exchange_rates::load_currency_converter;
+use crate::commands::{get_constraints, get_financial_periods, get_initiatives, get_resource_pools, get_resources, require_scenario};
+use crate::db::{Constraint, FinancialPeriod, Initiative, Resource, ResourcePool};
+use tauri::State;
+
+pub struct ScenarioData {
+    pub initiatives: Vec<Initiative>,
+    pub dependencies: Vec<InitiativeDependency>,
+    pub requirements: Vec<InitiativeResourceRequirement>,
+    pub splits: Vec<PoolSplit>,
+    pub pools: Vec<ResourcePool>,
+    pub role_capacities: Vec<PoolRoleCapacity>,
+    // Pool members, whose roles stand in for pools without role capacities
+    pub resources: Vec<Resource>,
+    pub constraints: Vec<Constraint>,
+    pub constraint_links: Vec<InitiativeConstraintLink>,
+    pub periods: Vec<FinancialPeriod>,
+    pub converter: CurrencyConverter,
+    // What-if values for this scenario; initiative and period overrides are already applied
+    pub overrides: Vec<ScenarioOverride>,
+}
+
+impl ScenarioData {
+    /// Demand against capacity for every pool, with the scenario's capacity overrides applied.
+    /// Requested demand counts alongside approved demand.
+    pub fn resource_allocation(&self) -> Vec<PoolPeriodAllocation> {
+        self.resource_allocation_including(true)
+    }
+
+    /// As resource_allocation, leaving out demand still awaiting approval unless asked for
+    pub fn resource_allocation_including(&self, include_unapproved: bool) -> Vec<PoolPeriodAllocation> {
+        let approved: Vec<InitiativeResourceRequirement>;
+        let requirements = if include_unapproved {
+            &self.requirements
+        } else {
+            approved = self.requirements.iter().filter(|r| r.is_approved()).cloned().collect();
+            &approved
+        };
+        let mut allocations = calculate_resource_allocation(&self.initiatives, requirements, &self.splits, &self.pools, &self.role_capacities, &self.resources);
+        apply_capacity_overrides(&mut allocations, &self.overrides);
+        allocations
+    }
+
+    /// One pool's allocation with the scenario's initiatives rearranged, for trying out moves
+    pub fn pool_allocation_for(&self, initiatives: &[Initiative], pool_id: &str) -> Vec<PoolPeriodAllocation> {
+        let pools: Vec<ResourcePool> = self.pools.iter().filter(|p| p.id == pool_id).cloned().collect();
+        let mut allocations = calculate_resource_allocation(initiatives, &self.requirements, &self.splits, &pools, &self.role_capacities, &self.resources);
+        apply_capacity_overrides(&mut allocations, &self.overrides);
+        allocations
+    }
+}
+
+pub async fn load_scenario_data(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: &str) -> Result<ScenarioData, String> {
+    require_scenario(db.clone(), scenario_id).await?;
+
+    let mut initiatives = get_initiatives(db.clone(), Some(scenario_id.to_string())).await?;
+    let pools = get_resource_pools(db.clone()).await?;
+    let resources = get_resources(db.clone(), None).await?;
+    let constraints = get_constraints(db.clone()).await?;
+    let mut periods = get_financial_periods(db.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // Only edges between initiatives of this scenario
+    let dependencies: Vec<InitiativeDependency> = sqlx::query_as!(
+        InitiativeDependency,
+        r#"SELECT d.id, d.predecessor_id, d.successor_id, d.dependency_type, d.lag_days, d.created_at
+        FROM initiative_dependencies d
+        JOIN initiatives s ON s.id = d.successor_id
+        JOIN initiatives p ON p.id = d.predecessor_id
+        WHERE s.scenario_id = ? AND p.scenario_id = ?"#,
+        scenario_id,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let requirements: Vec<InitiativeResourceRequirement> = sqlx::query_as!(
+        InitiativeResourceRequirement,
+        r#"SELECT r.id, r.initiative_id, r.resource_pool_id, r.effort_required,
+            r.period_start, r.period_end, r.role, r.approval_status, r.approver_note, r.generated as "generated: bool", r.created_at
+        FROM initiative_resource_requirements r
+        JOIN initiatives i ON i.id = r.initiative_id
+        WHERE i.scenario_id = ? AND r.approval_status != 'Rejected'"#,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let splits: Vec<PoolSplit> = sqlx::query_as!(
+        PoolSplit,
+        r#"SELECT p.id, p.initiative_id, p.resource_pool_id, p.percentage, p.created_at
+        FROM initiative_pool_splits p
+        JOIN initiatives i ON i.id = p.initiative_id
+        WHERE i.scenario_id = ?"#,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let role_capacities: Vec<PoolRoleCapacity> = sqlx::query_as!(
+        PoolRoleCapacity,
+        r#"SELECT id, resource_pool_id, role, capacity_per_period, created_at, updated_at
+        FROM pool_role_capacities ORDER BY resource_pool_id, role"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let constraint_links: Vec<InitiativeConstraintLink> = sqlx::query_as!(
+        InitiativeConstraintLink,
+        r#"SELECT l.id, l.initiative_id, l.constraint_id, l.created_at
+        FROM initiative_constraints l
+        JOIN initiatives i ON i.id = l.initiative_id
+        WHERE i.scenario_id = ?"#,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let overrides: Vec<ScenarioOverride> = sqlx::query_as!(
+        ScenarioOverride,
+        r#"SELECT id, scenario_id, entity_type, entity_id, field, override_value,
+            effective_from, effective_to, created_at, updated_at
+        FROM scenario_overrides WHERE scenario_id = ? ORDER BY created_at"#,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    apply_initiative_overrides(&mut initiatives, &overrides);
+    apply_period_overrides(&mut periods, &overrides);
+
+    let converter = load_currency_converter(pool).await?;
+
+    Ok(ScenarioData {
+        initiatives,
+        dependencies,
+        requirements,
+        splits,
+        pools,
+        role_capacities,
+        resources,
+        constraints,
+        constraint_links,
+        periods,
+        converter,
+        overrides,
+    })
+}
@@ -0,0 +1,246 @@
+// Tauri commands for scenario growth
+// Initiative count and effort on each day a scenario changed, rebuilt from the audit log
+
+use crate::commands::audit::AuditEntry;
+use crate::commands::digest::entry_changes;
+use crate::commands::get_scenario;
+use crate::commands::rows::row_to_json;
+use crate::commands::workspace_diff::normalise_timestamp;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrowthPoint {
+    pub date: String,
+    // As at the end of the day, not counting Cancelled initiatives, as the dashboard summary does
+    pub initiative_count: i64,
+    pub total_effort: f64,
+    // Audit entries that touched the scenario that day
+    pub change_count: i64,
+}
+
+type Change = (String, Option<Value>, Option<Value>);
+
+// (initiative id, before, after) for every initiative an entry touched, including scenario-wide
+// operations that copy or replace initiatives wholesale
+fn growth_changes(entry: &AuditEntry) -> Vec<Change> {
+    let parse = |json: &Option<String>| json.as_deref().and_then(|j| serde_json::from_str::<Value>(j).ok());
+    let rows = |value: Option<Value>| match value {
+        Some(Value::Array(rows)) => rows,
+        _ => Vec::new(),
+    };
+    let id_of = |row: &Value| row.get("id").and_then(Value::as_str).map(str::to_string);
+
+    match (entry.entity_type.as_str(), entry.action.as_str()) {
+        // Only the scenario is recorded, before and after
+        ("Initiative", "MoveScenario") => {
+            let (before, after) = (parse(&entry.before_json), parse(&entry.after_json));
+            let scenario = |v: &Option<Value>| v.as_ref().and_then(|v| v.get("scenario_id")).cloned().unwrap_or(Value::Null);
+            let (from, to) = (scenario(&before), scenario(&after));
+            let moved: Vec<String> = after
+                .as_ref()
+                .and_then(|a| a.get("moved_initiative_ids"))
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|id| id.as_str().map(str::to_string))
+                .collect();
+            moved
+                .into_iter()
+                .map(|id| (id, Some(serde_json::json!({ "scenario_id": from })), Some(serde_json::json!({ "scenario_id": to }))))
+                .collect()
+        }
+        ("Initiative", _) => entry_changes(entry),
+        // Full rows discarded before, and copies tagged with their table after
+        ("Scenario", "ResetToBaseline" | "Snapshot") => {
+            let discarded = rows(parse(&entry.before_json)).into_iter().filter_map(|row| Some((id_of(&row)?, Some(row), None)));
+            let copied = rows(parse(&entry.after_json))
+                .into_iter()
+                .filter(|item| item.get("table").and_then(Value::as_str) == Some("initiatives"))
+                .filter_map(|item| {
+                    let row = item.get("row")?.clone();
+                    Some((id_of(&row)?, None, Some(row)))
+                });
+            discarded.chain(copied).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+// Put an initiative back as it was before a change. A before row may hold only the columns
+// that changed, so it is laid over the later row rather than replacing it.
+fn undo(state: &mut HashMap<String, Value>, (id, before, after): Change) {
+    match (before, state.get_mut(&id)) {
+        (None, _) => {
+            state.remove(&id);
+        }
+        (Some(Value::Object(fields)), Some(Value::Object(row))) if after.is_some() => row.extend(fields),
+        (Some(before), _) => {
+            state.insert(id, before);
+        }
+    }
+}
+
+fn in_scenario(row: Option<&Value>, scenario_id: &str) -> bool {
+    row.and_then(|r| r.get("scenario_id")).and_then(Value::as_str) == Some(scenario_id)
+}
+
+fn point(date: &str, state: &HashMap<String, Value>, scenario_id: &str, change_count: i64) -> GrowthPoint {
+    let active: Vec<&Value> = state
+        .values()
+        .filter(|row| in_scenario(Some(row), scenario_id))
+        .filter(|row| row.get("status").and_then(Value::as_str) != Some("Cancelled"))
+        .collect();
+    GrowthPoint {
+        date: date.to_string(),
+        initiative_count: active.len() as i64,
+        total_effort: active.iter().filter_map(|row| row.get("effort_estimate").and_then(Value::as_f64)).sum(),
+        change_count,
+    }
+}
+
+/// Walk the audit log back from today's initiatives, newest entry first, taking a point at the
+/// end of each day something in the scenario changed. Nothing before `created_on` is replayed;
+/// the state left then is the scenario as it started, a point of its own if nothing else that
+/// day was recorded. Points come back oldest first.
+pub fn reconstruct_growth(scenario_id: &str, created_on: Option<&str>, current: Vec<Value>, entries: &[AuditEntry]) -> Vec<GrowthPoint> {
+    let mut state: HashMap<String, Value> = current
+        .into_iter()
+        .filter_map(|row| Some((row.get("id")?.as_str()?.to_string(), row)))
+        .collect();
+    let day_of = |timestamp: &str| normalise_timestamp(timestamp).get(..10).map(str::to_string);
+
+    let mut points: Vec<GrowthPoint> = Vec::new();
+    let mut index = 0;
+    while index < entries.len() {
+        let Some(day) = entries[index].created_at.as_deref().and_then(day_of) else {
+            index += 1;
+            continue;
+        };
+        if created_on.is_some_and(|created| day.as_str() < created) {
+            break;
+        }
+
+        let end = entries[index..]
+            .iter()
+            .position(|e| e.created_at.as_deref().and_then(day_of).as_deref() != Some(day.as_str()))
+            .map_or(entries.len(), |offset| index + offset);
+        let at_end_of_day = point(&day, &state, scenario_id, 0);
+
+        let mut change_count = 0;
+        for entry in &entries[index..end] {
+            let mut touched = entry.entity_type == "Scenario" && entry.entity_id.as_deref() == Some(scenario_id);
+            for change in growth_changes(entry) {
+                let id = change.0.clone();
+                touched |= in_scenario(state.get(&id), scenario_id);
+                undo(&mut state, change);
+                touched |= in_scenario(state.get(&id), scenario_id);
+            }
+            change_count += touched as i64;
+        }
+        if change_count > 0 {
+            points.push(GrowthPoint { change_count, ..at_end_of_day });
+        }
+        index = end;
+    }
+
+    if let Some(created) = created_on {
+        if points.last().is_none_or(|p| p.date != created) {
+            points.push(point(created, &state, scenario_id, 0));
+        }
+    }
+
+    points.reverse();
+    points
+}
+
+// ============================================
+// SCENARIO GROWTH COMMANDS
+// ============================================
+
+/// How the scenario's initiative count and total effort have grown, one point per day it
+/// changed, oldest first. Stored values, before any scenario overrides.
+#[tauri::command]
+pub async fn get_scenario_growth(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<GrowthPoint>, String> {
+    let scenario = get_scenario(db.clone(), scenario_id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    // Every scenario's initiatives, as moves bring them in from elsewhere
+    let current: Vec<Value> = sqlx::query("SELECT * FROM initiatives")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(row_to_json)
+        .collect();
+
+    let entries: Vec<AuditEntry> = sqlx::query_as!(
+        AuditEntry,
+        r#"SELECT
+            id, group_id, entity_type, entity_id, action, description,
+            before_json, after_json, created_at
+        FROM audit_log
+        WHERE entity_type IN ('Initiative', 'Scenario')
+        ORDER BY created_at DESC, rowid DESC"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let created_on = scenario.created_at.as_deref().map(normalise_timestamp).and_then(|t| t.get(..10).map(str::to_string));
+    Ok(reconstruct_growth(&scenario_id, created_on.as_deref(), current, &entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(action: &str, at: &str, before: Option<Value>, after: Option<Value>) -> AuditEntry {
+        let id_of = |v: &Option<Value>| v.as_ref().and_then(|v| v.get("id")).and_then(Value::as_str).map(str::to_string);
+        AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            group_id: None,
+            entity_type: "Initiative".to_string(),
+            entity_id: id_of(&before).or_else(|| id_of(&after)),
+            action: action.to_string(),
+            description: None,
+            before_json: before.map(|v| v.to_string()),
+            after_json: after.map(|v| v.to_string()),
+            created_at: Some(at.to_string()),
+        }
+    }
+
+    fn row(id: &str, effort: f64) -> Value {
+        json!({ "id": id, "scenario_id": "plan", "status": "Planned", "effort_estimate": effort })
+    }
+
+    #[test]
+    fn growth_is_replayed_back_from_today() {
+        // Newest first, as the command reads them
+        let entries = vec![
+            entry("Delete", "2027-03-05T16:00:00Z", Some(row("c", 5.0)), None),
+            entry("Update", "2027-03-03T12:00:00Z", Some(row("b", 10.0)), Some(row("b", 30.0))),
+            entry("Create", "2027-03-03T09:00:00Z", None, Some(row("c", 5.0))),
+            entry("Create", "2027-03-01T09:00:00Z", None, Some(row("b", 10.0))),
+            entry("Create", "2027-02-20T09:00:00Z", None, Some(row("stale", 1.0))),
+        ];
+        let current = vec![row("a", 20.0), row("b", 30.0), json!({ "id": "other", "scenario_id": "elsewhere", "effort_estimate": 99.0 })];
+
+        let points = reconstruct_growth("plan", Some("2027-02-28"), current, &entries);
+        let trend: Vec<(&str, i64, f64, i64)> = points.iter().map(|p| (p.date.as_str(), p.initiative_count, p.total_effort, p.change_count)).collect();
+        assert_eq!(
+            trend,
+            [
+                ("2027-02-28", 1, 20.0, 0),
+                ("2027-03-01", 2, 30.0, 1),
+                ("2027-03-03", 3, 55.0, 2),
+                ("2027-03-05", 2, 50.0, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,232 @@
+// Tauri commands for per-scenario what-if overrides
+// Stored beside the scenario and read by the calculations, never written back to the entities
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::engine::overrides::{ScenarioOverride, validate_override};
+use crate::commands::entities::EntityType;
+use crate::commands::fetch::{fetch_financial_periods, fetch_initiatives, fetch_resource_pools, single};
+use crate::commands::{ensure_scenarios_unlocked, require_scenario};
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewScenarioOverride {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub field: String,
+    pub override_value: String,
+    pub effective_from: Option<String>,
+    pub effective_to: Option<String>,
+}
+
+// The overridden entity must exist, and an initiative must belong to the scenario
+async fn ensure_override_target(conn: &mut SqliteConnection, scenario_id: &str, entity_type: &str, entity_id: &str) -> Result<(), String> {
+    let ids = [entity_id.to_string()];
+    match entity_type {
+        "Initiative" => {
+            let initiative = single(fetch_initiatives(&mut *conn, &ids).await?, EntityType::Initiative, entity_id)?;
+            if initiative.scenario_id != scenario_id {
+                return Err(format!("Initiative {} is not in scenario {}", entity_id, scenario_id));
+            }
+        }
+        "ResourcePool" => {
+            single(fetch_resource_pools(&mut *conn, &ids).await?, EntityType::ResourcePool, entity_id)?;
+        }
+        "FinancialPeriod" => {
+            single(fetch_financial_periods(&mut *conn, &ids).await?, EntityType::FinancialPeriod, entity_id)?;
+        }
+        // validate_override has already rejected anything else
+        _ => {}
+    }
+    Ok(())
+}
+
+// ============================================
+// SCENARIO OVERRIDE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_scenario_overrides(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<Vec<ScenarioOverride>, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<ScenarioOverride> = sqlx::query_as!(
+        ScenarioOverride,
+        r#"SELECT id, scenario_id, entity_type, entity_id, field, override_value,
+            effective_from, effective_to, created_at, updated_at
+        FROM scenario_overrides WHERE scenario_id = ?
+        ORDER BY entity_type, entity_id, field, effective_from"#,
+        scenario_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+/// Override a field for one scenario's calculations, replacing any override of the same field
+/// whose effective dates overlap
+#[tauri::command]
+pub async fn set_scenario_override(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, scenario_override: NewScenarioOverride) -> Result<ScenarioOverride, String> {
+    let value = validate_override(
+        &scenario_override.entity_type,
+        &scenario_override.field,
+        &scenario_override.override_value,
+        scenario_override.effective_from.as_deref(),
+        scenario_override.effective_to.as_deref(),
+    )?;
+    let existing = get_scenario_overrides(db.clone(), scenario_id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+    let created = ScenarioOverride {
+        id: uuid::Uuid::new_v4().to_string(),
+        scenario_id: scenario_id.clone(),
+        entity_type: scenario_override.entity_type,
+        entity_id: scenario_override.entity_id,
+        field: scenario_override.field,
+        override_value: value,
+        effective_from: scenario_override.effective_from,
+        effective_to: scenario_override.effective_to,
+        created_at: Some(now.clone()),
+        updated_at: Some(now.clone()),
+    };
+
+    let replaced: Vec<&ScenarioOverride> = existing
+        .iter()
+        .filter(|o| o.entity_type == created.entity_type && o.entity_id == created.entity_id && o.field == created.field)
+        .filter(|o| o.overlaps(&created))
+        .collect();
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    ensure_scenarios_unlocked(&mut tx, std::slice::from_ref(&scenario_id), &[]).await?;
+    ensure_override_target(&mut tx, &scenario_id, &created.entity_type, &created.entity_id).await?;
+
+    for old in &replaced {
+        sqlx::query!("DELETE FROM scenario_overrides WHERE id = ?", old.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    sqlx::query!(
+        r#"INSERT INTO scenario_overrides (id, scenario_id, entity_type, entity_id, field, override_value,
+            effective_from, effective_to, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        created.id,
+        created.scenario_id,
+        created.entity_type,
+        created.entity_id,
+        created.field,
+        created.override_value,
+        created.effective_from,
+        created.effective_to,
+        now,
+        now
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Scenario.name().to_string(),
+        entity_id: Some(scenario_id),
+        action: "SetOverride".to_string(),
+        description: Some(format!(
+            "Override {}.{} on {} with {}",
+            created.entity_type, created.field, created.entity_id, created.override_value
+        )),
+        before: serde_json::to_value(&replaced).ok(),
+        after: serde_json::to_value(&created).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn clear_scenario_override(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let existing: ScenarioOverride = sqlx::query_as!(
+        ScenarioOverride,
+        r#"SELECT id, scenario_id, entity_type, entity_id, field, override_value,
+            effective_from, effective_to, created_at, updated_at
+        FROM scenario_overrides WHERE id = ?"#,
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Scenario override {} not found", id))?;
+
+    ensure_scenarios_unlocked(&mut tx, std::slice::from_ref(&existing.scenario_id), &[]).await?;
+
+    sqlx::query!("DELETE FROM scenario_overrides WHERE id = ?", id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    record_audit(&mut tx, NewAuditEntry {
+        entity_type: EntityType::Scenario.name().to_string(),
+        entity_id: Some(existing.scenario_id.clone()),
+        action: "ClearOverride".to_string(),
+        description: Some(format!("Cleared override of {}.{} on {}", existing.entity_type, existing.field, existing.entity_id)),
+        before: serde_json::to_value(&existing).ok(),
+        ..Default::default()
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Remove every override from a scenario, returning how many there were
+#[tauri::command]
+pub async fn clear_scenario_overrides(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<i64, String> {
+    let existing = get_scenario_overrides(db.clone(), scenario_id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    ensure_scenarios_unlocked(&mut tx, std::slice::from_ref(&scenario_id), &[]).await?;
+
+    let cleared = sqlx::query!("DELETE FROM scenario_overrides WHERE scenario_id = ?", scenario_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?
+        .rows_affected() as i64;
+
+    if cleared > 0 {
+        record_audit(&mut tx, NewAuditEntry {
+            entity_type: EntityType::Scenario.name().to_string(),
+            entity_id: Some(scenario_id),
+            action: "ClearOverride".to_string(),
+            description: Some(format!("Cleared {} override(s)", cleared)),
+            before: serde_json::to_value(&existing).ok(),
+            ..Default::default()
+        })
+        .await?;
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(cleared)
+}
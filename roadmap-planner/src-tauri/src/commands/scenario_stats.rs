@@ -0,0 +1,185 @@
+// Tauri commands for cached scenario stats
+// Dashboard totals stored per scenario and dropped whenever anything they are worked out from changes
+
+use crate::commands::engine::dates::today;
+use crate::commands::exchange_rates::load_currency_converter;
+use crate::commands::risk::{RiskWeights, calculate_risk_score};
+use crate::commands::scenario_data::load_scenario_data;
+use crate::commands::stale_data::{DEFAULT_STALE_AFTER_DAYS, count_stale_critical_systems};
+use crate::commands::summaries::{DEFAULT_BEHIND_THRESHOLD, summarise_scenario};
+use crate::commands::{get_initiatives, get_scenario};
+use crate::db::{Initiative, get_current_timestamp};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioStats {
+    pub scenario_id: String,
+    // Reporting currency the cost was converted into
+    pub currency: String,
+    pub initiative_count: i64,
+    pub status_counts: BTreeMap<String, i64>,
+    pub total_cost: f64,
+    pub total_effort: f64,
+    pub risk_score: f64,
+    pub risk_level: String,
+    pub computed_at: String,
+    // Estate-wide and dependent on today's date, so counted on every read rather than stored
+    pub stale_critical_systems: i64,
+}
+
+pub fn count_statuses(initiatives: &[Initiative]) -> BTreeMap<String, i64> {
+    let mut counts = BTreeMap::new();
+    for initiative in initiatives {
+        *counts.entry(initiative.status.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+// ============================================
+// SCENARIO STATS COMMANDS
+// ============================================
+
+/// Recompute the scenario's totals and risk, the same way as get_scenario_summaries and
+/// get_scenario_risk_score, and store them
+#[tauri::command]
+pub async fn refresh_scenario_stats(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<ScenarioStats, String> {
+    let scenario = get_scenario(db.clone(), scenario_id.clone()).await?;
+    let initiatives = get_initiatives(db.clone(), Some(scenario_id.clone())).await?;
+    let data = load_scenario_data(db.clone(), &scenario_id).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+    let converter = load_currency_converter(pool).await?;
+
+    let summary = summarise_scenario(scenario, &initiatives, &converter, today(), DEFAULT_BEHIND_THRESHOLD);
+    let risk = calculate_risk_score(&scenario_id, &data, &RiskWeights::default());
+
+    let stats = ScenarioStats {
+        scenario_id,
+        currency: summary.currency,
+        initiative_count: summary.initiative_count,
+        status_counts: count_statuses(&initiatives),
+        total_cost: summary.total_cost,
+        total_effort: summary.total_effort,
+        risk_score: risk.score,
+        risk_level: risk.level,
+        computed_at: get_current_timestamp(),
+        stale_critical_systems: count_stale_critical_systems(pool, DEFAULT_STALE_AFTER_DAYS).await?,
+    };
+
+    let status_counts = serde_json::to_string(&stats.status_counts).map_err(|e| e.to_string())?;
+    sqlx::query!(
+        r#"INSERT INTO scenario_stats
+            (scenario_id, currency, initiative_count, status_counts, total_cost, total_effort, risk_score, risk_level, computed_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(scenario_id) DO UPDATE SET
+            currency = excluded.currency,
+            initiative_count = excluded.initiative_count,
+            status_counts = excluded.status_counts,
+            total_cost = excluded.total_cost,
+            total_effort = excluded.total_effort,
+            risk_score = excluded.risk_score,
+            risk_level = excluded.risk_level,
+            computed_at = excluded.computed_at"#,
+        stats.scenario_id,
+        stats.currency,
+        stats.initiative_count,
+        status_counts,
+        stats.total_cost,
+        stats.total_effort,
+        stats.risk_score,
+        stats.risk_level,
+        stats.computed_at
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(stats)
+}
+
+/// The stored stats, recomputed first when there are none or the reporting currency has changed
+#[tauri::command]
+pub async fn get_scenario_stats(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<ScenarioStats, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let cached = sqlx::query!(
+        r#"SELECT scenario_id as "scenario_id!", currency, initiative_count, status_counts, total_cost, total_effort,
+            risk_score, risk_level, computed_at
+        FROM scenario_stats WHERE scenario_id = ?"#,
+        scenario_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let converter = load_currency_converter(pool).await?;
+
+    match cached {
+        Some(row) if row.currency == converter.reporting_currency => Ok(ScenarioStats {
+            scenario_id: row.scenario_id,
+            currency: row.currency,
+            initiative_count: row.initiative_count,
+            status_counts: serde_json::from_str(&row.status_counts).map_err(|e| e.to_string())?,
+            total_cost: row.total_cost,
+            total_effort: row.total_effort,
+            risk_score: row.risk_score,
+            risk_level: row.risk_level,
+            computed_at: row.computed_at,
+            stale_critical_systems: count_stale_critical_systems(pool, DEFAULT_STALE_AFTER_DAYS).await?,
+        }),
+        _ => refresh_scenario_stats(db, scenario_id).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Connection, SqliteConnection};
+
+    async fn cached_rows(conn: &mut SqliteConnection) -> i64 {
+        sqlx::query_scalar!("SELECT COUNT(*) FROM scenario_stats").fetch_one(conn).await.unwrap()
+    }
+
+    async fn cache(conn: &mut SqliteConnection, scenario_id: &str) {
+        sqlx::query!(
+            "INSERT OR REPLACE INTO scenario_stats VALUES (?, 'GBP', 2, '{}', 0, 0, 10, 'Low', '2026-10-01 09:00:00')",
+            scenario_id
+        )
+        .execute(conn)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn changing_a_risk_input_drops_the_cached_stats() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        crate::db::migrations::migrate_if_empty(&mut conn).await.unwrap();
+        sqlx::query!("INSERT INTO scenarios (id, name) VALUES ('s', 'Baseline'), ('t', 'Stretch')").execute(&mut conn).await.unwrap();
+        for id in ["a", "b"] {
+            sqlx::query!(
+                "INSERT INTO initiatives (id, name, type, status, priority, scenario_id) VALUES (?, ?, 'New', 'Planned', 'Must', 's')",
+                id,
+                id
+            )
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        }
+
+        cache(&mut conn, "s").await;
+        cache(&mut conn, "t").await;
+        sqlx::query!("INSERT INTO initiative_dependencies (id, predecessor_id, successor_id, dependency_type) VALUES ('d', 'a', 'b', 'FinishToStart')")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        // Only the scenario the dependency belongs to is recomputed
+        assert_eq!(cached_rows(&mut conn).await, 1);
+
+        cache(&mut conn, "s").await;
+        sqlx::query!("INSERT INTO resource_pools (id, name, capacity_unit, period_type) VALUES ('p', 'Platform', 'FTE', 'Month')").execute(&mut conn).await.unwrap();
+        assert_eq!(cached_rows(&mut conn).await, 0);
+    }
+}
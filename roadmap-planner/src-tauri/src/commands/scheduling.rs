@@ -0,0 +1,121 @@
+// Tauri commands for initiative scheduling
+// Date adjustments that keep initiatives aligned with planning periods and calendars
+
+use crate::commands::calendars::load_working_calendar;
+use crate::commands::engine::dates::{format_date, parse_date};
+use crate::commands::engine::effort::effort_unit_days;
+use crate::commands::get_initiative;
+use crate::db::{Initiative, get_current_timestamp};
+use tauri::State;
+
+// ============================================
+// PERIOD ALIGNMENT COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn clamp_initiative_to_periods(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<Initiative, String> {
+    let initiative = get_initiative(db.clone(), id.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let bounds = sqlx::query!(
+        r#"SELECT MIN(start_date) as "earliest: String", MAX(end_date) as "latest: String"
+        FROM financial_periods"#
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let (earliest, latest) = match (bounds.earliest, bounds.latest) {
+        (Some(earliest), Some(latest)) => (earliest, latest),
+        _ => return Err("No financial periods defined".to_string()),
+    };
+
+    if initiative.start_date.is_none() && initiative.end_date.is_none() {
+        return Err(format!("Initiative {} has no dates to clamp", id));
+    }
+
+    // Dates are ISO-8601 strings, so lexical comparison matches date order
+    let outside = initiative.start_date.as_deref().is_some_and(|s| s > latest.as_str())
+        || initiative.end_date.as_deref().is_some_and(|e| e < earliest.as_str());
+    if outside {
+        return Err(format!(
+            "Initiative {} falls entirely outside the financial periods ({} to {})",
+            id, earliest, latest
+        ));
+    }
+
+    let start_date = initiative.start_date.map(|s| if s < earliest { earliest.clone() } else { s });
+    let end_date = initiative.end_date.map(|e| if e > latest { latest.clone() } else { e });
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        "UPDATE initiatives SET start_date = ?, end_date = ?, updated_at = ? WHERE id = ?",
+        start_date,
+        end_date,
+        now,
+        id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_initiative(db, id).await
+}
+
+// ============================================
+// EFFORT SCHEDULING COMMANDS
+// ============================================
+
+/// Set the end date to the last working day needed to burn the effort estimate at the given
+/// full-time equivalents (default 1), counting from the start date in the chosen calendar.
+/// Effort without a unit is taken as person-days.
+#[tauri::command]
+pub async fn schedule_initiative_from_effort(db: State<'_, tauri_plugin_sql::DbInstances>, id: String, fte: Option<f64>, calendar_id: Option<String>) -> Result<Initiative, String> {
+    let fte = fte.unwrap_or(1.0);
+    if fte <= 0.0 {
+        return Err(format!("FTE must be greater than 0, got {}", fte));
+    }
+
+    let initiative = get_initiative(db.clone(), id.clone()).await?;
+
+    let start = initiative
+        .start_date
+        .as_deref()
+        .and_then(parse_date)
+        .ok_or_else(|| format!("Initiative {} needs a start date to schedule from", id))?;
+    let effort = initiative
+        .effort_estimate
+        .filter(|e| *e > 0.0)
+        .ok_or_else(|| format!("Initiative {} has no effort estimate to schedule from", id))?;
+    let unit_days = match initiative.effort_unit.as_deref() {
+        Some(unit) => effort_unit_days(unit).ok_or_else(|| format!("Unknown effort unit {}", unit))?,
+        None => 1.0,
+    };
+
+    let calendar = load_working_calendar(&db, calendar_id.as_deref()).await?;
+    let working_days = (effort * unit_days / fte).ceil() as i64;
+    let end = calendar
+        .finish_date(start, working_days)
+        .ok_or_else(|| format!("No end date found for {} working days from {}", working_days, format_date(start)))?;
+    let end_date = format_date(end);
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        "UPDATE initiatives SET end_date = ?, updated_at = ? WHERE id = ?",
+        end_date,
+        now,
+        id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_initiative(db, id).await
+}
@@ -0,0 +1,181 @@
+// Tauri commands for the database schema version
+// Reports applied and pending migrations, and upgrades older databases on request
+
+use crate::commands::backup::create_backup;
+use crate::db::migrations::{MIGRATIONS, MIGRATIONS_TABLE, current_version, latest_version, migrator};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqliteConnection};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub description: String,
+    pub installed_on: String,
+    pub success: bool,
+    pub execution_time_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingMigration {
+    pub version: i64,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaInfo {
+    pub current_version: i64,
+    // The version this build of the app expects
+    pub expected_version: i64,
+    pub applied: Vec<AppliedMigration>,
+    pub pending: Vec<PendingMigration>,
+    // Written by a newer build; the app opens it read-only
+    pub newer_than_app: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AppMode {
+    Ready,
+    // Older database; run_pending_migrations brings it up to date
+    UpgradeRequired,
+    // Newer database than this build understands
+    ReadOnly,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppStatus {
+    pub mode: AppMode,
+    pub read_only: bool,
+    pub schema_version: i64,
+    pub expected_version: i64,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationRun {
+    pub from_version: i64,
+    pub to_version: i64,
+    pub applied: Vec<PendingMigration>,
+    // None when there was nothing to apply
+    pub backup_path: Option<String>,
+}
+
+async fn load_schema_info(conn: &mut SqliteConnection) -> Result<SchemaInfo, String> {
+    let current = current_version(&mut *conn).await?;
+
+    let applied = if current == 0 {
+        Vec::new()
+    } else {
+        sqlx::query(&format!(
+            "SELECT version, description, installed_on, success, execution_time FROM {} ORDER BY version",
+            MIGRATIONS_TABLE
+        ))
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|row| AppliedMigration {
+            version: row.get("version"),
+            description: row.get("description"),
+            installed_on: row.get("installed_on"),
+            success: row.get("success"),
+            // Recorded in nanoseconds
+            execution_time_ms: row.get::<i64, _>("execution_time") / 1_000_000,
+        })
+        .collect()
+    };
+
+    let pending = MIGRATIONS
+        .iter()
+        .filter(|m| m.version > current)
+        .map(|m| PendingMigration { version: m.version, description: m.description.to_string() })
+        .collect();
+
+    Ok(SchemaInfo {
+        current_version: current,
+        expected_version: latest_version(),
+        applied,
+        pending,
+        newer_than_app: current > latest_version(),
+    })
+}
+
+pub fn app_status(info: &SchemaInfo) -> AppStatus {
+    let (mode, message) = if info.newer_than_app {
+        (AppMode::ReadOnly, Some(format!(
+            "This database uses schema version {} but this version of the app understands up to {}. It is open read-only; update the app to make changes.",
+            info.current_version, info.expected_version
+        )))
+    } else if !info.pending.is_empty() {
+        (AppMode::UpgradeRequired, Some(format!(
+            "This database was created by an older version of the app ({} update(s) pending). A backup is taken before upgrading.",
+            info.pending.len()
+        )))
+    } else {
+        (AppMode::Ready, None)
+    };
+
+    AppStatus {
+        mode,
+        read_only: mode == AppMode::ReadOnly,
+        schema_version: info.current_version,
+        expected_version: info.expected_version,
+        message,
+    }
+}
+
+// ============================================
+// SCHEMA COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_schema_info(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<SchemaInfo, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    load_schema_info(&mut conn).await
+}
+
+#[tauri::command]
+pub async fn get_app_status(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<AppStatus, String> {
+    let info = get_schema_info(db).await?;
+
+    Ok(app_status(&info))
+}
+
+/// Back up the workspace, then apply every migration newer than the database
+#[tauri::command]
+pub async fn run_pending_migrations(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<MigrationRun, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let info = get_schema_info(db.clone()).await?;
+    if info.newer_than_app {
+        return Err(app_status(&info).message.unwrap_or_default());
+    }
+    if info.pending.is_empty() {
+        return Ok(MigrationRun {
+            from_version: info.current_version,
+            to_version: info.current_version,
+            applied: Vec::new(),
+            backup_path: None,
+        });
+    }
+
+    // VACUUM INTO cannot run inside the migration transactions
+    let backup_path = create_backup(pool, "pre-migration").await?;
+
+    migrator()
+        .await?
+        .run(pool)
+        .await
+        .map_err(|e| format!("Migration failed; the backup at {} is unchanged: {}", backup_path, e))?;
+
+    Ok(MigrationRun {
+        from_version: info.current_version,
+        to_version: latest_version(),
+        applied: info.pending,
+        backup_path: Some(backup_path),
+    })
+}
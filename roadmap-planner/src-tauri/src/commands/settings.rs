@@ -0,0 +1,75 @@
+// Settings helpers for Roadmap Planner commands
+// Reads from the key-value settings table shared with the frontend
+
+use chrono::NaiveDate;
+use chrono::format::{Item, StrftimeItems};
+use sqlx::SqlitePool;
+
+pub async fn read_setting(pool: &SqlitePool, key: &str) -> Result<Option<String>, String> {
+    let value = sqlx::query_scalar!("SELECT value FROM settings WHERE key = ?", key)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(value.flatten())
+}
+
+/// Boolean setting stored as "true"/"false", falling back to the default when unset
+pub async fn read_bool_setting(pool: &SqlitePool, key: &str, default: bool) -> Result<bool, String> {
+    Ok(match read_setting(pool, key).await?.as_deref() {
+        Some("true") | Some("1") => true,
+        Some("false") | Some("0") => false,
+        _ => default,
+    })
+}
+
+// Every aggregation converts into this currency
+pub const REPORTING_CURRENCY_SETTING: &str = "reporting_currency";
+const DEFAULT_REPORTING_CURRENCY: &str = "GBP";
+
+pub async fn read_reporting_currency(pool: &SqlitePool) -> Result<String, String> {
+    Ok(read_setting(pool, REPORTING_CURRENCY_SETTING)
+        .await?
+        .unwrap_or_else(|| DEFAULT_REPORTING_CURRENCY.to_string()))
+}
+
+// strftime pattern for dates in exports; the frontend keeps its own display formats
+pub const DATE_FORMAT_SETTING: &str = "date_format";
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+pub async fn read_date_format(pool: &SqlitePool) -> Result<String, String> {
+    let format = read_setting(pool, DATE_FORMAT_SETTING)
+        .await?
+        .unwrap_or_else(|| DEFAULT_DATE_FORMAT.to_string());
+
+    validate_date_format(&format)?;
+    Ok(format)
+}
+
+/// A date has no time or zone, so chrono panics formatting one with %H, %M, %z and the like.
+/// Formatting a sample date first turns those into an error along with unknown specifiers.
+pub fn validate_date_format(format: &str) -> Result<(), String> {
+    use std::fmt::Write;
+
+    let mut sample = String::new();
+    let invalid = StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+        || write!(sample, "{}", NaiveDate::MIN.format(format)).is_err();
+    if invalid {
+        return Err(format!("Setting {} must be a date format without time or zone fields, got {}", DATE_FORMAT_SETTING, format));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_formats_with_time_fields_are_refused() {
+        assert!(validate_date_format("%d/%m/%Y").is_ok());
+        assert!(validate_date_format("%e %B %Y").is_ok());
+        assert!(validate_date_format("%Y-%m-%d %H:%M").is_err());
+        assert!(validate_date_format("%Y-%m-%dT%z").is_err());
+        assert!(validate_date_format("%Y-%Q").is_err());
+    }
+}
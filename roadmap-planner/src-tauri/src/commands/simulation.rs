@@ -0,0 +1,53 @@
+// Tauri commands for Monte Carlo simulation
+// Schedule risk runs over a scenario, reproducible from the returned seed
+
+use crate::commands::engine::dependencies::{LagWarning, lead_warnings};
+use crate::commands::engine::simulation::{self, DEFAULT_ITERATIONS, KeyDateForecast, MAX_ITERATIONS, seed_from_clock};
+use crate::commands::scenario_data::load_scenario_data;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleSimulation {
+    pub scenario_id: String,
+    pub iterations: u32,
+    // Pass back in to repeat the run
+    pub seed: u64,
+    pub key_dates: Vec<KeyDateForecast>,
+    // Key-date initiatives without start and end dates, which can't be simulated
+    pub undated_key_initiatives: Vec<String>,
+    // Leads the planned dates can't honour, clamped to the predecessor's start
+    pub lag_warnings: Vec<LagWarning>,
+}
+
+// ============================================
+// SIMULATION COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn simulate_schedule(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, iterations: Option<u32>, seed: Option<u64>) -> Result<ScheduleSimulation, String> {
+    let iterations = iterations.unwrap_or(DEFAULT_ITERATIONS);
+    if iterations == 0 || iterations > MAX_ITERATIONS {
+        return Err(format!("Iterations must be between 1 and {}, got {}", MAX_ITERATIONS, iterations));
+    }
+    let seed = seed.unwrap_or_else(seed_from_clock);
+
+    let data = load_scenario_data(db, &scenario_id).await?;
+    let (key_dates, undated_key_initiatives) = simulation::simulate_schedule(
+        &data.initiatives,
+        &data.dependencies,
+        &data.constraints,
+        &data.constraint_links,
+        iterations,
+        seed,
+    );
+
+    Ok(ScheduleSimulation {
+        scenario_id,
+        iterations,
+        seed,
+        key_dates,
+        undated_key_initiatives,
+        lag_warnings: lead_warnings(&data.initiatives, &data.dependencies),
+    })
+}
@@ -0,0 +1,219 @@
+// Tauri commands for stale data
+// Rows nobody has edited or reviewed in a while, ranked by how much an out-of-date entry matters
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::bulk::BulkUpdateResult;
+use crate::commands::engine::dates::{format_date, parse_date, today};
+use crate::commands::entities::EntityType;
+use crate::commands::rows::{ids_json, row_to_json};
+use crate::db::get_current_timestamp;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use tauri::State;
+
+// The age the dashboard counts stale critical systems at
+pub const DEFAULT_STALE_AFTER_DAYS: i64 = 180;
+
+// The later of updated_at and last_reviewed_at, in the form normalise_timestamp gives
+const LAST_TOUCHED: &str = "MAX(REPLACE(SUBSTR(updated_at, 1, 19), 'T', ' '), COALESCE(REPLACE(SUBSTR(last_reviewed_at, 1, 19), 'T', ' '), ''))";
+
+const IDS: &str = "(SELECT value FROM json_each(?1))";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleEntity {
+    pub entity_type: EntityType,
+    pub id: String,
+    pub name: Option<String>,
+    // The later of its last edit and last review
+    pub last_touched: String,
+    pub days_stale: i64,
+    // Higher is reviewed first: a system's criticality weight, or 4 for an initiative in progress
+    pub review_priority: i64,
+    pub row: Value,
+}
+
+/// Relative weight of a system's criticality, from 1 for Low to 4 for Critical
+pub fn criticality_weight(criticality: &str) -> f64 {
+    match criticality {
+        "Critical" => 4.0,
+        "High" => 3.0,
+        "Medium" => 2.0,
+        _ => 1.0,
+    }
+}
+
+/// How much a stale row matters. Critical systems and initiatives in flight come first; work
+/// that is finished or cancelled, and every other entity type, come last.
+pub fn review_priority(entity_type: EntityType, row: &Value) -> i64 {
+    let field = |name: &str| row.get(name).and_then(Value::as_str).unwrap_or_default();
+    match entity_type {
+        EntityType::System => criticality_weight(field("criticality")) as i64,
+        EntityType::Initiative => match field("status") {
+            "InProgress" => 4,
+            "Planned" => 3,
+            "Proposed" => 2,
+            _ => 1,
+        },
+        _ => 1,
+    }
+}
+
+/// Rank rows last touched before the cutoff: highest priority first, then the longest untouched
+pub fn rank_stale(entity_type: EntityType, rows: Vec<Value>, on: NaiveDate) -> Vec<StaleEntity> {
+    let mut stale: Vec<StaleEntity> = rows
+        .into_iter()
+        .filter_map(|row| {
+            let last_touched = row.get("last_touched")?.as_str()?.to_string();
+            let days_stale = (on - parse_date(&last_touched)?).num_days();
+            Some(StaleEntity {
+                entity_type,
+                id: row.get("id")?.as_str()?.to_string(),
+                name: row.get("name").and_then(Value::as_str).map(str::to_string),
+                last_touched,
+                days_stale,
+                review_priority: review_priority(entity_type, &row),
+                row,
+            })
+        })
+        .collect();
+    stale.sort_by(|a, b| {
+        b.review_priority
+            .cmp(&a.review_priority)
+            .then_with(|| a.last_touched.cmp(&b.last_touched))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    stale
+}
+
+fn stale_cutoff(older_than_days: i64) -> Result<String, String> {
+    if older_than_days < 0 {
+        return Err(format!("older_than_days must be zero or more, got {}", older_than_days));
+    }
+    Ok(format_date(today() - Duration::days(older_than_days)))
+}
+
+/// Critical systems not edited or reviewed in `older_than_days`, for the dashboard
+pub async fn count_stale_critical_systems(pool: &sqlx::SqlitePool, older_than_days: i64) -> Result<i64, String> {
+    let cutoff = stale_cutoff(older_than_days)?;
+    sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM systems WHERE criticality = 'Critical' AND lifecycle_stage != 'Retired' AND {} < ?",
+        LAST_TOUCHED
+    ))
+    .bind(&cutoff)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+// ============================================
+// STALE DATA COMMANDS
+// ============================================
+
+/// Rows of `entity_type` neither edited nor reviewed in the last `older_than_days`, in the
+/// order they should be reviewed
+#[tauri::command]
+pub async fn get_stale_entities(db: State<'_, tauri_plugin_sql::DbInstances>, entity_type: EntityType, older_than_days: i64) -> Result<Vec<StaleEntity>, String> {
+    let cutoff = stale_cutoff(older_than_days)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<Value> = sqlx::query(&format!(
+        "SELECT *, {touched} AS last_touched FROM {table} WHERE {touched} < ?",
+        touched = LAST_TOUCHED,
+        table = entity_type.table()
+    ))
+    .bind(&cutoff)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .iter()
+    .map(row_to_json)
+    .collect();
+
+    Ok(rank_stale(entity_type, rows, today()))
+}
+
+/// Stamp the rows as reviewed now, leaving updated_at alone, with a single grouped audit entry
+#[tauri::command]
+pub async fn mark_reviewed(db: State<'_, tauri_plugin_sql::DbInstances>, entity_type: EntityType, ids: Vec<String>) -> Result<BulkUpdateResult, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let table = entity_type.table();
+    let mut seen = HashSet::new();
+    let ids: Vec<String> = ids.into_iter().filter(|id| seen.insert(id.clone())).collect();
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let before: Vec<Value> = sqlx::query(&format!("SELECT id, last_reviewed_at FROM {} WHERE id IN {}", table, IDS))
+        .bind(ids_json(&ids))
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(row_to_json)
+        .collect();
+
+    let found: HashSet<&str> = before.iter().filter_map(|row| row["id"].as_str()).collect();
+    let updated_ids: Vec<String> = ids.iter().filter(|id| found.contains(id.as_str())).cloned().collect();
+    let not_found_ids: Vec<String> = ids.iter().filter(|id| !found.contains(id.as_str())).cloned().collect();
+
+    if updated_ids.is_empty() {
+        return Ok(BulkUpdateResult { updated_ids, not_found_ids, audit_group_id: None });
+    }
+
+    let now = get_current_timestamp();
+    sqlx::query(&format!("UPDATE {} SET last_reviewed_at = ?2 WHERE id IN {}", table, IDS))
+        .bind(ids_json(&updated_ids))
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let after: Vec<Value> = updated_ids
+        .iter()
+        .map(|id| serde_json::json!({ "id": id, "last_reviewed_at": now }))
+        .collect();
+
+    let group_id = uuid::Uuid::new_v4().to_string();
+    record_audit(&mut tx, NewAuditEntry {
+        group_id: Some(group_id.clone()),
+        entity_type: entity_type.name().to_string(),
+        entity_id: None,
+        action: "Review".to_string(),
+        description: Some(format!("Marked {} {} rows as reviewed", updated_ids.len(), entity_type.name())),
+        before: Some(Value::Array(before)),
+        after: Some(Value::Array(after)),
+    })
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(BulkUpdateResult { updated_ids, not_found_ids, audit_group_id: Some(group_id) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn critical_systems_are_reviewed_first() {
+        let rows = vec![
+            json!({ "id": "crm", "name": "CRM", "criticality": "Medium", "last_touched": "2026-01-10 09:00:00" }),
+            json!({ "id": "ledger", "name": "Ledger", "criticality": "Critical", "last_touched": "2026-06-01 12:00:00" }),
+            json!({ "id": "wiki", "name": "Wiki", "criticality": "Low", "last_touched": "2025-11-01 08:00:00" }),
+            json!({ "id": "hr", "name": "HR", "criticality": "Medium", "last_touched": "2025-12-24 17:00:00" }),
+        ];
+        let stale = rank_stale(EntityType::System, rows, parse_date("2026-10-01").unwrap());
+        let order: Vec<(&str, i64, i64)> = stale.iter().map(|s| (s.id.as_str(), s.review_priority, s.days_stale)).collect();
+        assert_eq!(order, [("ledger", 4, 122), ("hr", 2, 281), ("crm", 2, 264), ("wiki", 1, 334)]);
+
+        assert_eq!(review_priority(EntityType::Initiative, &json!({ "status": "InProgress" })), 4);
+        assert_eq!(review_priority(EntityType::Initiative, &json!({ "status": "Complete" })), 1);
+        assert!(stale_cutoff(-1).is_err());
+    }
+}
@@ -0,0 +1,457 @@
+// Tauri commands for scenario summaries
+// Headline totals, progress figures and budget envelope checks per scenario
+
+use crate::commands::calendars::load_working_calendar;
+use crate::commands::engine::budget::{BeyondHorizonBudget, PeriodBudget, PeriodCost, calculate_beyond_horizon, calculate_budget_report, phase_initiative_cost};
+use crate::commands::engine::currency::{CurrencyConverter, CurrencyWarning};
+use crate::commands::engine::dates::{DateSpan, parse_date, today};
+use crate::commands::engine::progress::{InitiativeProgress, initiative_progress};
+use crate::commands::exchange_rates::load_currency_converter;
+use crate::commands::scenario_approvals::load_approval_states;
+use crate::commands::scenario_data::load_scenario_data;
+use crate::commands::stale_data::{DEFAULT_STALE_AFTER_DAYS, count_stale_critical_systems};
+use crate::commands::{get_financial_periods, get_initiative, get_initiatives, get_scenarios, require_scenario};
+use crate::db::{Initiative, Scenario};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::State;
+
+// Percentage points behind plan before an initiative is flagged
+pub const DEFAULT_BEHIND_THRESHOLD: f64 = 20.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioSummary {
+    pub scenario_id: String,
+    pub scenario_name: String,
+    pub is_baseline: bool,
+    pub initiative_count: i64,
+    // Reporting currency of the converted figures
+    pub currency: String,
+    pub total_cost_native: BTreeMap<String, f64>,
+    pub total_cost: f64,
+    pub total_effort: f64,
+    // Cost-weighted planned vs reported progress
+    pub planned_value: f64,
+    pub earned_value: f64,
+    pub schedule_performance_index: Option<f64>,
+    pub behind_schedule: Vec<InitiativeProgress>,
+    // Critical systems not edited or reviewed in DEFAULT_STALE_AFTER_DAYS. Estate-wide, so the
+    // same on every scenario; summarise_scenario leaves it at zero.
+    pub stale_critical_systems: i64,
+    // Sign-off state, as get_scenario_approvals reports it
+    pub approval_state: String,
+    pub warnings: Vec<CurrencyWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DurationStats {
+    pub scenario_id: String,
+    pub dated_count: i64,
+    pub undated_count: i64,
+    // Set when durations count working days in this calendar rather than calendar days
+    pub calendar_id: Option<String>,
+    // Durations in days, counting both the start and end date
+    pub min_days: Option<i64>,
+    pub max_days: Option<i64>,
+    pub mean_days: Option<f64>,
+    pub median_days: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetCut {
+    pub initiative_id: String,
+    pub initiative_name: String,
+    pub cost: f64,
+    // Scenario total once this and every earlier cut is made
+    pub total_after: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetReport {
+    pub scenario_id: String,
+    pub currency: String,
+    pub periods: Vec<PeriodBudget>,
+    pub beyond_horizon: BeyondHorizonBudget,
+    // Period spend plus beyond_horizon, which matches the sum of dated cost estimates
+    pub total_planned_spend: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetEnvelope {
+    pub scenario_id: String,
+    pub currency: String,
+    pub total_cap: f64,
+    pub total_cost: f64,
+    pub exceeds_cap: bool,
+    pub overrun: f64,
+    // Largest costs first, stopping once the total fits under the cap
+    pub suggested_cuts: Vec<BudgetCut>,
+    // False when cutting every candidate still leaves the total over the cap
+    pub achievable: bool,
+    pub warnings: Vec<CurrencyWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostDriver {
+    pub initiative: Initiative,
+    // cost_estimate in the reporting currency; None when uncosted or no rate applies
+    pub converted_cost: Option<f64>,
+    pub currency: String,
+}
+
+fn median(sorted: &[i64]) -> Option<f64> {
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((sorted[mid - 1] + sorted[mid]) as f64 / 2.0),
+        _ => Some(sorted[mid] as f64),
+    }
+}
+
+// ============================================
+// SCENARIO SUMMARY COMMANDS
+// ============================================
+
+/// Headline totals and progress for one scenario, with costs in the converter's reporting currency
+pub fn summarise_scenario(scenario: Scenario, initiatives: &[Initiative], converter: &CurrencyConverter, as_of: NaiveDate, behind_threshold: f64) -> ScenarioSummary {
+    let active: Vec<_> = initiatives.iter().filter(|i| i.status != "Cancelled").collect();
+
+    let mut total_cost_native: BTreeMap<String, f64> = BTreeMap::new();
+    let mut total_cost = 0.0;
+    let mut planned_value = 0.0;
+    let mut earned_value = 0.0;
+    let mut behind_schedule = Vec::new();
+    let mut warnings = Vec::new();
+
+    for initiative in &active {
+        // Whole-initiative costs convert at the rate effective when the initiative starts
+        let on = initiative.start_date.as_deref().and_then(parse_date).unwrap_or(as_of);
+        let currency = converter.currency_of(initiative.currency.as_deref());
+        // An unconvertible cost still counts towards schedule tracking, just not the values
+        let cost = match initiative.cost_estimate {
+            Some(native) => {
+                *total_cost_native.entry(currency.to_string()).or_default() += native;
+                converter
+                    .convert_or_warn(native, currency, on, "Initiative", &initiative.id, &mut warnings)
+                    .unwrap_or(0.0)
+            }
+            None => 0.0,
+        };
+        total_cost += cost;
+
+        let Some(progress) = initiative_progress(initiative, as_of) else {
+            continue;
+        };
+        planned_value += cost * progress.planned_percent / 100.0;
+        earned_value += cost * progress.reported_percent / 100.0;
+
+        if -progress.variance > behind_threshold {
+            behind_schedule.push(progress);
+        }
+    }
+
+    behind_schedule.sort_by(|a, b| a.variance.total_cmp(&b.variance));
+
+    ScenarioSummary {
+        scenario_id: scenario.id,
+        scenario_name: scenario.name,
+        is_baseline: scenario.is_baseline,
+        initiative_count: active.len() as i64,
+        currency: converter.reporting_currency.clone(),
+        total_cost_native,
+        total_cost,
+        total_effort: active.iter().filter_map(|i| i.effort_estimate).sum(),
+        planned_value,
+        earned_value,
+        schedule_performance_index: (planned_value > 0.0).then(|| earned_value / planned_value),
+        behind_schedule,
+        stale_critical_systems: 0,
+        approval_state: "NotSubmitted".to_string(),
+        warnings,
+    }
+}
+
+#[tauri::command]
+pub async fn get_scenario_summaries(db: State<'_, tauri_plugin_sql::DbInstances>, behind_threshold: Option<f64>) -> Result<Vec<ScenarioSummary>, String> {
+    let threshold = behind_threshold.unwrap_or(DEFAULT_BEHIND_THRESHOLD);
+    let as_of = today();
+    let scenarios = get_scenarios(db.clone()).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+    let converter = load_currency_converter(pool).await?;
+    let stale_critical_systems = count_stale_critical_systems(pool, DEFAULT_STALE_AFTER_DAYS).await?;
+    let mut approval_states = load_approval_states(pool).await?;
+
+    let mut summaries = Vec::with_capacity(scenarios.len());
+
+    for scenario in scenarios {
+        let initiatives = get_initiatives(db.clone(), Some(scenario.id.clone())).await?;
+        let mut summary = summarise_scenario(scenario, &initiatives, &converter, as_of, threshold);
+        summary.stale_critical_systems = stale_critical_systems;
+        if let Some(state) = approval_states.remove(&summary.scenario_id) {
+            summary.approval_state = state;
+        }
+        summaries.push(summary);
+    }
+
+    Ok(summaries)
+}
+
+// ============================================
+// DURATION STATISTICS COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_duration_stats(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, calendar_id: Option<String>) -> Result<DurationStats, String> {
+    let calendar = match &calendar_id {
+        Some(id) => Some(load_working_calendar(&db, Some(id)).await?),
+        None => None,
+    };
+    let initiatives = get_initiatives(db, Some(scenario_id.clone())).await?;
+
+    // Initiatives missing either date (or with end before start) count as undated
+    let mut durations: Vec<i64> = initiatives
+        .iter()
+        .filter_map(|i| DateSpan::parse_inclusive(i.start_date.as_deref(), i.end_date.as_deref()))
+        .map(|span| match &calendar {
+            Some(calendar) => calendar.working_days_in(&span),
+            None => span.days(),
+        })
+        .collect();
+    durations.sort_unstable();
+
+    let undated_count = (initiatives.len() - durations.len()) as i64;
+    let mean_days = (!durations.is_empty())
+        .then(|| durations.iter().sum::<i64>() as f64 / durations.len() as f64);
+
+    Ok(DurationStats {
+        scenario_id,
+        dated_count: durations.len() as i64,
+        undated_count,
+        calendar_id,
+        min_days: durations.first().copied(),
+        max_days: durations.last().copied(),
+        mean_days,
+        median_days: median(&durations),
+    })
+}
+
+// ============================================
+// BUDGET REPORT COMMANDS
+// ============================================
+
+/// Planned spend per financial period, with cost dated after the last period in its own bucket
+#[tauri::command]
+pub async fn get_budget_report(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<BudgetReport, String> {
+    let data = load_scenario_data(db, &scenario_id).await?;
+
+    let periods = calculate_budget_report(&data.initiatives, &data.periods, &data.converter);
+    let beyond_horizon = calculate_beyond_horizon(&data.initiatives, &data.periods, &data.converter);
+    let total_planned_spend = periods.iter().map(|p| p.planned_spend).sum::<f64>() + beyond_horizon.planned_spend;
+
+    Ok(BudgetReport {
+        scenario_id,
+        currency: data.converter.reporting_currency.clone(),
+        periods,
+        beyond_horizon,
+        total_planned_spend,
+    })
+}
+
+/// One initiative's cost pro-rated by days across the financial periods it spans, for cash-flow planning
+#[tauri::command]
+pub async fn get_initiative_cost_phasing(db: State<'_, tauri_plugin_sql::DbInstances>, initiative_id: String) -> Result<Vec<PeriodCost>, String> {
+    let initiative = get_initiative(db.clone(), initiative_id).await?;
+    let periods = get_financial_periods(db).await?;
+
+    phase_initiative_cost(&initiative, &periods)
+}
+
+// ============================================
+// BUDGET ENVELOPE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn check_scenario_budget(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, total_cap: f64) -> Result<BudgetEnvelope, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let as_of = today();
+    let initiatives = get_initiatives(db.clone(), Some(scenario_id.clone())).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+    let converter = load_currency_converter(pool).await?;
+
+    let mut warnings = Vec::new();
+    let mut total_cost = 0.0;
+    // Completed work can't be cut, so only other initiatives are candidates
+    let mut candidates = Vec::new();
+
+    for initiative in initiatives.iter().filter(|i| i.status != "Cancelled") {
+        // Same conversion as the scenario summary, with null costs counting as zero
+        let on = initiative.start_date.as_deref().and_then(parse_date).unwrap_or(as_of);
+        let currency = converter.currency_of(initiative.currency.as_deref());
+        let cost = initiative
+            .cost_estimate
+            .and_then(|native| converter.convert_or_warn(native, currency, on, "Initiative", &initiative.id, &mut warnings))
+            .unwrap_or(0.0);
+
+        total_cost += cost;
+        if initiative.status != "Complete" && cost > 0.0 {
+            candidates.push((initiative, cost));
+        }
+    }
+
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.name.cmp(&b.0.name)));
+
+    let mut suggested_cuts = Vec::new();
+    let mut remaining = total_cost;
+    for (initiative, cost) in candidates {
+        if remaining <= total_cap {
+            break;
+        }
+        remaining -= cost;
+        suggested_cuts.push(BudgetCut {
+            initiative_id: initiative.id.clone(),
+            initiative_name: initiative.name.clone(),
+            cost,
+            total_after: remaining,
+        });
+    }
+
+    Ok(BudgetEnvelope {
+        scenario_id,
+        currency: converter.reporting_currency.clone(),
+        total_cap,
+        total_cost,
+        exceeds_cap: total_cost > total_cap,
+        overrun: (total_cost - total_cap).max(0.0),
+        suggested_cuts,
+        achievable: remaining <= total_cap,
+        warnings,
+    })
+}
+
+// ============================================
+// COST DRIVER COMMANDS
+// ============================================
+
+/// Initiatives ranked by cost in the reporting currency, converted at each one's start date (or
+/// `as_of` when undated). Uncosted initiatives and those with no rate come last, by name.
+pub fn rank_by_cost(initiatives: Vec<Initiative>, converter: &CurrencyConverter, as_of: NaiveDate) -> Vec<CostDriver> {
+    let mut ranked: Vec<CostDriver> = initiatives
+        .into_iter()
+        .map(|initiative| {
+            let on = initiative.start_date.as_deref().and_then(parse_date).unwrap_or(as_of);
+            let currency = converter.currency_of(initiative.currency.as_deref());
+            let converted_cost = initiative.cost_estimate.and_then(|native| converter.convert(native, currency, on));
+            CostDriver { initiative, converted_cost, currency: converter.reporting_currency.clone() }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        a.converted_cost
+            .is_none()
+            .cmp(&b.converted_cost.is_none())
+            .then_with(|| b.converted_cost.unwrap_or(0.0).total_cmp(&a.converted_cost.unwrap_or(0.0)))
+            .then_with(|| a.initiative.name.cmp(&b.initiative.name))
+    });
+    ranked
+}
+
+/// The `limit` costliest initiatives in the reporting currency
+#[tauri::command]
+pub async fn get_top_initiatives_by_cost(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, limit: i64) -> Result<Vec<CostDriver>, String> {
+    if limit < 1 {
+        return Err(format!("Limit must be at least 1, got {}", limit));
+    }
+
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let initiatives = get_initiatives(db.clone(), Some(scenario_id)).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+    let converter = load_currency_converter(pool).await?;
+
+    let mut ranked = rank_by_cost(initiatives, &converter, today());
+    ranked.truncate(limit as usize);
+
+    Ok(ranked)
+}
+
+// ============================================
+// STALE WORK COMMANDS
+// ============================================
+
+/// In-progress initiatives that started more than `older_than_days` ago, oldest first
+#[tauri::command]
+pub async fn get_stale_initiatives(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, older_than_days: i64) -> Result<Vec<Initiative>, String> {
+    if older_than_days < 0 {
+        return Err(format!("older_than_days must be zero or more, got {}", older_than_days));
+    }
+
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let cutoff = today() - Duration::days(older_than_days);
+    let mut stale: Vec<(NaiveDate, Initiative)> = get_initiatives(db, Some(scenario_id))
+        .await?
+        .into_iter()
+        .filter(|i| i.status == "InProgress")
+        .filter_map(|i| Some((parse_date(i.start_date.as_deref()?)?, i)))
+        .filter(|(start, _)| *start < cutoff)
+        .collect();
+
+    stale.sort_by(|(a_start, a), (b_start, b)| a_start.cmp(b_start).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(stale.into_iter().map(|(_, i)| i).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::currency::ExchangeRate;
+    use crate::commands::engine::test_initiative;
+
+    fn costed(id: &str, cost: Option<f64>, currency: Option<&str>) -> Initiative {
+        Initiative {
+            start_date: Some("2026-03-01".to_string()),
+            end_date: Some("2026-09-30".to_string()),
+            cost_estimate: cost,
+            currency: currency.map(str::to_string),
+            ..test_initiative(id)
+        }
+    }
+
+    #[test]
+    fn costs_are_ranked_in_the_reporting_currency() {
+        let rate = ExchangeRate {
+            id: "jpy".to_string(),
+            from_currency: "JPY".to_string(),
+            to_currency: "GBP".to_string(),
+            rate: 0.005,
+            effective_date: "2026-01-01".to_string(),
+            created_at: None,
+            updated_at: None,
+        };
+        let converter = CurrencyConverter::new("GBP", &[rate]);
+        let initiatives = vec![
+            costed("yen", Some(900_000.0), Some("JPY")),
+            costed("uncosted", None, None),
+            costed("euro", Some(1_000_000.0), Some("EUR")),
+            costed("sterling", Some(500_000.0), Some("GBP")),
+            costed("default", Some(10_000.0), None),
+        ];
+
+        let ranked = rank_by_cost(initiatives, &converter, parse_date("2026-10-01").unwrap());
+        let order: Vec<(&str, Option<f64>)> = ranked.iter().map(|d| (d.initiative.id.as_str(), d.converted_cost)).collect();
+        // No EUR rate, so the largest native cost ranks with the uncosted
+        assert_eq!(
+            order,
+            [("sterling", Some(500_000.0)), ("default", Some(10_000.0)), ("yen", Some(4_500.0)), ("euro", None), ("uncosted", None)]
+        );
+        assert!(ranked.iter().all(|d| d.currency == "GBP"));
+    }
+}
@@ -0,0 +1,141 @@
+// Tauri commands for resource time off
+// Leave and other unavailable days, subtracted from a resource's availability
+
+use crate::commands::engine::dates::DateSpan;
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceTimeOff {
+    pub id: String,
+    pub resource_id: String,
+    // Both dates are included
+    pub start_date: String,
+    pub end_date: String,
+    pub reason: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl ResourceTimeOff {
+    pub fn span(&self) -> Option<DateSpan> {
+        DateSpan::parse_inclusive(Some(&self.start_date), Some(&self.end_date))
+    }
+}
+
+fn validate_time_off(time_off: &ResourceTimeOff) -> Result<(), String> {
+    if time_off.span().is_none() {
+        return Err(format!(
+            "Time off must have valid dates with the end on or after the start, got {} to {}",
+            time_off.start_date, time_off.end_date
+        ));
+    }
+    Ok(())
+}
+
+// ============================================
+// TIME OFF COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn get_time_off(db: State<'_, tauri_plugin_sql::DbInstances>, resource_id: String) -> Result<Vec<ResourceTimeOff>, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let rows: Vec<ResourceTimeOff> = sqlx::query_as!(
+        ResourceTimeOff,
+        r#"SELECT id, resource_id, start_date, end_date, reason, created_at, updated_at
+        FROM resource_time_off WHERE resource_id = ? ORDER BY start_date"#,
+        resource_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+#[tauri::command]
+pub async fn get_time_off_entry(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<ResourceTimeOff, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query_as!(
+        ResourceTimeOff,
+        r#"SELECT id, resource_id, start_date, end_date, reason, created_at, updated_at
+        FROM resource_time_off WHERE id = ?"#,
+        id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Time off {} not found", id))
+}
+
+#[tauri::command]
+pub async fn create_time_off(db: State<'_, tauri_plugin_sql::DbInstances>, time_off: ResourceTimeOff) -> Result<ResourceTimeOff, String> {
+    validate_time_off(&time_off)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"INSERT INTO resource_time_off (id, resource_id, start_date, end_date, reason, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+        time_off.id,
+        time_off.resource_id,
+        time_off.start_date,
+        time_off.end_date,
+        time_off.reason,
+        now,
+        now
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_time_off_entry(db, time_off.id).await
+}
+
+#[tauri::command]
+pub async fn update_time_off(db: State<'_, tauri_plugin_sql::DbInstances>, time_off: ResourceTimeOff) -> Result<ResourceTimeOff, String> {
+    validate_time_off(&time_off)?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let now = get_current_timestamp();
+
+    sqlx::query!(
+        r#"UPDATE resource_time_off SET
+            resource_id = ?, start_date = ?, end_date = ?, reason = ?, updated_at = ?
+        WHERE id = ?"#,
+        time_off.resource_id,
+        time_off.start_date,
+        time_off.end_date,
+        time_off.reason,
+        now,
+        time_off.id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_time_off_entry(db, time_off.id).await
+}
+
+#[tauri::command]
+pub async fn delete_time_off(db: State<'_, tauri_plugin_sql::DbInstances>, id: String) -> Result<(), String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    sqlx::query!("DELETE FROM resource_time_off WHERE id = ?", id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+// Tauri commands for the roadmap timeline
+// Date bounds for zooming the view to fit, without loading every initiative
+
+use crate::commands::engine::dates::{add_months, format_date, parse_date, period_start, today};
+use crate::commands::require_scenario;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+// Window shown for a scenario with nothing dated: the current month and the year after it
+const DEFAULT_WINDOW_MONTHS: i32 = 12;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineBounds {
+    pub scenario_id: String,
+    pub start_date: String,
+    pub end_date: String,
+    // Initiatives missing a start or end date, which the timeline can't place
+    pub undated_count: i64,
+    // True when nothing was dated and the window is the default around today
+    pub is_default: bool,
+}
+
+/// Fill in whichever end is missing so the window is never empty or inverted
+pub fn resolve_timeline_bounds(start: Option<NaiveDate>, end: Option<NaiveDate>, today: NaiveDate) -> (NaiveDate, NaiveDate, bool) {
+    match (start, end) {
+        (Some(start), Some(end)) if start <= end => (start, end, false),
+        (Some(start), Some(end)) => (end, start, false),
+        (Some(start), None) => (start, add_months(start, DEFAULT_WINDOW_MONTHS), false),
+        (None, Some(end)) => (add_months(end, -DEFAULT_WINDOW_MONTHS), end, false),
+        (None, None) => {
+            let start = period_start(today, "Month");
+            (start, add_months(start, DEFAULT_WINDOW_MONTHS) - Duration::days(1), true)
+        }
+    }
+}
+
+// ============================================
+// TIMELINE COMMANDS
+// ============================================
+
+/// Earliest and latest dates across the scenario's initiatives and milestones, the constraints
+/// linked to them, and the financial periods
+#[tauri::command]
+pub async fn get_timeline_bounds(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String) -> Result<TimelineBounds, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let row = sqlx::query!(
+        r#"SELECT
+            MIN(d.start_date) as "start_date?: String",
+            MAX(d.end_date) as "end_date?: String",
+            (SELECT COUNT(*) FROM initiatives
+                WHERE scenario_id = ?1 AND (start_date IS NULL OR end_date IS NULL)) as "undated_count!: i64"
+        FROM (
+            SELECT start_date, end_date FROM initiatives WHERE scenario_id = ?1
+            UNION ALL
+            SELECT m.target_date, m.target_date FROM milestones m
+            JOIN initiatives i ON i.id = m.initiative_id WHERE i.scenario_id = ?1
+            UNION ALL
+            SELECT c.effective_date, c.expiry_date FROM constraints c
+            WHERE c.id IN (SELECT ic.constraint_id FROM initiative_constraints ic
+                JOIN initiatives i ON i.id = ic.initiative_id WHERE i.scenario_id = ?1)
+            UNION ALL
+            SELECT start_date, end_date FROM financial_periods
+        ) d"#,
+        scenario_id
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let (start, end, is_default) = resolve_timeline_bounds(
+        row.start_date.as_deref().and_then(parse_date),
+        row.end_date.as_deref().and_then(parse_date),
+        today(),
+    );
+
+    Ok(TimelineBounds {
+        scenario_id,
+        start_date: format_date(start),
+        end_date: format_date(end),
+        undated_count: row.undated_count,
+        is_default,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        parse_date(value).unwrap()
+    }
+
+    #[test]
+    fn missing_bounds_fall_back_around_today() {
+        let today = date("2026-10-16");
+
+        assert_eq!(
+            resolve_timeline_bounds(None, None, today),
+            (date("2026-10-01"), date("2027-09-30"), true)
+        );
+        assert_eq!(
+            resolve_timeline_bounds(Some(date("2027-01-01")), None, today),
+            (date("2027-01-01"), date("2028-01-01"), false)
+        );
+        assert_eq!(
+            resolve_timeline_bounds(Some(date("2027-01-01")), Some(date("2027-06-30")), today),
+            (date("2027-01-01"), date("2027-06-30"), false)
+        );
+    }
+}
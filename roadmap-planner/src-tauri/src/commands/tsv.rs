@@ -0,0 +1,363 @@
+// Tauri commands for tab-separated exports
+// Plain text for the clipboard, laid out for pasting into a spreadsheet
+
+use crate::commands::engine::dates::parse_date;
+use crate::commands::entities::EntityType;
+use crate::commands::require_scenario;
+use crate::commands::rows::row_to_json;
+use crate::commands::settings::read_date_format;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// Roadmap layouts that combine several tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoadmapView {
+    // Dated initiatives in start order, with their capabilities and predecessors
+    Timeline,
+    // Named resource allocations with the initiative and pool they belong to
+    Allocations,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExportSource {
+    Entity(EntityType),
+    View(RoadmapView),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum ColumnKind {
+    Text,
+    Date,
+    Number,
+    Flag,
+}
+
+pub(crate) struct ExportColumn {
+    pub(crate) key: &'static str,
+    pub(crate) header: &'static str,
+    expr: &'static str,
+    pub(crate) kind: ColumnKind,
+}
+
+pub(crate) struct ExportSpec {
+    pub(crate) name: &'static str,
+    from: &'static str,
+    // Column matched against the scenario id, for scenario-scoped sources
+    scenario_column: Option<&'static str>,
+    order_by: &'static str,
+    pub(crate) columns: &'static [ExportColumn],
+}
+
+const fn col(key: &'static str, header: &'static str, expr: &'static str, kind: ColumnKind) -> ExportColumn {
+    ExportColumn { key, header, expr, kind }
+}
+
+use ColumnKind::{Date, Flag, Number, Text};
+
+const INITIATIVE_CAPABILITIES: &str = "(SELECT group_concat(c.name, ', ') FROM initiative_capabilities ic JOIN capabilities c ON c.id = ic.capability_id WHERE ic.initiative_id = t.id)";
+
+// The initiative's own colour, else that of its first capability lane
+const INITIATIVE_COLOUR: &str = "COALESCE(t.colour, (SELECT c.colour FROM initiative_capabilities ic JOIN capabilities c ON c.id = ic.capability_id WHERE ic.initiative_id = t.id ORDER BY c.sort_order, c.name LIMIT 1))";
+
+pub(crate) fn export_spec(source: ExportSource) -> ExportSpec {
+    match source {
+        ExportSource::Entity(EntityType::Capability) => ExportSpec {
+            name: "Capability",
+            from: "capabilities t LEFT JOIN capabilities parent ON parent.id = t.parent_id",
+            scenario_column: None,
+            order_by: "parent.name, t.sort_order, t.name",
+            columns: const { &[
+                col("name", "Name", "t.name", Text),
+                col("type", "Type", "t.type", Text),
+                col("parent", "Parent", "parent.name", Text),
+                col("sort_order", "Sort Order", "t.sort_order", Number),
+                col("description", "Description", "t.description", Text),
+            ] },
+        },
+        ExportSource::Entity(EntityType::System) => ExportSpec {
+            name: "System",
+            from: "systems t LEFT JOIN capabilities c ON c.id = t.capability_id",
+            scenario_column: None,
+            order_by: "t.name",
+            columns: const { &[
+                col("name", "Name", "t.name", Text),
+                col("capability", "Capability", "c.name", Text),
+                col("owner", "Owner", "t.owner", Text),
+                col("vendor", "Vendor", "t.vendor", Text),
+                col("technology_stack", "Technology Stack", "t.technology_stack", Text),
+                col("lifecycle_stage", "Lifecycle Stage", "t.lifecycle_stage", Text),
+                col("criticality", "Criticality", "t.criticality", Text),
+                col("support_end_date", "Support End", "t.support_end_date", Date),
+                col("extended_support_end_date", "Extended Support End", "t.extended_support_end_date", Date),
+                col("external_ref", "External Ref", "t.external_ref", Text),
+                col("description", "Description", "t.description", Text),
+            ] },
+        },
+        ExportSource::Entity(EntityType::Initiative) => ExportSpec {
+            name: "Initiative",
+            from: "initiatives t JOIN scenarios s ON s.id = t.scenario_id",
+            scenario_column: Some("t.scenario_id"),
+            order_by: "t.name",
+            columns: const { &[
+                col("reference_code", "Ref", "t.reference_code", Text),
+                col("name", "Name", "t.name", Text),
+                col("scenario", "Scenario", "s.name", Text),
+                col("type", "Type", "t.type", Text),
+                col("status", "Status", "t.status", Text),
+                col("priority", "Priority", "t.priority", Text),
+                col("start_date", "Start", "t.start_date", Date),
+                col("end_date", "End", "t.end_date", Date),
+                col("percent_complete", "% Complete", "t.percent_complete", Number),
+                col("effort_estimate", "Effort", "t.effort_estimate", Number),
+                col("effort_unit", "Effort Unit", "t.effort_unit", Text),
+                col("cost_estimate", "Cost", "t.cost_estimate", Number),
+                col("currency", "Currency", "t.currency", Text),
+                col("capabilities", "Capabilities", INITIATIVE_CAPABILITIES, Text),
+                col("external_ref", "External Ref", "t.external_ref", Text),
+                col("is_key_date", "Key Date", "t.is_key_date", Flag),
+                col("colour", "Colour", "t.colour", Text),
+                col("icon", "Icon", "t.icon", Text),
+                col("description", "Description", "t.description", Text),
+            ] },
+        },
+        ExportSource::Entity(EntityType::Scenario) => ExportSpec {
+            name: "Scenario",
+            from: "scenarios t LEFT JOIN scenarios parent ON parent.id = t.parent_scenario_id",
+            scenario_column: None,
+            order_by: "t.is_baseline DESC, t.name",
+            columns: const { &[
+                col("name", "Name", "t.name", Text),
+                col("type", "Type", "t.type", Text),
+                col("is_baseline", "Baseline", "t.is_baseline", Flag),
+                col("parent", "Parent", "parent.name", Text),
+                col("description", "Description", "t.description", Text),
+            ] },
+        },
+        ExportSource::Entity(EntityType::ResourcePool) => ExportSpec {
+            name: "ResourcePool",
+            from: "resource_pools t",
+            scenario_column: None,
+            order_by: "t.name",
+            columns: const { &[
+                col("name", "Name", "t.name", Text),
+                col("capacity_per_period", "Capacity", "t.capacity_per_period", Number),
+                col("capacity_unit", "Capacity Unit", "t.capacity_unit", Text),
+                col("period_type", "Period", "t.period_type", Text),
+                col("description", "Description", "t.description", Text),
+            ] },
+        },
+        ExportSource::Entity(EntityType::Resource) => ExportSpec {
+            name: "Resource",
+            from: "resources t LEFT JOIN resource_pools p ON p.id = t.resource_pool_id",
+            scenario_column: None,
+            order_by: "t.name",
+            columns: const { &[
+                col("name", "Name", "t.name", Text),
+                col("role", "Role", "t.role", Text),
+                col("pool", "Pool", "p.name", Text),
+                col("availability", "Availability", "t.availability", Number),
+                col("skills", "Skills", "t.skills", Text),
+                col("start_date", "Start", "t.start_date", Date),
+                col("end_date", "End", "t.end_date", Date),
+            ] },
+        },
+        ExportSource::Entity(EntityType::Constraint) => ExportSpec {
+            name: "Constraint",
+            from: "constraints t",
+            scenario_column: None,
+            order_by: "t.name",
+            columns: const { &[
+                col("name", "Name", "t.name", Text),
+                col("type", "Type", "t.type", Text),
+                col("hardness", "Hardness", "t.hardness", Text),
+                col("effective_date", "Effective", "t.effective_date", Date),
+                col("expiry_date", "Expiry", "t.expiry_date", Date),
+                col("description", "Description", "t.description", Text),
+            ] },
+        },
+        ExportSource::Entity(EntityType::FinancialPeriod) => ExportSpec {
+            name: "FinancialPeriod",
+            from: "financial_periods t",
+            scenario_column: None,
+            order_by: "t.start_date",
+            columns: const { &[
+                col("name", "Name", "t.name", Text),
+                col("type", "Type", "t.type", Text),
+                col("start_date", "Start", "t.start_date", Date),
+                col("end_date", "End", "t.end_date", Date),
+                col("budget_available", "Budget", "t.budget_available", Number),
+                col("currency", "Currency", "t.currency", Text),
+                col("closed", "Closed", "t.closed", Flag),
+            ] },
+        },
+        ExportSource::View(RoadmapView::Timeline) => ExportSpec {
+            name: "Timeline",
+            from: "initiatives t",
+            scenario_column: Some("t.scenario_id"),
+            order_by: "t.start_date IS NULL, t.start_date, t.name",
+            columns: const { &[
+                col("reference_code", "Ref", "t.reference_code", Text),
+                col("name", "Initiative", "t.name", Text),
+                col("status", "Status", "t.status", Text),
+                col("priority", "Priority", "t.priority", Text),
+                col("start_date", "Start", "t.start_date", Date),
+                col("end_date", "End", "t.end_date", Date),
+                col("percent_complete", "% Complete", "t.percent_complete", Number),
+                col("is_key_date", "Key Date", "t.is_key_date", Flag),
+                col("colour", "Colour", INITIATIVE_COLOUR, Text),
+                col("icon", "Icon", "t.icon", Text),
+                col("capabilities", "Capabilities", INITIATIVE_CAPABILITIES, Text),
+                col(
+                    "predecessors",
+                    "Depends On",
+                    "(SELECT group_concat(p.name, ', ') FROM initiative_dependencies d JOIN initiatives p ON p.id = d.predecessor_id WHERE d.successor_id = t.id)",
+                    Text,
+                ),
+            ] },
+        },
+        ExportSource::View(RoadmapView::Allocations) => ExportSpec {
+            name: "Allocations",
+            from: "initiative_resources a JOIN initiatives t ON t.id = a.initiative_id JOIN resources r ON r.id = a.resource_id LEFT JOIN resource_pools p ON p.id = r.resource_pool_id",
+            scenario_column: Some("t.scenario_id"),
+            order_by: "r.name, COALESCE(a.start_date, t.start_date), t.name",
+            columns: const { &[
+                col("resource", "Resource", "r.name", Text),
+                col("pool", "Pool", "p.name", Text),
+                col("initiative", "Initiative", "t.name", Text),
+                col("allocation_percent", "Allocation %", "a.allocation_percent", Number),
+                // An allocation without its own window runs for the initiative
+                col("start_date", "Start", "COALESCE(a.start_date, t.start_date)", Date),
+                col("end_date", "End", "COALESCE(a.end_date, t.end_date)", Date),
+            ] },
+        },
+    }
+}
+
+// Picks the requested columns in the requested order, or all of them
+fn select_columns<'a>(spec: &'a ExportSpec, columns: Option<&[String]>) -> Result<Vec<&'a ExportColumn>, String> {
+    let Some(requested) = columns else {
+        return Ok(spec.columns.iter().collect());
+    };
+    if requested.is_empty() {
+        return Err(format!("Select at least one {} column", spec.name));
+    }
+
+    requested
+        .iter()
+        .map(|key| {
+            spec.columns.iter().find(|c| c.key == key).ok_or_else(|| {
+                let allowed: Vec<&str> = spec.columns.iter().map(|c| c.key).collect();
+                format!("Unknown {} column \"{}\"; allowed columns are: {}", spec.name, key, allowed.join(", "))
+            })
+        })
+        .collect()
+}
+
+/// Backslash-escape tabs and line breaks so every record stays on one row
+pub fn escape_cell(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+/// One JSON object per record, keyed by column key, in the spec's order. scenario_id
+/// filters scenario-scoped sources and is ignored for the rest.
+pub(crate) async fn fetch_export_records(pool: &SqlitePool, spec: &ExportSpec, selected: &[&ExportColumn], scenario_id: Option<&str>) -> Result<Vec<Value>, String> {
+    let scenario_filter = spec.scenario_column.zip(scenario_id);
+
+    let select_list: Vec<String> = selected.iter().map(|c| format!("{} AS \"{}\"", c.expr, c.key)).collect();
+    let where_clause = scenario_filter.map(|(column, _)| format!(" WHERE {} = ?", column)).unwrap_or_default();
+    let sql = format!("SELECT {} FROM {}{} ORDER BY {}", select_list.join(", "), spec.from, where_clause, spec.order_by);
+
+    let mut query = sqlx::query(&sql);
+    if let Some((_, scenario_id)) = scenario_filter {
+        query = query.bind(scenario_id);
+    }
+    let rows = query.fetch_all(pool).await.map_err(|e| e.to_string())?;
+
+    Ok(rows.iter().map(row_to_json).collect())
+}
+
+fn format_cell(value: &Value, kind: ColumnKind, date_format: &str) -> String {
+    let text = match (value, kind) {
+        (Value::Null, _) => return String::new(),
+        (Value::Number(n), Flag) => if n.as_i64() == Some(0) { "No" } else { "Yes" }.to_string(),
+        (Value::String(s), Date) => parse_date(s).map(|d| d.format(date_format).to_string()).unwrap_or_else(|| s.clone()),
+        (Value::String(s), _) => s.clone(),
+        (other, _) => other.to_string(),
+    };
+    escape_cell(&text)
+}
+
+// ============================================
+// TSV EXPORT COMMANDS
+// ============================================
+
+/// Header row plus one line per record. scenario_id is required for initiatives and
+/// the roadmap views, and ignored for workspace-wide entities.
+#[tauri::command]
+pub async fn export_tsv_string(db: State<'_, tauri_plugin_sql::DbInstances>, entity: ExportSource, scenario_id: Option<String>, columns: Option<Vec<String>>) -> Result<String, String> {
+    let spec = export_spec(entity);
+    let selected = select_columns(&spec, columns.as_deref())?;
+
+    let scenario_id = match (spec.scenario_column, scenario_id) {
+        (Some(_), Some(scenario_id)) => {
+            require_scenario(db.clone(), &scenario_id).await?;
+            Some(scenario_id)
+        }
+        (Some(_), None) => return Err(format!("{} export needs a scenario", spec.name)),
+        (None, _) => None,
+    };
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let date_format = read_date_format(pool).await?;
+
+    let records = fetch_export_records(pool, &spec, &selected, scenario_id.as_deref()).await?;
+
+    let mut lines = Vec::with_capacity(records.len() + 1);
+    lines.push(selected.iter().map(|c| c.header).collect::<Vec<_>>().join("\t"));
+    for record in &records {
+        let cells: Vec<String> = selected
+            .iter()
+            .map(|c| format_cell(record.get(c.key).unwrap_or(&Value::Null), c.kind, &date_format))
+            .collect();
+        lines.push(cells.join("\t"));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_tabs_and_newlines_are_escaped() {
+        assert_eq!(escape_cell("Phase 1\tPhase 2\r\nSee C:\\docs"), "Phase 1\\tPhase 2\\r\\nSee C:\\\\docs");
+        assert_eq!(format_cell(&Value::from("2025-03-31"), Date, "%d/%m/%Y"), "31/03/2025");
+        assert_eq!(format_cell(&Value::from(1), Flag, "%Y-%m-%d"), "Yes");
+    }
+
+    #[test]
+    fn unknown_columns_list_the_allowed_ones() {
+        let spec = export_spec(ExportSource::Entity(EntityType::Constraint));
+        let picked = select_columns(&spec, Some(&["hardness".to_string(), "name".to_string()])).unwrap();
+        assert_eq!(picked.iter().map(|c| c.key).collect::<Vec<_>>(), vec!["hardness", "name"]);
+
+        let err = select_columns(&spec, Some(&["owner".to_string()])).err().unwrap();
+        assert!(err.contains("allowed columns are: name, type, hardness"));
+    }
+}
@@ -0,0 +1,512 @@
+// Tauri commands for idempotent upserts from external sync scripts
+// A record is found by the chosen key and patched with the fields sent, or created when there is none
+
+use crate::commands::csv_import::{CRITICALITIES, DEFAULT_CRITICALITY, DEFAULT_LIFECYCLE_STAGE, LIFECYCLE_STAGES};
+use crate::commands::engine::dates::{format_date, parse_date};
+use crate::commands::entities::EntityType;
+use crate::commands::fetch::{fetch_initiatives, fetch_systems, single};
+use crate::commands::{SavedInitiative, create_initiative, ensure_exists, get_system, update_initiative};
+use crate::db::{Initiative, System, get_current_timestamp};
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+use tauri::State;
+
+// The key an upsert finds an existing record by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UpsertMatch {
+    Id,
+    // Ignoring case; initiatives are matched within their scenario
+    Name,
+    // Ignoring case; initiatives are matched within their scenario
+    ExternalRef,
+}
+
+impl UpsertMatch {
+    fn column(self) -> &'static str {
+        match self {
+            UpsertMatch::Id => "id",
+            UpsertMatch::Name => "name",
+            UpsertMatch::ExternalRef => "external_ref",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UpsertAction {
+    Created,
+    Updated,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Upserted<T> {
+    pub action: UpsertAction,
+    pub record: T,
+}
+
+// Fields left out are kept as they are on update, and take their defaults on create
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SystemPatch {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub owner: Option<String>,
+    pub vendor: Option<String>,
+    pub technology_stack: Option<Vec<String>>,
+    pub lifecycle_stage: Option<String>,
+    pub criticality: Option<String>,
+    pub support_end_date: Option<String>,
+    pub extended_support_end_date: Option<String>,
+    pub capability_id: Option<String>,
+    pub external_ref: Option<String>,
+}
+
+impl SystemPatch {
+    fn key(&self, match_on: UpsertMatch) -> Option<&str> {
+        match match_on {
+            UpsertMatch::Id => self.id.as_deref(),
+            UpsertMatch::Name => self.name.as_deref(),
+            UpsertMatch::ExternalRef => self.external_ref.as_deref(),
+        }
+    }
+
+    /// Write the fields present onto `system`, checking each as the CSV import does
+    pub fn apply(&self, system: &mut System) -> Result<(), String> {
+        let text = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+
+        if let Some(name) = text(&self.name) {
+            system.name = name;
+        }
+        if self.description.is_some() {
+            system.description = text(&self.description);
+        }
+        if self.owner.is_some() {
+            system.owner = text(&self.owner);
+        }
+        if self.vendor.is_some() {
+            system.vendor = text(&self.vendor);
+        }
+        if let Some(technologies) = &self.technology_stack {
+            let technologies: Vec<&str> = technologies.iter().map(|t| t.trim()).filter(|t| !t.is_empty()).collect();
+            system.technology_stack = (!technologies.is_empty()).then(|| serde_json::to_string(&technologies).unwrap_or_default());
+        }
+        if let Some(stage) = &self.lifecycle_stage {
+            system.lifecycle_stage = one_of(stage, LIFECYCLE_STAGES, "lifecycle_stage")?;
+        }
+        if let Some(criticality) = &self.criticality {
+            system.criticality = one_of(criticality, CRITICALITIES, "criticality")?;
+        }
+        if let Some(value) = &self.support_end_date {
+            system.support_end_date = Some(date(value, "support_end_date")?);
+        }
+        if let Some(value) = &self.extended_support_end_date {
+            system.extended_support_end_date = Some(date(value, "extended_support_end_date")?);
+        }
+        if self.capability_id.is_some() {
+            system.capability_id = text(&self.capability_id);
+        }
+        if self.external_ref.is_some() {
+            system.external_ref = text(&self.external_ref);
+        }
+
+        if system.name.is_empty() {
+            return Err("name is required to create a system".to_string());
+        }
+        if let (Some(support), Some(extended)) = (&system.support_end_date, &system.extended_support_end_date) {
+            if extended < support {
+                return Err("extended_support_end_date is before support_end_date".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+// Fields left out are kept as they are on update, and take their defaults on create
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InitiativePatch {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub initiative_type: Option<String>,
+    pub status: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub effort_estimate: Option<f64>,
+    pub effort_uncertainty: Option<String>,
+    pub cost_estimate: Option<f64>,
+    pub cost_uncertainty: Option<String>,
+    pub priority: Option<String>,
+    pub scenario_id: Option<String>,
+    pub percent_complete: Option<f64>,
+    pub progress_from_milestones: Option<bool>,
+    pub currency: Option<String>,
+    pub effort_unit: Option<String>,
+    pub effort_profile: Option<String>,
+    pub external_ref: Option<String>,
+    pub is_key_date: Option<bool>,
+    pub colour: Option<String>,
+    pub icon: Option<String>,
+}
+
+impl InitiativePatch {
+    fn key(&self, match_on: UpsertMatch) -> Option<&str> {
+        match match_on {
+            UpsertMatch::Id => self.id.as_deref(),
+            UpsertMatch::Name => self.name.as_deref(),
+            UpsertMatch::ExternalRef => self.external_ref.as_deref(),
+        }
+    }
+
+    /// Write the fields present onto `initiative`; create_initiative and update_initiative check them
+    pub fn apply(&self, initiative: &mut Initiative) -> Result<(), String> {
+        patch(&mut initiative.name, &self.name);
+        patch_optional(&mut initiative.description, &self.description);
+        patch(&mut initiative.initiative_type, &self.initiative_type);
+        patch(&mut initiative.status, &self.status);
+        patch_optional(&mut initiative.start_date, &self.start_date);
+        patch_optional(&mut initiative.end_date, &self.end_date);
+        patch_optional(&mut initiative.effort_estimate, &self.effort_estimate);
+        patch_optional(&mut initiative.effort_uncertainty, &self.effort_uncertainty);
+        patch_optional(&mut initiative.cost_estimate, &self.cost_estimate);
+        patch_optional(&mut initiative.cost_uncertainty, &self.cost_uncertainty);
+        patch(&mut initiative.priority, &self.priority);
+        patch(&mut initiative.scenario_id, &self.scenario_id);
+        patch(&mut initiative.percent_complete, &self.percent_complete);
+        patch(&mut initiative.progress_from_milestones, &self.progress_from_milestones);
+        patch_optional(&mut initiative.currency, &self.currency);
+        patch_optional(&mut initiative.effort_unit, &self.effort_unit);
+        patch(&mut initiative.effort_profile, &self.effort_profile);
+        patch_optional(&mut initiative.external_ref, &self.external_ref);
+        patch(&mut initiative.is_key_date, &self.is_key_date);
+        patch_optional(&mut initiative.colour, &self.colour);
+        patch_optional(&mut initiative.icon, &self.icon);
+
+        if initiative.name.trim().is_empty() {
+            return Err("name is required to create an initiative".to_string());
+        }
+        if initiative.scenario_id.is_empty() {
+            return Err("scenario_id is required to create an initiative".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn patch<T: Clone>(field: &mut T, value: &Option<T>) {
+    if let Some(value) = value {
+        *field = value.clone();
+    }
+}
+
+fn patch_optional<T: Clone>(field: &mut Option<T>, value: &Option<T>) {
+    if value.is_some() {
+        *field = value.clone();
+    }
+}
+
+fn one_of(value: &str, allowed: &[&'static str], what: &str) -> Result<String, String> {
+    allowed
+        .iter()
+        .find(|a| a.eq_ignore_ascii_case(value.trim()))
+        .map(|a| a.to_string())
+        .ok_or_else(|| format!("{} must be one of {}, got \"{}\"", what, allowed.join(", "), value))
+}
+
+fn date(value: &str, what: &str) -> Result<String, String> {
+    parse_date(value)
+        .map(format_date)
+        .ok_or_else(|| format!("{} must be a YYYY-MM-DD date, got \"{}\"", what, value))
+}
+
+/// The id of the row whose `match_on` column is `value`, within the scenario when one is given.
+/// More than one match is an error rather than a guess.
+pub async fn find_upsert_match(
+    conn: &mut SqliteConnection,
+    entity_type: EntityType,
+    match_on: UpsertMatch,
+    value: &str,
+    scenario_id: Option<&str>,
+) -> Result<Option<String>, String> {
+    let sql = format!(
+        "SELECT id FROM {} WHERE {} = ?{}{} ORDER BY id LIMIT 2",
+        entity_type.table(),
+        match_on.column(),
+        if match_on == UpsertMatch::Id { "" } else { " COLLATE NOCASE" },
+        if scenario_id.is_some() { " AND scenario_id = ?" } else { "" }
+    );
+    let mut query = sqlx::query_scalar::<_, String>(&sql).bind(value.trim());
+    if let Some(scenario_id) = scenario_id {
+        query = query.bind(scenario_id);
+    }
+    let mut ids = query.fetch_all(&mut *conn).await.map_err(|e| e.to_string())?;
+
+    if ids.len() > 1 {
+        return Err(format!(
+            "More than one {} has {} \"{}\"; match on Id instead",
+            entity_type.name(),
+            match_on.column(),
+            value
+        ));
+    }
+    Ok(ids.pop())
+}
+
+// An id sent alongside another key has to agree with the record that key finds
+fn check_id(sent: Option<&str>, found: &str, entity_type: EntityType) -> Result<(), String> {
+    match sent {
+        Some(id) if id != found => Err(format!("The payload's id {} differs from the matching {} {}", id, entity_type.name(), found)),
+        _ => Ok(()),
+    }
+}
+
+// ============================================
+// UPSERT COMMANDS
+// ============================================
+
+/// Update the system `match_on` finds with the fields sent, or create it when there is none
+#[tauri::command]
+pub async fn upsert_system(db: State<'_, tauri_plugin_sql::DbInstances>, system: SystemPatch, match_on: UpsertMatch) -> Result<Upserted<System>, String> {
+    let key = system
+        .key(match_on)
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| format!("Matching on {} needs {} in the payload", match_on.column(), match_on.column()))?
+        .to_string();
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let (action, mut record) = match find_upsert_match(&mut tx, EntityType::System, match_on, &key, None).await? {
+        Some(id) => {
+            check_id(system.id.as_deref(), &id, EntityType::System)?;
+            (UpsertAction::Updated, single(fetch_systems(&mut tx, std::slice::from_ref(&id)).await?, EntityType::System, &id)?)
+        }
+        None => (
+            UpsertAction::Created,
+            System {
+                id: system.id.clone().unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                name: String::new(),
+                description: None,
+                owner: None,
+                vendor: None,
+                technology_stack: None,
+                lifecycle_stage: DEFAULT_LIFECYCLE_STAGE.to_string(),
+                criticality: DEFAULT_CRITICALITY.to_string(),
+                support_end_date: None,
+                extended_support_end_date: None,
+                capability_id: None,
+                external_ref: None,
+                created_at: None,
+                updated_at: None,
+            },
+        ),
+    };
+    system.apply(&mut record)?;
+    if let Some(capability_id) = &record.capability_id {
+        ensure_exists(&mut tx, EntityType::Capability, capability_id).await?;
+    }
+
+    // technology_stack is written as stored, already a JSON array
+    let now = get_current_timestamp();
+    match action {
+        UpsertAction::Created => {
+            sqlx::query!(
+                r#"INSERT INTO systems (id, name, description, owner, vendor, technology_stack,
+                    lifecycle_stage, criticality, support_end_date, extended_support_end_date,
+                    capability_id, external_ref, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+                record.id,
+                record.name,
+                record.description,
+                record.owner,
+                record.vendor,
+                record.technology_stack,
+                record.lifecycle_stage,
+                record.criticality,
+                record.support_end_date,
+                record.extended_support_end_date,
+                record.capability_id,
+                record.external_ref,
+                now,
+                now
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+        UpsertAction::Updated => {
+            sqlx::query!(
+                r#"UPDATE systems SET
+                    name = ?, description = ?, owner = ?, vendor = ?, technology_stack = ?,
+                    lifecycle_stage = ?, criticality = ?, support_end_date = ?, extended_support_end_date = ?,
+                    capability_id = ?, external_ref = ?, updated_at = ?
+                WHERE id = ?"#,
+                record.name,
+                record.description,
+                record.owner,
+                record.vendor,
+                record.technology_stack,
+                record.lifecycle_stage,
+                record.criticality,
+                record.support_end_date,
+                record.extended_support_end_date,
+                record.capability_id,
+                record.external_ref,
+                now,
+                record.id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(Upserted { action, record: get_system(db, record.id).await? })
+}
+
+/// Update the initiative `match_on` finds with the fields sent, or create it when there is
+/// none. Matching by name or external ref needs scenario_id, as scenario copies share both.
+#[tauri::command]
+pub async fn upsert_initiative(db: State<'_, tauri_plugin_sql::DbInstances>, initiative: InitiativePatch, match_on: UpsertMatch) -> Result<Upserted<SavedInitiative>, String> {
+    let key = initiative
+        .key(match_on)
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| format!("Matching on {} needs {} in the payload", match_on.column(), match_on.column()))?
+        .to_string();
+    let scenario_id = match match_on {
+        UpsertMatch::Id => None,
+        _ => Some(
+            initiative
+                .scenario_id
+                .as_deref()
+                .ok_or_else(|| format!("Matching on {} needs scenario_id in the payload", match_on.column()))?,
+        ),
+    };
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let existing = match find_upsert_match(&mut conn, EntityType::Initiative, match_on, &key, scenario_id).await? {
+        Some(id) => {
+            check_id(initiative.id.as_deref(), &id, EntityType::Initiative)?;
+            Some(single(fetch_initiatives(&mut conn, std::slice::from_ref(&id)).await?, EntityType::Initiative, &id)?)
+        }
+        None => None,
+    };
+    drop(conn);
+
+    // The create and update commands do the validation, lock checks and audit
+    match existing {
+        Some(mut record) => {
+            initiative.apply(&mut record)?;
+            Ok(Upserted { action: UpsertAction::Updated, record: update_initiative(db, record).await? })
+        }
+        None => {
+            let mut record = Initiative {
+                id: initiative.id.clone().unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                name: String::new(),
+                description: None,
+                initiative_type: "New".to_string(),
+                status: "Proposed".to_string(),
+                start_date: None,
+                end_date: None,
+                effort_estimate: None,
+                effort_uncertainty: None,
+                cost_estimate: None,
+                cost_uncertainty: None,
+                priority: "Should".to_string(),
+                scenario_id: String::new(),
+                percent_complete: 0.0,
+                progress_from_milestones: false,
+                currency: None,
+                effort_unit: None,
+                effort_profile: "Flat".to_string(),
+                external_ref: None,
+                reference_code: None,
+                is_key_date: false,
+                colour: None,
+                icon: None,
+                created_at: None,
+                updated_at: None,
+            };
+            initiative.apply(&mut record)?;
+            Ok(Upserted { action: UpsertAction::Created, record: create_initiative(db, record).await? })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::Connection;
+
+    fn ledger() -> System {
+        System {
+            id: "ledger".to_string(),
+            name: "General Ledger".to_string(),
+            description: Some("Finance system of record".to_string()),
+            owner: Some("Finance".to_string()),
+            vendor: None,
+            technology_stack: Some("[\"Oracle\"]".to_string()),
+            lifecycle_stage: "Production".to_string(),
+            criticality: "High".to_string(),
+            support_end_date: Some("2027-03-31".to_string()),
+            extended_support_end_date: None,
+            capability_id: None,
+            external_ref: Some("CI-0042".to_string()),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn a_patch_changes_only_the_fields_it_carries() {
+        let mut system = ledger();
+        let patch = SystemPatch {
+            owner: Some("Group Finance".to_string()),
+            criticality: Some("critical".to_string()),
+            extended_support_end_date: Some("2028-03-31".to_string()),
+            ..Default::default()
+        };
+        patch.apply(&mut system).unwrap();
+
+        assert_eq!(system.owner.as_deref(), Some("Group Finance"));
+        assert_eq!(system.criticality, "Critical");
+        assert_eq!(system.extended_support_end_date.as_deref(), Some("2028-03-31"));
+        assert_eq!(system.description.as_deref(), Some("Finance system of record"));
+        assert_eq!(system.technology_stack.as_deref(), Some("[\"Oracle\"]"));
+
+        let bad = SystemPatch { extended_support_end_date: Some("2026-12-31".to_string()), ..Default::default() };
+        assert!(bad.apply(&mut ledger()).is_err());
+        assert!(SystemPatch::default().apply(&mut System { name: String::new(), ..ledger() }).is_err());
+    }
+
+    #[tokio::test]
+    async fn names_and_refs_match_ignoring_case_but_never_ambiguously() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        crate::db::migrations::migrate_if_empty(&mut conn).await.unwrap();
+        for (id, name, external_ref) in [("a", "Ledger", "CI-1"), ("b", "CRM", "CI-2"), ("c", "crm", "CI-3")] {
+            sqlx::query!(
+                "INSERT INTO systems (id, name, lifecycle_stage, criticality, external_ref) VALUES (?, ?, 'Production', 'Medium', ?)",
+                id,
+                name,
+                external_ref
+            )
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        }
+
+        let system = EntityType::System;
+        assert_eq!(find_upsert_match(&mut conn, system, UpsertMatch::ExternalRef, "ci-1", None).await.unwrap().as_deref(), Some("a"));
+        assert_eq!(find_upsert_match(&mut conn, system, UpsertMatch::Name, " ledger ", None).await.unwrap().as_deref(), Some("a"));
+        assert_eq!(find_upsert_match(&mut conn, system, UpsertMatch::Id, "z", None).await.unwrap(), None);
+        assert!(find_upsert_match(&mut conn, system, UpsertMatch::Name, "CRM", None).await.is_err());
+    }
+}
@@ -0,0 +1,475 @@
+// Tauri commands for workspace validation
+// One lint pass over the whole workspace, with findings keyed by rule id
+
+use crate::commands::engine::budget::{horizon_overrun, planning_horizon_end};
+use crate::commands::engine::constraints::check_all_constraints;
+use crate::commands::engine::dates::parse_date;
+use crate::commands::engine::dependencies::{lead_warnings, validate_lag_days};
+use crate::commands::exchange_rates::validate_currency_code;
+use crate::commands::funding::load_derived_budgets;
+use crate::commands::maintenance::{SiblingOrder, plan_sort_order_repair};
+use crate::commands::objectives::get_objectives;
+use crate::commands::scenario_data::load_scenario_data;
+use crate::commands::settings::read_bool_setting;
+use crate::commands::{get_capabilities, get_constraints, get_financial_periods, get_initiatives, get_resource_pools, get_resources, get_systems};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqliteConnection};
+use std::collections::HashSet;
+use tauri::State;
+
+// Names at least this similar (0 to 1) are reported as likely duplicates
+const DUPLICATE_NAME_THRESHOLD: f64 = 0.9;
+
+// Statuses that commit to doing the work, so the initiative should be estimated
+const COMMITTED_STATUSES: [&str; 2] = ["Planned", "InProgress"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+pub struct ValidationRule {
+    pub id: &'static str,
+    pub severity: Severity,
+    // Command that fixes every finding of this rule, for the UI's fix button
+    pub repair_command: Option<&'static str>,
+}
+
+const fn rule(id: &'static str, severity: Severity, repair_command: Option<&'static str>) -> ValidationRule {
+    ValidationRule { id, severity, repair_command }
+}
+
+pub const RULES: &[ValidationRule] = &[
+    rule("dates.invalid", Severity::Error, None),
+    rule("dates.end_before_start", Severity::Error, None),
+    rule("enums.currency_code", Severity::Warning, None),
+    rule("integrity.database", Severity::Error, None),
+    rule("integrity.foreign_key", Severity::Error, None),
+    rule("duplicates.similar_name", Severity::Warning, None),
+    rule("constraints.hard_violation", Severity::Error, None),
+    rule("constraints.soft_violation", Severity::Warning, None),
+    rule("estimates.missing", Severity::Warning, None),
+    rule("budget.beyond_horizon", Severity::Warning, None),
+    rule("budget.derived_mismatch", Severity::Warning, Some("recalculate_period_budgets")),
+    rule("dependencies.lag_range", Severity::Error, None),
+    rule("dependencies.lead_clamped", Severity::Warning, None),
+    rule("dependencies.cross_scenario", Severity::Error, None),
+    rule("capabilities.sort_order", Severity::Info, Some("repair_sort_orders")),
+];
+
+/// Setting that turns a rule off, e.g. validation.estimates.missing.suppressed = true
+pub fn suppression_setting(rule_id: &str) -> String {
+    format!("validation.{}.suppressed", rule_id)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    pub rule_id: String,
+    pub severity: Severity,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub message: String,
+    pub repair_command: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceValidation {
+    pub errors: Vec<Finding>,
+    pub warnings: Vec<Finding>,
+    pub info: Vec<Finding>,
+    pub suppressed_rules: Vec<String>,
+}
+
+#[derive(Default)]
+struct Findings {
+    found: Vec<Finding>,
+}
+
+impl Findings {
+    fn add(&mut self, rule_id: &str, entity_type: &str, entity_id: Option<&str>, message: String) {
+        let rule = RULES.iter().find(|r| r.id == rule_id).expect("every rule id is listed in RULES");
+        self.found.push(Finding {
+            rule_id: rule.id.to_string(),
+            severity: rule.severity,
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.map(|id| id.to_string()),
+            message,
+            repair_command: rule.repair_command.map(|c| c.to_string()),
+        });
+    }
+
+    // Report unparseable dates, then an end that falls before its start
+    fn check_span(&mut self, entity_type: &str, id: &str, name: &str, start: Option<&str>, end: Option<&str>, what: &str) {
+        let mut parsed = Vec::new();
+        for value in [start, end] {
+            match value {
+                Some(v) => match parse_date(v) {
+                    Some(d) => parsed.push(Some(d)),
+                    None => {
+                        self.add("dates.invalid", entity_type, Some(id), format!("\"{}\" has an invalid date {}", name, v));
+                        parsed.push(None);
+                    }
+                },
+                None => parsed.push(None),
+            }
+        }
+        if let (Some(start), Some(end)) = (parsed[0], parsed[1]) {
+            if end < start {
+                self.add(
+                    "dates.end_before_start",
+                    entity_type,
+                    Some(id),
+                    format!("\"{}\" {} {} is before its start {}", name, what, end, start),
+                );
+            }
+        }
+    }
+
+    fn check_currency(&mut self, entity_type: &str, id: &str, name: &str, currency: Option<&str>) {
+        let Some(code) = currency else {
+            return;
+        };
+        if validate_currency_code(code).is_ok() {
+            return;
+        }
+        let normalised = code.trim().to_ascii_uppercase();
+        let message = if validate_currency_code(&normalised).is_ok() {
+            format!("\"{}\" has currency {:?}, which should be stored as {}", name, code, normalised)
+        } else {
+            format!("\"{}\" has currency {:?}, which is not a three-letter ISO code", name, code)
+        };
+        self.add("enums.currency_code", entity_type, Some(id), message);
+    }
+
+    fn check_duplicates<'a>(&mut self, entity_type: &str, named: impl IntoIterator<Item = (&'a str, &'a str)>) {
+        let named: Vec<(&str, &str)> = named.into_iter().collect();
+        for (i, (id_a, name_a)) in named.iter().enumerate() {
+            for (id_b, name_b) in &named[i + 1..] {
+                let similarity = name_similarity(name_a, name_b);
+                if similarity >= DUPLICATE_NAME_THRESHOLD {
+                    self.add(
+                        "duplicates.similar_name",
+                        entity_type,
+                        Some(id_b),
+                        format!("\"{}\" looks like a duplicate of \"{}\" ({} {})", name_b, name_a, entity_type, id_a),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn normalise_name(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 1 minus the edit distance over the longer length, after ignoring case and punctuation
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = normalise_name(a).chars().collect();
+    let b: Vec<char> = normalise_name(b).chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+/// A row's primary key for a finding, joined with '/' when the key has several columns. Link
+/// tables such as scenario_stats have no id column, and a table without a declared key, or a
+/// lookup that fails, falls back to the rowid so a broken row never stops the pass.
+pub async fn row_key(conn: &mut SqliteConnection, table: &str, rowid: i64) -> String {
+    let key_columns: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?) WHERE pk > 0 ORDER BY pk")
+        .bind(table)
+        .fetch_all(&mut *conn)
+        .await
+        .unwrap_or_default();
+
+    if !key_columns.is_empty() {
+        let key = key_columns
+            .iter()
+            .map(|c| format!("CAST(\"{}\" AS TEXT)", c.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" || '/' || ");
+        let found: Result<Option<String>, _> = sqlx::query_scalar(&format!("SELECT {} FROM \"{}\" WHERE rowid = ?", key, table.replace('"', "\"\"")))
+            .bind(rowid)
+            .fetch_optional(&mut *conn)
+            .await;
+        if let Ok(Some(key)) = found {
+            return key;
+        }
+    }
+    format!("rowid {}", rowid)
+}
+
+// ============================================
+// VALIDATION COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn validate_workspace(db: State<'_, tauri_plugin_sql::DbInstances>) -> Result<WorkspaceValidation, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut suppressed_rules = Vec::new();
+    for rule in RULES {
+        if read_bool_setting(pool, &suppression_setting(rule.id), false).await? {
+            suppressed_rules.push(rule.id.to_string());
+        }
+    }
+
+    let capabilities = get_capabilities(db.clone()).await?;
+    let systems = get_systems(db.clone()).await?;
+    let initiatives = get_initiatives(db.clone(), None).await?;
+    let pools = get_resource_pools(db.clone()).await?;
+    let resources = get_resources(db.clone(), None).await?;
+    let constraints = get_constraints(db.clone()).await?;
+    let periods = get_financial_periods(db.clone()).await?;
+    let objectives = get_objectives(db.clone()).await?;
+
+    let mut findings = Findings::default();
+
+    // Dates and enum-like values
+    for i in &initiatives {
+        findings.check_span("Initiative", &i.id, &i.name, i.start_date.as_deref(), i.end_date.as_deref(), "end date");
+        findings.check_currency("Initiative", &i.id, &i.name, i.currency.as_deref());
+    }
+    for r in &resources {
+        findings.check_span("Resource", &r.id, &r.name, r.start_date.as_deref(), r.end_date.as_deref(), "end date");
+    }
+    for c in &constraints {
+        findings.check_span("Constraint", &c.id, &c.name, c.effective_date.as_deref(), c.expiry_date.as_deref(), "expiry date");
+    }
+    for p in &periods {
+        findings.check_span("FinancialPeriod", &p.id, &p.name, Some(&p.start_date), Some(&p.end_date), "end date");
+        findings.check_currency("FinancialPeriod", &p.id, &p.name, p.currency.as_deref());
+    }
+    for s in &systems {
+        findings.check_span("System", &s.id, &s.name, s.support_end_date.as_deref(), s.extended_support_end_date.as_deref(), "extended support end");
+    }
+
+    // Database integrity
+    let quick_check: Vec<String> = sqlx::query("PRAGMA quick_check")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|r| r.get::<String, _>(0))
+        .collect();
+    for problem in quick_check.into_iter().filter(|m| m != "ok") {
+        findings.add("integrity.database", "Database", None, problem);
+    }
+
+    let broken = sqlx::query("PRAGMA foreign_key_check")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    for row in broken {
+        let table: String = row.get("table");
+        let parent: String = row.get("parent");
+        let rowid: Option<i64> = row.get("rowid");
+        let id = match rowid {
+            Some(rowid) => Some(row_key(&mut conn, &table, rowid).await),
+            None => None,
+        };
+        findings.add(
+            "integrity.foreign_key",
+            &table,
+            id.as_deref(),
+            format!("A row in {} references a missing row in {}", table, parent),
+        );
+    }
+
+    // Rows written before the lag range was enforced
+    let lags = sqlx::query!("SELECT id, lag_days FROM initiative_dependencies WHERE lag_days IS NOT NULL")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for dep in lags {
+        if let Err(message) = validate_lag_days(dep.lag_days.unwrap_or(0)) {
+            findings.add("dependencies.lag_range", "InitiativeDependency", Some(&dep.id), message);
+        }
+    }
+
+    // Links between scenarios written before they were refused
+    let crossing = sqlx::query!(
+        r#"SELECT d.id, p.name as predecessor_name, p.scenario_id as predecessor_scenario_id,
+            s.name as successor_name, s.scenario_id as successor_scenario_id
+        FROM initiative_dependencies d
+        JOIN initiatives p ON p.id = d.predecessor_id
+        JOIN initiatives s ON s.id = d.successor_id
+        WHERE p.scenario_id <> s.scenario_id"#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    for dep in crossing {
+        findings.add(
+            "dependencies.cross_scenario",
+            "InitiativeDependency",
+            Some(&dep.id),
+            format!(
+                "\"{}\" in {} depends on \"{}\" in {}; dependencies must stay within one scenario",
+                dep.successor_name, dep.successor_scenario_id, dep.predecessor_name, dep.predecessor_scenario_id
+            ),
+        );
+    }
+
+    // Likely duplicates; initiatives only clash within their own scenario
+    findings.check_duplicates("Capability", capabilities.iter().map(|c| (c.id.as_str(), c.name.as_str())));
+    findings.check_duplicates("System", systems.iter().map(|s| (s.id.as_str(), s.name.as_str())));
+    findings.check_duplicates("ResourcePool", pools.iter().map(|p| (p.id.as_str(), p.name.as_str())));
+    findings.check_duplicates("Resource", resources.iter().map(|r| (r.id.as_str(), r.name.as_str())));
+    findings.check_duplicates("Constraint", constraints.iter().map(|c| (c.id.as_str(), c.name.as_str())));
+    findings.check_duplicates("Objective", objectives.iter().map(|o| (o.id.as_str(), o.name.as_str())));
+    let scenario_ids: HashSet<&str> = initiatives.iter().map(|i| i.scenario_id.as_str()).collect();
+    for scenario_id in scenario_ids {
+        findings.check_duplicates(
+            "Initiative",
+            initiatives.iter().filter(|i| i.scenario_id == scenario_id).map(|i| (i.id.as_str(), i.name.as_str())),
+        );
+    }
+
+    // Constraints and dependency leads as they stand on the baseline
+    let baseline_id = sqlx::query_scalar!("SELECT id FROM scenarios WHERE is_baseline = 1 LIMIT 1")
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(baseline_id) = baseline_id {
+        let data = load_scenario_data(db.clone(), &baseline_id).await?;
+        for v in check_all_constraints(&data.initiatives, &data.constraints, &data.constraint_links) {
+            let rule_id = if v.hardness == "Hard" { "constraints.hard_violation" } else { "constraints.soft_violation" };
+            findings.add(rule_id, "Initiative", Some(&v.initiative_id), v.message);
+        }
+        for w in lead_warnings(&data.initiatives, &data.dependencies) {
+            findings.add("dependencies.lead_clamped", "InitiativeDependency", Some(&w.dependency_id), w.message);
+        }
+    }
+
+    for i in initiatives.iter().filter(|i| COMMITTED_STATUSES.contains(&i.status.as_str())) {
+        let missing: Vec<&str> = [("effort", i.effort_estimate), ("cost", i.cost_estimate)]
+            .into_iter()
+            .filter(|(_, v)| v.is_none())
+            .map(|(what, _)| what)
+            .collect();
+        if !missing.is_empty() {
+            findings.add(
+                "estimates.missing",
+                "Initiative",
+                Some(&i.id),
+                format!("\"{}\" is {} but has no {} estimate", i.name, i.status, missing.join(" or ")),
+            );
+        }
+    }
+
+    // Cost no financial period picks up
+    let horizon_end = planning_horizon_end(&periods);
+    for overrun in initiatives.iter().filter_map(|i| horizon_overrun(i, horizon_end)) {
+        findings.add("budget.beyond_horizon", "Initiative", Some(&overrun.initiative_id), overrun.message);
+    }
+
+    // Stored budgets that have drifted from the funding behind them
+    for budget in load_derived_budgets(pool).await? {
+        if budget.source_count == 0 || !budget.exceeds_tolerance {
+            continue;
+        }
+        let stored = budget.stored_budget.map(|b| format!("{:.2}", b)).unwrap_or_else(|| "none".to_string());
+        findings.add(
+            "budget.derived_mismatch",
+            "FinancialPeriod",
+            Some(&budget.period_id),
+            format!(
+                "\"{}\" has a budget of {} {} but its funding sources add up to {:.2}",
+                budget.period_name, stored, budget.currency, budget.derived_budget
+            ),
+        );
+    }
+
+    let siblings: Vec<SiblingOrder> = capabilities
+        .iter()
+        .map(|c| SiblingOrder {
+            id: c.id.clone(),
+            parent_id: c.parent_id.clone(),
+            name: c.name.clone(),
+            sort_order: c.sort_order,
+        })
+        .collect();
+    let (_, repairs) = plan_sort_order_repair(&siblings);
+    for (id, _) in repairs.iter().flatten() {
+        findings.add("capabilities.sort_order", "Capability", Some(id), "Sort order collides with a sibling or leaves a gap".to_string());
+    }
+
+    let mut report = WorkspaceValidation {
+        errors: Vec::new(),
+        warnings: Vec::new(),
+        info: Vec::new(),
+        suppressed_rules,
+    };
+    for finding in findings.found.into_iter().filter(|f| !report.suppressed_rules.contains(&f.rule_id)) {
+        match finding.severity {
+            Severity::Error => report.errors.push(finding),
+            Severity::Warning => report.warnings.push(finding),
+            Severity::Info => report.info.push(finding),
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_differing_only_in_case_and_punctuation_match() {
+        assert_eq!(name_similarity("Customer Portal", "customer-portal"), 1.0);
+        assert!(name_similarity("Customer Portal", "Customer Portals") >= DUPLICATE_NAME_THRESHOLD);
+        assert!(name_similarity("Customer Portal", "Partner Portal") < DUPLICATE_NAME_THRESHOLD);
+    }
+
+    #[tokio::test]
+    async fn broken_rows_are_keyed_without_an_id_column() {
+        use sqlx::Connection;
+
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::query(
+            "CREATE TABLE stats (scenario_id TEXT PRIMARY KEY, total REAL);
+            CREATE TABLE lines (snapshot_id TEXT, period_id TEXT, PRIMARY KEY (snapshot_id, period_id));
+            CREATE TABLE notes (body TEXT);
+            INSERT INTO stats VALUES ('gone', 1.0);
+            INSERT INTO lines VALUES ('snap', 'q1');
+            INSERT INTO notes VALUES ('orphan');",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+        assert_eq!(row_key(&mut conn, "stats", 1).await, "gone");
+        assert_eq!(row_key(&mut conn, "lines", 1).await, "snap/q1");
+        assert_eq!(row_key(&mut conn, "notes", 1).await, "rowid 1");
+        assert_eq!(row_key(&mut conn, "missing_table", 7).await, "rowid 7");
+    }
+
+    #[test]
+    fn every_rule_id_is_unique() {
+        let ids: HashSet<&str> = RULES.iter().map(|r| r.id).collect();
+        assert_eq!(ids.len(), RULES.len());
+    }
+}
@@ -0,0 +1,323 @@
+// Tauri commands for comparing two workspace database files
+// Row-by-row diff keyed by primary key, shaped so a merge can consume it
+
+use crate::commands::entities::EntityType;
+use crate::commands::rows::row_to_json;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row, SqliteConnection};
+use std::collections::{BTreeMap, BTreeSet};
+use tauri::State;
+
+// Changes returned per table unless the caller asks for more
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+pub type TableRows = BTreeMap<String, Map<String, Value>>;
+
+// Relative to the current workspace: Added rows exist only in the other file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NewerSide {
+    Ours,
+    Theirs,
+    Same,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub ours: Value,
+    pub theirs: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowDiff {
+    // Stable "table:key" id, used to accept individual changes in a merge
+    pub change_id: String,
+    pub table: String,
+    pub key: String,
+    pub kind: DiffKind,
+    pub newer: NewerSide,
+    pub ours_updated_at: Option<String>,
+    pub theirs_updated_at: Option<String>,
+    pub fields: Vec<FieldChange>,
+    // The whole row for Added (theirs) and Removed (ours)
+    pub row: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableDiff {
+    pub table: String,
+    pub entity_type: Option<EntityType>,
+    pub key_column: String,
+    pub added_count: i64,
+    pub removed_count: i64,
+    pub changed_count: i64,
+    pub unchanged_count: i64,
+    // Columns present on only one side (schema version skew) are not compared
+    pub ignored_columns: Vec<String>,
+    pub offset: usize,
+    pub changes: Vec<RowDiff>,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceDiff {
+    pub other_path: String,
+    pub tables: Vec<TableDiff>,
+    // Tables without an id or single-column primary key
+    pub skipped_tables: Vec<String>,
+    pub total_added: i64,
+    pub total_removed: i64,
+    pub total_changed: i64,
+}
+
+/// Open another workspace file without any chance of writing to it
+pub async fn open_workspace_read_only(path: &str) -> Result<SqliteConnection, String> {
+    if !std::path::Path::new(path).is_file() {
+        return Err(format!("Workspace file {} does not exist", path));
+    }
+
+    SqliteConnectOptions::new()
+        .filename(path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| format!("Could not open {}: {}", path, e))
+}
+
+/// User tables, excluding SQLite internals and the migration ledger
+pub async fn list_tables(conn: &mut SqliteConnection) -> Result<Vec<String>, String> {
+    sqlx::query_scalar::<_, String>(
+        r#"SELECT name FROM sqlite_master
+        WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name NOT LIKE '_sqlx_%'
+        ORDER BY name"#,
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Column names in declaration order, plus the column rows are keyed by
+pub async fn table_columns(conn: &mut SqliteConnection, table: &str) -> Result<(Vec<String>, Option<String>), String> {
+    let info = sqlx::query(&format!("PRAGMA table_info(\"{}\")", table))
+        .fetch_all(conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let columns: Vec<String> = info.iter().map(|r| r.get::<String, _>("name")).collect();
+    let primary: Vec<String> = info
+        .iter()
+        .filter(|r| r.get::<i64, _>("pk") > 0)
+        .map(|r| r.get::<String, _>("name"))
+        .collect();
+
+    let key = if columns.iter().any(|c| c == "id") {
+        Some("id".to_string())
+    } else if primary.len() == 1 {
+        primary.into_iter().next()
+    } else {
+        None
+    };
+
+    Ok((columns, key))
+}
+
+fn key_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+pub async fn load_table_rows(conn: &mut SqliteConnection, table: &str, key: &str) -> Result<TableRows, String> {
+    let rows = sqlx::query(&format!("SELECT * FROM \"{}\"", table))
+        .fetch_all(conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .iter()
+        .filter_map(|row| match row_to_json(row) {
+            Value::Object(object) => Some((key_string(object.get(key)?), object)),
+            _ => None,
+        })
+        .collect())
+}
+
+fn updated_at(row: &Map<String, Value>) -> Option<String> {
+    row.get("updated_at")
+        .or_else(|| row.get("created_at"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+// Timestamps are written both as RFC 3339 and as SQLite datetime('now')
+pub fn normalise_timestamp(value: &str) -> String {
+    value.get(..19).unwrap_or(value).replace('T', " ")
+}
+
+fn newer_side(ours: Option<&str>, theirs: Option<&str>) -> NewerSide {
+    match (ours.map(normalise_timestamp), theirs.map(normalise_timestamp)) {
+        (Some(o), Some(t)) if o > t => NewerSide::Ours,
+        (Some(o), Some(t)) if o < t => NewerSide::Theirs,
+        (Some(_), Some(_)) => NewerSide::Same,
+        _ => NewerSide::Unknown,
+    }
+}
+
+/// Every difference between two versions of a table, ordered by key
+pub fn diff_table_rows(table: &str, compared: &[String], ours: &TableRows, theirs: &TableRows) -> (Vec<RowDiff>, i64) {
+    let keys: BTreeSet<&String> = ours.keys().chain(theirs.keys()).collect();
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+
+    for key in keys {
+        let ours_row = ours.get(key);
+        let theirs_row = theirs.get(key);
+        let ours_updated_at = ours_row.and_then(updated_at);
+        let theirs_updated_at = theirs_row.and_then(updated_at);
+
+        let (kind, fields, row) = match (ours_row, theirs_row) {
+            (None, Some(t)) => (DiffKind::Added, Vec::new(), Some(Value::Object(t.clone()))),
+            (Some(o), None) => (DiffKind::Removed, Vec::new(), Some(Value::Object(o.clone()))),
+            (Some(o), Some(t)) => {
+                let fields: Vec<FieldChange> = compared
+                    .iter()
+                    .filter_map(|field| {
+                        let ours_value = o.get(field).cloned().unwrap_or(Value::Null);
+                        let theirs_value = t.get(field).cloned().unwrap_or(Value::Null);
+                        (ours_value != theirs_value).then(|| FieldChange {
+                            field: field.clone(),
+                            ours: ours_value,
+                            theirs: theirs_value,
+                        })
+                    })
+                    .collect();
+                if fields.is_empty() {
+                    unchanged += 1;
+                    continue;
+                }
+                (DiffKind::Changed, fields, None)
+            }
+            (None, None) => continue,
+        };
+
+        changes.push(RowDiff {
+            change_id: format!("{}:{}", table, key),
+            table: table.to_string(),
+            key: key.clone(),
+            kind,
+            newer: newer_side(ours_updated_at.as_deref(), theirs_updated_at.as_deref()),
+            ours_updated_at,
+            theirs_updated_at,
+            fields,
+            row,
+        });
+    }
+
+    (changes, unchanged)
+}
+
+pub struct TableComparison {
+    pub key_column: String,
+    pub changes: Vec<RowDiff>,
+    pub unchanged_count: i64,
+    pub ignored_columns: Vec<String>,
+}
+
+/// Full diff of one table between the two workspaces, with the shared columns compared
+pub async fn diff_table(ours: &mut SqliteConnection, theirs: &mut SqliteConnection, table: &str, ours_exists: bool, theirs_exists: bool) -> Result<Option<TableComparison>, String> {
+    let (ours_columns, ours_key) = if ours_exists { table_columns(ours, table).await? } else { (Vec::new(), None) };
+    let (theirs_columns, theirs_key) = if theirs_exists { table_columns(theirs, table).await? } else { (Vec::new(), None) };
+
+    let Some(key) = ours_key.or(theirs_key) else {
+        return Ok(None);
+    };
+
+    let ours_rows = if ours_exists { load_table_rows(ours, table, &key).await? } else { TableRows::new() };
+    let theirs_rows = if theirs_exists { load_table_rows(theirs, table, &key).await? } else { TableRows::new() };
+
+    let compared: Vec<String> = ours_columns.iter().filter(|c| theirs_columns.contains(c)).cloned().collect();
+    let ignored: Vec<String> = ours_columns
+        .iter()
+        .chain(theirs_columns.iter())
+        .filter(|c| !compared.contains(c))
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let (changes, unchanged_count) = diff_table_rows(table, &compared, &ours_rows, &theirs_rows);
+    Ok(Some(TableComparison { key_column: key, changes, unchanged_count, ignored_columns: ignored }))
+}
+
+// ============================================
+// WORKSPACE DIFF COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn diff_workspaces(db: State<'_, tauri_plugin_sql::DbInstances>, other_path: String, table: Option<String>, offset: Option<usize>, limit: Option<usize>) -> Result<WorkspaceDiff, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+
+    let mut ours = pool.acquire().await.map_err(|e| e.to_string())?;
+    let mut theirs = open_workspace_read_only(&other_path).await?;
+
+    let ours_tables = list_tables(&mut ours).await?;
+    let theirs_tables = list_tables(&mut theirs).await?;
+    let all_tables: BTreeSet<&String> = ours_tables.iter().chain(theirs_tables.iter()).collect();
+
+    let mut tables = Vec::new();
+    let mut skipped_tables = Vec::new();
+
+    for name in all_tables {
+        if table.as_ref().is_some_and(|t| t != name) {
+            continue;
+        }
+
+        let diff = diff_table(&mut ours, &mut theirs, name, ours_tables.contains(name), theirs_tables.contains(name)).await?;
+        let Some(TableComparison { key_column, changes, unchanged_count, ignored_columns }) = diff else {
+            skipped_tables.push(name.clone());
+            continue;
+        };
+
+        let count = |kind: DiffKind| changes.iter().filter(|c| c.kind == kind).count() as i64;
+        let (added_count, removed_count, changed_count) = (count(DiffKind::Added), count(DiffKind::Removed), count(DiffKind::Changed));
+        let truncated = changes.len() > offset + limit;
+
+        tables.push(TableDiff {
+            table: name.clone(),
+            entity_type: EntityType::ALL.into_iter().find(|e| e.table() == name),
+            key_column,
+            added_count,
+            removed_count,
+            changed_count,
+            unchanged_count,
+            ignored_columns,
+            offset,
+            changes: changes.into_iter().skip(offset).take(limit).collect(),
+            truncated,
+        });
+    }
+
+    Ok(WorkspaceDiff {
+        other_path,
+        total_added: tables.iter().map(|t| t.added_count).sum(),
+        total_removed: tables.iter().map(|t| t.removed_count).sum(),
+        total_changed: tables.iter().map(|t| t.changed_count).sum(),
+        tables,
+        skipped_tables,
+    })
+}
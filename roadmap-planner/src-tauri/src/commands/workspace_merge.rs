@@ -0,0 +1,424 @@
+// Tauri commands for merging another workspace into the current one
+// Applies changes from a workspace diff under a conflict strategy
+
+use crate::commands::audit::{NewAuditEntry, record_audit};
+use crate::commands::backup::create_backup;
+use crate::commands::entities::EntityType;
+use crate::commands::journal::JournaledOperation;
+use crate::commands::reference_codes::{claim_reference_code, next_reference_code};
+use crate::commands::rows::bind_json;
+use crate::commands::workspace_diff::{
+    DiffKind, NewerSide, RowDiff, TableComparison, diff_table, list_tables, normalise_timestamp,
+    open_workspace_read_only, table_columns,
+};
+use crate::db::get_current_timestamp;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Row, SqliteConnection};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tauri::State;
+
+// Workspace-local history and counters are never merged
+const EXCLUDED_TABLES: [&str; 3] = ["audit_log", "operation_journal", "reference_sequences"];
+
+// When the workspaces last converged; rows changed on both sides since then conflict
+const LAST_MERGED_SETTING: &str = "workspace_merge.last_merged_at";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MergeStrategy {
+    // Take whichever side has the later updated_at
+    NewestWins,
+    // Take every added or changed row from the other workspace
+    TheirsWins,
+    // Apply only these change ids from a prior diff
+    Interactive(Vec<String>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedChange {
+    pub change_id: String,
+    pub kind: DiffKind,
+    // Brought in only because an accepted row references it
+    pub pulled_in: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedChange {
+    pub change_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeResult {
+    pub backup_path: String,
+    pub audit_group_id: Option<String>,
+    pub applied: Vec<AppliedChange>,
+    // Rows changed on both sides that the strategy could not settle
+    pub conflicts: Vec<RowDiff>,
+    pub rejected: Vec<RejectedChange>,
+    // Changes the strategy leaves alone, such as rows that exist only here
+    pub skipped_count: i64,
+}
+
+struct ForeignKey {
+    column: String,
+    table: String,
+    target: String,
+}
+
+struct TableInfo {
+    key_column: String,
+    columns: Vec<String>,
+    foreign_keys: Vec<ForeignKey>,
+}
+
+fn is_conflict(diff: &RowDiff, base: Option<&str>) -> bool {
+    if diff.kind != DiffKind::Changed {
+        return false;
+    }
+    let Some(base) = base.map(normalise_timestamp) else {
+        // Without a common point either side may have changed
+        return true;
+    };
+    let changed_since = |ts: &Option<String>| ts.as_deref().is_some_and(|t| normalise_timestamp(t) > base);
+    changed_since(&diff.ours_updated_at) && changed_since(&diff.theirs_updated_at)
+}
+
+enum Decision {
+    Apply,
+    Conflict,
+    Skip,
+}
+
+fn decide(diff: &RowDiff, strategy: &MergeStrategy, accepted: &HashSet<&str>, base: Option<&str>) -> Decision {
+    let conflict = is_conflict(diff, base);
+    match strategy {
+        MergeStrategy::Interactive(_) if accepted.contains(diff.change_id.as_str()) => Decision::Apply,
+        MergeStrategy::Interactive(_) if conflict => Decision::Conflict,
+        MergeStrategy::Interactive(_) => Decision::Skip,
+        // Rows missing from theirs are only deleted when explicitly accepted
+        _ if diff.kind == DiffKind::Removed => Decision::Skip,
+        MergeStrategy::TheirsWins => Decision::Apply,
+        MergeStrategy::NewestWins => match diff.newer {
+            NewerSide::Theirs => Decision::Apply,
+            NewerSide::Ours => Decision::Skip,
+            NewerSide::Same | NewerSide::Unknown if diff.kind == DiffKind::Added => Decision::Apply,
+            NewerSide::Same | NewerSide::Unknown if conflict => Decision::Conflict,
+            NewerSide::Same | NewerSide::Unknown => Decision::Skip,
+        },
+    }
+}
+
+async fn table_info(conn: &mut SqliteConnection, table: &str, key_column: String) -> Result<TableInfo, String> {
+    let (columns, _) = table_columns(conn, table).await?;
+    let foreign_keys = sqlx::query(&format!("PRAGMA foreign_key_list(\"{}\")", table))
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|r| ForeignKey {
+            column: r.get::<String, _>("from"),
+            table: r.get::<String, _>("table"),
+            target: r.get::<Option<String>, _>("to").unwrap_or_else(|| "id".to_string()),
+        })
+        .collect();
+
+    Ok(TableInfo { key_column, columns, foreign_keys })
+}
+
+// The value a planned change would leave in a column, if the change sets it
+fn new_value<'a>(diff: &'a RowDiff, column: &str) -> Option<&'a Value> {
+    match diff.kind {
+        DiffKind::Added => diff.row.as_ref()?.get(column),
+        DiffKind::Changed => diff.fields.iter().find(|f| f.field == column).map(|f| &f.theirs),
+        DiffKind::Removed => None,
+    }
+}
+
+fn key_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+async fn exists_here(conn: &mut SqliteConnection, table: &str, column: &str, value: &Value) -> Result<bool, String> {
+    let sql = format!("SELECT COUNT(*) FROM \"{}\" WHERE \"{}\" = ?", table, column);
+    let count: i64 = bind_json(sqlx::query(&sql), value)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .get(0);
+    Ok(count > 0)
+}
+
+/// Added initiatives keep their reference code unless this workspace already uses it.
+/// Scenario copies share a code, so a renumbered code is reused for the rest of them.
+async fn assign_merged_reference(conn: &mut SqliteConnection, diff: &mut RowDiff, recoded: &mut HashMap<String, String>) -> Result<(), String> {
+    let Some(row) = diff.row.as_mut().and_then(|r| r.as_object_mut()) else {
+        return Ok(());
+    };
+    let carried = row.get("reference_code").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+
+    let code = match recoded.get(&carried) {
+        Some(code) => code.clone(),
+        None => {
+            let code = match claim_reference_code(conn, &carried).await? {
+                Some(code) => code,
+                None => next_reference_code(conn).await?,
+            };
+            if !carried.is_empty() {
+                recoded.insert(carried, code.clone());
+            }
+            code
+        }
+    };
+    row.insert("reference_code".to_string(), Value::String(code));
+    Ok(())
+}
+
+async fn apply_change(conn: &mut SqliteConnection, diff: &RowDiff, info: &TableInfo) -> Result<(), String> {
+    match diff.kind {
+        DiffKind::Added => {
+            let row = diff.row.as_ref().and_then(|r| r.as_object()).ok_or("Added change without a row")?;
+            // Columns this workspace doesn't have are dropped
+            let columns: Vec<&String> = info.columns.iter().filter(|c| row.contains_key(*c)).collect();
+            let sql = format!(
+                "INSERT INTO \"{}\" ({}) VALUES ({})",
+                diff.table,
+                columns.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(", "),
+                vec!["?"; columns.len()].join(", ")
+            );
+            let mut query = sqlx::query(&sql);
+            for column in &columns {
+                query = bind_json(query, &row[column.as_str()]);
+            }
+            query.execute(&mut *conn).await.map_err(|e| e.to_string())?;
+        }
+        DiffKind::Changed => {
+            let fields: Vec<_> = diff.fields.iter().filter(|f| info.columns.contains(&f.field)).collect();
+            if fields.is_empty() {
+                return Ok(());
+            }
+            let sql = format!(
+                "UPDATE \"{}\" SET {} WHERE \"{}\" = ?",
+                diff.table,
+                fields.iter().map(|f| format!("\"{}\" = ?", f.field)).collect::<Vec<_>>().join(", "),
+                info.key_column
+            );
+            let mut query = sqlx::query(&sql);
+            for field in &fields {
+                query = bind_json(query, &field.theirs);
+            }
+            query.bind(&diff.key).execute(&mut *conn).await.map_err(|e| e.to_string())?;
+        }
+        DiffKind::Removed => {
+            sqlx::query(&format!("DELETE FROM \"{}\" WHERE \"{}\" = ?", diff.table, info.key_column))
+                .bind(&diff.key)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+// ============================================
+// WORKSPACE MERGE COMMANDS
+// ============================================
+
+#[tauri::command]
+pub async fn merge_workspace(db: State<'_, tauri_plugin_sql::DbInstances>, other_path: String, strategy: MergeStrategy, base: Option<String>) -> Result<MergeResult, String> {
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let mut theirs = open_workspace_read_only(&other_path).await?;
+
+    // VACUUM INTO cannot run inside the merge transaction
+    let backup_path = create_backup(pool, "pre-merge").await?;
+
+    let operation = JournaledOperation::start(pool, "merge_workspace", serde_json::json!({ "otherPath": other_path, "strategy": strategy, "base": base })).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Rows may arrive in any order; references are checked at commit
+    sqlx::query("PRAGMA defer_foreign_keys = ON")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let base = match base {
+        Some(b) => Some(b),
+        None => sqlx::query_scalar!("SELECT value FROM settings WHERE key = ?", LAST_MERGED_SETTING)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?
+            .flatten(),
+    };
+
+    let ours_tables = list_tables(&mut tx).await?;
+    let theirs_tables = list_tables(&mut theirs).await?;
+
+    let mut diffs: BTreeMap<String, RowDiff> = BTreeMap::new();
+    let mut tables: HashMap<String, TableInfo> = HashMap::new();
+    let mut rejected = Vec::new();
+
+    for table in theirs_tables.iter().filter(|t| !EXCLUDED_TABLES.contains(&t.as_str())) {
+        let ours_exists = ours_tables.contains(table);
+        let Some(TableComparison { key_column, changes, .. }) = diff_table(&mut tx, &mut theirs, table, ours_exists, true).await? else {
+            continue;
+        };
+        if !ours_exists {
+            rejected.extend(changes.into_iter().map(|c| RejectedChange {
+                change_id: c.change_id,
+                reason: format!("Table {} does not exist in this workspace", table),
+            }));
+            continue;
+        }
+        tables.insert(table.clone(), table_info(&mut tx, table, key_column).await?);
+        diffs.extend(changes.into_iter().map(|c| (c.change_id.clone(), c)));
+    }
+
+    let accepted: HashSet<&str> = match &strategy {
+        MergeStrategy::Interactive(ids) => ids.iter().map(|id| id.as_str()).collect(),
+        _ => HashSet::new(),
+    };
+
+    let mut planned: HashSet<String> = HashSet::new();
+    let mut conflicts = Vec::new();
+    let mut skipped_count = 0;
+
+    for (change_id, diff) in &diffs {
+        match decide(diff, &strategy, &accepted, base.as_deref()) {
+            Decision::Apply => {
+                planned.insert(change_id.clone());
+            }
+            Decision::Conflict => conflicts.push(diff.clone()),
+            Decision::Skip => skipped_count += 1,
+        }
+    }
+
+    // Every reference a planned row makes must resolve here after the merge: pull the
+    // target in from theirs when it was added there, otherwise reject the row
+    let mut pulled_in: HashSet<String> = HashSet::new();
+    loop {
+        let mut settled = true;
+
+        for change_id in planned.iter().cloned().collect::<Vec<_>>() {
+            let diff = &diffs[&change_id];
+            let Some(info) = tables.get(&diff.table) else {
+                continue;
+            };
+
+            for fk in &info.foreign_keys {
+                let Some(value) = new_value(diff, &fk.column).filter(|v| !v.is_null()) else {
+                    continue;
+                };
+                let target_id = format!("{}:{}", fk.table, key_string(value));
+                let target_planned = planned.contains(&target_id);
+                let target_removed = target_planned && diffs[&target_id].kind == DiffKind::Removed;
+
+                if target_planned && !target_removed {
+                    continue;
+                }
+                if !target_removed && exists_here(&mut tx, &fk.table, &fk.target, value).await? {
+                    continue;
+                }
+                if diffs.get(&target_id).is_some_and(|d| d.kind == DiffKind::Added) {
+                    planned.insert(target_id.clone());
+                    pulled_in.insert(target_id);
+                } else {
+                    planned.remove(&change_id);
+                    rejected.push(RejectedChange {
+                        change_id: change_id.clone(),
+                        reason: format!("References {} which is not in this workspace or the merge", target_id),
+                    });
+                }
+                settled = false;
+                break;
+            }
+        }
+
+        if settled {
+            break;
+        }
+    }
+
+    let group_id = uuid::Uuid::new_v4().to_string();
+    let mut applied = Vec::new();
+
+    let mut recoded: HashMap<String, String> = HashMap::new();
+
+    for (change_id, diff) in diffs.iter().filter(|(id, _)| planned.contains(*id)) {
+        let mut diff = diff.clone();
+        if diff.table == "initiatives" && diff.kind == DiffKind::Added {
+            assign_merged_reference(&mut tx, &mut diff, &mut recoded).await?;
+        }
+        let diff = &diff;
+
+        apply_change(&mut tx, diff, &tables[&diff.table]).await?;
+
+        let (before, after) = match diff.kind {
+            DiffKind::Added => (None, diff.row.clone()),
+            DiffKind::Removed => (diff.row.clone(), None),
+            DiffKind::Changed => (
+                Some(Value::Object(diff.fields.iter().map(|f| (f.field.clone(), f.ours.clone())).collect())),
+                Some(Value::Object(diff.fields.iter().map(|f| (f.field.clone(), f.theirs.clone())).collect())),
+            ),
+        };
+        let entity_type = EntityType::ALL
+            .into_iter()
+            .find(|e| e.table() == diff.table)
+            .map(|e| e.name().to_string())
+            .unwrap_or_else(|| diff.table.clone());
+
+        record_audit(&mut tx, NewAuditEntry {
+            group_id: Some(group_id.clone()),
+            entity_type,
+            entity_id: Some(diff.key.clone()),
+            action: format!("Merge{:?}", diff.kind),
+            description: Some(format!("Merged {} from {}", change_id, other_path)),
+            before,
+            after,
+        })
+        .await?;
+
+        applied.push(AppliedChange {
+            change_id: change_id.clone(),
+            kind: diff.kind,
+            pulled_in: pulled_in.contains(change_id),
+        });
+    }
+
+    let violations = sqlx::query("PRAGMA foreign_key_check")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !violations.is_empty() {
+        let tables: Vec<String> = violations.iter().map(|r| r.get::<String, _>("table")).collect();
+        return Err(format!("Merge would break references in: {}", tables.join(", ")));
+    }
+
+    let now = get_current_timestamp();
+    sqlx::query!(
+        r#"INSERT INTO settings (key, value, updated_at) VALUES (?, ?, ?)
+        ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at"#,
+        LAST_MERGED_SETTING,
+        now,
+        now
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    operation.complete(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(MergeResult {
+        backup_path,
+        audit_group_id: (!applied.is_empty()).then_some(group_id),
+        applied,
+        conflicts,
+        rejected,
+        skipped_count,
+    })
+}
@@ -0,0 +1,356 @@
+// Tauri commands for the Excel roadmap workbook
+// One sheet per table, with the same columns as the tab-separated exports
+
+use crate::commands::engine::budget::{calculate_budget_report, phased_cost};
+use crate::commands::engine::currency::CurrencyConverter;
+use crate::commands::engine::dates::{FINANCIAL_PERIOD_TYPES, parse_date};
+use crate::commands::engine::resources::PoolPeriodAllocation;
+use crate::commands::entities::EntityType;
+use crate::commands::require_scenario;
+use crate::commands::scenario_data::load_scenario_data;
+use crate::commands::settings::read_date_format;
+use crate::commands::tsv::{ColumnKind, ExportSource, export_spec, fetch_export_records};
+use crate::db::{FinancialPeriod, Initiative};
+use chrono::{Datelike, NaiveDate};
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, XlsxError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use tauri::State;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum XlsxCell {
+    Empty,
+    Text(String),
+    Number(f64),
+    Date(NaiveDate),
+    Flag(bool),
+}
+
+#[derive(Debug, Clone)]
+pub struct XlsxSheet {
+    pub name: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<XlsxCell>>,
+    // Columns kept in view when scrolling right, as well as the header row
+    pub frozen_columns: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SheetSummary {
+    pub name: String,
+    // Data rows, not counting the header
+    pub row_count: usize,
+}
+
+fn text(value: &str) -> XlsxCell {
+    XlsxCell::Text(value.to_string())
+}
+
+fn number(value: Option<f64>) -> XlsxCell {
+    value.map(XlsxCell::Number).unwrap_or(XlsxCell::Empty)
+}
+
+fn typed_cell(value: &Value, kind: ColumnKind) -> XlsxCell {
+    match (value, kind) {
+        (Value::Null, _) => XlsxCell::Empty,
+        (Value::Number(n), ColumnKind::Flag) => XlsxCell::Flag(n.as_i64() != Some(0)),
+        (Value::Number(n), _) => number(n.as_f64()),
+        // Dates that don't parse are kept as written
+        (Value::String(s), ColumnKind::Date) => parse_date(s).map(XlsxCell::Date).unwrap_or_else(|| text(s)),
+        (Value::String(s), _) => text(s),
+        (other, _) => XlsxCell::Text(other.to_string()),
+    }
+}
+
+/// A sheet holding a tab-separated export's columns, so the two never drift apart
+async fn export_sheet(pool: &SqlitePool, name: &str, entity: EntityType, scenario_id: &str) -> Result<XlsxSheet, String> {
+    let spec = export_spec(ExportSource::Entity(entity));
+    let columns: Vec<_> = spec.columns.iter().collect();
+    let records = fetch_export_records(pool, &spec, &columns, Some(scenario_id)).await?;
+
+    Ok(XlsxSheet {
+        name: name.to_string(),
+        headers: columns.iter().map(|c| c.header.to_string()).collect(),
+        rows: records
+            .iter()
+            .map(|record| columns.iter().map(|c| typed_cell(record.get(c.key).unwrap_or(&Value::Null), c.kind)).collect())
+            .collect(),
+        frozen_columns: 0,
+    })
+}
+
+/// Each initiative's cost phased over the finest type of financial period defined, in the
+/// reporting currency, with planned spend, budget and variance rows beneath
+pub fn budget_sheet(initiatives: &[Initiative], periods: &[FinancialPeriod], converter: &CurrencyConverter) -> XlsxSheet {
+    let finest = FINANCIAL_PERIOD_TYPES.iter().rev().find(|t| periods.iter().any(|p| p.period_type == **t));
+    let mut columns: Vec<FinancialPeriod> = periods.iter().filter(|p| Some(&p.period_type.as_str()) == finest).cloned().collect();
+    columns.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+
+    let mut headers = vec!["Initiative".to_string()];
+    headers.extend(columns.iter().map(|p| p.name.clone()));
+    headers.push("Total".to_string());
+
+    let mut warnings = Vec::new();
+    let mut rows: Vec<Vec<XlsxCell>> = initiatives
+        .iter()
+        .filter(|i| i.status != "Cancelled")
+        .map(|initiative| {
+            let currency = converter.currency_of(initiative.currency.as_deref());
+            let costs: Vec<Option<f64>> = columns
+                .iter()
+                .map(|period| {
+                    let on = parse_date(&period.start_date)?;
+                    converter.convert_or_warn(phased_cost(initiative, period), currency, on, "Initiative", &initiative.id, &mut warnings)
+                })
+                .collect();
+            let mut row = vec![text(&initiative.name)];
+            row.extend(costs.iter().map(|c| number(*c)));
+            row.push(number(Some(costs.iter().flatten().sum())));
+            row
+        })
+        .collect();
+
+    let report = calculate_budget_report(initiatives, &columns, converter);
+    let planned: Vec<f64> = report.iter().map(|p| p.planned_spend).collect();
+    let budget: Vec<Option<f64>> = report.iter().map(|p| p.budget_available).collect();
+    let variance: Vec<Option<f64>> = report.iter().map(|p| p.variance).collect();
+    let total = |values: &[Option<f64>]| values.iter().copied().flatten().reduce(|a, b| a + b);
+
+    let mut planned_row = vec![text("Planned Spend")];
+    planned_row.extend(planned.iter().map(|v| XlsxCell::Number(*v)));
+    planned_row.push(XlsxCell::Number(planned.iter().sum()));
+    rows.push(planned_row);
+    for (label, values) in [("Budget", &budget), ("Variance", &variance)] {
+        let mut row = vec![text(label)];
+        row.extend(values.iter().map(|v| number(*v)));
+        row.push(number(total(values)));
+        rows.push(row);
+    }
+
+    XlsxSheet { name: "Budget by Period".to_string(), headers, rows, frozen_columns: 1 }
+}
+
+/// Demand against capacity for every pool and period
+pub fn capacity_sheet(allocations: &[PoolPeriodAllocation]) -> XlsxSheet {
+    let headers = ["Pool", "Unit", "Start", "End", "Capacity", "Demand", "Utilisation %"];
+    let date = |value: &str| parse_date(value).map(XlsxCell::Date).unwrap_or_else(|| text(value));
+    let rows = allocations
+        .iter()
+        .map(|a| {
+            vec![
+                text(&a.pool_name),
+                text(&a.unit),
+                date(&a.period_start),
+                date(&a.period_end),
+                XlsxCell::Number(a.capacity),
+                XlsxCell::Number(a.demand),
+                XlsxCell::Number(a.utilisation),
+            ]
+        })
+        .collect();
+
+    XlsxSheet { name: "Capacity by Pool".to_string(), headers: headers.map(String::from).to_vec(), rows, frozen_columns: 1 }
+}
+
+/// The Excel number format closest to a chrono date format, or ISO dates for anything
+/// without an equivalent
+pub fn excel_date_format(chrono_format: &str) -> String {
+    let mut excel = String::new();
+    let mut chars = chrono_format.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            excel.push(ch);
+            continue;
+        }
+        let token = match chars.next() {
+            Some('-') => chars.next().map(|c| format!("-{}", c)),
+            other => other.map(String::from),
+        };
+        let part = match token.as_deref() {
+            Some("Y") => "yyyy",
+            Some("y") => "yy",
+            Some("m") => "mm",
+            Some("-m") => "m",
+            Some("d") => "dd",
+            Some("-d") | Some("e") => "d",
+            Some("b") => "mmm",
+            Some("B") => "mmmm",
+            Some("a") => "ddd",
+            Some("A") => "dddd",
+            _ => return "yyyy-mm-dd".to_string(),
+        };
+        excel.push_str(part);
+    }
+    excel
+}
+
+fn write_sheets(workbook: &mut Workbook, sheets: &[XlsxSheet], date_format: &str) -> Result<(), XlsxError> {
+    let header = Format::new().set_bold();
+    let date = Format::new().set_num_format(date_format);
+
+    for sheet in sheets {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(&sheet.name)?;
+
+        for (col, title) in sheet.headers.iter().enumerate() {
+            worksheet.write_string_with_format(0, col as u16, title, &header)?;
+        }
+        for (index, row) in sheet.rows.iter().enumerate() {
+            let row_num = index as u32 + 1;
+            for (col, cell) in row.iter().enumerate() {
+                let col = col as u16;
+                match cell {
+                    XlsxCell::Empty => {}
+                    XlsxCell::Text(value) => {
+                        worksheet.write_string(row_num, col, value)?;
+                    }
+                    XlsxCell::Number(value) => {
+                        worksheet.write_number(row_num, col, *value)?;
+                    }
+                    XlsxCell::Flag(value) => {
+                        worksheet.write_boolean(row_num, col, *value)?;
+                    }
+                    XlsxCell::Date(value) => {
+                        let value = ExcelDateTime::from_ymd(value.year() as u16, value.month() as u8, value.day() as u8)?;
+                        worksheet.write_datetime_with_format(row_num, col, &value, &date)?;
+                    }
+                }
+            }
+        }
+
+        if !sheet.headers.is_empty() {
+            worksheet.autofilter(0, 0, sheet.rows.len() as u32, sheet.headers.len() as u16 - 1)?;
+        }
+        worksheet.set_freeze_panes(1, sheet.frozen_columns)?;
+        worksheet.autofit();
+    }
+    Ok(())
+}
+
+/// Write the sheets to an .xlsx file, bold headers with an autofilter and the header row frozen
+pub fn write_workbook(sheets: &[XlsxSheet], path: &str, date_format: &str) -> Result<(), String> {
+    let mut workbook = Workbook::new();
+    write_sheets(&mut workbook, sheets, date_format).map_err(|e| e.to_string())?;
+    workbook.save(path).map_err(|e| format!("Could not write {}: {}", path, e))
+}
+
+// ============================================
+// XLSX EXPORT COMMANDS
+// ============================================
+
+/// Write the scenario's roadmap workbook to `path`: Initiatives, Systems, Capabilities,
+/// Budget by Period and Capacity by Pool
+#[tauri::command]
+pub async fn export_xlsx(db: State<'_, tauri_plugin_sql::DbInstances>, scenario_id: String, path: String) -> Result<Vec<SheetSummary>, String> {
+    require_scenario(db.clone(), &scenario_id).await?;
+    let data = load_scenario_data(db.clone(), &scenario_id).await?;
+
+    let pool = db.0.get("sqlite:roadmap.db")
+        .ok_or_else(|| "Database not found".to_string())?;
+
+    let date_format = excel_date_format(&read_date_format(pool).await?);
+
+    let sheets = vec![
+        export_sheet(pool, "Initiatives", EntityType::Initiative, &scenario_id).await?,
+        export_sheet(pool, "Systems", EntityType::System, &scenario_id).await?,
+        export_sheet(pool, "Capabilities", EntityType::Capability, &scenario_id).await?,
+        budget_sheet(&data.initiatives, &data.periods, &data.converter),
+        capacity_sheet(&data.resource_allocation()),
+    ];
+
+    write_workbook(&sheets, &path, &date_format)?;
+
+    Ok(sheets
+        .into_iter()
+        .map(|s| SheetSummary { row_count: s.rows.len(), name: s.name })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::engine::test_initiative;
+    use calamine::{Data, Reader, Xlsx, open_workbook};
+
+    fn period(id: &str, start: &str, end: &str) -> FinancialPeriod {
+        FinancialPeriod {
+            id: id.to_string(),
+            name: id.to_string(),
+            period_type: "Quarter".to_string(),
+            start_date: start.to_string(),
+            end_date: end.to_string(),
+            budget_available: Some(1000.0),
+            currency: None,
+            closed: false,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn initiative(id: &str, start: &str, end: &str, cost: f64) -> Initiative {
+        Initiative {
+            start_date: Some(start.to_string()),
+            end_date: Some(end.to_string()),
+            cost_estimate: Some(cost),
+            ..test_initiative(id)
+        }
+    }
+
+    #[test]
+    fn chrono_date_formats_map_to_excel() {
+        assert_eq!(excel_date_format("%d/%m/%Y"), "dd/mm/yyyy");
+        assert_eq!(excel_date_format("%-d %b %Y"), "d mmm yyyy");
+        assert_eq!(excel_date_format("%Y-%m-%d %H:%M"), "yyyy-mm-dd");
+    }
+
+    #[test]
+    fn workbook_opens_with_typed_cells() {
+        let periods = vec![period("Q1", "2027-01-01", "2027-03-31"), period("Q2", "2027-04-01", "2027-06-30")];
+        // Half in each quarter, give or take a day's rounding
+        let initiatives = vec![initiative("Migrate", "2027-02-15", "2027-05-15", 900.0)];
+        let converter = CurrencyConverter::new("GBP", &[]);
+
+        let listing = XlsxSheet {
+            name: "Initiatives".to_string(),
+            headers: vec!["Name".to_string(), "Start".to_string(), "Key Date".to_string()],
+            rows: vec![
+                vec![text("Migrate"), XlsxCell::Date(parse_date("2027-02-15").unwrap()), XlsxCell::Flag(true)],
+                vec![text("Retire"), XlsxCell::Empty, XlsxCell::Flag(false)],
+            ],
+            frozen_columns: 0,
+        };
+        let sheets = vec![listing, budget_sheet(&initiatives, &periods, &converter), capacity_sheet(&[])];
+
+        let path = std::env::temp_dir().join(format!("roadmap-{}.xlsx", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap().to_string();
+        write_workbook(&sheets, &path, "dd/mm/yyyy").unwrap();
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        assert_eq!(workbook.sheet_names(), ["Initiatives", "Budget by Period", "Capacity by Pool"]);
+
+        let initiatives_range = workbook.worksheet_range("Initiatives").unwrap();
+        assert_eq!(initiatives_range.height(), 3);
+        assert_eq!(initiatives_range.get_value((0, 1)), Some(&Data::String("Start".to_string())));
+        match initiatives_range.get_value((1, 1)) {
+            Some(Data::DateTime(date)) => assert_eq!(date.as_f64(), 46433.0),
+            other => panic!("expected a date, got {:?}", other),
+        }
+        assert_eq!(initiatives_range.get_value((2, 2)), Some(&Data::Bool(false)));
+
+        let budget = workbook.worksheet_range("Budget by Period").unwrap();
+        // One initiative, then planned spend, budget and variance
+        assert_eq!(budget.height(), 5);
+        assert_eq!(budget.get_value((0, 1)), Some(&Data::String("Q1".to_string())));
+        let Some(Data::Float(total)) = budget.get_value((1, 3)) else {
+            panic!("expected a number for the total");
+        };
+        assert!((total - 900.0).abs() < 1e-9);
+        assert_eq!(budget.get_value((3, 1)), Some(&Data::Float(1000.0)));
+
+        let capacity = workbook.worksheet_range("Capacity by Pool").unwrap();
+        assert_eq!(capacity.height(), 1);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,82 @@
+// Connection configuration for the SQLite database
+// WAL mode lets a long read (e.g. an export) run while quick writes commit
+
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode};
+use sqlx::{ConnectOptions, Connection};
+use std::path::Path;
+use std::time::Duration;
+
+// How long a connection waits on a lock before returning "database is locked"
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn connect_options(path: &Path) -> SqliteConnectOptions {
+    SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .busy_timeout(BUSY_TIMEOUT)
+}
+
+// journal_mode is persisted in the database file, so switching it once at startup
+// also applies to the plugin's pooled connections (which keep sqlx's 5s busy_timeout)
+pub async fn configure_database(path: &Path) -> Result<(), sqlx::Error> {
+    let conn = connect_options(path).connect().await?;
+    conn.close().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn long_read_does_not_block_writes() {
+        let path = std::env::temp_dir().join(format!("roadmap-wal-{}.db", uuid::Uuid::new_v4()));
+        configure_database(&path).await.unwrap();
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .connect_with(connect_options(&path))
+            .await
+            .unwrap();
+
+        sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO items (name) VALUES ('first')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Hold a read transaction open, as a long export would
+        let mut reader = pool.acquire().await.unwrap();
+        sqlx::query("BEGIN").execute(&mut *reader).await.unwrap();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items")
+            .fetch_one(&mut *reader)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+
+        // Without WAL this write waits on the reader until busy_timeout expires
+        let write = sqlx::query("INSERT INTO items (name) VALUES ('second')").execute(&pool);
+        tokio::time::timeout(Duration::from_secs(1), write)
+            .await
+            .expect("write was blocked by the open read")
+            .unwrap();
+
+        // The reader keeps its snapshot until it finishes
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items")
+            .fetch_one(&mut *reader)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+        sqlx::query("COMMIT").execute(&mut *reader).await.unwrap();
+
+        drop(reader);
+        pool.close().await;
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+}
@@ -0,0 +1,14 @@
+-- Roadmap Planner Migration
+-- Version 2: Initiative to capability links
+
+-- Initiative Capabilities: Links initiatives to the capabilities they target
+CREATE TABLE initiative_capabilities (
+    id TEXT PRIMARY KEY,
+    initiative_id TEXT NOT NULL REFERENCES initiatives(id) ON DELETE CASCADE,
+    capability_id TEXT NOT NULL REFERENCES capabilities(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(initiative_id, capability_id)
+);
+
+CREATE INDEX idx_init_capabilities_initiative ON initiative_capabilities(initiative_id);
+CREATE INDEX idx_init_capabilities_capability ON initiative_capabilities(capability_id);
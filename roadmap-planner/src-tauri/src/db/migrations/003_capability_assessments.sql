@@ -0,0 +1,18 @@
+-- Roadmap Planner Migration
+-- Version 3: Capability maturity assessments
+
+-- Capability Assessments: Point-in-time maturity scores (1-5) per capability
+CREATE TABLE capability_assessments (
+    id TEXT PRIMARY KEY,
+    capability_id TEXT NOT NULL REFERENCES capabilities(id) ON DELETE CASCADE,
+    assessment_date TEXT NOT NULL,
+    maturity_score INTEGER NOT NULL CHECK (maturity_score BETWEEN 1 AND 5),
+    target_score INTEGER CHECK (target_score BETWEEN 1 AND 5),
+    notes TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(capability_id, assessment_date)
+);
+
+CREATE INDEX idx_capability_assessments_capability ON capability_assessments(capability_id);
+CREATE INDEX idx_capability_assessments_date ON capability_assessments(assessment_date);
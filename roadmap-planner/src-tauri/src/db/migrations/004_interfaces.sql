@@ -0,0 +1,21 @@
+-- Roadmap Planner Migration
+-- Version 4: System integration interfaces
+
+-- Interfaces: Documented integrations between systems
+-- System references are nulled (and the row flagged) when a system is deleted with the flag strategy
+CREATE TABLE interfaces (
+    id TEXT PRIMARY KEY,
+    source_system_id TEXT REFERENCES systems(id) ON DELETE SET NULL,
+    target_system_id TEXT REFERENCES systems(id) ON DELETE SET NULL,
+    name TEXT NOT NULL,
+    protocol TEXT,
+    frequency TEXT,
+    data_description TEXT,
+    criticality TEXT NOT NULL CHECK (criticality IN ('Critical', 'High', 'Medium', 'Low')),
+    is_orphaned INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_interfaces_source ON interfaces(source_system_id);
+CREATE INDEX idx_interfaces_target ON interfaces(target_system_id);
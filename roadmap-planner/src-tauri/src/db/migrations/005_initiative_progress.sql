@@ -0,0 +1,21 @@
+-- Roadmap Planner Migration
+-- Version 5: Initiative progress and milestones
+
+-- Reported progress (0-100), optionally derived from milestone completion
+ALTER TABLE initiatives ADD COLUMN percent_complete REAL NOT NULL DEFAULT 0 CHECK (percent_complete BETWEEN 0 AND 100);
+ALTER TABLE initiatives ADD COLUMN progress_from_milestones INTEGER NOT NULL DEFAULT 0;
+
+-- Milestones: Checkpoints within an initiative
+CREATE TABLE milestones (
+    id TEXT PRIMARY KEY,
+    initiative_id TEXT NOT NULL REFERENCES initiatives(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    target_date TEXT,
+    is_complete INTEGER NOT NULL DEFAULT 0,
+    completed_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_milestones_initiative ON milestones(initiative_id);
+CREATE INDEX idx_milestones_date ON milestones(target_date);
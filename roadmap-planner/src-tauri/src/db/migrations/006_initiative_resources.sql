@@ -0,0 +1,18 @@
+-- Roadmap Planner Migration
+-- Version 6: Named resource allocations
+
+-- Initiative Resources: Named resources allocated to initiatives
+CREATE TABLE initiative_resources (
+    id TEXT PRIMARY KEY,
+    initiative_id TEXT NOT NULL REFERENCES initiatives(id) ON DELETE CASCADE,
+    resource_id TEXT NOT NULL REFERENCES resources(id) ON DELETE CASCADE,
+    allocation_percent REAL NOT NULL CHECK (allocation_percent > 0 AND allocation_percent <= 100),
+    start_date TEXT,
+    end_date TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(initiative_id, resource_id)
+);
+
+CREATE INDEX idx_init_resources_alloc_initiative ON initiative_resources(initiative_id);
+CREATE INDEX idx_init_resources_alloc_resource ON initiative_resources(resource_id);
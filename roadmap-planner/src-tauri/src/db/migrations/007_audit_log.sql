@@ -0,0 +1,19 @@
+-- Roadmap Planner Migration
+-- Version 7: Audit log
+
+-- Audit Log: Record of changes, grouped so compound operations undo together
+CREATE TABLE audit_log (
+    id TEXT PRIMARY KEY,
+    group_id TEXT,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT,
+    action TEXT NOT NULL,
+    description TEXT,
+    before_json TEXT, -- JSON snapshot before the change
+    after_json TEXT, -- JSON snapshot after the change
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_audit_log_entity ON audit_log(entity_type, entity_id);
+CREATE INDEX idx_audit_log_group ON audit_log(group_id);
+CREATE INDEX idx_audit_log_created ON audit_log(created_at);
@@ -0,0 +1,33 @@
+-- Roadmap Planner Migration
+-- Version 8: Financial period close and actuals
+
+ALTER TABLE financial_periods ADD COLUMN closed INTEGER NOT NULL DEFAULT 0;
+
+-- Period Actuals: Recorded spend per initiative per financial period
+CREATE TABLE period_actuals (
+    id TEXT PRIMARY KEY,
+    financial_period_id TEXT NOT NULL REFERENCES financial_periods(id) ON DELETE CASCADE,
+    initiative_id TEXT NOT NULL REFERENCES initiatives(id) ON DELETE CASCADE,
+    amount REAL NOT NULL,
+    notes TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(financial_period_id, initiative_id)
+);
+
+CREATE INDEX idx_period_actuals_period ON period_actuals(financial_period_id);
+CREATE INDEX idx_period_actuals_initiative ON period_actuals(initiative_id);
+
+-- Period Close Reports: Budget vs actual figures frozen when a period is closed
+CREATE TABLE period_close_reports (
+    id TEXT PRIMARY KEY,
+    financial_period_id TEXT NOT NULL REFERENCES financial_periods(id) ON DELETE CASCADE,
+    budget_available REAL,
+    planned_spend REAL NOT NULL,
+    actual_spend REAL NOT NULL,
+    variance REAL,
+    lines_json TEXT NOT NULL, -- JSON array of per-initiative planned vs actual
+    closed_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_period_close_reports_period ON period_close_reports(financial_period_id);
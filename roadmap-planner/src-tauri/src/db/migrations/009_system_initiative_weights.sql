@@ -0,0 +1,6 @@
+-- Roadmap Planner Migration
+-- Version 9: Optional weighting on system to initiative links
+
+-- Share of the initiative's investment attributed to this system's capability.
+-- Unweighted links split the investment evenly across capabilities.
+ALTER TABLE system_initiatives ADD COLUMN weight REAL CHECK (weight IS NULL OR weight > 0);
@@ -0,0 +1,31 @@
+-- Roadmap Planner Migration
+-- Version 10: Multi-currency costs and budgets
+
+-- Workspace reporting currency; every aggregation converts into it
+INSERT OR IGNORE INTO settings (key, value) VALUES ('reporting_currency', 'GBP');
+
+-- ISO 4217 codes; existing rows take the reporting currency
+ALTER TABLE initiatives ADD COLUMN currency TEXT;
+ALTER TABLE financial_periods ADD COLUMN currency TEXT;
+
+UPDATE initiatives SET currency = (SELECT value FROM settings WHERE key = 'reporting_currency') WHERE currency IS NULL;
+UPDATE financial_periods SET currency = (SELECT value FROM settings WHERE key = 'reporting_currency') WHERE currency IS NULL;
+
+-- Close reports record the currency their figures were converted into
+ALTER TABLE period_close_reports ADD COLUMN currency TEXT;
+ALTER TABLE period_close_reports ADD COLUMN warnings_json TEXT;
+
+-- Exchange Rates: Effective-dated conversion rates (1 from_currency = rate to_currency)
+CREATE TABLE exchange_rates (
+    id TEXT PRIMARY KEY,
+    from_currency TEXT NOT NULL,
+    to_currency TEXT NOT NULL,
+    rate REAL NOT NULL CHECK (rate > 0),
+    effective_date TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(from_currency, to_currency, effective_date),
+    CHECK (from_currency != to_currency)
+);
+
+CREATE INDEX idx_exchange_rates_pair ON exchange_rates(from_currency, to_currency, effective_date);
@@ -0,0 +1,6 @@
+-- Roadmap Planner Migration
+-- Version 11: Effort units on initiatives
+
+-- Unit for effort_estimate and the initiative's pool requirements.
+-- NULL keeps the old behaviour: requirements are already in each pool's capacity unit.
+ALTER TABLE initiatives ADD COLUMN effort_unit TEXT CHECK (effort_unit IS NULL OR effort_unit IN ('PersonDays', 'PersonWeeks', 'PersonMonths', 'PersonYears'));
@@ -0,0 +1,27 @@
+-- Roadmap Planner Migration
+-- Version 12: Initiative comment threads with mentions
+
+-- Comments: Discussion thread per initiative; replies point at their parent comment
+CREATE TABLE comments (
+    id TEXT PRIMARY KEY,
+    initiative_id TEXT NOT NULL REFERENCES initiatives(id) ON DELETE CASCADE,
+    author_resource_id TEXT REFERENCES resources(id) ON DELETE SET NULL,
+    body TEXT NOT NULL,
+    reply_to_comment_id TEXT REFERENCES comments(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_comments_initiative ON comments(initiative_id, created_at);
+CREATE INDEX idx_comments_reply_to ON comments(reply_to_comment_id);
+
+-- Comment Mentions: Resources referenced by @[name] in a comment body
+CREATE TABLE comment_mentions (
+    id TEXT PRIMARY KEY,
+    comment_id TEXT NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    resource_id TEXT NOT NULL REFERENCES resources(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(comment_id, resource_id)
+);
+
+CREATE INDEX idx_comment_mentions_resource ON comment_mentions(resource_id);
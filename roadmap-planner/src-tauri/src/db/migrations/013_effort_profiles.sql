@@ -0,0 +1,5 @@
+-- Roadmap Planner Migration
+-- Version 13: Effort distribution profiles
+
+-- Shape of effort across an initiative's duration; Flat is the old linear spread
+ALTER TABLE initiatives ADD COLUMN effort_profile TEXT NOT NULL DEFAULT 'Flat' CHECK (effort_profile IN ('Flat', 'FrontLoaded', 'BackLoaded', 'Bell'));
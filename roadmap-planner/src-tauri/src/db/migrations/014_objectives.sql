@@ -0,0 +1,29 @@
+-- Roadmap Planner Migration
+-- Version 14: Strategic objectives and initiative alignment
+
+-- Objectives: Portfolio-level goals (OKRs), nested under a parent objective
+CREATE TABLE objectives (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    target_date TEXT,
+    owner TEXT,
+    parent_objective_id TEXT REFERENCES objectives(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_objectives_parent ON objectives(parent_objective_id);
+
+-- Initiative Objectives: How much each initiative contributes to an objective
+CREATE TABLE initiative_objectives (
+    id TEXT PRIMARY KEY,
+    initiative_id TEXT NOT NULL REFERENCES initiatives(id) ON DELETE CASCADE,
+    objective_id TEXT NOT NULL REFERENCES objectives(id) ON DELETE CASCADE,
+    contribution_weight REAL NOT NULL DEFAULT 1 CHECK (contribution_weight > 0),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(initiative_id, objective_id)
+);
+
+CREATE INDEX idx_init_objectives_initiative ON initiative_objectives(initiative_id);
+CREATE INDEX idx_init_objectives_objective ON initiative_objectives(objective_id);
@@ -0,0 +1,7 @@
+-- Roadmap Planner Migration
+-- Version 15: External reference ids on initiatives
+
+-- Key of the matching item in an external tracker, e.g. a Jira issue key
+ALTER TABLE initiatives ADD COLUMN external_ref TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_initiatives_external_ref ON initiatives(external_ref COLLATE NOCASE);
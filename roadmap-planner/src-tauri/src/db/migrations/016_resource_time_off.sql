@@ -0,0 +1,16 @@
+-- Roadmap Planner Migration
+-- Version 16: Time off for named resources
+
+-- Resource Time Off: Days a resource is unavailable, such as leave or training; both dates are included
+CREATE TABLE resource_time_off (
+    id TEXT PRIMARY KEY,
+    resource_id TEXT NOT NULL REFERENCES resources(id) ON DELETE CASCADE,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    reason TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    CHECK (end_date >= start_date)
+);
+
+CREATE INDEX idx_resource_time_off_resource ON resource_time_off(resource_id, start_date);
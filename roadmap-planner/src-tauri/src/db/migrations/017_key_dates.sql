@@ -0,0 +1,5 @@
+-- Roadmap Planner Migration
+-- Version 17: Key date initiatives
+
+-- Initiatives whose finish date is tracked by schedule simulation, e.g. a go-live
+ALTER TABLE initiatives ADD COLUMN is_key_date INTEGER NOT NULL DEFAULT 0 CHECK (is_key_date IN (0, 1));
@@ -0,0 +1,8 @@
+-- Roadmap Planner Migration
+-- Version 18: Initiative colour and icon overrides
+
+-- Hex colour for the initiative's bar; NULL uses the colour of its capability lane
+ALTER TABLE initiatives ADD COLUMN colour TEXT;
+
+-- Small marker shown on the bar
+ALTER TABLE initiatives ADD COLUMN icon TEXT CHECK (icon IN ('risk', 'regulatory', 'customer-facing'));
@@ -0,0 +1,18 @@
+-- Roadmap Planner Migration
+-- Version 19: Dependency lag range
+
+-- Negative lag_days is a lead: the successor may overlap the predecessor.
+-- Anything beyond a year either way is rejected as a data entry mistake.
+CREATE TRIGGER initiative_dependencies_lag_insert
+BEFORE INSERT ON initiative_dependencies
+WHEN ABS(COALESCE(NEW.lag_days, 0)) > 365
+BEGIN
+    SELECT RAISE(ABORT, 'Dependency lag must be between -365 and 365 days');
+END;
+
+CREATE TRIGGER initiative_dependencies_lag_update
+BEFORE UPDATE OF lag_days ON initiative_dependencies
+WHEN ABS(COALESCE(NEW.lag_days, 0)) > 365
+BEGIN
+    SELECT RAISE(ABORT, 'Dependency lag must be between -365 and 365 days');
+END;
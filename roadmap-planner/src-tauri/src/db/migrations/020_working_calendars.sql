@@ -0,0 +1,22 @@
+-- Roadmap Planner Migration
+-- Version 20: Working calendars
+
+-- Working Calendars: Which weekdays are worked, for turning effort into dates
+CREATE TABLE working_calendars (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    -- Comma-separated weekday abbreviations, e.g. 'Mon,Tue,Wed,Thu,Fri'
+    working_days TEXT NOT NULL DEFAULT 'Mon,Tue,Wed,Thu,Fri',
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Calendar Holidays: Specific non-working dates in a calendar
+CREATE TABLE calendar_holidays (
+    id TEXT PRIMARY KEY,
+    calendar_id TEXT NOT NULL REFERENCES working_calendars(id) ON DELETE CASCADE,
+    date TEXT NOT NULL,
+    name TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(calendar_id, date)
+);
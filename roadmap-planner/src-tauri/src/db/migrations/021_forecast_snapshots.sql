@@ -0,0 +1,58 @@
+-- Roadmap Planner Migration
+-- Version 21: Rolling forecast snapshots
+
+-- Forecast Snapshots: A captured run of a scenario's budget report. Figures are copied
+-- rather than referenced, so history survives later edits and deletions.
+CREATE TABLE forecast_snapshots (
+    id TEXT PRIMARY KEY,
+    scenario_id TEXT NOT NULL,
+    scenario_name TEXT NOT NULL,
+    label TEXT NOT NULL,
+    -- Reporting currency of every figure in the snapshot
+    currency TEXT NOT NULL,
+    captured_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_forecast_snapshots_scenario ON forecast_snapshots(scenario_id, captured_at);
+
+-- Forecast Snapshot Periods: Budget and planned spend per financial period
+CREATE TABLE forecast_snapshot_periods (
+    snapshot_id TEXT NOT NULL REFERENCES forecast_snapshots(id) ON DELETE CASCADE,
+    financial_period_id TEXT NOT NULL,
+    period_name TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    budget_available REAL,
+    planned_spend REAL NOT NULL,
+    PRIMARY KEY (snapshot_id, financial_period_id)
+);
+
+CREATE INDEX idx_forecast_snapshot_periods_period ON forecast_snapshot_periods(financial_period_id);
+
+-- Forecast Snapshot Lines: Each initiative's planned spend in a period, non-zero only
+CREATE TABLE forecast_snapshot_lines (
+    snapshot_id TEXT NOT NULL REFERENCES forecast_snapshots(id) ON DELETE CASCADE,
+    financial_period_id TEXT NOT NULL,
+    initiative_id TEXT NOT NULL,
+    initiative_name TEXT NOT NULL,
+    planned_spend REAL NOT NULL,
+    PRIMARY KEY (snapshot_id, financial_period_id, initiative_id)
+);
+
+-- Snapshots are a record of what the forecast said at the time
+CREATE TRIGGER forecast_snapshots_immutable
+BEFORE UPDATE ON forecast_snapshots
+BEGIN
+    SELECT RAISE(ABORT, 'Forecast snapshots cannot be changed');
+END;
+
+CREATE TRIGGER forecast_snapshot_periods_immutable
+BEFORE UPDATE ON forecast_snapshot_periods
+BEGIN
+    SELECT RAISE(ABORT, 'Forecast snapshots cannot be changed');
+END;
+
+CREATE TRIGGER forecast_snapshot_lines_immutable
+BEFORE UPDATE ON forecast_snapshot_lines
+BEGIN
+    SELECT RAISE(ABORT, 'Forecast snapshots cannot be changed');
+END;
@@ -0,0 +1,224 @@
+-- Roadmap Planner Migration
+-- Version 22: Locked scenario snapshots
+
+-- Scenarios frozen at approval time. Locking is one-way, and the triggers below reject
+-- any change to a locked scenario or to the initiatives and links it owns.
+ALTER TABLE scenarios ADD COLUMN is_locked INTEGER NOT NULL DEFAULT 0 CHECK (is_locked IN (0, 1));
+
+CREATE TRIGGER scenarios_locked_update
+BEFORE UPDATE ON scenarios
+WHEN OLD.is_locked = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER scenarios_locked_delete
+BEFORE DELETE ON scenarios
+WHEN OLD.is_locked = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+-- Initiatives: moving an initiative into or out of a locked scenario counts as a change
+CREATE TRIGGER initiatives_locked_insert
+BEFORE INSERT ON initiatives
+WHEN (SELECT is_locked FROM scenarios WHERE id = NEW.scenario_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiatives_locked_update
+BEFORE UPDATE ON initiatives
+WHEN (SELECT is_locked FROM scenarios WHERE id = OLD.scenario_id) = 1
+    OR (SELECT is_locked FROM scenarios WHERE id = NEW.scenario_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiatives_locked_delete
+BEFORE DELETE ON initiatives
+WHEN (SELECT is_locked FROM scenarios WHERE id = OLD.scenario_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+-- Rows owned by a locked scenario's initiatives, as copied by the snapshot
+CREATE TRIGGER milestones_locked_insert
+BEFORE INSERT ON milestones
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER milestones_locked_update
+BEFORE UPDATE ON milestones
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER milestones_locked_delete
+BEFORE DELETE ON milestones
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_dependencies_locked_insert
+BEFORE INSERT ON initiative_dependencies
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.predecessor_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.successor_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_dependencies_locked_update
+BEFORE UPDATE ON initiative_dependencies
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.predecessor_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.successor_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.predecessor_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.successor_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_dependencies_locked_delete
+BEFORE DELETE ON initiative_dependencies
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.predecessor_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.successor_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_capabilities_locked_insert
+BEFORE INSERT ON initiative_capabilities
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_capabilities_locked_update
+BEFORE UPDATE ON initiative_capabilities
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_capabilities_locked_delete
+BEFORE DELETE ON initiative_capabilities
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_resource_requirements_locked_insert
+BEFORE INSERT ON initiative_resource_requirements
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_resource_requirements_locked_update
+BEFORE UPDATE ON initiative_resource_requirements
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_resource_requirements_locked_delete
+BEFORE DELETE ON initiative_resource_requirements
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_resources_locked_insert
+BEFORE INSERT ON initiative_resources
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_resources_locked_update
+BEFORE UPDATE ON initiative_resources
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_resources_locked_delete
+BEFORE DELETE ON initiative_resources
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER system_initiatives_locked_insert
+BEFORE INSERT ON system_initiatives
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER system_initiatives_locked_update
+BEFORE UPDATE ON system_initiatives
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER system_initiatives_locked_delete
+BEFORE DELETE ON system_initiatives
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_constraints_locked_insert
+BEFORE INSERT ON initiative_constraints
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_constraints_locked_update
+BEFORE UPDATE ON initiative_constraints
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_constraints_locked_delete
+BEFORE DELETE ON initiative_constraints
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_objectives_locked_insert
+BEFORE INSERT ON initiative_objectives
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_objectives_locked_update
+BEFORE UPDATE ON initiative_objectives
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_objectives_locked_delete
+BEFORE DELETE ON initiative_objectives
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
@@ -0,0 +1,13 @@
+-- Roadmap Planner Migration
+-- Version 23: Unlocking scenarios
+
+-- Scenarios: the lock may now be lifted by unlock_scenario. Any other change to a
+-- locked scenario is still rejected.
+DROP TRIGGER scenarios_locked_update;
+
+CREATE TRIGGER scenarios_locked_update
+BEFORE UPDATE ON scenarios
+WHEN OLD.is_locked = 1 AND NEW.is_locked = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
@@ -0,0 +1,38 @@
+-- Roadmap Planner Migration
+-- Version 24: Initiative pool splits
+
+-- Initiative Pool Splits: Share of an initiative's effort estimate drawn from each pool,
+-- used for pool demand wherever no explicit requirement covers the period
+CREATE TABLE initiative_pool_splits (
+    id TEXT PRIMARY KEY,
+    initiative_id TEXT NOT NULL REFERENCES initiatives(id) ON DELETE CASCADE,
+    resource_pool_id TEXT NOT NULL REFERENCES resource_pools(id) ON DELETE CASCADE,
+    percentage REAL NOT NULL CHECK (percentage > 0 AND percentage <= 100),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(initiative_id, resource_pool_id)
+);
+
+CREATE INDEX idx_pool_splits_pool ON initiative_pool_splits(resource_pool_id);
+
+-- Splits belong to their initiative, so a locked scenario's splits are locked too
+CREATE TRIGGER initiative_pool_splits_locked_insert
+BEFORE INSERT ON initiative_pool_splits
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_pool_splits_locked_update
+BEFORE UPDATE ON initiative_pool_splits
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+    OR (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = NEW.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+CREATE TRIGGER initiative_pool_splits_locked_delete
+BEFORE DELETE ON initiative_pool_splits
+WHEN (SELECT s.is_locked FROM initiatives i JOIN scenarios s ON s.id = i.scenario_id WHERE i.id = OLD.initiative_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
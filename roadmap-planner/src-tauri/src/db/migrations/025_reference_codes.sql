@@ -0,0 +1,38 @@
+-- Roadmap Planner Migration
+-- Version 25: Initiative reference codes
+
+-- Short public identifier such as RM-0142, assigned at creation and never changed.
+-- Scenario copies of an initiative keep its code, so it is unique within a scenario.
+ALTER TABLE initiatives ADD COLUMN reference_code TEXT;
+
+CREATE UNIQUE INDEX idx_initiatives_reference_code ON initiatives(scenario_id, reference_code COLLATE NOCASE);
+CREATE INDEX idx_initiatives_reference_code_lookup ON initiatives(reference_code COLLATE NOCASE);
+
+-- Last number handed out per sequence; only ever moves forward
+CREATE TABLE reference_sequences (
+    name TEXT PRIMARY KEY,
+    last_value INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT OR IGNORE INTO settings (key, value) VALUES ('reference_code_prefix', 'RM');
+
+-- Number existing initiatives in creation order. Locked snapshots get codes too, so
+-- their guard is lifted for the backfill and restored unchanged.
+DROP TRIGGER initiatives_locked_update;
+
+WITH numbered AS (
+    SELECT id, ROW_NUMBER() OVER (ORDER BY created_at, id) AS n FROM initiatives
+)
+UPDATE initiatives
+SET reference_code = 'RM-' || printf('%04d', (SELECT n FROM numbered WHERE numbered.id = initiatives.id));
+
+CREATE TRIGGER initiatives_locked_update
+BEFORE UPDATE ON initiatives
+WHEN (SELECT is_locked FROM scenarios WHERE id = OLD.scenario_id) = 1
+    OR (SELECT is_locked FROM scenarios WHERE id = NEW.scenario_id) = 1
+BEGIN
+    SELECT RAISE(ABORT, 'Scenario is locked; approved snapshots cannot be changed');
+END;
+
+INSERT INTO reference_sequences (name, last_value)
+VALUES ('initiatives', (SELECT COUNT(*) FROM initiatives));
@@ -0,0 +1,17 @@
+-- Roadmap Planner Migration
+-- Version 26: Role capacity within resource pools
+
+-- Pool Role Capacities: How much of a pool's capacity each role provides, per period and in
+-- the pool's capacity unit. A pool without rows here takes its roles from its members.
+CREATE TABLE pool_role_capacities (
+    id TEXT PRIMARY KEY,
+    resource_pool_id TEXT NOT NULL REFERENCES resource_pools(id) ON DELETE CASCADE,
+    role TEXT NOT NULL,
+    capacity_per_period REAL NOT NULL CHECK (capacity_per_period >= 0),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(resource_pool_id, role COLLATE NOCASE)
+);
+
+-- The role a pool allocation needs; NULL draws on the pool as a whole
+ALTER TABLE initiative_resource_requirements ADD COLUMN role TEXT;
@@ -0,0 +1,22 @@
+-- Roadmap Planner Migration
+-- Version 27: Per-scenario what-if overrides
+
+-- Scenario Overrides: Values the calculations use in place of an entity's own field while
+-- analysing one scenario. The entity itself is left untouched. override_value is stored as
+-- text and read according to the field; effective dates only apply to fields that vary by period.
+CREATE TABLE scenario_overrides (
+    id TEXT PRIMARY KEY,
+    scenario_id TEXT NOT NULL REFERENCES scenarios(id) ON DELETE CASCADE,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    field TEXT NOT NULL,
+    override_value TEXT NOT NULL,
+    effective_from TEXT,
+    effective_to TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    CHECK (effective_from IS NULL OR effective_to IS NULL OR effective_from <= effective_to)
+);
+
+CREATE INDEX idx_scenario_overrides_scenario ON scenario_overrides(scenario_id);
+CREATE INDEX idx_scenario_overrides_entity ON scenario_overrides(entity_type, entity_id);
@@ -0,0 +1,13 @@
+-- Roadmap Planner Migration
+-- Version 28: Review stamps on every entity table
+
+-- When someone last confirmed the row is still accurate. Set without touching updated_at, so
+-- the two can tell a reviewed-but-unchanged row from an edited one.
+ALTER TABLE capabilities ADD COLUMN last_reviewed_at TEXT;
+ALTER TABLE systems ADD COLUMN last_reviewed_at TEXT;
+ALTER TABLE initiatives ADD COLUMN last_reviewed_at TEXT;
+ALTER TABLE scenarios ADD COLUMN last_reviewed_at TEXT;
+ALTER TABLE resource_pools ADD COLUMN last_reviewed_at TEXT;
+ALTER TABLE resources ADD COLUMN last_reviewed_at TEXT;
+ALTER TABLE constraints ADD COLUMN last_reviewed_at TEXT;
+ALTER TABLE financial_periods ADD COLUMN last_reviewed_at TEXT;
@@ -0,0 +1,7 @@
+-- Roadmap Planner Migration
+-- Version 29: Manual initiative health override
+
+-- Health is normally derived from schedule, progress, constraint and allocation signals.
+-- A status set here wins over the derived one and is reported as manual.
+ALTER TABLE initiatives ADD COLUMN health_override TEXT CHECK (health_override IN ('Red', 'Amber', 'Green'));
+ALTER TABLE initiatives ADD COLUMN health_override_reason TEXT;
@@ -0,0 +1,11 @@
+-- Roadmap Planner Migration
+-- Version 30: Pool allocation approval
+
+-- Demand on a pool is requested by planners and approved or rejected by the pool's manager.
+-- Existing allocations were already committed, so they start out approved. Rejected demand
+-- is kept for the record but never counts towards utilisation.
+ALTER TABLE initiative_resource_requirements ADD COLUMN approval_status TEXT NOT NULL DEFAULT 'Approved'
+    CHECK (approval_status IN ('Requested', 'Approved', 'Rejected'));
+ALTER TABLE initiative_resource_requirements ADD COLUMN approver_note TEXT;
+
+CREATE INDEX idx_init_resources_pool_approval ON initiative_resource_requirements(resource_pool_id, approval_status);
@@ -0,0 +1,35 @@
+-- Roadmap Planner Migration
+-- Version 31: Cached scenario summary stats
+
+-- Headline totals for the dashboard, worked out once rather than on every open.
+-- Any change to a scenario's initiatives drops its row, so the next read recomputes it.
+CREATE TABLE scenario_stats (
+    scenario_id TEXT PRIMARY KEY REFERENCES scenarios(id) ON DELETE CASCADE,
+    currency TEXT NOT NULL,
+    initiative_count INTEGER NOT NULL,
+    -- JSON object of initiative status to count
+    status_counts TEXT NOT NULL,
+    total_cost REAL NOT NULL,
+    total_effort REAL NOT NULL,
+    risk_score REAL NOT NULL,
+    risk_level TEXT NOT NULL,
+    computed_at TEXT NOT NULL
+);
+
+CREATE TRIGGER scenario_stats_initiative_insert
+AFTER INSERT ON initiatives
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id = NEW.scenario_id;
+END;
+
+CREATE TRIGGER scenario_stats_initiative_update
+AFTER UPDATE ON initiatives
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (OLD.scenario_id, NEW.scenario_id);
+END;
+
+CREATE TRIGGER scenario_stats_initiative_delete
+AFTER DELETE ON initiatives
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id = OLD.scenario_id;
+END;
@@ -0,0 +1,44 @@
+-- Roadmap Planner Migration
+-- Version 32: Scenario approval and sign-off
+
+-- Named sign-off decisions on a scenario. Decisions belong to an approval round; once every
+-- required role has approved in the round, the scenario is approved and locked.
+CREATE TABLE scenario_approvals (
+    id TEXT PRIMARY KEY,
+    scenario_id TEXT NOT NULL REFERENCES scenarios(id) ON DELETE CASCADE,
+    approver_name TEXT NOT NULL,
+    role TEXT NOT NULL,
+    decision TEXT NOT NULL CHECK (decision IN ('Approve', 'Reject')),
+    comment TEXT,
+    approval_round INTEGER NOT NULL,
+    decided_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_scenario_approvals_scenario ON scenario_approvals(scenario_id, approval_round);
+
+ALTER TABLE scenarios ADD COLUMN approval_round INTEGER NOT NULL DEFAULT 1;
+-- The round in which the scenario was last approved; NULL if it never has been
+ALTER TABLE scenarios ADD COLUMN approved_round INTEGER;
+
+-- Changing an initiative after approval (the scenario has to be unlocked first) starts a new
+-- round, so the scenario reads as approved-with-changes until it is signed off again
+CREATE TRIGGER scenario_approval_initiative_insert
+AFTER INSERT ON initiatives
+BEGIN
+    UPDATE scenarios SET approval_round = approval_round + 1
+    WHERE id = NEW.scenario_id AND approved_round = approval_round;
+END;
+
+CREATE TRIGGER scenario_approval_initiative_update
+AFTER UPDATE ON initiatives
+BEGIN
+    UPDATE scenarios SET approval_round = approval_round + 1
+    WHERE id IN (OLD.scenario_id, NEW.scenario_id) AND approved_round = approval_round;
+END;
+
+CREATE TRIGGER scenario_approval_initiative_delete
+AFTER DELETE ON initiatives
+BEGIN
+    UPDATE scenarios SET approval_round = approval_round + 1
+    WHERE id = OLD.scenario_id AND approved_round = approval_round;
+END;
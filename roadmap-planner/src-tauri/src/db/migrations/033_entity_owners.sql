@@ -0,0 +1,9 @@
+-- Roadmap Planner Migration
+-- Version 33: Resources as owners of systems and initiatives
+
+-- The free-text systems.owner stays for display when no owner resource is set
+ALTER TABLE systems ADD COLUMN owner_resource_id TEXT REFERENCES resources(id) ON DELETE SET NULL;
+ALTER TABLE initiatives ADD COLUMN owner_resource_id TEXT REFERENCES resources(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_systems_owner_resource ON systems(owner_resource_id);
+CREATE INDEX idx_initiatives_owner_resource ON initiatives(owner_resource_id);
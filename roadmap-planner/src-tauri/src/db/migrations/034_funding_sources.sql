@@ -0,0 +1,23 @@
+-- Roadmap Planner Migration
+-- Version 34: Funding sources and derived period budgets
+
+-- Money committed to a financial period, split into capital and operating spend
+CREATE TABLE funding_sources (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    financial_period_id TEXT NOT NULL REFERENCES financial_periods(id) ON DELETE CASCADE,
+    capex_amount REAL NOT NULL DEFAULT 0 CHECK (capex_amount >= 0),
+    opex_amount REAL NOT NULL DEFAULT 0 CHECK (opex_amount >= 0),
+    currency TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_funding_sources_period ON funding_sources(financial_period_id);
+
+-- Manual periods keep a hand-typed budget_available; derived ones take it from their funding
+-- sources whenever budgets are recalculated
+ALTER TABLE financial_periods ADD COLUMN budget_mode TEXT NOT NULL DEFAULT 'Manual' CHECK (budget_mode IN ('Manual', 'Derived'));
+-- The capital and operating parts of a derived budget_available; NULL until first derived
+ALTER TABLE financial_periods ADD COLUMN budget_capex REAL;
+ALTER TABLE financial_periods ADD COLUMN budget_opex REAL;
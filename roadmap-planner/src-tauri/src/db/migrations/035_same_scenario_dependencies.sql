@@ -0,0 +1,20 @@
+-- Roadmap Planner Migration
+-- Version 35: Dependencies stay within one scenario
+
+-- Scenario views only draw edges between their own initiatives, so an edge across scenarios
+-- would vanish from both. Edges already in the database are reported by validate_workspace.
+CREATE TRIGGER initiative_dependencies_same_scenario_insert
+BEFORE INSERT ON initiative_dependencies
+WHEN (SELECT scenario_id FROM initiatives WHERE id = NEW.predecessor_id)
+    IS NOT (SELECT scenario_id FROM initiatives WHERE id = NEW.successor_id)
+BEGIN
+    SELECT RAISE(ABORT, 'Dependencies must link initiatives in the same scenario');
+END;
+
+CREATE TRIGGER initiative_dependencies_same_scenario_update
+BEFORE UPDATE OF predecessor_id, successor_id ON initiative_dependencies
+WHEN (SELECT scenario_id FROM initiatives WHERE id = NEW.predecessor_id)
+    IS NOT (SELECT scenario_id FROM initiatives WHERE id = NEW.successor_id)
+BEGIN
+    SELECT RAISE(ABORT, 'Dependencies must link initiatives in the same scenario');
+END;
@@ -0,0 +1,18 @@
+-- Roadmap Planner Migration
+-- Version 36: Operation journal for crash recovery
+
+-- One row per run of a compound command, written before its transaction opens. The command
+-- marks it Completed inside that transaction, so a row left Started means the app died mid-way.
+CREATE TABLE operation_journal (
+    id TEXT PRIMARY KEY,
+    -- The app run that wrote the row; rows from the current run are never reported as interrupted
+    session_id TEXT NOT NULL,
+    command TEXT NOT NULL,
+    -- The command's arguments as JSON, enough to run it again
+    args_json TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Started' CHECK (status IN ('Started', 'Completed', 'Failed', 'Discarded')),
+    started_at TEXT NOT NULL DEFAULT (datetime('now')),
+    finished_at TEXT
+);
+
+CREATE INDEX idx_operation_journal_status ON operation_journal(status, started_at);
@@ -0,0 +1,11 @@
+-- Roadmap Planner Migration
+-- Version 37: Default resource pools for capabilities and generated allocations
+
+-- The delivery team that usually works on a capability; children without their own inherit it
+ALTER TABLE capabilities ADD COLUMN default_pool_id TEXT REFERENCES resource_pools(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_capabilities_default_pool ON capabilities(default_pool_id);
+
+-- 1 for pool allocations auto_allocate_initiative wrote, which it replaces when run again;
+-- allocations entered by hand stay 0 and are never touched by it
+ALTER TABLE initiative_resource_requirements ADD COLUMN generated INTEGER NOT NULL DEFAULT 0 CHECK (generated IN (0, 1));
@@ -0,0 +1,7 @@
+-- Roadmap Planner Migration
+-- Version 38: External reference ids on systems
+
+-- Key of the matching record in an external inventory, e.g. a CMDB configuration item id
+ALTER TABLE systems ADD COLUMN external_ref TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_systems_external_ref ON systems(external_ref COLLATE NOCASE);
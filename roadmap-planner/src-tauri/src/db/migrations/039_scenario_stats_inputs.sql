@@ -0,0 +1,204 @@
+-- Roadmap Planner Migration
+-- Version 39: Scenario stats invalidation on risk inputs
+
+-- The cached risk score also depends on dependencies, resourcing, constraints, periods,
+-- overrides and exchange rates. Rows tied to an initiative or scenario drop that scenario's
+-- stats; the shared tables feed every scenario, so a change there drops them all.
+
+CREATE TRIGGER scenario_stats_requirement_insert
+AFTER INSERT ON initiative_resource_requirements
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id = NEW.initiative_id);
+END;
+
+CREATE TRIGGER scenario_stats_requirement_update
+AFTER UPDATE ON initiative_resource_requirements
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id IN (OLD.initiative_id, NEW.initiative_id));
+END;
+
+CREATE TRIGGER scenario_stats_requirement_delete
+AFTER DELETE ON initiative_resource_requirements
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id = OLD.initiative_id);
+END;
+
+CREATE TRIGGER scenario_stats_pool_split_insert
+AFTER INSERT ON initiative_pool_splits
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id = NEW.initiative_id);
+END;
+
+CREATE TRIGGER scenario_stats_pool_split_update
+AFTER UPDATE ON initiative_pool_splits
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id IN (OLD.initiative_id, NEW.initiative_id));
+END;
+
+CREATE TRIGGER scenario_stats_pool_split_delete
+AFTER DELETE ON initiative_pool_splits
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id = OLD.initiative_id);
+END;
+
+CREATE TRIGGER scenario_stats_constraint_link_insert
+AFTER INSERT ON initiative_constraints
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id = NEW.initiative_id);
+END;
+
+CREATE TRIGGER scenario_stats_constraint_link_update
+AFTER UPDATE ON initiative_constraints
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id IN (OLD.initiative_id, NEW.initiative_id));
+END;
+
+CREATE TRIGGER scenario_stats_constraint_link_delete
+AFTER DELETE ON initiative_constraints
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id = OLD.initiative_id);
+END;
+
+CREATE TRIGGER scenario_stats_dependency_insert
+AFTER INSERT ON initiative_dependencies
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id IN (NEW.predecessor_id, NEW.successor_id));
+END;
+
+CREATE TRIGGER scenario_stats_dependency_update
+AFTER UPDATE ON initiative_dependencies
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id IN (OLD.predecessor_id, OLD.successor_id, NEW.predecessor_id, NEW.successor_id));
+END;
+
+CREATE TRIGGER scenario_stats_dependency_delete
+AFTER DELETE ON initiative_dependencies
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (SELECT scenario_id FROM initiatives WHERE id IN (OLD.predecessor_id, OLD.successor_id));
+END;
+
+CREATE TRIGGER scenario_stats_override_insert
+AFTER INSERT ON scenario_overrides
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id = NEW.scenario_id;
+END;
+
+CREATE TRIGGER scenario_stats_override_update
+AFTER UPDATE ON scenario_overrides
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id IN (OLD.scenario_id, NEW.scenario_id);
+END;
+
+CREATE TRIGGER scenario_stats_override_delete
+AFTER DELETE ON scenario_overrides
+BEGIN
+    DELETE FROM scenario_stats WHERE scenario_id = OLD.scenario_id;
+END;
+
+CREATE TRIGGER scenario_stats_pool_insert
+AFTER INSERT ON resource_pools
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_pool_update
+AFTER UPDATE ON resource_pools
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_pool_delete
+AFTER DELETE ON resource_pools
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_resource_insert
+AFTER INSERT ON resources
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_resource_update
+AFTER UPDATE ON resources
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_resource_delete
+AFTER DELETE ON resources
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_role_capacity_insert
+AFTER INSERT ON pool_role_capacities
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_role_capacity_update
+AFTER UPDATE ON pool_role_capacities
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_role_capacity_delete
+AFTER DELETE ON pool_role_capacities
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_constraint_insert
+AFTER INSERT ON constraints
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_constraint_update
+AFTER UPDATE ON constraints
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_constraint_delete
+AFTER DELETE ON constraints
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_period_insert
+AFTER INSERT ON financial_periods
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_period_update
+AFTER UPDATE ON financial_periods
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_period_delete
+AFTER DELETE ON financial_periods
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_exchange_rate_insert
+AFTER INSERT ON exchange_rates
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_exchange_rate_update
+AFTER UPDATE ON exchange_rates
+BEGIN
+    DELETE FROM scenario_stats;
+END;
+
+CREATE TRIGGER scenario_stats_exchange_rate_delete
+AFTER DELETE ON exchange_rates
+BEGIN
+    DELETE FROM scenario_stats;
+END;
@@ -1,2 +1,294 @@
 // Migrations module
-// SQL migrations are loaded via include_str! in lib.rs
+// SQL migrations, applied at startup to a new database and on request to an older one
+
+use super::connection::connect_options;
+use sqlx::error::BoxDynError;
+use sqlx::migrate::{Migration, MigrationSource, MigrationType, Migrator};
+use sqlx::{ConnectOptions, Connection, Row, SqliteConnection};
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+
+// Bookkeeping table shared with tauri-plugin-sql, which applied migrations in earlier builds
+pub const MIGRATIONS_TABLE: &str = "_sqlx_migrations";
+
+pub struct SchemaMigration {
+    pub version: i64,
+    pub description: &'static str,
+    pub sql: &'static str,
+}
+
+pub const MIGRATIONS: &[SchemaMigration] = &[
+    SchemaMigration {
+        version: 1,
+        description: "create initial tables",
+        sql: include_str!("001_initial_schema.sql"),
+    },
+    SchemaMigration {
+        version: 2,
+        description: "create initiative capability links",
+        sql: include_str!("002_initiative_capabilities.sql"),
+    },
+    SchemaMigration {
+        version: 3,
+        description: "create capability assessments",
+        sql: include_str!("003_capability_assessments.sql"),
+    },
+    SchemaMigration {
+        version: 4,
+        description: "create system interfaces",
+        sql: include_str!("004_interfaces.sql"),
+    },
+    SchemaMigration {
+        version: 5,
+        description: "add initiative progress and milestones",
+        sql: include_str!("005_initiative_progress.sql"),
+    },
+    SchemaMigration {
+        version: 6,
+        description: "create named resource allocations",
+        sql: include_str!("006_initiative_resources.sql"),
+    },
+    SchemaMigration {
+        version: 7,
+        description: "create audit log",
+        sql: include_str!("007_audit_log.sql"),
+    },
+    SchemaMigration {
+        version: 8,
+        description: "add financial period close and actuals",
+        sql: include_str!("008_period_close.sql"),
+    },
+    SchemaMigration {
+        version: 9,
+        description: "add system initiative link weights",
+        sql: include_str!("009_system_initiative_weights.sql"),
+    },
+    SchemaMigration {
+        version: 10,
+        description: "add multi-currency support",
+        sql: include_str!("010_currency.sql"),
+    },
+    SchemaMigration {
+        version: 11,
+        description: "add initiative effort units",
+        sql: include_str!("011_effort_units.sql"),
+    },
+    SchemaMigration {
+        version: 12,
+        description: "create initiative comments",
+        sql: include_str!("012_comments.sql"),
+    },
+    SchemaMigration {
+        version: 13,
+        description: "add initiative effort profiles",
+        sql: include_str!("013_effort_profiles.sql"),
+    },
+    SchemaMigration {
+        version: 14,
+        description: "create objectives and initiative alignment",
+        sql: include_str!("014_objectives.sql"),
+    },
+    SchemaMigration {
+        version: 15,
+        description: "add initiative external references",
+        sql: include_str!("015_external_refs.sql"),
+    },
+    SchemaMigration {
+        version: 16,
+        description: "create resource time off",
+        sql: include_str!("016_resource_time_off.sql"),
+    },
+    SchemaMigration {
+        version: 17,
+        description: "add initiative key date flag",
+        sql: include_str!("017_key_dates.sql"),
+    },
+    SchemaMigration {
+        version: 18,
+        description: "initiative colour and icon overrides",
+        sql: include_str!("018_initiative_appearance.sql"),
+    },
+    SchemaMigration {
+        version: 19,
+        description: "dependency lag range",
+        sql: include_str!("019_dependency_lag_range.sql"),
+    },
+    SchemaMigration {
+        version: 20,
+        description: "working calendars",
+        sql: include_str!("020_working_calendars.sql"),
+    },
+    SchemaMigration {
+        version: 21,
+        description: "rolling forecast snapshots",
+        sql: include_str!("021_forecast_snapshots.sql"),
+    },
+    SchemaMigration {
+        version: 22,
+        description: "scenario locking",
+        sql: include_str!("022_scenario_locking.sql"),
+    },
+    SchemaMigration {
+        version: 23,
+        description: "scenario unlocking",
+        sql: include_str!("023_scenario_unlock.sql"),
+    },
+    SchemaMigration {
+        version: 24,
+        description: "initiative pool splits",
+        sql: include_str!("024_initiative_pool_splits.sql"),
+    },
+    SchemaMigration {
+        version: 25,
+        description: "initiative reference codes",
+        sql: include_str!("025_reference_codes.sql"),
+    },
+    SchemaMigration {
+        version: 26,
+        description: "pool role capacities",
+        sql: include_str!("026_pool_role_capacities.sql"),
+    },
+    SchemaMigration {
+        version: 27,
+        description: "per-scenario what-if overrides",
+        sql: include_str!("027_scenario_overrides.sql"),
+    },
+    SchemaMigration {
+        version: 28,
+        description: "review stamps on every entity table",
+        sql: include_str!("028_last_reviewed.sql"),
+    },
+    SchemaMigration {
+        version: 29,
+        description: "initiative health override",
+        sql: include_str!("029_initiative_health_override.sql"),
+    },
+    SchemaMigration {
+        version: 30,
+        description: "pool allocation approval",
+        sql: include_str!("030_allocation_approvals.sql"),
+    },
+    SchemaMigration {
+        version: 31,
+        description: "cached scenario summary stats",
+        sql: include_str!("031_scenario_stats.sql"),
+    },
+    SchemaMigration {
+        version: 32,
+        description: "scenario approval and sign-off",
+        sql: include_str!("032_scenario_approvals.sql"),
+    },
+    SchemaMigration {
+        version: 33,
+        description: "resources as owners of systems and initiatives",
+        sql: include_str!("033_entity_owners.sql"),
+    },
+    SchemaMigration {
+        version: 34,
+        description: "funding sources and derived period budgets",
+        sql: include_str!("034_funding_sources.sql"),
+    },
+    SchemaMigration {
+        version: 35,
+        description: "dependencies stay within one scenario",
+        sql: include_str!("035_same_scenario_dependencies.sql"),
+    },
+    SchemaMigration {
+        version: 36,
+        description: "operation journal for crash recovery",
+        sql: include_str!("036_operation_journal.sql"),
+    },
+    SchemaMigration {
+        version: 37,
+        description: "capability default pools and generated allocations",
+        sql: include_str!("037_capability_default_pools.sql"),
+    },
+    SchemaMigration {
+        version: 38,
+        description: "external reference ids on systems",
+        sql: include_str!("038_system_external_refs.sql"),
+    },
+    SchemaMigration {
+        version: 39,
+        description: "scenario stats invalidation on risk inputs",
+        sql: include_str!("039_scenario_stats_inputs.sql"),
+    },
+];
+
+/// The schema version this build expects
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+#[derive(Debug)]
+struct MigrationList;
+
+impl MigrationSource<'static> for MigrationList {
+    fn resolve(self) -> Pin<Box<dyn Future<Output = Result<Vec<Migration>, BoxDynError>> + Send>> {
+        Box::pin(async {
+            // Same type and checksum as tauri-plugin-sql gave them, so earlier runs validate
+            Ok(MIGRATIONS
+                .iter()
+                .map(|m| Migration::new(m.version, m.description.into(), MigrationType::ReversibleUp, m.sql.into(), false))
+                .collect())
+        })
+    }
+}
+
+pub async fn migrator() -> Result<Migrator, String> {
+    Migrator::new(MigrationList).await.map_err(|e| e.to_string())
+}
+
+/// Highest successfully applied migration; zero for a database that has never been migrated
+pub async fn current_version(conn: &mut SqliteConnection) -> Result<i64, String> {
+    let tracked: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?")
+        .bind(MIGRATIONS_TABLE)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    if tracked == 0 {
+        return Ok(0);
+    }
+
+    sqlx::query(&format!("SELECT COALESCE(MAX(version), 0) AS version FROM {} WHERE success = 1", MIGRATIONS_TABLE))
+        .fetch_one(&mut *conn)
+        .await
+        .map(|row| row.get::<i64, _>("version"))
+        .map_err(|e| e.to_string())
+}
+
+/// Migrate a database with no tables yet. Databases from older builds are left for
+/// an explicit upgrade, so nothing is changed without a backup being taken first.
+pub async fn migrate_if_empty(conn: &mut SqliteConnection) -> Result<(), String> {
+    let tables: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'")
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    if tables > 0 {
+        return Ok(());
+    }
+
+    migrator().await?.run(&mut *conn).await.map_err(|e| e.to_string())
+}
+
+pub async fn initialise_new_database(path: &Path) -> Result<(), String> {
+    let mut conn = connect_options(path).connect().await.map_err(|e| e.to_string())?;
+    migrate_if_empty(&mut conn).await?;
+    conn.close().await.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn new_database_is_migrated_to_the_latest_version() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        assert_eq!(current_version(&mut conn).await.unwrap(), 0);
+
+        migrate_if_empty(&mut conn).await.unwrap();
+        assert_eq!(current_version(&mut conn).await.unwrap(), latest_version());
+        assert!(MIGRATIONS.windows(2).all(|w| w[0].version + 1 == w[1].version));
+    }
+}
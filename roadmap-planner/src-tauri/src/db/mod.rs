@@ -2,4 +2,5 @@
 // The actual database operations are handled by tauri-plugin-sql
 // which is called directly from the frontend via @tauri-apps/plugin-sql
 
+pub mod connection;
 pub mod migrations;
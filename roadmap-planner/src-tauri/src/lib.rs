@@ -1,22 +1,25 @@
-use tauri_plugin_sql::{Migration, MigrationKind};
+use tauri::Manager;
 
 mod db;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let migrations = vec![Migration {
-        version: 1,
-        description: "create initial tables",
-        sql: include_str!("db/migrations/001_initial_schema.sql"),
-        kind: MigrationKind::Up,
-    }];
-
     tauri::Builder::default()
         .plugin(
-            tauri_plugin_sql::Builder::default()
-                .add_migrations("sqlite:roadmap.db", migrations)
-                .build(),
+            // Migrations are applied in setup rather than by the plugin, so an older
+            // database is only upgraded explicitly
+            tauri_plugin_sql::Builder::default().build(),
         )
+        .setup(|app| {
+            // Same location tauri-plugin-sql resolves "sqlite:roadmap.db" to
+            let dir = app.path().app_config_dir()?;
+            std::fs::create_dir_all(&dir)?;
+            let path = dir.join("roadmap.db");
+            tauri::async_runtime::block_on(db::connection::configure_database(&path))?;
+            // Older databases wait for run_pending_migrations, which backs them up first
+            tauri::async_runtime::block_on(db::migrations::initialise_new_database(&path))?;
+            Ok(())
+        })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }